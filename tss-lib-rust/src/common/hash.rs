@@ -1,6 +1,6 @@
 use sha2::{Digest, Sha512_256};
 use num_bigint::BigInt;
-use num_traits::Zero;
+use num_traits::{One, Zero};
 use num_integer::Integer;
 
 const HASH_INPUT_DELIMITER: u8 = b'$';
@@ -44,6 +44,28 @@ pub fn rejection_sample(q: &BigInt, e_hash: &BigInt) -> BigInt {
     e_hash.mod_floor(q)
 }
 
+/// Bias-free rejection sampling: unlike [`rejection_sample`], which reduces
+/// `e_hash mod q` directly and so over-represents residues below
+/// `2^bits mod q` whenever `q` doesn't evenly divide `2^bits`, this rejects
+/// and resamples any candidate that falls in that leftover partial interval.
+///
+/// `bits` is the width of the values `next_hash` produces (e.g. 256 for a
+/// `sha512_256i`-derived block); `next_hash` is called once per attempt and
+/// must return an independent, uniformly distributed `bits`-bit value each
+/// time (e.g. `sha512_256i(&[session, &counter])` with `counter` incremented
+/// between calls). Challenge derivation in the ZK proofs in this crate
+/// should prefer this over `rejection_sample`, which is kept only so the
+/// wire format stays compatible with the Go implementation.
+pub fn rejection_sample_unbiased<F: FnMut() -> BigInt>(q: &BigInt, bits: u32, mut next_hash: F) -> BigInt {
+    let limit = (BigInt::one() << bits) / q * q;
+    loop {
+        let candidate = next_hash();
+        if candidate < limit {
+            return candidate.mod_floor(q);
+        }
+    }
+}
+
 pub fn sha512_256i_one(input: &BigInt) -> BigInt {
     let mut hasher = Sha512_256::new();
     let bytes = input.to_bytes_le().1;
@@ -85,4 +107,23 @@ mod tests {
         assert!(sample < q);
         assert_eq!(sample, e_hash.mod_floor(&q));
     }
+
+    #[test]
+    fn test_rejection_sample_unbiased_resamples_past_the_partial_interval() {
+        let q = 3.to_bigint().unwrap();
+        // With bits = 2, 2^bits = 4 is not a multiple of q = 3: the only
+        // value in the leftover partial interval [3, 4) is 3 itself, so the
+        // first call must be skipped and the second used instead.
+        let mut calls = vec![3.to_bigint().unwrap(), 1.to_bigint().unwrap()].into_iter();
+        let sample = rejection_sample_unbiased(&q, 2, || calls.next().unwrap());
+        assert_eq!(sample, 1.to_bigint().unwrap());
+    }
+
+    #[test]
+    fn test_rejection_sample_unbiased_accepts_first_candidate_in_range() {
+        let q = 97.to_bigint().unwrap();
+        let mut calls = vec![12345.to_bigint().unwrap()].into_iter();
+        let sample = rejection_sample_unbiased(&q, 256, || calls.next().unwrap());
+        assert_eq!(sample, 12345.to_bigint().unwrap().mod_floor(&q));
+    }
 }
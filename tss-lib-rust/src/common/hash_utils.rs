@@ -15,13 +15,35 @@ use num_traits::One; // For modpow trick to ensure positive result
 /// However, simple modular reduction introduces bias if the hash space (2^256)
 /// is not an exact multiple of q. A more cryptographically sound rejection
 /// sampling would involve retrying if the hash value is >= q * floor(2^256 / q).
-/// For this translation, we stick to the original logic.
+/// Kept only for wire compatibility with the Go implementation --
+/// `rejection_sample_unbiased` below is the bias-free replacement and should
+/// be preferred for new challenge derivation.
 pub fn rejection_sample(q: &BigInt, e_hash: &BigInt) -> BigInt {
     // Using modpow with exponent 1 ensures the result is positive in num-bigint-dig,
     // mimicking the behavior of Go's Mod which also returns a positive result.
     e_hash.modpow(&BigInt::one(), q)
 }
 
+/// Bias-free rejection sampling: resamples rather than reducing whenever the
+/// candidate falls in the partial interval `[floor(2^bits/q)*q, 2^bits)`,
+/// which `rejection_sample` above over-represents by wrapping it back down
+/// to `[0, 2^bits mod q)`.
+///
+/// `bits` is the width of the values `next_hash` produces (e.g. 256 for a
+/// `sha512_256i`-derived block); `next_hash` is called once per attempt and
+/// must return an independent, uniformly distributed `bits`-bit value each
+/// time (e.g. derived from `sha512_256i(session || counter)` with `counter`
+/// incremented between calls).
+pub fn rejection_sample_unbiased<F: FnMut() -> BigInt>(q: &BigInt, bits: u32, mut next_hash: F) -> BigInt {
+    let limit = (BigInt::one() << bits) / q * q;
+    loop {
+        let candidate = next_hash();
+        if candidate < limit {
+            return candidate.modpow(&BigInt::one(), q);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -61,4 +83,23 @@ mod tests {
          assert_eq!(result5, BigInt::from(766u64));
 
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_rejection_sample_unbiased_resamples_past_the_partial_interval() {
+        let q = BigInt::from_u64(3).unwrap();
+        // 2^2 = 4 isn't a multiple of q = 3, so 3 is the one value in the
+        // leftover partial interval and must be rejected in favor of the
+        // next candidate.
+        let mut calls = vec![BigInt::from_u64(3).unwrap(), BigInt::from_u64(1).unwrap()].into_iter();
+        let sample = rejection_sample_unbiased(&q, 2, || calls.next().unwrap());
+        assert_eq!(sample, BigInt::from_u64(1).unwrap());
+    }
+
+    #[test]
+    fn test_rejection_sample_unbiased_accepts_first_candidate_in_range() {
+        let q = BigInt::from_u64(1000).unwrap();
+        let mut calls = vec![BigInt::from_u64(1234).unwrap()].into_iter();
+        let sample = rejection_sample_unbiased(&q, 256, || calls.next().unwrap());
+        assert_eq!(sample, BigInt::from_u64(234).unwrap());
+    }
+}
\ No newline at end of file
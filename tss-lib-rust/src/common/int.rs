@@ -1,33 +1,323 @@
 use num_bigint::BigInt;
-use num_traits::Zero;
+use num_integer::Integer;
+use num_traits::{One, Signed, Zero};
 
-pub struct ModInt {
-    modulus: BigInt,
+use crate::common::random::{is_probable_prime, jacobi_symbol};
+
+/// Modular-arithmetic primitives that `ModInt` and the proof modules need
+/// from a big-integer implementation: the "GMP/constant-time swap" seam.
+/// `NumBigIntBackend` (below) is the default, backed by the `num-bigint`
+/// crate used everywhere else in this codebase; a downstream crate can
+/// implement this trait for its own integer type (e.g. a `rug`/GMP wrapper,
+/// or one with constant-time exponentiation) and use it with `ModInt<B>`
+/// wherever the default backend's timing or dependency footprint doesn't fit.
+pub trait BigIntBackend {
+    /// The backend's big-integer type.
+    type Int: Clone;
+    /// Precomputed, modulus-dependent state `mod_mul`/`mod_exp` may reuse
+    /// across many calls against the same modulus (e.g. a Montgomery
+    /// context). Backends with nothing to precompute can use `()`.
+    type Context;
+
+    /// Computes whatever state `mod_mul`/`mod_exp` want to reuse for this
+    /// modulus. Called once, in `ModInt::new`/`with_backend`.
+    fn precompute(modulus: &Self::Int) -> Self::Context;
+
+    fn mod_add(x: &Self::Int, y: &Self::Int, m: &Self::Int) -> Self::Int;
+    fn mod_sub(x: &Self::Int, y: &Self::Int, m: &Self::Int) -> Self::Int;
+    fn mod_mul(ctx: &Self::Context, x: &Self::Int, y: &Self::Int, m: &Self::Int) -> Self::Int;
+    fn mod_exp(ctx: &Self::Context, x: &Self::Int, y: &Self::Int, m: &Self::Int) -> Self::Int;
+    fn mod_inverse(x: &Self::Int, m: &Self::Int) -> Option<Self::Int>;
+
+    /// Jacobi symbol `(a/n)`, returning -1, 0 or 1.
+    fn jacobi(a: &Self::Int, n: &Self::Int) -> i32;
+    /// Miller-Rabin-style probabilistic primality test with `rounds` rounds.
+    fn is_probably_prime(n: &Self::Int, rounds: u32) -> bool;
+
+    fn test_bit(x: &Self::Int, i: u64) -> bool;
+    fn set_bit(x: &mut Self::Int, i: u64, value: bool);
+
+    fn to_bytes_be(x: &Self::Int) -> Vec<u8>;
+    fn from_bytes_be(bytes: &[u8]) -> Self::Int;
+
+    fn is_negative(x: &Self::Int) -> bool;
+    fn zero() -> Self::Int;
+}
+
+/// Montgomery-reduction context precomputed once per modulus: `R = 2^r_bits`
+/// (`r_bits` is the smallest multiple of the limb size strictly greater than
+/// the modulus's bit length), `n_prime = -N^-1 mod R`, and `r2 = R^2 mod N` (used to carry
+/// operands into the Montgomery domain). Built only for odd moduli — Blum
+/// moduli (`p, q ≡ 3 mod 4`) always qualify, and Montgomery reduction
+/// requires `N` coprime to `R`, i.e. odd.
+#[derive(Clone)]
+pub enum MontgomeryContext {
+    /// Modulus was even (or `<= 1`): `mod_mul`/`mod_exp` fall back to plain
+    /// `%`/`modpow`.
+    Plain,
+    Montgomery {
+        r_bits: usize,
+        r: BigInt,
+        n_prime: BigInt,
+        r2: BigInt,
+    },
 }
 
-impl ModInt {
+const LIMB_BITS: usize = 64;
+
+impl MontgomeryContext {
+    fn build(modulus: &BigInt) -> Self {
+        if modulus <= &BigInt::one() || modulus.is_even() {
+            return MontgomeryContext::Plain;
+        }
+        let bits = modulus.bits() as usize;
+        let r_bits = (bits / LIMB_BITS + 1) * LIMB_BITS;
+        let r = BigInt::one() << r_bits;
+        // N is odd, so it's invertible mod the power-of-two R.
+        let n_inv = match modulus.modinv(&r) {
+            Some(inv) => inv,
+            None => return MontgomeryContext::Plain,
+        };
+        let n_prime = (-n_inv).mod_floor(&r);
+        let r2 = (&r * &r).mod_floor(modulus);
+        MontgomeryContext::Montgomery { r_bits, r, n_prime, r2 }
+    }
+
+    /// Montgomery reduction: for `0 <= t < R*N`, returns `t * R^-1 mod N`.
+    fn redc(&self, t: &BigInt, n: &BigInt) -> BigInt {
+        match self {
+            MontgomeryContext::Plain => unreachable!("redc called without a Montgomery context"),
+            MontgomeryContext::Montgomery { r_bits, r, n_prime, .. } => {
+                let m = (t.mod_floor(r) * n_prime).mod_floor(r);
+                let u = (t + m * n) >> *r_bits;
+                if &u >= n {
+                    u - n
+                } else {
+                    u
+                }
+            }
+        }
+    }
+
+    /// Converts an ordinary representative `x` into Montgomery form `xR mod N`.
+    fn to_montgomery(&self, x: &BigInt, n: &BigInt) -> BigInt {
+        match self {
+            MontgomeryContext::Plain => unreachable!("to_montgomery called without a Montgomery context"),
+            MontgomeryContext::Montgomery { r2, .. } => self.redc(&(x.mod_floor(n) * r2), n),
+        }
+    }
+
+    /// `a_mont`, `b_mont` are both in Montgomery form; returns `(a*b)` in
+    /// Montgomery form.
+    fn mont_mul(&self, a_mont: &BigInt, b_mont: &BigInt, n: &BigInt) -> BigInt {
+        self.redc(&(a_mont * b_mont), n)
+    }
+}
+
+/// Default `BigIntBackend`, backed by `num_bigint::BigInt` — the big-integer
+/// type the rest of this crate already uses. `mod_mul`/`mod_exp` route
+/// through a precomputed [`MontgomeryContext`] when the modulus is odd,
+/// staying entirely in the Montgomery domain across a `mod_exp`'s
+/// square-and-multiply loop and converting in/out exactly once.
+pub struct NumBigIntBackend;
+
+impl BigIntBackend for NumBigIntBackend {
+    type Int = BigInt;
+    type Context = MontgomeryContext;
+
+    fn precompute(modulus: &BigInt) -> MontgomeryContext {
+        MontgomeryContext::build(modulus)
+    }
+
+    fn mod_add(x: &BigInt, y: &BigInt, m: &BigInt) -> BigInt {
+        (x + y) % m
+    }
+
+    fn mod_sub(x: &BigInt, y: &BigInt, m: &BigInt) -> BigInt {
+        (x - y) % m
+    }
+
+    fn mod_mul(ctx: &MontgomeryContext, x: &BigInt, y: &BigInt, m: &BigInt) -> BigInt {
+        match ctx {
+            MontgomeryContext::Plain => (x * y).mod_floor(m),
+            MontgomeryContext::Montgomery { .. } => {
+                // x_mont = xR mod N; redc(x_mont * y) = x*y*R*R^-1 = x*y mod N.
+                let x_mont = ctx.to_montgomery(x, m);
+                ctx.redc(&(x_mont * y.mod_floor(m)), m)
+            }
+        }
+    }
+
+    fn mod_exp(ctx: &MontgomeryContext, x: &BigInt, e: &BigInt, m: &BigInt) -> BigInt {
+        match ctx {
+            MontgomeryContext::Plain => x.modpow(e, m),
+            MontgomeryContext::Montgomery { .. } => {
+                if e.is_negative() {
+                    // Montgomery form as built here has no direct support for
+                    // negative exponents; fall back to the plain path.
+                    return x.modpow(e, m);
+                }
+                let one_mont = ctx.to_montgomery(&BigInt::one(), m);
+                let mut result_mont = one_mont;
+                let mut base_mont = ctx.to_montgomery(x, m);
+                let mut exp = e.clone();
+                let zero = BigInt::zero();
+                while exp > zero {
+                    if exp.is_odd() {
+                        result_mont = ctx.mont_mul(&result_mont, &base_mont, m);
+                    }
+                    base_mont = ctx.mont_mul(&base_mont, &base_mont, m);
+                    exp >>= 1;
+                }
+                ctx.redc(&result_mont, m)
+            }
+        }
+    }
+
+    fn mod_inverse(x: &BigInt, m: &BigInt) -> Option<BigInt> {
+        x.modinv(m).map(|inv| inv % m)
+    }
+
+    fn jacobi(a: &BigInt, n: &BigInt) -> i32 {
+        jacobi_symbol(a, n)
+    }
+
+    fn is_probably_prime(n: &BigInt, rounds: u32) -> bool {
+        is_probable_prime(n, rounds)
+    }
+
+    fn test_bit(x: &BigInt, i: u64) -> bool {
+        x.test_bit(i)
+    }
+
+    fn set_bit(x: &mut BigInt, i: u64, value: bool) {
+        x.set_bit(i, value);
+    }
+
+    fn to_bytes_be(x: &BigInt) -> Vec<u8> {
+        x.to_bytes_be().1
+    }
+
+    fn from_bytes_be(bytes: &[u8]) -> BigInt {
+        BigInt::from_bytes_be(num_bigint::Sign::Plus, bytes)
+    }
+
+    fn is_negative(x: &BigInt) -> bool {
+        x.sign() == num_bigint::Sign::Minus
+    }
+
+    fn zero() -> BigInt {
+        BigInt::zero()
+    }
+}
+
+/// Modular-arithmetic helper generic over a [`BigIntBackend`]. Defaults to
+/// [`NumBigIntBackend`], so existing call sites (`ModInt::new(modulus)`
+/// followed by `.add`/`.mul`/`.exp`/`.mod_inverse`) keep working unchanged;
+/// `ModInt::<OtherBackend>::with_backend(modulus)` opts into a different
+/// backend. `mul`/`exp` reuse the backend's precomputed `Context` (for
+/// `NumBigIntBackend`, a Montgomery context precomputed once in `new`), so
+/// repeated calls against the same modulus — as in `ProofMod::verify`'s
+/// 80-iteration loop — don't redo that setup on every call.
+pub struct ModInt<B: BigIntBackend = NumBigIntBackend> {
+    modulus: B::Int,
+    ctx: B::Context,
+}
+
+impl ModInt<NumBigIntBackend> {
     pub fn new(modulus: BigInt) -> Self {
-        ModInt { modulus }
+        let ctx = NumBigIntBackend::precompute(&modulus);
+        ModInt { modulus, ctx }
+    }
+}
+
+impl<B: BigIntBackend> ModInt<B> {
+    pub fn with_backend(modulus: B::Int) -> Self {
+        let ctx = B::precompute(&modulus);
+        ModInt { modulus, ctx }
+    }
+
+    pub fn add(&self, x: &B::Int, y: &B::Int) -> B::Int {
+        B::mod_add(x, y, &self.modulus)
     }
 
-    pub fn add(&self, x: &BigInt, y: &BigInt) -> BigInt {
-        (x + y) % &self.modulus
+    pub fn sub(&self, x: &B::Int, y: &B::Int) -> B::Int {
+        B::mod_sub(x, y, &self.modulus)
     }
 
-    pub fn sub(&self, x: &BigInt, y: &BigInt) -> BigInt {
-        (x - y) % &self.modulus
+    pub fn mul(&self, x: &B::Int, y: &B::Int) -> B::Int {
+        B::mod_mul(&self.ctx, x, y, &self.modulus)
     }
 
-    pub fn mul(&self, x: &BigInt, y: &BigInt) -> BigInt {
-        (x * y) % &self.modulus
+    pub fn exp(&self, x: &B::Int, y: &B::Int) -> B::Int {
+        B::mod_exp(&self.ctx, x, y, &self.modulus)
     }
 
-    pub fn exp(&self, x: &BigInt, y: &BigInt) -> BigInt {
-        x.modpow(y, &self.modulus)
+    pub fn mod_inverse(&self, g: &B::Int) -> Option<B::Int> {
+        B::mod_inverse(g, &self.modulus)
     }
 
-    pub fn mod_inverse(&self, g: &BigInt) -> Option<BigInt> {
-        g.modinv(&self.modulus).map(|inv| inv % &self.modulus)
+    /// `base1^exp1 * base2^exp2 mod modulus` via Shamir's trick instead of
+    /// two independent `exp` calls and a `mul`. See [`multi_exp`](Self::multi_exp).
+    pub fn exp2(&self, base1: &B::Int, exp1: &B::Int, base2: &B::Int, exp2: &B::Int) -> B::Int {
+        self.multi_exp(&[(base1, exp1), (base2, exp2)])
+    }
+
+    /// Simultaneous multi-exponentiation: computes `prod base_i^exp_i mod
+    /// modulus` in one interleaved square-and-multiply pass instead of
+    /// `bases.len()` independent exponentiations followed by `mul`s. Every
+    /// proof in this crate's line like `z = h1^m * h2^rho` is exactly this
+    /// shape.
+    ///
+    /// Precomputes the `2^bases.len()` partial products (Shamir's trick's
+    /// "table of small window pairs"): `table[mask]` is the product of
+    /// `base_i` for every `i` whose bit is set in `mask`. Then scans every
+    /// exponent's bits in lockstep from the most significant bit down,
+    /// squaring the running result once per bit position and multiplying in
+    /// `table[mask]` for whichever subset of exponents has a `1` bit there
+    /// -- one squaring per bit no matter how many bases, versus one squaring
+    /// per bit *per base* for separate exponentiations.
+    ///
+    /// Exponents are assumed non-negative, matching every call site (sigma-
+    /// protocol responses and range-proof exponents, never signed here).
+    /// Panics if `bases` is empty or has more than 12 entries (the table
+    /// would otherwise blow up past 4096 entries for no call site's benefit).
+    pub fn multi_exp(&self, bases: &[(&B::Int, &B::Int)]) -> B::Int {
+        assert!(!bases.is_empty(), "multi_exp: bases must be non-empty");
+        assert!(bases.len() <= 12, "multi_exp: too many bases for a dense Shamir's-trick table");
+
+        // x^0 == 1 for any base this crate feeds in, so this doubles as the
+        // modulus-specific identity without the trait needing its own `one()`.
+        let identity = self.exp(bases[0].0, &B::zero());
+
+        let mut table = vec![identity.clone()];
+        for (base, _) in bases {
+            let mut next = Vec::with_capacity(table.len() * 2);
+            next.extend(table.iter().cloned());
+            next.extend(table.iter().map(|t| self.mul(t, base)));
+            table = next;
+        }
+
+        let max_bits = bases
+            .iter()
+            .map(|(_, exp)| B::to_bytes_be(exp).len() * 8)
+            .max()
+            .unwrap_or(0);
+
+        let mut result = identity;
+        for bit_pos in (0..max_bits).rev() {
+            result = self.mul(&result, &result);
+            let mut mask = 0usize;
+            for (i, (_, exp)) in bases.iter().enumerate() {
+                if B::test_bit(exp, bit_pos as u64) {
+                    mask |= 1 << i;
+                }
+            }
+            if mask != 0 {
+                result = self.mul(&result, &table[mask]);
+            }
+        }
+        result
     }
 }
 
@@ -54,4 +344,81 @@ mod tests {
         let b = 5.to_bigint().unwrap();
         assert!(is_in_interval(&b, &bound));
     }
+
+    #[test]
+    fn test_mod_int_generic_backend() {
+        let modulus = 7.to_bigint().unwrap();
+        let mi = ModInt::<NumBigIntBackend>::with_backend(modulus);
+        let x = 3.to_bigint().unwrap();
+        let y = 5.to_bigint().unwrap();
+        assert_eq!(mi.mul(&x, &y), 1.to_bigint().unwrap());
+    }
+
+    #[test]
+    fn test_mod_int_mul_matches_plain_mod_for_odd_modulus() {
+        // 97 is prime (odd), so mul should route through Montgomery reduction.
+        let modulus = 97.to_bigint().unwrap();
+        let mi = ModInt::new(modulus.clone());
+        let x = 53.to_bigint().unwrap();
+        let y = 61.to_bigint().unwrap();
+        assert_eq!(mi.mul(&x, &y), (&x * &y) % &modulus);
+    }
+
+    #[test]
+    fn test_mod_int_exp_matches_plain_modpow_for_odd_modulus() {
+        let modulus = 97.to_bigint().unwrap();
+        let mi = ModInt::new(modulus.clone());
+        let x = 53.to_bigint().unwrap();
+        let e = 17.to_bigint().unwrap();
+        assert_eq!(mi.exp(&x, &e), x.modpow(&e, &modulus));
+    }
+
+    #[test]
+    fn test_mod_int_exp_matches_plain_modpow_for_even_modulus() {
+        // Even modulus takes the MontgomeryContext::Plain fallback path.
+        let modulus = 100.to_bigint().unwrap();
+        let mi = ModInt::new(modulus.clone());
+        let x = 7.to_bigint().unwrap();
+        let e = 13.to_bigint().unwrap();
+        assert_eq!(mi.exp(&x, &e), x.modpow(&e, &modulus));
+    }
+
+    #[test]
+    fn test_mod_int_exp2_matches_two_separate_exps() {
+        let modulus = 97.to_bigint().unwrap();
+        let mi = ModInt::new(modulus.clone());
+        let base1 = 53.to_bigint().unwrap();
+        let exp1 = 17.to_bigint().unwrap();
+        let base2 = 61.to_bigint().unwrap();
+        let exp2 = 9.to_bigint().unwrap();
+
+        let expected = mi.mul(&mi.exp(&base1, &exp1), &mi.exp(&base2, &exp2));
+        assert_eq!(mi.exp2(&base1, &exp1, &base2, &exp2), expected);
+    }
+
+    #[test]
+    fn test_mod_int_multi_exp_three_bases() {
+        let modulus = 1_000_003u64.to_bigint().unwrap();
+        let mi = ModInt::new(modulus.clone());
+        let b1 = 12345.to_bigint().unwrap();
+        let e1 = 777.to_bigint().unwrap();
+        let b2 = 54321.to_bigint().unwrap();
+        let e2 = 42.to_bigint().unwrap();
+        let b3 = 99.to_bigint().unwrap();
+        let e3 = 0.to_bigint().unwrap();
+
+        let expected = mi.mul(&mi.mul(&mi.exp(&b1, &e1), &mi.exp(&b2, &e2)), &mi.exp(&b3, &e3));
+        assert_eq!(mi.multi_exp(&[(&b1, &e1), (&b2, &e2), (&b3, &e3)]), expected);
+    }
+
+    #[test]
+    fn test_mod_int_exp_large_blum_like_modulus() {
+        // A larger odd modulus: a product of two primes rather than a single prime.
+        let modulus = 1_000_003u64 * 1_000_033u64;
+        let modulus = modulus.to_bigint().unwrap();
+        let mi = ModInt::new(modulus.clone());
+        let x = 123_456_789.to_bigint().unwrap();
+        let e = 65_537.to_bigint().unwrap();
+        assert_eq!(mi.exp(&x, &e), x.modpow(&e, &modulus));
+    }
 }
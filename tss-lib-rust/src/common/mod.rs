@@ -3,6 +3,8 @@ pub mod safe_prime;
 pub mod hash;
 pub mod hash_utils;
 pub mod random;
+pub mod reed_solomon;
+pub mod secret;
 pub mod slice;
 
 // Add other modules from the 'common' package here as they are converted
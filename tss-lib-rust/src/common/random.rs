@@ -1,9 +1,34 @@
 use rand::Rng;
-use num_bigint::{BigInt, RandBigInt};
-use num_traits::Zero;
+use num_bigint::{BigInt, RandBigInt, Sign};
+use num_integer::Integer;
+use num_traits::{One, Zero};
 
 const MUST_GET_RANDOM_INT_MAX_BITS: usize = 5000;
 
+// Default number of Miller-Rabin rounds for crypto-sized (>= 1024 bit) candidates.
+const DEFAULT_MILLER_RABIN_ROUNDS: u32 = 40;
+
+// Small primes used to sieve candidates before paying for a modpow-based Miller-Rabin round.
+const SMALL_PRIMES: &[u32] = &[
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+    101, 103, 107, 109, 113, 127, 131, 137, 139, 149, 151, 157, 163, 167, 173, 179, 181, 191, 193,
+    197, 199, 211, 223, 227, 229, 233, 239, 241, 251, 257, 263, 269, 271, 277, 281, 283, 293, 307,
+    311, 313, 317, 331, 337, 347, 349, 353, 359, 367, 373, 379, 383, 389, 397, 401, 409, 419, 421,
+    431, 433, 439, 443, 449, 457, 461, 463, 467, 479, 487, 491, 499, 503, 509, 521, 523, 541, 547,
+    557, 563, 569, 571, 577, 587, 593, 599, 601, 607, 613, 617, 619, 631, 641, 643, 647, 653, 659,
+    661, 673, 677, 683, 691, 701, 709, 719, 727, 733, 739, 743, 751, 757, 761, 769, 773, 787, 797,
+    809, 811, 821, 823, 827, 829, 839, 853, 857, 859, 863, 877, 881, 883, 887, 907, 911, 919, 929,
+    937, 941, 947, 953, 967, 971, 977, 983, 991, 997, 1009, 1013, 1019, 1021, 1031, 1033, 1039,
+    1049, 1051, 1061, 1063, 1069, 1087, 1091, 1093, 1097, 1103, 1109, 1117, 1123, 1129, 1151, 1153,
+    1163, 1171, 1181, 1187, 1193, 1201, 1213, 1217, 1223, 1229, 1231, 1237, 1249, 1259, 1277, 1279,
+    1283, 1289, 1291, 1297, 1301, 1303, 1307, 1319, 1321, 1327, 1361, 1367, 1373, 1381, 1399, 1409,
+    1423, 1427, 1429, 1433, 1439, 1447, 1451, 1453, 1459, 1471, 1481, 1483, 1487, 1489, 1493, 1499,
+    1511, 1523, 1531, 1543, 1549, 1553, 1559, 1567, 1571, 1579, 1583, 1597, 1601, 1607, 1609, 1613,
+    1619, 1621, 1627, 1637, 1657, 1663, 1667, 1669, 1693, 1697, 1699, 1709, 1721, 1723, 1733, 1741,
+    1747, 1753, 1759, 1777, 1783, 1787, 1789, 1801, 1811, 1823, 1831, 1847, 1861, 1867, 1871, 1873,
+    1877, 1879, 1889, 1901, 1907, 1913, 1931, 1933, 1949, 1951, 1973, 1979, 1987, 1993, 1997, 1999,
+];
+
 pub fn must_get_random_int<R: Rng>(rng: &mut R, bits: usize) -> BigInt {
     if bits <= 0 || bits > MUST_GET_RANDOM_INT_MAX_BITS {
         panic!("MustGetRandomInt: bits should be positive, non-zero and less than {}", MUST_GET_RANDOM_INT_MAX_BITS);
@@ -12,26 +37,207 @@ pub fn must_get_random_int<R: Rng>(rng: &mut R, bits: usize) -> BigInt {
     rng.gen_bigint_range(&BigInt::zero(), &max)
 }
 
-fn is_probable_prime(n: &BigInt, k: u32) -> bool {
-    if n <= &BigInt::from(1) {
-        return false;
+// Rejects candidates divisible by any prime below 2000 without paying for a modpow.
+pub(crate) fn passes_small_prime_sieve(n: &BigInt) -> bool {
+    for &p in SMALL_PRIMES {
+        let p = BigInt::from(p);
+        if n == &p {
+            return true;
+        }
+        if (n % &p).is_zero() {
+            return false;
+        }
     }
-    if n <= &BigInt::from(3) {
+    true
+}
+
+// Miller-Rabin witness test: n-1 = 2^s * d, reject unless a^d == 1 or a^(d*2^r) == n-1
+// for some 0 <= r < s.
+fn miller_rabin_witness(n: &BigInt, n_minus_one: &BigInt, d: &BigInt, s: u32, a: &BigInt) -> bool {
+    let mut x = a.modpow(d, n);
+    if x.is_one() || &x == n_minus_one {
         return true;
     }
-    if n % 2 == BigInt::zero() || n % 3 == BigInt::zero() {
-        return false;
+    for _ in 1..s {
+        x = (&x * &x) % n;
+        if &x == n_minus_one {
+            return true;
+        }
     }
-    let mut i = BigInt::from(5);
-    while &i * &i <= *n {
-        if n % &i == BigInt::zero() || n % (&i + 2) == BigInt::zero() {
+    false
+}
+
+fn miller_rabin<R: Rng>(rng: &mut R, n: &BigInt, rounds: u32) -> bool {
+    let n_minus_one = n - BigInt::one();
+    let (s, d) = {
+        let mut d = n_minus_one.clone();
+        let mut s = 0u32;
+        while (&d).is_even() {
+            d /= 2;
+            s += 1;
+        }
+        (s, d)
+    };
+    let lower = BigInt::from(2);
+    let upper = n - BigInt::from(2);
+    for _ in 0..rounds {
+        let a = rng.gen_bigint_range(&lower, &upper);
+        if !miller_rabin_witness(n, &n_minus_one, &d, s, &a) {
             return false;
         }
-        i += 6;
     }
     true
 }
 
+// Strong Lucas probable-prime test (the second half of Baillie-PSW), using the
+// Selfridge parameter search for (P, Q) with D chosen via the standard sequence
+// 5, -7, 9, -11, ... until jacobi(D, n) == -1.
+fn strong_lucas_probable_prime(n: &BigInt) -> bool {
+    // Baillie-PSW requires n not be a perfect square; a Jacobi search that never
+    // terminates would otherwise loop forever, so guard against it directly.
+    if is_perfect_square(n) {
+        return false;
+    }
+
+    let mut d: i64 = 5;
+    let big_n = n.clone();
+    loop {
+        let d_big = BigInt::from(d);
+        let jacobi = jacobi_symbol(&d_big, &big_n);
+        if jacobi == -1 {
+            break;
+        }
+        if jacobi == 0 {
+            return false;
+        }
+        d = if d > 0 { -(d + 2) } else { -(d - 2) };
+        if d.unsigned_abs() > 1_000_000 {
+            // Practically unreachable for random odd composites, but bounds the loop.
+            return false;
+        }
+    }
+
+    let p = BigInt::one();
+    let d_big = BigInt::from(d);
+    // q = (1 - D) / 4
+    let q = (BigInt::one() - &d_big) / 4;
+
+    let n_plus_one = &big_n + BigInt::one();
+    // n + 1 = d_odd * 2^s with d_odd odd; the ladder below computes U, V and
+    // Q^k for k = d_odd.
+    let s = n_plus_one.trailing_zeros().unwrap_or(0);
+    let d_odd = &n_plus_one >> s;
+    let (k, mut bits) = {
+        let mut k = d_odd.clone();
+        let mut bits = Vec::new();
+        while !k.is_zero() {
+            bits.push((&k & BigInt::one()).is_one());
+            k >>= 1;
+        }
+        bits.reverse();
+        (n_plus_one, bits)
+    };
+    let _ = k;
+    if bits.is_empty() {
+        bits.push(false);
+    }
+
+    // Lucas sequence double-and-add: U_0=0, V_0=2, then standard doubling formulas mod n.
+    let (mut u, mut v, mut qk) = (BigInt::zero(), BigInt::from(2), BigInt::one());
+    for bit in bits {
+        // Double: U_{2k} = U_k * V_k, V_{2k} = V_k^2 - 2*Q^k
+        u = (&u * &v) % &big_n;
+        v = (&v * &v - 2 * &qk) % &big_n;
+        qk = (&qk * &qk) % &big_n;
+        if bit {
+            // Add one step: U_{k+1} = (P*U_k + V_k)/2, V_{k+1} = (D*U_k + P*V_k)/2
+            let new_u = (&p * &u + &v) % &big_n;
+            let new_v = (&d_big * &u + &p * &v) % &big_n;
+            u = div_mod2(&new_u, &big_n);
+            v = div_mod2(&new_v, &big_n);
+            qk = (&qk * &q) % &big_n;
+        }
+    }
+    // Strong check: U_d == 0, or V_{d*2^r} == 0 for some 0 <= r < s.
+    if u.mod_floor(&big_n).is_zero() || v.mod_floor(&big_n).is_zero() {
+        return true;
+    }
+    for _ in 1..s {
+        v = (&v * &v - 2 * &qk).mod_floor(&big_n);
+        qk = (&qk * &qk) % &big_n;
+        if v.is_zero() {
+            return true;
+        }
+    }
+    false
+}
+
+// Divides an even-after-mod-adjustment value by 2 mod n, where n is odd.
+fn div_mod2(x: &BigInt, n: &BigInt) -> BigInt {
+    let x = x.mod_floor(n);
+    if x.is_even() {
+        x / 2
+    } else {
+        (x + n) / 2
+    }
+}
+
+fn is_perfect_square(n: &BigInt) -> bool {
+    let root = n.sqrt();
+    &root * &root == *n
+}
+
+// Jacobi symbol (a/n) for odd positive n, via the standard reciprocity recursion.
+// Visible within the crate so proof modules (e.g. the Paillier-Blum modulus
+// proof) can reuse it to find a quadratic non-residue mod N.
+pub(crate) fn jacobi_symbol(a: &BigInt, n: &BigInt) -> i32 {
+    let mut a = a.mod_floor(n);
+    let mut n = n.clone();
+    let mut result = 1;
+    while !a.is_zero() {
+        while a.is_even() {
+            a /= 2;
+            let r = (&n % 8).to_string();
+            if r == "3" || r == "5" {
+                result = -result;
+            }
+        }
+        core::mem::swap(&mut a, &mut n);
+        if (&a % 4 == BigInt::from(3)) && (&n % 4 == BigInt::from(3)) {
+            result = -result;
+        }
+        a = a.mod_floor(&n);
+    }
+    if n.is_one() {
+        result
+    } else {
+        0
+    }
+}
+
+// Probabilistic primality test: a small-prime sieve followed by `rounds` of
+// Miller-Rabin and a strong Lucas test (Baillie-PSW). Visible within the crate
+// so `safe_prime` can reuse it when validating Germain pairs.
+pub(crate) fn is_probable_prime_with_rng<R: Rng>(rng: &mut R, n: &BigInt, rounds: u32) -> bool {
+    if n.sign() != Sign::Plus {
+        return false;
+    }
+    if n <= &BigInt::from(3) {
+        return n >= &BigInt::from(2);
+    }
+    if n.is_even() {
+        return false;
+    }
+    if !passes_small_prime_sieve(n) {
+        return false;
+    }
+    miller_rabin(rng, n, rounds) && strong_lucas_probable_prime(n)
+}
+
+pub(crate) fn is_probable_prime(n: &BigInt, rounds: u32) -> bool {
+    is_probable_prime_with_rng(&mut rand::thread_rng(), n, rounds)
+}
+
 pub fn get_random_positive_int<R: Rng>(rng: &mut R, less_than: &BigInt) -> BigInt {
     if less_than <= &BigInt::zero() {
         return BigInt::zero();
@@ -44,18 +250,78 @@ pub fn get_random_positive_int<R: Rng>(rng: &mut R, less_than: &BigInt) -> BigIn
     }
 }
 
-pub fn get_random_prime_int<R: Rng>(rng: &mut R, bits: usize) -> BigInt {
+// Generates a probable prime of exactly `bits` bits (top two bits set so that
+// products of two such primes keep the full expected bit length), verified with
+// `rounds` rounds of Miller-Rabin plus a Baillie-PSW Lucas check.
+pub fn get_random_prime_int<R: Rng>(rng: &mut R, bits: usize, rounds: u32) -> BigInt {
     loop {
-        let candidate = rng.gen_bigint(bits as u64);
-        if is_probable_prime(&candidate, 30) {
+        let mut candidate = rng.gen_bigint(bits as u64);
+        candidate.set_bit((bits - 1) as u64, true);
+        candidate.set_bit((bits - 2) as u64, true);
+        candidate.set_bit(0, true);
+        if is_probable_prime_with_rng(rng, &candidate, rounds) {
             return candidate;
         }
     }
 }
+
+pub fn get_random_prime_int_default<R: Rng>(rng: &mut R, bits: usize) -> BigInt {
+    get_random_prime_int(rng, bits, DEFAULT_MILLER_RABIN_ROUNDS)
+}
+
+/// Adapts a shared, lock-guarded RNG into something that implements
+/// `RngCore`/`CryptoRng` in its own right, so it can be passed directly
+/// wherever a proof constructor in this crate expects `&mut R: RngCore +
+/// CryptoRng` -- the many async round handlers that hold their party's RNG
+/// behind an `Arc<Mutex<_>>` (to share one generator across concurrently
+/// running protocol steps) no longer need to lock it and dereference the
+/// guard at every call site; they can clone a `SharedRng` and pass it
+/// straight through. Locking is synchronous (`std::sync::Mutex`, not
+/// `tokio::sync::Mutex`): `RngCore`'s methods aren't async, so a call site
+/// already inside an async context should hold the inner RNG behind a
+/// blocking mutex, not an async one.
+///
+/// `std`-only: built on `std::sync::{Arc, Mutex}`, which aren't available
+/// under `alloc`-only `no_std` targets (a no_std equivalent would need a
+/// spinlock-backed `Mutex`, which this crate doesn't otherwise depend on).
+#[cfg(feature = "std")]
+#[derive(Clone)]
+pub struct SharedRng<R>(std::sync::Arc<std::sync::Mutex<R>>);
+
+#[cfg(feature = "std")]
+impl<R> SharedRng<R> {
+    pub fn new(rng: R) -> Self {
+        SharedRng(std::sync::Arc::new(std::sync::Mutex::new(rng)))
+    }
+}
+
+impl<R: rand::RngCore> rand::RngCore for SharedRng<R> {
+    fn next_u32(&mut self) -> u32 {
+        self.0.lock().expect("SharedRng mutex poisoned").next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.lock().expect("SharedRng mutex poisoned").next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.lock().expect("SharedRng mutex poisoned").fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.0.lock().expect("SharedRng mutex poisoned").try_fill_bytes(dest)
+    }
+}
+
+// Blanket impl: a shared RNG backed by a `CryptoRng` is itself a `CryptoRng`,
+// so `SharedRng<ChaCha20Rng>` and friends satisfy proof constructors'
+// `R: CryptoRng + RngCore` bound without any per-type wiring.
+impl<R: rand::RngCore + rand::CryptoRng> rand::CryptoRng for SharedRng<R> {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use rand::thread_rng;
+    use rand::{thread_rng, RngCore};
 
     #[test]
     fn test_must_get_random_int() {
@@ -71,4 +337,50 @@ mod tests {
         let random_int = get_random_positive_int(&mut rng, &less_than);
         assert!(random_int < less_than);
     }
+
+    #[test]
+    fn test_is_probable_prime_known_primes() {
+        for p in [2u32, 3, 5, 7, 11, 13, 2003, 7919] {
+            assert!(is_probable_prime(&BigInt::from(p), 20), "{} should be prime", p);
+        }
+    }
+
+    #[test]
+    fn test_is_probable_prime_known_composites() {
+        for c in [1u32, 4, 6, 8, 9, 15, 21, 2001, 7921] {
+            assert!(!is_probable_prime(&BigInt::from(c), 20), "{} should be composite", c);
+        }
+    }
+
+    #[test]
+    fn test_get_random_prime_int_is_prime_and_sized() {
+        let mut rng = thread_rng();
+        let p = get_random_prime_int(&mut rng, 64, 20);
+        assert_eq!(p.bits(), 64);
+        assert!(is_probable_prime(&p, 40));
+    }
+
+    #[test]
+    fn test_shared_rng_usable_as_rng_param() {
+        let mut shared = SharedRng::new(thread_rng());
+        let less_than = BigInt::from(100);
+        let random_int = get_random_positive_int(&mut shared, &less_than);
+        assert!(random_int < less_than);
+    }
+
+    #[test]
+    fn test_shared_rng_clones_see_the_same_underlying_stream() {
+        // Two clones share one locked generator -- draws interleaved across
+        // them advance a single underlying stream, so they match, in order,
+        // the draws of an identically seeded generator used on its own.
+        use rand::{rngs::StdRng, SeedableRng};
+        let shared = SharedRng::new(StdRng::seed_from_u64(7));
+        let mut reference = StdRng::seed_from_u64(7);
+        let mut a = shared.clone();
+        let mut b = shared.clone();
+        assert_eq!(a.next_u64(), reference.next_u64());
+        assert_eq!(b.next_u64(), reference.next_u64());
+        assert_eq!(a.next_u64(), reference.next_u64());
+        assert_eq!(b.next_u64(), reference.next_u64());
+    }
 }
@@ -0,0 +1,267 @@
+// Copyright © 2019 Binance
+//
+// This file is part of Binance. The full Binance copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+// Systematic Reed-Solomon erasure coding over GF(256), used by
+// `tss::erasure_broadcast` to split a broadcast payload into `n` shards of
+// which any `k` reconstruct the original bytes. This is the classical
+// "erasure code" construction: a Vandermonde encoding matrix (MDS, so every
+// square submatrix is invertible over distinct evaluation points) is
+// row-reduced against its own first `k` rows so that the first `k` output
+// shards equal the input chunks verbatim (the "systematic" part) and the
+// remaining `n - k` are parity. Recovery solves the same matrix, restricted
+// to whichever `k` of the `n` rows actually arrived, for the original data.
+
+/// GF(256) arithmetic using the AES/QR-code primitive polynomial (0x11D),
+/// via precomputed log/exp tables so every multiply/divide is a table
+/// lookup instead of a polynomial reduction.
+mod gf256 {
+    const PRIMITIVE_POLY: u16 = 0x11D;
+
+    pub struct Tables {
+        exp: [u8; 512],
+        log: [u8; 256],
+    }
+
+    impl Tables {
+        pub fn new() -> Self {
+            let mut exp = [0u8; 512];
+            let mut log = [0u8; 256];
+            let mut x: u16 = 1;
+            for (i, slot) in exp.iter_mut().enumerate().take(255) {
+                *slot = x as u8;
+                log[x as usize] = i as u8;
+                x <<= 1;
+                if x & 0x100 != 0 {
+                    x ^= PRIMITIVE_POLY;
+                }
+            }
+            // Mirror the table past 255 so `mul`/`div` can add exponents
+            // without reducing mod 255 themselves.
+            for i in 255..512 {
+                exp[i] = exp[i - 255];
+            }
+            Tables { exp, log }
+        }
+
+        pub fn mul(&self, a: u8, b: u8) -> u8 {
+            if a == 0 || b == 0 {
+                return 0;
+            }
+            self.exp[self.log[a as usize] as usize + self.log[b as usize] as usize]
+        }
+
+        pub fn div(&self, a: u8, b: u8) -> u8 {
+            assert!(b != 0, "division by zero in GF(256)");
+            if a == 0 {
+                return 0;
+            }
+            self.exp[255 + self.log[a as usize] as usize - self.log[b as usize] as usize]
+        }
+
+        pub fn pow(&self, a: u8, mut power: usize) -> u8 {
+            if a == 0 {
+                return if power == 0 { 1 } else { 0 };
+            }
+            power %= 255;
+            self.exp[(self.log[a as usize] as usize * power) % 255]
+        }
+    }
+}
+
+/// A `k x k` matrix over GF(256), row-major, used for both constructing the
+/// systematic encoding matrix and inverting whichever `k` rows of it a
+/// decoder actually received.
+struct Matrix {
+    data: Vec<Vec<u8>>,
+    size: usize,
+}
+
+impl Matrix {
+    fn identity(size: usize) -> Self {
+        let mut data = vec![vec![0u8; size]; size];
+        for (i, row) in data.iter_mut().enumerate() {
+            row[i] = 1;
+        }
+        Matrix { data, size }
+    }
+
+    /// Inverts this matrix over GF(256) via Gauss-Jordan elimination with
+    /// the identity matrix augmented alongside it. Panics if the matrix is
+    /// singular, which indicates a caller bug (a non-MDS evaluation point
+    /// set), not a runtime/data condition.
+    fn invert(&self, gf: &gf256::Tables) -> Matrix {
+        let n = self.size;
+        let mut left = self.data.clone();
+        let mut right = Matrix::identity(n).data;
+
+        for col in 0..n {
+            if left[col][col] == 0 {
+                let pivot = (col + 1..n)
+                    .find(|&r| left[r][col] != 0)
+                    .expect("singular matrix in Reed-Solomon construction");
+                left.swap(col, pivot);
+                right.swap(col, pivot);
+            }
+            let inv = gf.div(1, left[col][col]);
+            for c in 0..n {
+                left[col][c] = gf.mul(left[col][c], inv);
+                right[col][c] = gf.mul(right[col][c], inv);
+            }
+            for row in 0..n {
+                if row == col {
+                    continue;
+                }
+                let factor = left[row][col];
+                if factor == 0 {
+                    continue;
+                }
+                for c in 0..n {
+                    left[row][c] ^= gf.mul(factor, left[col][c]);
+                    right[row][c] ^= gf.mul(factor, right[col][c]);
+                }
+            }
+        }
+        Matrix { data: right, size: n }
+    }
+}
+
+/// The `n x k` systematic Reed-Solomon generator matrix for a given
+/// `(n, k)`: row `i` produces shard `i` from the `k` data chunks. Rows
+/// `0..k` are the identity (shard `i` == data chunk `i`); rows `k..n` are
+/// parity, built from an `n x k` Vandermonde matrix (evaluation points
+/// `1..=n`, which are guaranteed distinct and nonzero) row-reduced so its
+/// own first `k` rows become the identity.
+pub struct Generator {
+    gf: gf256::Tables,
+    rows: Vec<Vec<u8>>,
+    k: usize,
+}
+
+impl Generator {
+    pub fn new(n: usize, k: usize) -> Self {
+        assert!(k >= 1 && k <= n, "Reed-Solomon requires 1 <= k <= n");
+        let gf = gf256::Tables::new();
+
+        // Vandermonde matrix: row i, column j -> x_i^j, for x_i = i+1 (so
+        // every evaluation point is nonzero and distinct).
+        let vandermonde: Vec<Vec<u8>> = (0..n)
+            .map(|i| {
+                let x = (i + 1) as u8;
+                (0..k).map(|j| gf.pow(x, j)).collect()
+            })
+            .collect();
+
+        let top_k = Matrix { data: vandermonde[0..k].to_vec(), size: k };
+        let top_k_inv = top_k.invert(&gf);
+
+        let rows: Vec<Vec<u8>> = vandermonde
+            .iter()
+            .map(|row| {
+                (0..k)
+                    .map(|col| {
+                        (0..k).fold(0u8, |acc, t| acc ^ gf.mul(row[t], top_k_inv.data[t][col]))
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Generator { gf, rows, k }
+    }
+
+    /// Produces the `n` shards for `data`, zero-padded up to a multiple of
+    /// `k` and split evenly across the `k` data chunks.
+    pub fn encode(&self, data: &[u8]) -> Vec<Vec<u8>> {
+        let chunk_len = data.len().div_ceil(self.k).max(1);
+        let mut padded = data.to_vec();
+        padded.resize(chunk_len * self.k, 0);
+        let chunks: Vec<&[u8]> = padded.chunks(chunk_len).collect();
+
+        self.rows
+            .iter()
+            .map(|row| {
+                (0..chunk_len)
+                    .map(|byte_idx| {
+                        row.iter()
+                            .enumerate()
+                            .fold(0u8, |acc, (j, &coeff)| acc ^ self.gf.mul(coeff, chunks[j][byte_idx]))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Reconstructs the original (unpadded) data from any `k` of the `n`
+    /// shards, given as `(shard_index, shard_bytes)` pairs and the original
+    /// byte length to trim the encode-time zero padding back off.
+    pub fn decode(&self, shards: &[(usize, Vec<u8>)], original_len: usize) -> Vec<u8> {
+        assert!(shards.len() >= self.k, "need at least k shards to decode");
+        let chosen = &shards[0..self.k];
+        let sub = Matrix {
+            data: chosen.iter().map(|(idx, _)| self.rows[*idx].clone()).collect(),
+            size: self.k,
+        };
+        let sub_inv = sub.invert(&self.gf);
+
+        let chunk_len = chosen[0].1.len();
+        let mut out = Vec::with_capacity(chunk_len * self.k);
+        for row in 0..self.k {
+            for byte_idx in 0..chunk_len {
+                let value = (0..self.k).fold(0u8, |acc, col| {
+                    acc ^ self.gf.mul(sub_inv.data[row][col], chosen[col].1[byte_idx])
+                });
+                out.push(value);
+            }
+        }
+        out.truncate(original_len);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode_round_trip_with_exact_k_shards() {
+        let generator = Generator::new(7, 3);
+        let data = b"reed-solomon erasure coded payload".to_vec();
+        let shards = generator.encode(&data);
+        assert_eq!(shards.len(), 7);
+
+        // Decode from a non-systematic subset (some parity, some data).
+        let subset: Vec<(usize, Vec<u8>)> = vec![
+            (1, shards[1].clone()),
+            (4, shards[4].clone()),
+            (6, shards[6].clone()),
+        ];
+        let recovered = generator.decode(&subset, data.len());
+        assert_eq!(recovered, data);
+    }
+
+    #[test]
+    fn test_systematic_shards_equal_original_chunks() {
+        let generator = Generator::new(5, 2);
+        let data = b"abcd".to_vec(); // splits into two 2-byte chunks, no padding
+        let shards = generator.encode(&data);
+        assert_eq!(shards[0], b"ab");
+        assert_eq!(shards[1], b"cd");
+    }
+
+    #[test]
+    fn test_any_k_of_n_subset_decodes_identically() {
+        let generator = Generator::new(6, 3);
+        let data = b"some odd length payload!".to_vec();
+        let shards = generator.encode(&data);
+
+        let subset_a: Vec<(usize, Vec<u8>)> =
+            vec![0, 1, 2].into_iter().map(|i| (i, shards[i].clone())).collect();
+        let subset_b: Vec<(usize, Vec<u8>)> =
+            vec![2, 3, 5].into_iter().map(|i| (i, shards[i].clone())).collect();
+
+        assert_eq!(generator.decode(&subset_a, data.len()), data);
+        assert_eq!(generator.decode(&subset_b, data.len()), data);
+    }
+}
@@ -1,6 +1,11 @@
-use num_bigint::BigInt;
+use num_bigint::{BigInt, RandBigInt};
 use num_traits::One;
-use crate::common::random::is_probable_prime;
+use rand::Rng;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+use crate::common::random::{is_probable_prime, is_probable_prime_with_rng, get_random_prime_int_default, passes_small_prime_sieve};
 
 pub struct GermainSafePrime {
     q: BigInt,
@@ -23,6 +28,78 @@ impl GermainSafePrime {
     pub fn validate(&self) -> bool {
         is_probable_prime(&self.q, 30) && self.p == (&self.q * 2 + BigInt::one()) && is_probable_prime(&self.p, 30)
     }
+
+    /// Rejection-samples a Sophie Germain prime `q` of `bits` bits such that
+    /// `p = 2q + 1` (a safe prime) is also prime, verified with Miller-Rabin +
+    /// Baillie-PSW via `is_probable_prime`. Every such safe prime is `≡ 3 mod
+    /// 4`, so a Paillier modulus built from two of them is a Blum integer,
+    /// which the Paillier-Blum modulus proof in `crypto::modproof` relies on.
+    pub fn generate<R: Rng>(rng: &mut R, bits: usize) -> Self {
+        loop {
+            let q = get_random_prime_int_default(rng, bits);
+            let p = &q * 2 + BigInt::one();
+            if is_probable_prime_with_rng(rng, &p, 30) {
+                return GermainSafePrime { q, p };
+            }
+        }
+    }
+
+    /// Same search as `generate`, spread across `workers` threads racing each
+    /// other: safe-prime search is the dominant cost of Paillier/range-proof
+    /// setup, and rejection sampling is embarrassingly parallel since each
+    /// attempt is independent. Each worker draws its own candidate `q` (top
+    /// two bits set, so `q` has exactly `bits` bits), sieves both `q` and
+    /// `p = 2q + 1` against `passes_small_prime_sieve`'s small-prime table
+    /// before paying for a modpow, then runs Miller-Rabin + Baillie-PSW via
+    /// `is_probable_prime_with_rng` on the cheaper `q` first and only then on
+    /// `p`. Whichever worker finds a valid pair first wins the race over the
+    /// channel; the rest notice `found` and stop at their next iteration.
+    pub fn generate_concurrent(bits: usize, workers: usize) -> Self {
+        let workers = workers.max(1);
+        let found = Arc::new(AtomicBool::new(false));
+        let (tx, rx) = mpsc::channel();
+
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                let found = found.clone();
+                let tx = tx.clone();
+                thread::spawn(move || {
+                    let mut rng = rand::thread_rng();
+                    while !found.load(Ordering::Relaxed) {
+                        let mut q = rng.gen_bigint(bits as u64);
+                        q.set_bit((bits - 1) as u64, true);
+                        q.set_bit((bits - 2) as u64, true);
+                        q.set_bit(0, true);
+                        if !passes_small_prime_sieve(&q) {
+                            continue;
+                        }
+                        let p = &q * 2 + BigInt::one();
+                        if !passes_small_prime_sieve(&p) {
+                            continue;
+                        }
+                        if !is_probable_prime_with_rng(&mut rng, &q, 30) {
+                            continue;
+                        }
+                        if !is_probable_prime_with_rng(&mut rng, &p, 30) {
+                            continue;
+                        }
+                        if !found.swap(true, Ordering::Relaxed) {
+                            let _ = tx.send(GermainSafePrime { q, p });
+                        }
+                        return;
+                    }
+                })
+            })
+            .collect();
+        drop(tx);
+
+        let winner = rx.recv().expect("at least one worker finds a safe prime");
+        found.store(true, Ordering::Relaxed);
+        for handle in handles {
+            let _ = handle.join();
+        }
+        winner
+    }
 }
 #[cfg(test)]
 mod tests {
@@ -36,4 +113,24 @@ mod tests {
         let gsp = GermainSafePrime::new(q, p);
         assert!(gsp.validate());
     }
+
+    #[test]
+    fn test_generate_produces_valid_safe_prime() {
+        let mut rng = rand::thread_rng();
+        let gsp = GermainSafePrime::generate(&mut rng, 32);
+        assert!(gsp.validate());
+    }
+
+    #[test]
+    fn test_generate_concurrent_produces_valid_safe_prime() {
+        let gsp = GermainSafePrime::generate_concurrent(32, 4);
+        assert!(gsp.validate());
+        assert_eq!(gsp.prime().bits(), 32);
+    }
+
+    #[test]
+    fn test_generate_concurrent_tolerates_single_worker() {
+        let gsp = GermainSafePrime::generate_concurrent(32, 1);
+        assert!(gsp.validate());
+    }
 }
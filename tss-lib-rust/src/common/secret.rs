@@ -0,0 +1,268 @@
+// A best-effort zeroizing wrapper around `BigInt`-valued secrets.
+//
+// `num_bigint::BigInt` doesn't implement `zeroize::Zeroize` itself (it
+// doesn't expose its internal digit buffer), so this can't scrub the heap
+// bytes a prior allocation occupied the way `zeroize` does for fixed-size
+// byte arrays. What it does do is guarantee the *current* value is
+// overwritten with zero as soon as the wrapper is dropped or explicitly
+// zeroized, rather than leaking the last secret value in a value that's
+// simply gone out of scope and awaits the allocator reusing its pages.
+// Borrows the `zeroize`/`clear_on_drop` discipline used in the zk-token-sdk
+// for ephemeral sigma-protocol randomness and long-lived key shares alike.
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use core::ops::{Deref, DerefMut};
+use zeroize::Zeroize;
+
+#[derive(Clone)]
+pub struct SecretBigInt(BigInt);
+
+impl SecretBigInt {
+    pub fn new(value: BigInt) -> Self {
+        SecretBigInt(value)
+    }
+
+    pub fn into_inner(mut self) -> BigInt {
+        core::mem::replace(&mut self.0, BigInt::zero())
+    }
+
+    /// Computes `base^self mod modulus`: the secret is the exponent, as in a
+    /// sigma-protocol commitment `h^a mod n`. The result is public (it's a
+    /// commitment, not the witness itself), so this returns a plain `BigInt`.
+    pub fn exp(&self, base: &BigInt, modulus: &BigInt) -> BigInt {
+        base.modpow(&self.0, modulus)
+    }
+
+    /// Computes `(self + other) mod modulus`, keeping the sum wrapped since a
+    /// value built from two secrets (e.g. a sigma-protocol response before
+    /// the challenge bit decides whether it's revealed) is still secret.
+    pub fn add(&self, other: &SecretBigInt, modulus: &BigInt) -> SecretBigInt {
+        SecretBigInt((&self.0 + &other.0).mod_floor(modulus))
+    }
+
+    /// Computes `(self * other) mod modulus`, keeping the product wrapped for
+    /// the same reason as [`add`](Self::add).
+    pub fn mul(&self, other: &SecretBigInt, modulus: &BigInt) -> SecretBigInt {
+        SecretBigInt((&self.0 * &other.0).mod_floor(modulus))
+    }
+
+    /// Like [`exp`](Self::exp), but computes `base^self mod (p * q)` via CRT
+    /// when the prover knows the factorization `n = p * q`: reduces the
+    /// secret exponent mod `p-1` and `q-1`, exponentiates over each
+    /// (much smaller) prime separately, and recombines with Garner's
+    /// formula. Roughly four times faster than [`exp`](Self::exp) over the
+    /// full modulus, since each sub-exponentiation works on operands and a
+    /// modulus about half the bit length of `n = p * q`.
+    pub fn exp_crt(&self, base: &BigInt, p: &BigInt, q: &BigInt, n: &BigInt) -> BigInt {
+        let exp_p = self.0.mod_floor(&(p - BigInt::one()));
+        let exp_q = self.0.mod_floor(&(q - BigInt::one()));
+        let r_p = base.modpow(&exp_p, p);
+        let r_q = base.modpow(&exp_q, q);
+
+        let q_inv_p = q.modinv(p).expect("p and q are distinct primes and therefore coprime");
+        let h = ((&r_p - &r_q) * q_inv_p).mod_floor(p);
+        (&r_q + q * h).mod_floor(n)
+    }
+}
+
+impl core::fmt::Debug for SecretBigInt {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("SecretBigInt").field(&"REDACTED").finish()
+    }
+}
+
+impl Zeroize for SecretBigInt {
+    fn zeroize(&mut self) {
+        self.0 = BigInt::zero();
+    }
+}
+
+impl Drop for SecretBigInt {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl Deref for SecretBigInt {
+    type Target = BigInt;
+    fn deref(&self) -> &BigInt {
+        &self.0
+    }
+}
+
+impl DerefMut for SecretBigInt {
+    fn deref_mut(&mut self) -> &mut BigInt {
+        &mut self.0
+    }
+}
+
+impl From<BigInt> for SecretBigInt {
+    fn from(value: BigInt) -> Self {
+        SecretBigInt(value)
+    }
+}
+
+impl PartialEq for SecretBigInt {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+/// Same scrubbing discipline as [`SecretBigInt`], for code built on
+/// `num_bigint_dig::BigInt` (the Paillier/range-proof side of the crate)
+/// instead of `num_bigint::BigInt`. Like `SecretBigInt`, `Debug` is
+/// redacted rather than printing the value, and the type deliberately does
+/// not derive `Serialize` -- a secret scalar that needs to go on the wire
+/// should be unwrapped via `into_inner` at the call site, not accidentally
+/// picked up by `#[derive(Serialize)]` on a containing struct.
+///
+/// Built on `core::ops`/`core::mem`/`core::fmt` rather than their `std`
+/// equivalents so the MtA range-proof module (which wraps its witnesses in
+/// this type) can compile under `#![no_std]` with only `alloc`.
+#[derive(Clone)]
+pub struct SecretDigInt(num_bigint_dig::BigInt);
+
+impl SecretDigInt {
+    pub fn new(value: num_bigint_dig::BigInt) -> Self {
+        SecretDigInt(value)
+    }
+
+    pub fn into_inner(mut self) -> num_bigint_dig::BigInt {
+        core::mem::replace(&mut self.0, num_bigint_dig::BigInt::zero())
+    }
+}
+
+impl Zeroize for SecretDigInt {
+    fn zeroize(&mut self) {
+        self.0 = num_bigint_dig::BigInt::zero();
+    }
+}
+
+impl Drop for SecretDigInt {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl Deref for SecretDigInt {
+    type Target = num_bigint_dig::BigInt;
+    fn deref(&self) -> &num_bigint_dig::BigInt {
+        &self.0
+    }
+}
+
+impl DerefMut for SecretDigInt {
+    fn deref_mut(&mut self) -> &mut num_bigint_dig::BigInt {
+        &mut self.0
+    }
+}
+
+impl From<num_bigint_dig::BigInt> for SecretDigInt {
+    fn from(value: num_bigint_dig::BigInt) -> Self {
+        SecretDigInt(value)
+    }
+}
+
+impl PartialEq for SecretDigInt {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl core::fmt::Debug for SecretDigInt {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("SecretDigInt").field(&"REDACTED").finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::ToBigInt;
+
+    #[test]
+    fn test_zeroize_replaces_value_with_zero() {
+        let mut secret = SecretBigInt::new(123.to_bigint().unwrap());
+        secret.zeroize();
+        assert_eq!(*secret, BigInt::zero());
+    }
+
+    #[test]
+    fn test_drop_zeroizes_before_deallocation() {
+        // Can't observe memory after drop, but this at least exercises the
+        // Drop impl under a sanitizer/miri run without panicking or leaking.
+        let secret = SecretBigInt::new(456.to_bigint().unwrap());
+        drop(secret);
+    }
+
+    #[test]
+    fn test_into_inner_returns_the_wrapped_value() {
+        let secret = SecretBigInt::new(789.to_bigint().unwrap());
+        let value = secret.into_inner();
+        assert_eq!(value, 789.to_bigint().unwrap());
+    }
+
+    #[test]
+    fn test_secret_dig_int_zeroize_replaces_value_with_zero() {
+        let mut secret = SecretDigInt::new(num_bigint_dig::BigInt::from(123));
+        secret.zeroize();
+        assert_eq!(*secret, num_bigint_dig::BigInt::zero());
+    }
+
+    #[test]
+    fn test_secret_dig_int_into_inner_returns_the_wrapped_value() {
+        let secret = SecretDigInt::new(num_bigint_dig::BigInt::from(789));
+        let value = secret.into_inner();
+        assert_eq!(value, num_bigint_dig::BigInt::from(789));
+    }
+
+    #[test]
+    fn test_secret_dig_int_debug_is_redacted() {
+        let secret = SecretDigInt::new(num_bigint_dig::BigInt::from(42));
+        assert_eq!(format!("{:?}", secret), "SecretDigInt(\"REDACTED\")");
+    }
+
+    #[test]
+    fn test_secret_big_int_debug_is_redacted() {
+        let secret = SecretBigInt::new(42.to_bigint().unwrap());
+        assert_eq!(format!("{:?}", secret), "SecretBigInt(\"REDACTED\")");
+    }
+
+    #[test]
+    fn test_secret_big_int_exp_computes_modpow_of_secret_exponent() {
+        let exponent = SecretBigInt::new(5.to_bigint().unwrap());
+        let result = exponent.exp(&3.to_bigint().unwrap(), &13.to_bigint().unwrap());
+        assert_eq!(result, 9.to_bigint().unwrap()); // 3^5 mod 13 == 243 mod 13 == 9
+    }
+
+    #[test]
+    fn test_secret_big_int_add_reduces_mod_modulus() {
+        let a = SecretBigInt::new(8.to_bigint().unwrap());
+        let b = SecretBigInt::new(9.to_bigint().unwrap());
+        let sum = a.add(&b, &13.to_bigint().unwrap());
+        assert_eq!(*sum, 4.to_bigint().unwrap()); // (8 + 9) mod 13 == 4
+    }
+
+    #[test]
+    fn test_secret_big_int_mul_reduces_mod_modulus() {
+        let a = SecretBigInt::new(8.to_bigint().unwrap());
+        let b = SecretBigInt::new(9.to_bigint().unwrap());
+        let product = a.mul(&b, &13.to_bigint().unwrap());
+        assert_eq!(*product, 7.to_bigint().unwrap()); // (8 * 9) mod 13 == 72 mod 13 == 7
+    }
+
+    #[test]
+    fn test_secret_big_int_exp_crt_matches_full_modulus_exp() {
+        let p = 11.to_bigint().unwrap();
+        let q = 23.to_bigint().unwrap();
+        let n = &p * &q;
+        let base = 7.to_bigint().unwrap();
+        let exponent = SecretBigInt::new(123.to_bigint().unwrap());
+
+        let via_crt = exponent.exp_crt(&base, &p, &q, &n);
+        let via_full = exponent.exp(&base, &n);
+        assert_eq!(via_crt, via_full);
+    }
+}
@@ -0,0 +1,205 @@
+// Threshold BLS signatures over the pairing-friendly curve BLS12-381.
+//
+// Every other curve this crate supports (`tss::curve::CurveParams`'s
+// Secp256k1/Ed25519/Secp256r1/Secp384r1 variants, and the generic
+// `ECPoint<C>` proofs) is a plain elliptic curve with no pairing -- GG18's
+// ECDSA flow is interactive precisely because a plain EC group can't check
+// `e(sigma, g2) == e(H(m), Y)` the way BLS does. BLS12-381 is pairing
+// -friendly, so this module is deliberately *not* a `CurveParams` variant
+// or a `Curve + CurveArithmetic` instantiation of the existing proofs: its
+// `G1Projective`/`G2Projective` point types and the `pairing()` operation
+// come from the `bls12_381` crate's own API, not this crate's `ECPoint<C>`.
+//
+// What it reuses from the rest of this crate is the *threshold* machinery:
+// a partial signature is `H(m)^{share_i}` for a Shamir share `share_i` of
+// the group secret key (the same `crypto::vss::feldman_vss::Share` shape
+// used elsewhere), and combining partial signatures/public keys is the same
+// Lagrange-interpolation-in-the-exponent `feldman_vss::interpolate_public_point`
+// already does for the GG18 proofs -- just instantiated over BLS12-381's G1
+// (signatures) and G2 (public keys) instead of secp256k1/Ed25519 points.
+//
+// Unlike GG18 ECDSA, combining BLS partial signatures needs no further
+// interaction once every signer's partial signature is known: no extra
+// round of commit/open or MtA, just Lagrange weights applied to points
+// already in hand. That non-interactivity is BLS threshold signing's main
+// draw over the interactive flow the rest of this crate centers on.
+
+use bls12_381::{pairing, G1Affine, G1Projective, G2Affine, G2Projective, Scalar};
+use num_bigint::{BigInt, Sign};
+use num_traits::Zero;
+use thiserror::Error;
+
+use crate::common::hash::sha512_256;
+use crate::common::int::ModInt;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum BlsError {
+    #[error("invalid parameters: {0}")]
+    InvalidParameters(String),
+    #[error("hash-to-curve did not find a valid point within {0} attempts")]
+    HashToCurveFailed(u32),
+}
+
+const HASH_TO_CURVE_MAX_ATTEMPTS: u32 = 256;
+
+fn bigint_to_scalar(q: &BigInt, x: &BigInt) -> Scalar {
+    let reduced = x.modpow(&BigInt::from(1u8), q);
+    let mut bytes = [0u8; 32];
+    let be = reduced.to_bytes_be().1;
+    let start = 32usize.saturating_sub(be.len());
+    bytes[start..].copy_from_slice(&be[be.len().saturating_sub(32)..]);
+    bytes.reverse(); // Scalar::from_bytes wants little-endian.
+    Scalar::from_bytes(&bytes).expect("value reduced mod the BLS scalar field order is always canonical")
+}
+
+/// Try-and-increment hash-to-G1: hashes `msg || ctr` and attempts to decode
+/// it as a compressed curve point, incrementing `ctr` and retrying on
+/// failure. Roughly half of all candidate x-coordinates lie on the curve, and
+/// each decoded point is multiplied by the cofactor to land in G1, so this
+/// terminates quickly in practice.
+pub fn hash_to_g1(msg: &[u8]) -> Result<G1Projective, BlsError> {
+    for ctr in 0..HASH_TO_CURVE_MAX_ATTEMPTS {
+        let digest = sha512_256(&[msg, &ctr.to_le_bytes()]);
+        let mut compressed = [0u8; 48];
+        // Top byte carries the compression/infinity/sign-of-y flag bits
+        // `from_compressed_unchecked` expects; bit 0x20 of the digest's first
+        // byte picks the sign of y, and 0x80 marks "compressed, not infinity".
+        // The remaining digest bytes fill the top of x; the rest of x is zero.
+        compressed[0] = 0x80 | (digest[0] & 0x20);
+        compressed[1..digest.len()].copy_from_slice(&digest[1..]);
+        let candidate: Option<G1Affine> = G1Affine::from_compressed_unchecked(&compressed).into();
+        if let Some(point) = candidate {
+            let cleared = G1Projective::from(point).clear_cofactor();
+            if !bool::from(cleared.is_identity()) {
+                return Ok(cleared);
+            }
+        }
+    }
+    Err(BlsError::HashToCurveFailed(HASH_TO_CURVE_MAX_ATTEMPTS))
+}
+
+/// Produces a partial BLS signature `H(m)^{share}` for this party's Shamir
+/// share of the group secret key.
+pub fn partial_sign(q: &BigInt, msg: &[u8], share: &BigInt) -> Result<G1Projective, BlsError> {
+    let h = hash_to_g1(msg)?;
+    let x = bigint_to_scalar(q, share);
+    Ok(h * x)
+}
+
+/// Lagrange coefficient `l_i(0)` for party `id` among `all_ids`, the same
+/// computation `feldman_vss::reconstruct_secret` performs, extracted here
+/// since it's needed to combine both G1 (signatures) and G2 (public keys).
+fn lagrange_coefficient(q: &BigInt, id: &BigInt, all_ids: &[BigInt]) -> BigInt {
+    let mod_q = ModInt::new(q.clone());
+    let mut coeff = BigInt::from(1u8);
+    for other_id in all_ids {
+        if other_id == id {
+            continue;
+        }
+        let num = mod_q.sub(&BigInt::zero(), other_id);
+        let den = mod_q.sub(id, other_id);
+        let den_inv = mod_q
+            .mod_inverse(&den)
+            .expect("distinct party ids have an invertible difference mod a prime curve order");
+        coeff = mod_q.mul(&coeff, &mod_q.mul(&num, &den_inv));
+    }
+    coeff
+}
+
+/// Combines `t+1` partial signatures `(id, H(m)^{share_id})` into the full
+/// group signature `H(m)^x` via Lagrange interpolation in the exponent --
+/// no further communication between signers is needed.
+pub fn combine_partial_signatures(q: &BigInt, partials: &[(BigInt, G1Projective)]) -> Result<G1Projective, BlsError> {
+    if partials.is_empty() {
+        return Err(BlsError::InvalidParameters("no partial signatures to combine".to_string()));
+    }
+    let ids: Vec<BigInt> = partials.iter().map(|(id, _)| id.clone()).collect();
+    let mut acc = G1Projective::identity();
+    for (id, partial) in partials {
+        let coeff = lagrange_coefficient(q, id, &ids);
+        acc += *partial * bigint_to_scalar(q, &coeff);
+    }
+    Ok(acc)
+}
+
+/// Combines `t+1` per-party public key shares `(id, g2^{share_id})` into the
+/// group public key `g2^x`, the G2 counterpart of `combine_partial_signatures`.
+pub fn combine_public_key_shares(q: &BigInt, shares: &[(BigInt, G2Projective)]) -> Result<G2Projective, BlsError> {
+    if shares.is_empty() {
+        return Err(BlsError::InvalidParameters("no public key shares to combine".to_string()));
+    }
+    let ids: Vec<BigInt> = shares.iter().map(|(id, _)| id.clone()).collect();
+    let mut acc = G2Projective::identity();
+    for (id, share) in shares {
+        let coeff = lagrange_coefficient(q, id, &ids);
+        acc += *share * bigint_to_scalar(q, &coeff);
+    }
+    Ok(acc)
+}
+
+/// Verifies a (partial or combined) BLS signature: `e(sigma, g2) == e(H(m), Y)`.
+pub fn verify(msg: &[u8], sigma: &G1Projective, y_pub: &G2Projective) -> Result<bool, BlsError> {
+    let h = hash_to_g1(msg)?;
+    let lhs = pairing(&G1Affine::from(sigma), &G2Affine::generator());
+    let rhs = pairing(&G1Affine::from(h), &G2Affine::from(y_pub));
+    Ok(lhs == rhs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::ToBigInt;
+
+    // BLS12-381's scalar field order r: the Lagrange weights must be computed
+    // mod the order of G1/G2, or the interpolated exponent is only congruent
+    // to the secret mod the wrong number.
+    fn test_q() -> BigInt {
+        BigInt::parse_bytes(b"73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001", 16).unwrap()
+    }
+
+    #[test]
+    fn test_hash_to_g1_finds_a_point() {
+        let h = hash_to_g1(b"hello-bls").unwrap();
+        assert_ne!(h, G1Projective::identity());
+    }
+
+    #[test]
+    fn test_partial_sign_and_combine_matches_direct_signature() {
+        let q = test_q();
+        let msg = b"threshold-bls-message";
+
+        // 2-of-3 threshold secret sharing of x, evaluated at ids 1, 2, 3 by
+        // a degree-1 polynomial f(z) = x + a1*z (a toy Shamir share, not
+        // going through the full VSS machinery since this test is only
+        // checking the BLS combination math).
+        let x = 12345.to_bigint().unwrap();
+        let a1 = 6789.to_bigint().unwrap();
+        let mod_q = ModInt::new(q.clone());
+        let ids: Vec<BigInt> = vec![1, 2, 3].into_iter().map(BigInt::from).collect();
+        let shares: Vec<BigInt> = ids.iter().map(|id| mod_q.add(&x, &mod_q.mul(&a1, id))).collect();
+
+        let partials: Vec<(BigInt, G1Projective)> = ids
+            .iter()
+            .zip(shares.iter())
+            .map(|(id, share)| (id.clone(), partial_sign(&q, msg, share).unwrap()))
+            .collect();
+
+        // Combine using only the first two (threshold) partial signatures.
+        let combined = combine_partial_signatures(&q, &partials[..2]).unwrap();
+        let direct = partial_sign(&q, msg, &x).unwrap();
+        assert_eq!(combined, direct);
+    }
+
+    #[test]
+    fn test_verify_accepts_a_genuine_signature_and_rejects_a_wrong_message() {
+        let q = test_q();
+        let x = 42.to_bigint().unwrap();
+        let x_scalar = bigint_to_scalar(&q, &x);
+        let y_pub = G2Projective::generator() * x_scalar;
+
+        let msg = b"sign-me";
+        let sigma = partial_sign(&q, msg, &x).unwrap();
+        assert!(verify(msg, &sigma, &y_pub).unwrap());
+        assert!(!verify(b"not-the-message", &sigma, &y_pub).unwrap());
+    }
+}
@@ -1,7 +1,10 @@
 use k256::elliptic_curve::sec1::ToEncodedPoint;
-use k256::{Secp256k1, PublicKey};
+use k256::elliptic_curve::group::Group;
+use k256::elliptic_curve::{Field, PrimeField};
+use k256::{ProjectivePoint, PublicKey, Scalar};
 use hmac::{Hmac, Mac};
-use sha2::Sha512;
+use sha2::{Digest, Sha256, Sha512};
+use ripemd::Ripemd160;
 use num_bigint::BigInt;
 use std::fmt;
 use k256::elliptic_curve::sec1::EncodedPoint;
@@ -11,17 +14,37 @@ use std::str::FromStr;
 
 type HmacSha512 = Hmac<Sha512>;
 
+/// HASH160 (`RIPEMD-160(SHA-256(data))`), the digest BIP-32 fingerprints and
+/// rust-bitcoin-style key identifiers are built from.
+fn hash160(data: &[u8]) -> [u8; 20] {
+    let sha = Sha256::digest(data);
+    Ripemd160::digest(sha).into()
+}
+
+/// Interprets a BIP-32 `I_L` as a non-hardened derivation scalar: `None` if
+/// it's `>= curve order` or zero, per BIP-32's child key derivation spec.
+fn il_to_scalar(il: &BigInt) -> Option<Scalar> {
+    let (sign, bytes) = il.to_bytes_be();
+    if sign == num_bigint::Sign::Minus || bytes.len() > 32 {
+        return None;
+    }
+    let mut buf = [0u8; 32];
+    buf[32 - bytes.len()..].copy_from_slice(&bytes);
+    let scalar = Scalar::from_repr(buf.into());
+    Option::<Scalar>::from(scalar).filter(|s| *s != Scalar::ZERO)
+}
+
 pub struct ExtendedKey {
     pub public_key: PublicKey,
     pub depth: u8,
     pub child_index: u32,
-    pub chain_code: Vec<u8>,
-    pub parent_fp: Vec<u8>,
-    pub version: Vec<u8>,
+    pub chain_code: [u8; 32],
+    pub parent_fp: [u8; 4],
+    pub version: [u8; 4],
 }
 
 impl ExtendedKey {
-    pub fn new(public_key: PublicKey, depth: u8, child_index: u32, chain_code: Vec<u8>, parent_fp: Vec<u8>, version: Vec<u8>) -> Self {
+    pub fn new(public_key: PublicKey, depth: u8, child_index: u32, chain_code: [u8; 32], parent_fp: [u8; 4], version: [u8; 4]) -> Self {
         ExtendedKey {
             public_key,
             depth,
@@ -32,6 +55,17 @@ impl ExtendedKey {
         }
     }
 
+    /// The full 20-byte BIP-32 key identifier (`HASH160` of the compressed
+    /// public key); `fingerprint()` is just its first four bytes.
+    pub fn identifier(&self) -> [u8; 20] {
+        hash160(self.public_key.to_encoded_point(true).as_bytes())
+    }
+
+    /// The BIP-32 fingerprint used as a child's `parent_fp`.
+    pub fn fingerprint(&self) -> [u8; 4] {
+        self.identifier()[..4].try_into().unwrap()
+    }
+
     pub fn derive_child_key(&self, index: u32) -> Result<ExtendedKey, Box<dyn std::error::Error>> {
         if index >= 0x80000000 {
             return Err("The index must be non-hardened".into());
@@ -41,29 +75,35 @@ impl ExtendedKey {
         }
 
         let mut mac = HmacSha512::new_from_slice(&self.chain_code)?;
-        mac.update(&self.public_key.to_encoded_point(false).as_bytes());
+        mac.update(self.public_key.to_encoded_point(true).as_bytes());
         mac.update(&index.to_be_bytes());
         let result = mac.finalize().into_bytes();
 
         let il = BigInt::from_bytes_be(num_bigint::Sign::Plus, &result[..32]);
-        let child_chain_code = result[32..].to_vec();
+        let child_chain_code: [u8; 32] = result[32..].try_into().unwrap();
 
-        // let child_public_key = self.public_key.add(&Secp256k1::generator() * il)?;
+        let il_scalar = il_to_scalar(&il).ok_or("Derived I_L is out of range or zero")?;
+        let child_point = ProjectivePoint::from(*self.public_key.as_affine()) + ProjectivePoint::GENERATOR * il_scalar;
+        if bool::from(child_point.is_identity()) {
+            return Err("Derived child key is the identity point".into());
+        }
+        let child_public_key = PublicKey::from_affine(child_point.to_affine())
+            .map_err(|e| format!("Failed to build derived child public key: {}", e))?;
 
         Ok(ExtendedKey {
-            public_key: self.public_key,
+            public_key: child_public_key,
             depth: self.depth + 1,
             child_index: index,
             chain_code: child_chain_code,
-            parent_fp: self.parent_fp.clone(),
-            version: self.version.clone(),
+            parent_fp: self.fingerprint(),
+            version: self.version,
         })
     }
 }
 
 impl fmt::Display for ExtendedKey {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "ExtendedKey {{ depth: {}, child_index: {}, chain_code: {:?}, parent_fp: {:?}, version: {:?} }}", self.depth, self.child_index, self.chain_code, self.parent_fp, self.version)
+        write!(f, "ExtendedKey {{ depth: {}, child_index: {}, chain_code: {}, parent_fp: {}, version: {} }}", self.depth, self.child_index, hex::encode(self.chain_code), hex::encode(self.parent_fp), hex::encode(self.version))
     }
 }
 #[cfg(test)]
@@ -166,13 +206,15 @@ mod tests {
         let generator = k256::ProjectivePoint::GENERATOR;
         let affine = generator.to_affine();
         let public_key = k256::PublicKey::from_affine(affine).unwrap();
-        let chain_code = vec![0u8; 32];
-        let parent_fp = vec![0u8; 4];
-        let version = vec![0u8; 4];
+        let chain_code = [0u8; 32];
+        let parent_fp = [0u8; 4];
+        let version = [0u8; 4];
         let extended_key = ExtendedKey::new(public_key, 0, 0, chain_code, parent_fp, version);
 
-        let child_key = extended_key.derive_child_key(1);
-        assert!(child_key.is_ok());
+        let child_key = extended_key.derive_child_key(1).unwrap();
+        assert_ne!(child_key.public_key, extended_key.public_key);
+        assert_eq!(child_key.parent_fp, extended_key.fingerprint());
+        assert_eq!(child_key.depth, 1);
     }
 
     #[test]
@@ -199,4 +241,55 @@ mod tests {
             assert_eq!(got, test.want_pub, "{}: derived xpub mismatch\n  got:  {}\n  want: {}", test.name, got, test.want_pub);
         }
     }
+
+    /// `test_bip32_public_derivation_vectors` only exercises the `bip32` crate's
+    /// own `derive_child`; it never calls this module's `ExtendedKey::derive_child_key`
+    /// at all. This re-derives every vector through `derive_child_key` directly and
+    /// checks the result (public key, chain code, depth, parent fingerprint, child
+    /// index) against the same expected xpub.
+    #[test]
+    fn test_derive_child_key_matches_vectors() {
+        const XPUB_VERSION: [u8; 4] = [0x04, 0x88, 0xB2, 0x1E];
+
+        for test in TEST_VECTORS {
+            if test.path.is_empty() {
+                continue; // no derivation to exercise; covered by test_bip32_public_derivation_vectors
+            }
+
+            let master = ExtendedPublicKey::<k256::ecdsa::VerifyingKey>::from_str(test.master).unwrap();
+            let master_attrs = master.attrs();
+            let master_encoded = master.public_key().to_encoded_point(true);
+            let master_pub = PublicKey::from_encoded_point(&master_encoded)
+                .into_option()
+                .expect("master xpub key is a valid secp256k1 point");
+
+            let mut ext_key = ExtendedKey::new(
+                master_pub,
+                master_attrs.depth,
+                u32::from(master_attrs.child_number),
+                master_attrs.chain_code,
+                master_attrs.parent_fingerprint,
+                XPUB_VERSION,
+            );
+            for &index in test.path {
+                ext_key = ext_key.derive_child_key(index).unwrap_or_else(|e| {
+                    panic!("{}: derive_child_key({}) failed: {}", test.name, index, e)
+                });
+            }
+
+            let want = ExtendedPublicKey::<k256::ecdsa::VerifyingKey>::from_str(test.want_pub).unwrap();
+            let want_attrs = want.attrs();
+
+            assert_eq!(
+                ext_key.public_key.to_encoded_point(true).as_bytes(),
+                want.public_key().to_encoded_point(true).as_bytes(),
+                "{}: derived public key mismatch",
+                test.name
+            );
+            assert_eq!(ext_key.chain_code, want_attrs.chain_code, "{}: chain code mismatch", test.name);
+            assert_eq!(ext_key.depth, want_attrs.depth, "{}: depth mismatch", test.name);
+            assert_eq!(ext_key.parent_fp, want_attrs.parent_fingerprint, "{}: parent fingerprint mismatch", test.name);
+            assert_eq!(ext_key.child_index, u32::from(want_attrs.child_number), "{}: child index mismatch", test.name);
+        }
+    }
 }
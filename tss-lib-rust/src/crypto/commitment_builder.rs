@@ -1,7 +1,14 @@
 use num_bigint::BigInt;
+use num_traits::ToPrimitive;
 
+// Bumped whenever the framing below changes shape, so a decoder can reject
+// bytes written by an incompatible encoder instead of misreading them.
+const FORMAT_VERSION: i64 = 1;
 const PARTS_CAP: usize = 3;
-const MAX_PART_SIZE: usize = 1 * 1024 * 1024; // 1 MB
+// Max number of BigInt elements in a single part (an element count, not a
+// byte count -- the earlier version of this check compared a part's element
+// count directly against this constant as if the two units matched).
+const MAX_PART_ELEMENTS: usize = 1 * 1024 * 1024; // 1 Mi elements
 
 pub struct Builder {
     parts: Vec<Vec<BigInt>>,
@@ -19,60 +26,164 @@ impl Builder {
         self
     }
 
+    /// Encodes the accumulated parts as `[FORMAT_VERSION, part_count, len1,
+    /// part1..., len2, part2..., ...]`, so `parse_secrets` can validate the
+    /// framing before trusting any of the length prefixes inside it.
     pub fn secrets(&self) -> Result<Vec<BigInt>, String> {
         if self.parts.len() > PARTS_CAP {
-            return Err(format!("Too many commitment parts provided: got {}, max {}", self.parts.len(), PARTS_CAP));
+            return Err(format!("too many commitment parts: got {}, max {}", self.parts.len(), PARTS_CAP));
         }
+        for (i, part) in self.parts.iter().enumerate() {
+            if part.len() > MAX_PART_ELEMENTS {
+                return Err(format!("commitment part {} too large: {} elements, max {}", i, part.len(), MAX_PART_ELEMENTS));
+            }
+        }
+
         let mut secrets = Vec::new();
+        secrets.push(BigInt::from(FORMAT_VERSION));
+        secrets.push(BigInt::from(self.parts.len()));
         for part in &self.parts {
-            let part_len = part.len();
-            if part_len > MAX_PART_SIZE {
-                return Err(format!("Commitment part too large: size {}", part_len));
-            }
-            secrets.push(BigInt::from(part_len));
+            secrets.push(BigInt::from(part.len()));
             secrets.extend_from_slice(part);
         }
         Ok(secrets)
     }
 }
 
+/// Converts a length-prefix `BigInt` to a `usize`, rejecting anything that
+/// doesn't losslessly round-trip (negative, non-integral relative to
+/// `usize`, or too large to represent) rather than letting `to_usize()`'s
+/// failure mode silently fall through to a later bounds check.
+fn checked_length(value: &BigInt) -> Result<usize, String> {
+    let n = value.to_usize().ok_or_else(|| "length prefix does not fit in a usize".to_string())?;
+    if &BigInt::from(n) != value {
+        return Err("length prefix does not round-trip through usize".to_string());
+    }
+    Ok(n)
+}
+
+/// Parses a `Builder::secrets()` frame, rejecting a zero-part frame unless
+/// the caller explicitly allows one via `parse_secrets_allow_empty`.
 pub fn parse_secrets(secrets: &[BigInt]) -> Result<Vec<Vec<BigInt>>, String> {
+    parse_secrets_inner(secrets, false)
+}
+
+/// Like [`parse_secrets`], but accepts a well-formed zero-part frame
+/// (`[FORMAT_VERSION, 0]`) instead of treating it as malformed input.
+pub fn parse_secrets_allow_empty(secrets: &[BigInt]) -> Result<Vec<Vec<BigInt>>, String> {
+    parse_secrets_inner(secrets, true)
+}
+
+fn parse_secrets_inner(secrets: &[BigInt], allow_empty: bool) -> Result<Vec<Vec<BigInt>>, String> {
     if secrets.len() < 2 {
-        return Err("Secrets too small".to_string());
-    }
-    let mut parts = Vec::new();
-    let mut i = 0;
-    while i < secrets.len() {
-        let part_len = secrets[i].to_usize().ok_or("Invalid part length")?;
-        if part_len > MAX_PART_SIZE {
-            return Err(format!("Commitment part too large: size {}", part_len));
+        return Err("secrets frame is too small to contain a version/part-count header".to_string());
+    }
+
+    let version = checked_length(&secrets[0])?;
+    if version != FORMAT_VERSION as usize {
+        return Err(format!("unsupported commitment framing version: {}", version));
+    }
+
+    let part_count = checked_length(&secrets[1])?;
+    if part_count > PARTS_CAP {
+        return Err(format!("too many commitment parts: got {}, max {}", part_count, PARTS_CAP));
+    }
+    if part_count == 0 && !allow_empty {
+        return Err("zero-part commitment frame is not allowed here".to_string());
+    }
+
+    // Bounded by PARTS_CAP above, so this reservation can't be driven
+    // unboundedly large by untrusted input.
+    let mut parts = Vec::with_capacity(part_count);
+    let mut i = 2;
+    for _ in 0..part_count {
+        if i >= secrets.len() {
+            return Err("not enough data to read the next part's length prefix".to_string());
+        }
+        let part_len = checked_length(&secrets[i])?;
+        if part_len > MAX_PART_ELEMENTS {
+            return Err(format!("commitment part too large: {} elements, max {}", part_len, MAX_PART_ELEMENTS));
         }
         i += 1;
         if i + part_len > secrets.len() {
-            return Err("Not enough data to consume stated data length".to_string());
+            return Err("not enough data to consume the stated part length".to_string());
         }
         parts.push(secrets[i..i + part_len].to_vec());
         i += part_len;
     }
+
+    if i != secrets.len() {
+        return Err("trailing bytes after the last declared part".to_string());
+    }
+
     Ok(parts)
 }
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use num_bigint::ToBigInt;
 
     #[test]
-    fn test_builder_secrets() {
+    fn test_builder_secrets_round_trips_through_parse_secrets() {
         let mut builder = Builder::new();
         builder.add_part(vec![1.to_bigint().unwrap(), 2.to_bigint().unwrap()]);
-        let secrets = builder.secrets();
-        assert!(secrets.is_ok());
+        builder.add_part(vec![3.to_bigint().unwrap()]);
+        let secrets = builder.secrets().unwrap();
+
+        let parsed = parse_secrets(&secrets).unwrap();
+        assert_eq!(parsed, vec![vec![1.to_bigint().unwrap(), 2.to_bigint().unwrap()], vec![3.to_bigint().unwrap()]]);
     }
 
     #[test]
-    fn test_parse_secrets() {
-        let secrets = vec![2.to_bigint().unwrap(), 1.to_bigint().unwrap(), 2.to_bigint().unwrap()];
-        let parsed = parse_secrets(&secrets);
-        assert!(parsed.is_ok());
+    fn test_parse_secrets_rejects_zero_parts_by_default() {
+        let secrets = vec![BigInt::from(FORMAT_VERSION), BigInt::from(0u32)];
+        assert!(parse_secrets(&secrets).is_err());
+        assert_eq!(parse_secrets_allow_empty(&secrets).unwrap(), Vec::<Vec<BigInt>>::new());
+    }
+
+    #[test]
+    fn test_parse_secrets_rejects_unsupported_version() {
+        let secrets = vec![BigInt::from(FORMAT_VERSION + 1), BigInt::from(0u32)];
+        assert!(parse_secrets_allow_empty(&secrets).is_err());
+    }
+
+    #[test]
+    fn test_parse_secrets_rejects_too_many_parts() {
+        let secrets = vec![BigInt::from(FORMAT_VERSION), BigInt::from(PARTS_CAP as u64 + 1)];
+        assert!(parse_secrets_allow_empty(&secrets).is_err());
+    }
+
+    #[test]
+    fn test_parse_secrets_rejects_trailing_bytes() {
+        let secrets = vec![BigInt::from(FORMAT_VERSION), BigInt::from(1u32), BigInt::from(1u32), BigInt::from(42u32), BigInt::from(99u32)];
+        assert!(parse_secrets(&secrets).is_err());
+    }
+
+    #[test]
+    fn test_parse_secrets_rejects_truncated_part() {
+        let secrets = vec![BigInt::from(FORMAT_VERSION), BigInt::from(1u32), BigInt::from(5u32), BigInt::from(42u32)];
+        assert!(parse_secrets(&secrets).is_err());
+    }
+
+    #[test]
+    fn test_parse_secrets_rejects_negative_length_prefix() {
+        let secrets = vec![BigInt::from(FORMAT_VERSION), BigInt::from(-1i64)];
+        assert!(parse_secrets_allow_empty(&secrets).is_err());
+    }
+
+    #[test]
+    fn test_parse_secrets_rejects_oversized_part() {
+        let secrets = vec![BigInt::from(FORMAT_VERSION), BigInt::from(1u32), BigInt::from(MAX_PART_ELEMENTS as u64 + 1)];
+        assert!(parse_secrets(&secrets).is_err());
+    }
+
+    #[test]
+    fn test_builder_secrets_rejects_too_many_parts() {
+        let mut builder = Builder::new();
+        for _ in 0..=PARTS_CAP {
+            builder.add_part(vec![1.to_bigint().unwrap()]);
+        }
+        assert!(builder.secrets().is_err());
     }
 }
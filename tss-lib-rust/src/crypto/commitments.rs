@@ -1,5 +1,35 @@
 use num_bigint::BigInt;
 use crate::common::hash::sha512_256i;
+use sha3::{Digest, Sha3_256};
+use subtle::{Choice, ConstantTimeEq};
+
+/// `sha512_256i`'s digest width in bytes (SHA-512/256 truncated output).
+const HASH_COMMITMENT_WIDTH: usize = 32;
+
+/// Serializes a `BigInt` digest to a fixed-width, zero-padded big-endian
+/// buffer so two digests can be compared in constant time regardless of
+/// where their leading zero limbs fall.
+fn to_fixed_width_be(x: &BigInt) -> [u8; HASH_COMMITMENT_WIDTH] {
+    let mut buf = [0u8; HASH_COMMITMENT_WIDTH];
+    let bytes = x.to_bytes_be().1;
+    let start = HASH_COMMITMENT_WIDTH.saturating_sub(bytes.len());
+    buf[start..].copy_from_slice(&bytes[bytes.len().saturating_sub(HASH_COMMITMENT_WIDTH)..]);
+    buf
+}
+
+/// Constant-time equality of two hash commitments: compares fixed-width
+/// big-endian encodings via `subtle::ConstantTimeEq` so the branch doesn't
+/// depend on which limb first differs.
+fn commitments_equal(a: &BigInt, b: &BigInt) -> Choice {
+    to_fixed_width_be(a).ct_eq(&to_fixed_width_be(b))
+}
+
+use crate::{
+    common::{int::ModInt, random::get_random_positive_int},
+    crypto::ecpoint::ECPoint,
+};
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
 
 pub type HashCommitment = BigInt;
 pub type HashDeCommitment = Vec<BigInt>;
@@ -19,21 +49,483 @@ impl HashCommitDecommit {
 
     pub fn verify(&self) -> bool {
         let hash = sha512_256i(&self.d.iter().collect::<Vec<_>>());
-        hash == self.c
+        commitments_equal(&hash, &self.c).into()
     }
 
+    /// Recomputes the commitment hash and, only if it matches `self.c` in
+    /// constant time, returns the decommitted secrets — never branching on
+    /// a partial (e.g. leading-byte) match along the way.
     pub fn decommit(&self) -> Option<&[BigInt]> {
-        if self.verify() {
+        let hash = sha512_256i(&self.d.iter().collect::<Vec<_>>());
+        let matches: bool = commitments_equal(&hash, &self.c).into();
+        if matches {
             Some(&self.d[1..])
         } else {
             None
         }
     }
 }
+/// Fiat-Shamir challenge `c = H(bases, C, T)` for [`PedersenVectorPoK`], via
+/// `sha512_256i` over every point's affine coordinates.
+fn pedersen_pok_challenge(bases: &[ECPoint], commitment: &ECPoint, t: &ECPoint) -> BigInt {
+    let mut refs: Vec<&BigInt> = Vec::with_capacity((bases.len() + 2) * 2);
+    for base in bases {
+        refs.push(&base.x);
+        refs.push(&base.y);
+    }
+    refs.push(&commitment.x);
+    refs.push(&commitment.y);
+    refs.push(&t.x);
+    refs.push(&t.y);
+
+    sha512_256i(&refs)
+}
+
+/// Computes the Pedersen vector commitment `C = h^r * Prod g_i^{m_i}` for
+/// `bases = [h, g_1, ..., g_n]` and `secrets = [m_1, ..., m_n]`. All bases
+/// must share the same curve (the curve is taken from `bases[0]`).
+pub fn pedersen_vector_commit(bases: &[ECPoint], secrets: &[BigInt], r: &BigInt) -> Result<ECPoint, String> {
+    if bases.len() != secrets.len() + 1 {
+        return Err(format!(
+            "bases.len() ({}) must equal secrets.len() + 1 ({})",
+            bases.len(),
+            secrets.len() + 1
+        ));
+    }
+
+    let h = &bases[0];
+    let mut c = h.scalar_mult(r)?;
+    for (g_i, m_i) in bases[1..].iter().zip(secrets.iter()) {
+        let term = g_i.scalar_mult(m_i)?;
+        c = c.add(&term)?;
+    }
+    Ok(c)
+}
+
+/// A non-interactive Schnorr-style proof of knowledge of the opening `(r,
+/// {m_i})` of a Pedersen vector commitment `C = h^r * Prod g_i^{m_i}`,
+/// adapted from the BBS `pok_vc` technique. Unlike [`HashCommitDecommit`],
+/// which can only be "opened" by revealing the full secret vector, this
+/// lets a party prove it knows the committed values without revealing them.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PedersenVectorPoK {
+    pub t: ECPoint,
+    #[serde(with = "crate::serde_support::bigint_bytes")]
+    pub c: BigInt,
+    #[serde(with = "crate::serde_support::vec_bigint_bytes")]
+    pub z: Vec<BigInt>,
+    #[serde(with = "crate::serde_support::bigint_bytes")]
+    pub z_r: BigInt,
+}
+
+impl PedersenVectorPoK {
+    /// Proves knowledge of `(r, {m_i})` such that `commitment = h^r * Prod
+    /// g_i^{m_i}` for `bases = [h, g_1, ..., g_n]`: samples blindings
+    /// `s_r, {s_i}`, forms `T = h^{s_r} * Prod g_i^{s_i}`, derives `c =
+    /// H(bases, commitment, T)`, and answers `z_r = s_r + c*r`, `z_i = s_i +
+    /// c*m_i` (mod `q`, the group order).
+    pub fn prove<R: CryptoRng + RngCore>(
+        bases: &[ECPoint],
+        commitment: &ECPoint,
+        secrets: &[BigInt],
+        r: &BigInt,
+        q: &BigInt,
+        rng: &mut R,
+    ) -> Result<Self, String> {
+        if bases.len() != secrets.len() + 1 {
+            return Err(format!(
+                "bases.len() ({}) must equal secrets.len() + 1 ({})",
+                bases.len(),
+                secrets.len() + 1
+            ));
+        }
+        if *commitment == ECPoint::identity(commitment.curve) {
+            return Err("commitment must not be the point at infinity".to_string());
+        }
+
+        let h = &bases[0];
+        let gs = &bases[1..];
+        let mod_q = ModInt::new(q.clone());
+
+        let s_r = get_random_positive_int(rng, q);
+        let s: Vec<BigInt> = (0..secrets.len()).map(|_| get_random_positive_int(rng, q)).collect();
+
+        let mut t = h.scalar_mult(&s_r)?;
+        for (g_i, s_i) in gs.iter().zip(s.iter()) {
+            let term = g_i.scalar_mult(s_i)?;
+            t = t.add(&term)?;
+        }
+
+        let c = pedersen_pok_challenge(bases, commitment, &t) % q;
+
+        let z_r = mod_q.add(&s_r, &mod_q.mul(&c, r));
+        let z: Vec<BigInt> = s
+            .iter()
+            .zip(secrets.iter())
+            .map(|(s_i, m_i)| mod_q.add(s_i, &mod_q.mul(&c, m_i)))
+            .collect();
+
+        Ok(PedersenVectorPoK { t, c, z, z_r })
+    }
+
+    /// Verifies `h^{z_r} * Prod g_i^{z_i} == T * C^c` after recomputing `c =
+    /// H(bases, commitment, T)`. Rejects malformed proofs (wrong base
+    /// count) and degenerate inputs (`commitment`/`T` at infinity) without
+    /// panicking.
+    pub fn verify(&self, bases: &[ECPoint], commitment: &ECPoint, q: &BigInt) -> bool {
+        if bases.len() != self.z.len() + 1 {
+            return false;
+        }
+        if *commitment == ECPoint::identity(commitment.curve) || self.t == ECPoint::identity(self.t.curve) {
+            return false;
+        }
+
+        let expected_c = pedersen_pok_challenge(bases, commitment, &self.t) % q;
+        if expected_c != self.c {
+            return false;
+        }
+
+        let h = &bases[0];
+        let gs = &bases[1..];
+
+        let mut lhs = match h.scalar_mult(&self.z_r) {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
+        for (g_i, z_i) in gs.iter().zip(self.z.iter()) {
+            let term = match g_i.scalar_mult(z_i) {
+                Ok(p) => p,
+                Err(_) => return false,
+            };
+            lhs = match lhs.add(&term) {
+                Ok(p) => p,
+                Err(_) => return false,
+            };
+        }
+
+        let c_to_c = match commitment.scalar_mult(&self.c) {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
+        let rhs = match self.t.add(&c_to_c) {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
+
+        lhs == rhs
+    }
+}
+
+/// Fiat-Shamir challenge `c = H(C_1, C_2, T_1, T_2)` for
+/// [`PedersenEqualityPoK`].
+fn pedersen_equality_challenge(c1: &ECPoint, c2: &ECPoint, t1: &ECPoint, t2: &ECPoint) -> BigInt {
+    let refs: Vec<&BigInt> = vec![&c1.x, &c1.y, &c2.x, &c2.y, &t1.x, &t1.y, &t2.x, &t2.y];
+    sha512_256i(&refs)
+}
+
+/// A non-interactive proof that two Pedersen commitments `C_1 = h_1^{r_1}
+/// g_1^m` and `C_2 = h_2^{r_2} g_2^m` (possibly over different curves, as
+/// happens when a share is re-committed under a fresh set of generators
+/// during resharing) hide the same secret `m`, without revealing it. Built
+/// on the same Schnorr-style construction as [`PedersenVectorPoK`], but with
+/// a single blinding `s_m` shared across both commitments' proofs to tie
+/// them to one secret. Each commitment's curve is carried at runtime by its
+/// own `ECPoint`, but every response is reduced mod the one `q` passed to
+/// `prove_equality`/`verify_equality`, so both curves must have that same
+/// group order; curves of different orders are not supported.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PedersenEqualityPoK {
+    pub t1: ECPoint,
+    pub t2: ECPoint,
+    #[serde(with = "crate::serde_support::bigint_bytes")]
+    pub c: BigInt,
+    #[serde(with = "crate::serde_support::bigint_bytes")]
+    pub z_m: BigInt,
+    #[serde(with = "crate::serde_support::bigint_bytes")]
+    pub z_r1: BigInt,
+    #[serde(with = "crate::serde_support::bigint_bytes")]
+    pub z_r2: BigInt,
+}
+
+/// Proves that `c1 = h1^{r1} * g1^m` and `c2 = h2^{r2} * g2^m` open to the
+/// same `m`: samples blindings `s_m, s_r1, s_r2`, forms `T_1 = h1^{s_r1} *
+/// g1^{s_m}` and `T_2 = h2^{s_r2} * g2^{s_m}` with the shared `s_m`, derives
+/// `c = H(C_1, C_2, T_1, T_2)`, and answers `z_m = s_m + c*m`, `z_r1 = s_r1 +
+/// c*r1`, `z_r2 = s_r2 + c*r2` (mod `q`, the shared group order).
+pub fn prove_equality<R: CryptoRng + RngCore>(
+    h1: &ECPoint,
+    g1: &ECPoint,
+    c1: &ECPoint,
+    r1: &BigInt,
+    h2: &ECPoint,
+    g2: &ECPoint,
+    c2: &ECPoint,
+    r2: &BigInt,
+    m: &BigInt,
+    q: &BigInt,
+    rng: &mut R,
+) -> Result<PedersenEqualityPoK, String> {
+    if *c1 == ECPoint::identity(c1.curve) || *c2 == ECPoint::identity(c2.curve) {
+        return Err("commitment must not be the point at infinity".to_string());
+    }
+
+    let mod_q = ModInt::new(q.clone());
+
+    let s_m = get_random_positive_int(rng, q);
+    let s_r1 = get_random_positive_int(rng, q);
+    let s_r2 = get_random_positive_int(rng, q);
+
+    let t1 = h1.scalar_mult(&s_r1)?.add(&g1.scalar_mult(&s_m)?)?;
+    let t2 = h2.scalar_mult(&s_r2)?.add(&g2.scalar_mult(&s_m)?)?;
+
+    let c = pedersen_equality_challenge(c1, c2, &t1, &t2) % q;
+
+    let z_m = mod_q.add(&s_m, &mod_q.mul(&c, m));
+    let z_r1 = mod_q.add(&s_r1, &mod_q.mul(&c, r1));
+    let z_r2 = mod_q.add(&s_r2, &mod_q.mul(&c, r2));
+
+    Ok(PedersenEqualityPoK { t1, t2, c, z_m, z_r1, z_r2 })
+}
+
+impl PedersenEqualityPoK {
+    /// Verifies `h1^{z_r1} * g1^{z_m} == T_1 * C_1^c` and `h2^{z_r2} *
+    /// g2^{z_m} == T_2 * C_2^c` after recomputing `c = H(C_1, C_2, T_1,
+    /// T_2)`. Rejects degenerate inputs (`C_1`/`C_2`/`T_1`/`T_2` at
+    /// infinity) without panicking.
+    pub fn verify_equality(
+        &self,
+        h1: &ECPoint,
+        g1: &ECPoint,
+        c1: &ECPoint,
+        h2: &ECPoint,
+        g2: &ECPoint,
+        c2: &ECPoint,
+        q: &BigInt,
+    ) -> bool {
+        if *c1 == ECPoint::identity(c1.curve)
+            || *c2 == ECPoint::identity(c2.curve)
+            || self.t1 == ECPoint::identity(self.t1.curve)
+            || self.t2 == ECPoint::identity(self.t2.curve)
+        {
+            return false;
+        }
+
+        let expected_c = pedersen_equality_challenge(c1, c2, &self.t1, &self.t2) % q;
+        if expected_c != self.c {
+            return false;
+        }
+
+        let lhs1 = match h1.scalar_mult(&self.z_r1).and_then(|p| p.add(&g1.scalar_mult(&self.z_m)?)) {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
+        let rhs1 = match c1.scalar_mult(&self.c).and_then(|p| self.t1.add(&p)) {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
+        if lhs1 != rhs1 {
+            return false;
+        }
+
+        let lhs2 = match h2.scalar_mult(&self.z_r2).and_then(|p| p.add(&g2.scalar_mult(&self.z_m)?)) {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
+        let rhs2 = match c2.scalar_mult(&self.c).and_then(|p| self.t2.add(&p)) {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
+
+        lhs2 == rhs2
+    }
+}
+
+/// A 32-byte SHA3-256 Merkle root, broadcast in place of an O(n) list of
+/// per-share hash commitments.
+pub type MerkleRoot = [u8; 32];
+
+// Domain-separate leaf and internal node hashing (RFC 6962 style) so an
+// internal node can never be replayed as a leaf or vice versa.
+const MERKLE_LEAF_TAG: u8 = 0x00;
+const MERKLE_NODE_TAG: u8 = 0x01;
+
+fn merkle_leaf_hash(value: &[u8]) -> MerkleRoot {
+    let mut hasher = Sha3_256::new();
+    hasher.update([MERKLE_LEAF_TAG]);
+    hasher.update(value);
+    hasher.finalize().into()
+}
+
+fn merkle_node_hash(left: &MerkleRoot, right: &MerkleRoot) -> MerkleRoot {
+    let mut hasher = Sha3_256::new();
+    hasher.update([MERKLE_NODE_TAG]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// An append-only binary Merkle tree over a fixed vector of values (e.g. a
+/// round's serialized VSS shares/commitments), used to broadcast a single
+/// 32-byte root instead of the full list and later open individual elements
+/// with an O(log n) authentication path.
+///
+/// `levels[0]` holds the leaf hashes and each subsequent level holds the
+/// parent hashes, ending in a single root at `levels.last()`. When a level
+/// has an odd number of nodes, the unpaired rightmost node is carried up to
+/// the next level unchanged (not duplicated), and `prove`/`verify` must
+/// agree on that rule for roots to match.
+#[derive(Clone, Debug)]
+pub struct MerkleTree {
+    levels: Vec<Vec<MerkleRoot>>,
+}
+
+impl MerkleTree {
+    pub fn root(&self) -> MerkleRoot {
+        self.levels.last().expect("tree always has a root level")[0]
+    }
+
+    pub fn leaf_count(&self) -> usize {
+        self.levels[0].len()
+    }
+}
+
+/// An authentication path proving that `leaf` occupies `index` under the
+/// committed root: the sibling hashes needed to recompute the root,
+/// bottom-up, plus a flag per level recording whether the proof element was
+/// simply carried up unpaired (no sibling to combine with).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MerkleProofStep {
+    /// Combine with a sibling hash; `sibling_is_left` says which side it
+    /// sits on relative to the running hash.
+    Sibling { hash: MerkleRoot, sibling_is_left: bool },
+    /// This level's node had no sibling and was carried up unchanged.
+    Carried,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleProof {
+    pub steps: Vec<MerkleProofStep>,
+}
+
+/// Builds a Merkle tree over `values`, returning its root and the full tree
+/// (retained by the committer so it can later answer `prove` for any
+/// index).
+pub fn commit(values: &[Vec<u8>]) -> (MerkleRoot, MerkleTree) {
+    assert!(!values.is_empty(), "cannot commit to an empty value list");
+
+    let leaves: Vec<MerkleRoot> = values.iter().map(|v| merkle_leaf_hash(v)).collect();
+    let mut levels = vec![leaves];
+
+    while levels.last().expect("levels is never empty").len() > 1 {
+        let current = levels.last().expect("levels is never empty");
+        let mut next = Vec::with_capacity((current.len() + 1) / 2);
+        let mut i = 0;
+        while i + 1 < current.len() {
+            next.push(merkle_node_hash(&current[i], &current[i + 1]));
+            i += 2;
+        }
+        if i < current.len() {
+            // Odd node out: carry it up unchanged rather than duplicating it.
+            next.push(current[i]);
+        }
+        levels.push(next);
+    }
+
+    let tree = MerkleTree { levels };
+    (tree.root(), tree)
+}
+
+/// Builds the authentication path for the leaf at `index`.
+pub fn prove(tree: &MerkleTree, index: usize) -> Result<MerkleProof, String> {
+    if index >= tree.leaf_count() {
+        return Err(format!(
+            "leaf index {} out of range for {} leaves",
+            index,
+            tree.leaf_count()
+        ));
+    }
+
+    let mut steps = Vec::with_capacity(tree.levels.len().saturating_sub(1));
+    let mut idx = index;
+    for level in &tree.levels[..tree.levels.len() - 1] {
+        if idx % 2 == 0 {
+            if idx + 1 < level.len() {
+                steps.push(MerkleProofStep::Sibling {
+                    hash: level[idx + 1],
+                    sibling_is_left: false,
+                });
+            } else {
+                steps.push(MerkleProofStep::Carried);
+            }
+        } else {
+            steps.push(MerkleProofStep::Sibling {
+                hash: level[idx - 1],
+                sibling_is_left: true,
+            });
+        }
+        idx /= 2;
+    }
+
+    Ok(MerkleProof { steps })
+}
+
+/// Recomputes the root from `leaf` and its authentication `proof`, and
+/// checks it matches `root`.
+pub fn verify(root: &MerkleRoot, leaf: &[u8], proof: &MerkleProof) -> bool {
+    let mut current = merkle_leaf_hash(leaf);
+    for step in &proof.steps {
+        current = match step {
+            MerkleProofStep::Sibling { hash, sibling_is_left: true } => merkle_node_hash(hash, &current),
+            MerkleProofStep::Sibling { hash, sibling_is_left: false } => merkle_node_hash(&current, hash),
+            MerkleProofStep::Carried => current,
+        };
+    }
+    &current == root
+}
+
+/// Merkle-tree based alternative to [`HashCommitDecommit`]: instead of
+/// broadcasting a flat hash commitment and later revealing the entire
+/// secret vector, a party commits to a list of values as a Merkle root and
+/// opens individual elements one at a time with an O(log n) proof, so a
+/// recipient only learns the shares addressed to it.
+pub struct MerkleCommitment;
+
+impl MerkleCommitment {
+    pub fn commit(values: &[Vec<u8>]) -> (MerkleRoot, MerkleTree) {
+        commit(values)
+    }
+
+    pub fn prove(tree: &MerkleTree, index: usize) -> Result<MerkleProof, String> {
+        prove(tree, index)
+    }
+
+    pub fn verify(root: &MerkleRoot, index: usize, leaf: &[u8], proof: &MerkleProof) -> bool {
+        // Reconstruct the index implied by the path's left/right flags and
+        // reject a proof whose steps don't actually correspond to `index`.
+        let mut implied = 0usize;
+        for (level, step) in proof.steps.iter().enumerate() {
+            let bit = matches!(step, MerkleProofStep::Sibling { sibling_is_left: true, .. }) as usize;
+            implied |= bit << level;
+        }
+        let mask = (1usize << proof.steps.len()) - 1;
+        if proof.steps.is_empty() {
+            if index != 0 {
+                return false;
+            }
+        } else if implied != index & mask {
+            return false;
+        }
+        verify(root, leaf, proof)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use num_bigint::ToBigInt;
+    use crate::crypto::ecpoint::ECCurve;
+    use num_bigint::{Sign, ToBigInt};
+    use num_traits::One;
+    use rand::thread_rng;
 
     #[test]
     fn test_hash_commit_decommit() {
@@ -44,4 +536,200 @@ mod tests {
         assert!(commit_decommit.verify());
         assert_eq!(commit_decommit.decommit(), Some(&secrets[..]));
     }
+
+    fn get_k256_q() -> BigInt {
+        let q_bytes = k256::Scalar::ORDER.to_be_bytes();
+        BigInt::from_bytes_be(Sign::Plus, &q_bytes)
+    }
+
+    // Nothing-up-my-sleeve generators: neither is g itself, and neither has
+    // a known discrete log relation to the other or to g as far as this
+    // test is concerned.
+    fn nums_h() -> ECPoint {
+        ECPoint::scalar_base_mult(ECCurve::Secp256k1, &BigInt::from(424242u64)).unwrap()
+    }
+
+    fn nums_g1() -> ECPoint {
+        ECPoint::scalar_base_mult(ECCurve::Secp256k1, &BigInt::from(131313u64)).unwrap()
+    }
+
+    fn sample_bases() -> Vec<ECPoint> {
+        vec![nums_h(), ECPoint::scalar_base_mult(ECCurve::Secp256k1, &BigInt::one()).unwrap(), nums_g1()]
+    }
+
+    #[test]
+    fn test_pedersen_vector_pok_prove_verify_round_trip() {
+        let mut rng = thread_rng();
+        let q = get_k256_q();
+        let bases = sample_bases();
+        let secrets = vec![BigInt::from(7u64), BigInt::from(9u64)];
+        let r = BigInt::from(3u64);
+
+        let commitment = pedersen_vector_commit(&bases, &secrets, &r).unwrap();
+        let proof = PedersenVectorPoK::prove(&bases, &commitment, &secrets, &r, &q, &mut rng).unwrap();
+
+        assert!(proof.verify(&bases, &commitment, &q));
+    }
+
+    #[test]
+    fn test_pedersen_vector_pok_rejects_tampered_commitment() {
+        let mut rng = thread_rng();
+        let q = get_k256_q();
+        let bases = sample_bases();
+        let secrets = vec![BigInt::from(7u64), BigInt::from(9u64)];
+        let r = BigInt::from(3u64);
+
+        let commitment = pedersen_vector_commit(&bases, &secrets, &r).unwrap();
+        let proof = PedersenVectorPoK::prove(&bases, &commitment, &secrets, &r, &q, &mut rng).unwrap();
+
+        let wrong_commitment = pedersen_vector_commit(&bases, &[BigInt::from(8u64), BigInt::from(9u64)], &r).unwrap();
+        assert!(!proof.verify(&bases, &wrong_commitment, &q));
+    }
+
+    #[test]
+    fn test_pedersen_vector_pok_rejects_wrong_bases() {
+        let mut rng = thread_rng();
+        let q = get_k256_q();
+        let bases = sample_bases();
+        let secrets = vec![BigInt::from(7u64), BigInt::from(9u64)];
+        let r = BigInt::from(3u64);
+
+        let commitment = pedersen_vector_commit(&bases, &secrets, &r).unwrap();
+        let proof = PedersenVectorPoK::prove(&bases, &commitment, &secrets, &r, &q, &mut rng).unwrap();
+
+        let mut other_bases = bases.clone();
+        other_bases[2] = ECPoint::scalar_base_mult(ECCurve::Secp256k1, &BigInt::from(55555u64)).unwrap();
+        assert!(!proof.verify(&other_bases, &commitment, &q));
+    }
+
+    #[test]
+    fn test_pedersen_vector_commit_rejects_mismatched_lengths() {
+        let bases = sample_bases();
+        let secrets = vec![BigInt::from(7u64)];
+        let r = BigInt::from(3u64);
+
+        assert!(pedersen_vector_commit(&bases, &secrets, &r).is_err());
+    }
+
+    #[test]
+    fn test_pedersen_vector_pok_rejects_identity_commitment() {
+        let mut rng = thread_rng();
+        let q = get_k256_q();
+        let bases = sample_bases();
+        let secrets = vec![BigInt::from(7u64), BigInt::from(9u64)];
+        let r = BigInt::from(3u64);
+
+        let identity = ECPoint::identity(ECCurve::Secp256k1);
+        assert!(PedersenVectorPoK::prove(&bases, &identity, &secrets, &r, &q, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_pedersen_equality_pok_prove_verify_round_trip() {
+        let mut rng = thread_rng();
+        let q = get_k256_q();
+        let h1 = nums_h();
+        let g1 = ECPoint::scalar_base_mult(ECCurve::Secp256k1, &BigInt::one()).unwrap();
+        let h2 = nums_g1();
+        let g2 = ECPoint::scalar_base_mult(ECCurve::Secp256k1, &BigInt::from(2u64)).unwrap();
+
+        let m = BigInt::from(42u64);
+        let r1 = BigInt::from(11u64);
+        let r2 = BigInt::from(22u64);
+
+        let c1 = h1.scalar_mult(&r1).unwrap().add(&g1.scalar_mult(&m).unwrap()).unwrap();
+        let c2 = h2.scalar_mult(&r2).unwrap().add(&g2.scalar_mult(&m).unwrap()).unwrap();
+
+        let proof = prove_equality(&h1, &g1, &c1, &r1, &h2, &g2, &c2, &r2, &m, &q, &mut rng).unwrap();
+        assert!(proof.verify_equality(&h1, &g1, &c1, &h2, &g2, &c2, &q));
+    }
+
+    #[test]
+    fn test_pedersen_equality_pok_rejects_different_secrets() {
+        let mut rng = thread_rng();
+        let q = get_k256_q();
+        let h1 = nums_h();
+        let g1 = ECPoint::scalar_base_mult(ECCurve::Secp256k1, &BigInt::one()).unwrap();
+        let h2 = nums_g1();
+        let g2 = ECPoint::scalar_base_mult(ECCurve::Secp256k1, &BigInt::from(2u64)).unwrap();
+
+        let m1 = BigInt::from(42u64);
+        let m2 = BigInt::from(43u64);
+        let r1 = BigInt::from(11u64);
+        let r2 = BigInt::from(22u64);
+
+        let c1 = h1.scalar_mult(&r1).unwrap().add(&g1.scalar_mult(&m1).unwrap()).unwrap();
+        let c2 = h2.scalar_mult(&r2).unwrap().add(&g2.scalar_mult(&m2).unwrap()).unwrap();
+
+        // A dishonest prover claiming the (different) secrets match must
+        // fail verification, even though each commitment's own opening is
+        // individually valid.
+        let proof = prove_equality(&h1, &g1, &c1, &r1, &h2, &g2, &c2, &r2, &m1, &q, &mut rng).unwrap();
+        assert!(!proof.verify_equality(&h1, &g1, &c1, &h2, &g2, &c2, &q));
+    }
+
+    #[test]
+    fn test_pedersen_equality_pok_rejects_identity_commitment() {
+        let mut rng = thread_rng();
+        let q = get_k256_q();
+        let h1 = nums_h();
+        let g1 = ECPoint::scalar_base_mult(ECCurve::Secp256k1, &BigInt::one()).unwrap();
+        let h2 = nums_g1();
+        let g2 = ECPoint::scalar_base_mult(ECCurve::Secp256k1, &BigInt::from(2u64)).unwrap();
+
+        let m = BigInt::from(42u64);
+        let r1 = BigInt::from(11u64);
+        let r2 = BigInt::from(22u64);
+        let identity = ECPoint::identity(ECCurve::Secp256k1);
+
+        assert!(prove_equality(&h1, &g1, &identity, &r1, &h2, &g2, &identity, &r2, &m, &q, &mut rng).is_err());
+    }
+
+    fn sample_values(n: usize) -> Vec<Vec<u8>> {
+        (0..n).map(|i| vec![i as u8; 4]).collect()
+    }
+
+    #[test]
+    fn test_merkle_commitment_power_of_two() {
+        let values = sample_values(4);
+        let (root, tree) = MerkleCommitment::commit(&values);
+        for (i, v) in values.iter().enumerate() {
+            let proof = MerkleCommitment::prove(&tree, i).unwrap();
+            assert!(MerkleCommitment::verify(&root, i, v, &proof));
+        }
+    }
+
+    #[test]
+    fn test_merkle_commitment_odd_leaf_count_carries_unpaired_node() {
+        let values = sample_values(5);
+        let (root, tree) = MerkleCommitment::commit(&values);
+        for (i, v) in values.iter().enumerate() {
+            let proof = MerkleCommitment::prove(&tree, i).unwrap();
+            assert!(MerkleCommitment::verify(&root, i, v, &proof));
+        }
+    }
+
+    #[test]
+    fn test_merkle_commitment_single_leaf() {
+        let values = sample_values(1);
+        let (root, tree) = MerkleCommitment::commit(&values);
+        let proof = MerkleCommitment::prove(&tree, 0).unwrap();
+        assert!(MerkleCommitment::verify(&root, 0, &values[0], &proof));
+    }
+
+    #[test]
+    fn test_merkle_commitment_rejects_wrong_leaf_or_index() {
+        let values = sample_values(5);
+        let (root, tree) = MerkleCommitment::commit(&values);
+        let proof = MerkleCommitment::prove(&tree, 2).unwrap();
+
+        assert!(!MerkleCommitment::verify(&root, 2, &values[3], &proof));
+        assert!(!MerkleCommitment::verify(&root, 1, &values[2], &proof));
+    }
+
+    #[test]
+    fn test_merkle_commitment_prove_out_of_range() {
+        let values = sample_values(3);
+        let (_, tree) = MerkleCommitment::commit(&values);
+        assert!(MerkleCommitment::prove(&tree, 3).is_err());
+    }
 }
@@ -0,0 +1,554 @@
+use num_bigint::{BigInt, Sign};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use crate::common::hash::sha512_256i;
+use crate::common::int::ModInt;
+use crate::common::random::{get_random_positive_int, must_get_random_int};
+use crate::common::safe_prime::GermainSafePrime;
+use crate::common::secret::SecretBigInt;
+use crate::crypto::modproof::ProofMod;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+// Statistical security parameter: number of Girault/Fischlin sigma-protocol
+// challenges, matching the Go implementation.
+const ITERATIONS: usize = 128;
+
+/// Ring-Pedersen parameters: a safe-prime RSA modulus `n_tilde = p * q`
+/// together with `h1` and `h2 = h1^lambda mod n_tilde`, used as non-malleable
+/// commitment bases by the range proofs in later keygen/signing rounds.
+/// `lambda` is the discrete log of `h2` with respect to `h1` and is kept
+/// secret by the party that generated these parameters.
+pub struct DlnParams {
+    pub n_tilde: BigInt,
+    pub h1: BigInt,
+    pub h2: BigInt,
+    lambda: BigInt,
+    // Order of the subgroup of quadratic residues mod n_tilde (p' * q', the
+    // product of the two Sophie Germain factors), i.e. the modulus the
+    // sigma-protocol exponents are reduced under.
+    subgroup_order: BigInt,
+    // The two safe primes whose product is n_tilde, kept so `AuxParams`
+    // can additionally produce a `ProofMod` attesting n_tilde is a
+    // well-formed Blum modulus (safe primes are always ≡ 3 mod 4).
+    p: BigInt,
+    q: BigInt,
+}
+
+impl DlnParams {
+    /// Generates fresh Ring-Pedersen parameters from two freshly-sampled safe
+    /// primes of `bits / 2` bits each.
+    pub fn generate<R: rand::RngCore>(rng: &mut R, bits: usize) -> Self {
+        let half_bits = bits / 2;
+        loop {
+            let p_prime = GermainSafePrime::generate(rng, half_bits - 1);
+            let q_prime = GermainSafePrime::generate(rng, half_bits - 1);
+            let p = p_prime.safe_prime().clone();
+            let q = q_prime.safe_prime().clone();
+            let n_tilde = &p * &q;
+            let subgroup_order = p_prime.prime() * q_prime.prime();
+
+            // h1 is a random generator of the quadratic residues mod n_tilde.
+            let f1 = get_random_positive_int(rng, &n_tilde);
+            let h1 = (&f1 * &f1).mod_floor(&n_tilde);
+            let lambda = get_random_positive_int(rng, &subgroup_order);
+            if lambda.is_zero() || lambda.gcd(&subgroup_order) != BigInt::one() {
+                continue;
+            }
+            let h2 = h1.modpow(&lambda, &n_tilde);
+
+            return DlnParams { n_tilde, h1, h2, lambda, subgroup_order, p, q };
+        }
+    }
+
+    /// Proves `log_h1(h2) = lambda`. `session_id` binds the proof to this
+    /// protocol run (see [`Proof::new`]); callers MUST pass the same
+    /// `session_id` at verification time or the proof will be rejected.
+    pub fn prove_h1_to_h2<R: rand::RngCore>(&self, session_id: &[u8], rng: &mut R) -> Proof {
+        let lambda = SecretBigInt::new(self.lambda.clone());
+        Proof::new(session_id, &self.h1, &self.h2, &lambda, &self.subgroup_order, &self.n_tilde, Some((&self.p, &self.q)), rng)
+    }
+
+    /// Proves `log_h2(h1) = lambda^-1 mod subgroup_order`, the other
+    /// direction of the same relation. See [`prove_h1_to_h2`](Self::prove_h1_to_h2)
+    /// for `session_id`.
+    pub fn prove_h2_to_h1<R: rand::RngCore>(&self, session_id: &[u8], rng: &mut R) -> Proof {
+        let lambda_inv = SecretBigInt::new(
+            self.lambda.modinv(&self.subgroup_order)
+                .expect("lambda was rejection-sampled to be invertible mod subgroup_order"),
+        );
+        Proof::new(session_id, &self.h2, &self.h1, &lambda_inv, &self.subgroup_order, &self.n_tilde, Some((&self.p, &self.q)), rng)
+    }
+}
+
+/// A batched Girault/Fischlin sigma-protocol proof that `h1` and `h2`
+/// generate the same subgroup of `Z*_n`, i.e. that the prover knows
+/// `lambda = log_h1(h2)`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Proof {
+    alpha: Vec<BigInt>,
+    t: Vec<BigInt>,
+}
+
+impl Proof {
+    /// Runs the sigma protocol for `log_h1(h2) = lambda (mod ord)`: picks a
+    /// random `a_j` per iteration, commits to `alpha_j = h1^a_j mod n`, derives
+    /// batched Fiat-Shamir challenge bits `c_j` from hashing `h1, h2, n` with
+    /// every `alpha_j`, and reveals `t_j = a_j + c_j * lambda (mod ord)`.
+    ///
+    /// `lambda` and the per-iteration randomness `a_j` are the witness this
+    /// proof must not leak, so both are held as [`SecretBigInt`] and scrubbed
+    /// as soon as they're folded into the public `t_j` response.
+    ///
+    /// When the prover knows the factorization `n = p * q` (as `DlnParams`
+    /// always does), pass it as `safe_primes` to compute each `alpha_j` via
+    /// [`SecretBigInt::exp_crt`] instead of a full-modulus exponentiation --
+    /// the resulting `alpha` is numerically identical either way, so
+    /// verifiers can't tell which path produced a given proof.
+    ///
+    /// `session_id` is absorbed into the Fiat-Shamir challenge alongside the
+    /// statement (`h1, h2, n`) and commitments, binding this proof to a
+    /// single protocol run: the same proof verified under a different
+    /// `session_id` will be rejected, so one session's proof can't be
+    /// replayed into another.
+    pub fn new<R: rand::RngCore>(
+        session_id: &[u8],
+        h1: &BigInt,
+        h2: &BigInt,
+        lambda: &SecretBigInt,
+        ord: &BigInt,
+        n: &BigInt,
+        safe_primes: Option<(&BigInt, &BigInt)>,
+        rng: &mut R,
+    ) -> Self {
+        let a_vals: Vec<SecretBigInt> = (0..ITERATIONS)
+            .map(|_| SecretBigInt::new(get_random_positive_int(rng, ord)))
+            .collect();
+        let alpha: Vec<BigInt> = a_vals
+            .iter()
+            .map(|a_j| match safe_primes {
+                Some((p, q)) => a_j.exp_crt(h1, p, q, n),
+                None => a_j.exp(h1, n),
+            })
+            .collect();
+
+        let c = derive_challenge(session_id, h1, h2, n, &alpha);
+
+        let t: Vec<BigInt> = a_vals.into_iter().enumerate()
+            .map(|(j, a_j)| {
+                if bit(&c, j) {
+                    a_j.add(lambda, ord).into_inner()
+                } else {
+                    a_j.into_inner().mod_floor(ord)
+                }
+            })
+            .collect();
+
+        Proof { alpha, t }
+    }
+
+    /// Serializes the proof as `alpha_0, ..., alpha_127, t_0, ..., t_127`,
+    /// each big-endian-encoded and length-prefixed with a big-endian `u32`,
+    /// for embedding in a message's wire bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        for part in self.alpha.iter().chain(self.t.iter()) {
+            let bytes = part.to_bytes_be().1;
+            out.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+            out.extend_from_slice(&bytes);
+        }
+        out
+    }
+
+    /// Parses a proof previously produced by `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let mut ints = Vec::with_capacity(2 * ITERATIONS);
+        let mut cursor = 0usize;
+        while cursor < bytes.len() {
+            let len_bytes = bytes.get(cursor..cursor + 4).ok_or("truncated DLN proof length prefix")?;
+            let len = u32::from_be_bytes(len_bytes.try_into().unwrap()) as usize;
+            cursor += 4;
+            let part = bytes.get(cursor..cursor + len).ok_or("truncated DLN proof body")?;
+            ints.push(BigInt::from_bytes_be(Sign::Plus, part));
+            cursor += len;
+        }
+        if ints.len() != 2 * ITERATIONS {
+            return Err(format!("expected {} DLN proof components, got {}", 2 * ITERATIONS, ints.len()));
+        }
+        let t = ints.split_off(ITERATIONS);
+        Ok(Proof { alpha: ints, t })
+    }
+
+    /// Verifies that `h1^t_j == alpha_j * h2^c_j (mod n)` for every iteration,
+    /// where `c_j` is recomputed the same way the prover derived it.
+    /// `session_id` must match what [`Proof::new`] was given; a mismatched
+    /// or absent `session_id` makes this reject even an otherwise-valid
+    /// proof, which is what prevents replaying a proof across sessions.
+    pub fn verify(&self, session_id: &[u8], h1: &BigInt, h2: &BigInt, n: &BigInt) -> bool {
+        match self.challenge_for(session_id, h1, h2, n) {
+            Some(c) => (0..ITERATIONS).all(|j| self.check_iteration(j, h1, h2, n, &c)),
+            None => false,
+        }
+    }
+
+    /// Like [`verify`](Self::verify), but spreads the `ITERATIONS`
+    /// per-iteration checks across `pool` instead of running them on the
+    /// calling thread, and stops scheduling further iterations as soon as one
+    /// fails rather than always running all of them to completion. Intended
+    /// for callers (e.g. `DlnProofVerifier::verify_batch`) that already sized
+    /// a pool for a batch of proofs and have spare workers for a single
+    /// proof's 128 independent iterations.
+    #[cfg(feature = "parallel")]
+    pub fn verify_on_pool(&self, session_id: &[u8], h1: &BigInt, h2: &BigInt, n: &BigInt, pool: &rayon::ThreadPool) -> bool {
+        match self.challenge_for(session_id, h1, h2, n) {
+            Some(c) => pool.install(|| (0..ITERATIONS).into_par_iter().all(|j| self.check_iteration(j, h1, h2, n, &c))),
+            None => false,
+        }
+    }
+
+    /// Like [`verify`](Self::verify), but replaces the `ITERATIONS`
+    /// per-iteration equality checks with a single randomized aggregate:
+    /// draws fresh 128-bit random weights `r_j` from `rng` and checks
+    /// `h1^(Σ r_j·t_j) == (Π alpha_j^r_j) · h2^(Σ r_j·c_j) (mod n)`. If any
+    /// single iteration's equation is false, the combined check only passes
+    /// if that iteration's `r_j` happens to cancel it out against the
+    /// others, which happens with probability at most `2^-128` -- a
+    /// negligible soundness loss in exchange for replacing `2 * ITERATIONS`
+    /// modular exponentiations with two large ones plus one
+    /// `ITERATIONS`-base multi-exponentiation. Prefer the exact `verify`
+    /// wherever determinism, not just overwhelming-probability soundness,
+    /// is required.
+    pub fn verify_batched<R: rand::RngCore>(&self, session_id: &[u8], h1: &BigInt, h2: &BigInt, n: &BigInt, rng: &mut R) -> bool {
+        let c = match self.challenge_for(session_id, h1, h2, n) {
+            Some(c) => c,
+            None => return false,
+        };
+
+        let weights: Vec<BigInt> = (0..ITERATIONS).map(|_| must_get_random_int(rng, 128)).collect();
+
+        let mut t_sum = BigInt::zero();
+        let mut c_sum = BigInt::zero();
+        for (j, r_j) in weights.iter().enumerate() {
+            t_sum += r_j * &self.t[j];
+            if bit(&c, j) {
+                c_sum += r_j;
+            }
+        }
+
+        let mi = ModInt::new(n.clone());
+        let lhs = mi.exp(h1, &t_sum);
+        let rhs_h2 = mi.exp(h2, &c_sum);
+        let rhs_alpha = multi_exp_chunked(&mi, &self.alpha, &weights);
+        lhs == mi.mul(&rhs_alpha, &rhs_h2)
+    }
+
+    /// Validates the proof's shape and recomputes the Fiat-Shamir challenge
+    /// `c`, or returns `None` if the proof doesn't have exactly `ITERATIONS`
+    /// components.
+    fn challenge_for(&self, session_id: &[u8], h1: &BigInt, h2: &BigInt, n: &BigInt) -> Option<BigInt> {
+        if self.alpha.len() != ITERATIONS || self.t.len() != ITERATIONS {
+            return None;
+        }
+        Some(derive_challenge(session_id, h1, h2, n, &self.alpha))
+    }
+
+    /// Checks the single equation `h1^t_j == alpha_j * h2^c_j (mod n)` for
+    /// iteration `j`.
+    fn check_iteration(&self, j: usize, h1: &BigInt, h2: &BigInt, n: &BigInt, c: &BigInt) -> bool {
+        let lhs = h1.modpow(&self.t[j], n);
+        let rhs = if bit(c, j) {
+            (&self.alpha[j] * h2).mod_floor(n)
+        } else {
+            self.alpha[j].mod_floor(n)
+        };
+        lhs == rhs
+    }
+}
+
+/// The `(N~, h1, h2)` a party publishes for use as range-proof trusted
+/// setup (e.g. `RangeProofAlice::new`/`verify`), bundled with the Π^prm
+/// proof that `h2` is in the subgroup generated by `h1`. Recipients MUST
+/// call [`verify_aux`](Self::verify_aux) before trusting `n_tilde`/`h1`/`h2`:
+/// without it, a malicious "verifier" who secretly knows `log_h1(h2)` can
+/// break the hiding of the range proof's `z` and recover the prover's
+/// witness.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuxParams {
+    pub n_tilde: BigInt,
+    pub h1: BigInt,
+    pub h2: BigInt,
+    pub proof: Proof,
+    /// Companion Paillier-Blum modulus proof (`crypto::modproof::ProofMod`)
+    /// attesting `n_tilde` itself is a well-formed product of two primes
+    /// `≡ 3 mod 4`, the property `Proof`/`verify_aux` alone don't establish.
+    pub mod_proof: ProofMod,
+}
+
+impl AuxParams {
+    /// Generates fresh `(N~, h1, h2)` via [`DlnParams::generate`], proves the
+    /// `h1 -> h2` relation, and proves `n_tilde` is a Blum modulus, producing
+    /// the full bundle a party would send its counterparties during key
+    /// setup. `session` binds the modulus proof to this run so it can't be
+    /// replayed into a different session (see `ProofMod::new`).
+    pub fn generate<R: rand::RngCore>(rng: &mut R, bits: usize, session: &[u8]) -> Self {
+        let dln = DlnParams::generate(rng, bits);
+        let proof = dln.prove_h1_to_h2(session, rng);
+        let mod_proof = ProofMod::new(session, &dln.n_tilde, &dln.p, &dln.q, rng)
+            .expect("safe primes are always ≡ 3 mod 4 and coprime to n_tilde's order");
+        AuxParams { n_tilde: dln.n_tilde, h1: dln.h1, h2: dln.h2, proof, mod_proof }
+    }
+
+    /// Verifies both the Π^prm proof binding `h1` and `h2` under `n_tilde`
+    /// and the companion Π-mod proof that `n_tilde` is a Blum modulus. Only
+    /// once this returns `true` should `n_tilde`/`h1`/`h2` be passed into
+    /// `RangeProofAlice::verify` or any other range proof that treats them
+    /// as trusted setup. `session` must be the same value passed to
+    /// `generate`, or both proofs (now session-bound) will be rejected.
+    pub fn verify_aux(&self, session: &[u8]) -> bool {
+        self.proof.verify(session, &self.h1, &self.h2, &self.n_tilde) && self.mod_proof.verify(session, &self.n_tilde)
+    }
+}
+
+/// Derives the batched Fiat-Shamir challenge `c = H(session_id, h1, h2, n,
+/// alpha_0, ..., alpha_{ITERATIONS-1})`; bit `j` of `c` is the challenge for
+/// iteration `j`. Absorbing `session_id` binds the resulting proof to a
+/// single protocol run, the same way `modproof::derive_challenges`
+/// does for the Π-mod proof.
+fn derive_challenge(session_id: &[u8], h1: &BigInt, h2: &BigInt, n: &BigInt, alpha: &[BigInt]) -> BigInt {
+    let session_int = BigInt::from_bytes_be(Sign::Plus, session_id);
+    let mut inputs: Vec<&BigInt> = vec![&session_int, h1, h2, n];
+    inputs.extend(alpha.iter());
+    sha512_256i(&inputs)
+}
+
+fn bit(n: &BigInt, i: usize) -> bool {
+    n.test_bit(i as u64)
+}
+
+/// Computes `prod bases[i]^exps[i] mod modulus` for an arbitrary number of
+/// bases by running [`ModInt::multi_exp`] over successive windows of at most
+/// 12 bases (its dense Shamir's-trick table limit) and multiplying the
+/// partial products together.
+fn multi_exp_chunked(mi: &ModInt, bases: &[BigInt], exps: &[BigInt]) -> BigInt {
+    let pairs: Vec<(&BigInt, &BigInt)> = bases.iter().zip(exps.iter()).collect();
+    pairs
+        .chunks(12)
+        .map(|chunk| mi.multi_exp(chunk))
+        .reduce(|acc, partial| mi.mul(&acc, &partial))
+        .unwrap_or_else(BigInt::one)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SESSION_ID: &[u8] = b"dln-test-session";
+
+    #[test]
+    fn test_generate_params_satisfy_relation() {
+        let mut rng = rand::thread_rng();
+        let params = DlnParams::generate(&mut rng, 64);
+        assert_eq!(params.h1.modpow(&params.lambda, &params.n_tilde), params.h2);
+    }
+
+    #[test]
+    fn test_prove_and_verify_both_directions() {
+        let mut rng = rand::thread_rng();
+        let params = DlnParams::generate(&mut rng, 64);
+
+        let proof_forward = params.prove_h1_to_h2(SESSION_ID, &mut rng);
+        assert!(proof_forward.verify(SESSION_ID, &params.h1, &params.h2, &params.n_tilde));
+
+        let proof_backward = params.prove_h2_to_h1(SESSION_ID, &mut rng);
+        assert!(proof_backward.verify(SESSION_ID, &params.h2, &params.h1, &params.n_tilde));
+    }
+
+    #[test]
+    fn test_proof_bytes_round_trip() {
+        let mut rng = rand::thread_rng();
+        let params = DlnParams::generate(&mut rng, 64);
+        let proof = params.prove_h1_to_h2(SESSION_ID, &mut rng);
+
+        let bytes = proof.to_bytes();
+        let decoded = Proof::from_bytes(&bytes).unwrap();
+        assert_eq!(proof, decoded);
+        assert!(decoded.verify(SESSION_ID, &params.h1, &params.h2, &params.n_tilde));
+    }
+
+    #[test]
+    fn test_verify_rejects_mismatched_h2() {
+        let mut rng = rand::thread_rng();
+        let params = DlnParams::generate(&mut rng, 64);
+        let other = DlnParams::generate(&mut rng, 64);
+
+        let proof = params.prove_h1_to_h2(SESSION_ID, &mut rng);
+        assert!(!proof.verify(SESSION_ID, &params.h1, &other.h2, &params.n_tilde));
+    }
+
+    #[test]
+    fn test_verify_rejects_wrong_session() {
+        let mut rng = rand::thread_rng();
+        let params = DlnParams::generate(&mut rng, 64);
+
+        let proof = params.prove_h1_to_h2(SESSION_ID, &mut rng);
+        assert!(!proof.verify(b"a-different-session", &params.h1, &params.h2, &params.n_tilde));
+    }
+
+    #[test]
+    fn test_crt_prover_produces_a_verifiable_proof_identical_to_full_modulus_path() {
+        let mut rng = rand::thread_rng();
+        let dln = DlnParams::generate(&mut rng, 64);
+        let lambda_crt = SecretBigInt::new(dln.lambda.clone());
+        let lambda_full = SecretBigInt::new(dln.lambda.clone());
+
+        // Re-derive both paths with the same randomness source isn't
+        // possible (each draws its own `a_j`s), so instead just check each
+        // independently verifies -- the CRT path producing a proof
+        // verifiable via the ordinary full-modulus `verify` is exactly the
+        // "numerically identical alpha" property that matters to callers.
+        let proof_crt = Proof::new(SESSION_ID, &dln.h1, &dln.h2, &lambda_crt, &dln.subgroup_order, &dln.n_tilde, Some((&dln.p, &dln.q)), &mut rng);
+        assert!(proof_crt.verify(SESSION_ID, &dln.h1, &dln.h2, &dln.n_tilde));
+
+        let proof_full = Proof::new(SESSION_ID, &dln.h1, &dln.h2, &lambda_full, &dln.subgroup_order, &dln.n_tilde, None, &mut rng);
+        assert!(proof_full.verify(SESSION_ID, &dln.h1, &dln.h2, &dln.n_tilde));
+    }
+
+    #[test]
+    fn test_verify_batched_accepts_valid_proof() {
+        let mut rng = rand::thread_rng();
+        let params = DlnParams::generate(&mut rng, 64);
+        let proof = params.prove_h1_to_h2(SESSION_ID, &mut rng);
+        assert!(proof.verify_batched(SESSION_ID, &params.h1, &params.h2, &params.n_tilde, &mut rng));
+    }
+
+    #[test]
+    fn test_verify_batched_rejects_mismatched_h2() {
+        let mut rng = rand::thread_rng();
+        let params = DlnParams::generate(&mut rng, 64);
+        let other = DlnParams::generate(&mut rng, 64);
+
+        let proof = params.prove_h1_to_h2(SESSION_ID, &mut rng);
+        assert!(!proof.verify_batched(SESSION_ID, &params.h1, &other.h2, &params.n_tilde, &mut rng));
+    }
+
+    /// `from_bytes` must never panic on arbitrary input, and must reject
+    /// anything that isn't a well-formed re-serialization of a real proof
+    /// (truncated length prefixes, truncated bodies, wrong component counts).
+    #[test]
+    fn test_unmarshal_never_panics_on_arbitrary_bytes() {
+        let mut rng = rand::thread_rng();
+        let params = DlnParams::generate(&mut rng, 64);
+        let valid = params.prove_h1_to_h2(SESSION_ID, &mut rng).to_bytes();
+
+        // A handful of adversarial byte strings, plus every prefix of a
+        // genuinely valid proof's encoding (the most likely place a length-
+        // prefix parser panics instead of erroring).
+        let mut candidates: Vec<Vec<u8>> = vec![
+            vec![],
+            vec![0u8],
+            vec![0xff; 3],
+            vec![0xff; 4],
+            u32::MAX.to_be_bytes().to_vec(),
+            [u32::MAX.to_be_bytes().as_slice(), &[0, 1, 2]].concat(),
+        ];
+        for len in 0..valid.len() {
+            candidates.push(valid[..len].to_vec());
+        }
+
+        for bytes in candidates {
+            match Proof::from_bytes(&bytes) {
+                Ok(proof) => {
+                    // Whatever did parse must still verify-or-reject without
+                    // panicking against arbitrary parameters.
+                    let _ = proof.verify(SESSION_ID, &params.h1, &params.h2, &params.n_tilde);
+                }
+                Err(_) => {}
+            }
+        }
+    }
+
+    /// A valid proof must verify, and every single-bit mutation of its wire
+    /// encoding must make it either fail to parse or fail to verify -- never
+    /// silently succeed.
+    #[test]
+    fn test_single_bit_mutation_of_valid_proof_fails() {
+        let mut rng = rand::thread_rng();
+        let params = DlnParams::generate(&mut rng, 64);
+        let proof = params.prove_h1_to_h2(SESSION_ID, &mut rng);
+        let bytes = proof.to_bytes();
+        assert!(proof.verify(SESSION_ID, &params.h1, &params.h2, &params.n_tilde));
+
+        // Flipping every bit of a 128-iteration proof's encoding is
+        // expensive; sample one bit per byte position instead, which still
+        // exercises every length-prefix and every alpha/t component at
+        // least once.
+        for byte_idx in 0..bytes.len() {
+            let mut tampered = bytes.clone();
+            tampered[byte_idx] ^= 0x01;
+            let still_verifies = match Proof::from_bytes(&tampered) {
+                Ok(p) => p.verify(SESSION_ID, &params.h1, &params.h2, &params.n_tilde),
+                Err(_) => false,
+            };
+            assert!(!still_verifies, "bit flip at byte {} should break verification", byte_idx);
+        }
+    }
+
+    /// Perturbing any of `h1`, `h2`, or `n_tilde` by one must make a
+    /// genuinely valid proof fail to verify.
+    #[test]
+    fn test_parameter_perturbation_breaks_verification() {
+        let mut rng = rand::thread_rng();
+        let params = DlnParams::generate(&mut rng, 64);
+        let proof = params.prove_h1_to_h2(SESSION_ID, &mut rng);
+        assert!(proof.verify(SESSION_ID, &params.h1, &params.h2, &params.n_tilde));
+
+        let h1_plus_one = &params.h1 + BigInt::one();
+        assert!(!proof.verify(SESSION_ID, &h1_plus_one, &params.h2, &params.n_tilde));
+
+        let h2_plus_one = &params.h2 + BigInt::one();
+        assert!(!proof.verify(SESSION_ID, &params.h1, &h2_plus_one, &params.n_tilde));
+
+        let n_tilde_plus_one = &params.n_tilde + BigInt::one();
+        assert!(!proof.verify(SESSION_ID, &params.h1, &params.h2, &n_tilde_plus_one));
+    }
+
+    #[test]
+    fn test_aux_params_generate_and_verify() {
+        let mut rng = rand::thread_rng();
+        let aux = AuxParams::generate(&mut rng, 64, b"session");
+        assert!(aux.verify_aux(b"session"));
+    }
+
+    #[test]
+    fn test_aux_params_verify_aux_rejects_mismatched_h2() {
+        let mut rng = rand::thread_rng();
+        let aux = AuxParams::generate(&mut rng, 64, b"session");
+        let other = AuxParams::generate(&mut rng, 64, b"session");
+
+        let mut tampered = aux.clone();
+        tampered.h2 = other.h2;
+        assert!(!tampered.verify_aux(b"session"));
+    }
+
+    #[test]
+    fn test_aux_params_verify_aux_rejects_wrong_session() {
+        let mut rng = rand::thread_rng();
+        let aux = AuxParams::generate(&mut rng, 64, b"session-a");
+        assert!(!aux.verify_aux(b"session-b"));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_verify_on_pool_matches_verify() {
+        let mut rng = rand::thread_rng();
+        let params = DlnParams::generate(&mut rng, 64);
+        let proof = params.prove_h1_to_h2(SESSION_ID, &mut rng);
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+
+        assert!(proof.verify_on_pool(SESSION_ID, &params.h1, &params.h2, &params.n_tilde, &pool));
+
+        let mut tampered = proof.clone();
+        tampered.t[0] += BigInt::one();
+        assert!(!tampered.verify_on_pool(SESSION_ID, &params.h1, &params.h2, &params.n_tilde, &pool));
+    }
+}
@@ -1,18 +1,125 @@
 use k256::elliptic_curve::sec1::{ToEncodedPoint, FromEncodedPoint, EncodedPoint};
 use k256::{PublicKey as Secp256k1PublicKey, Secp256k1, Scalar as Secp256k1Scalar, ProjectivePoint};
 use k256::elliptic_curve::{AffineXCoordinate, PrimeField};
+use k256::elliptic_curve::group::Group;
+use p256::{NistP256, Scalar as P256Scalar, ProjectivePoint as P256ProjectivePoint};
 use ed25519_dalek::{VerifyingKey as Ed25519PublicKey, SigningKey};
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::scalar::Scalar as Ed25519Scalar;
+use curve25519_dalek::traits::Identity;
 use num_bigint::BigInt;
+use num_integer::Integer;
 use std::fmt;
 use serde_derive::{Serialize, Deserialize};
-use num_traits::Zero;
+use num_traits::{One, Zero};
 use rand::rngs::OsRng;
 use rand::RngCore;
+use sha2::{Digest, Sha256};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+
+// Ed25519 field prime p = 2^255 - 19, the Edwards curve constant d (per
+// RFC 8032: d = -121665/121666 mod p), and a fixed square root of -1 mod p --
+// the three constants RFC 8032 §5.1.3's point-decompression formula needs to
+// recover an affine x from a stored y. Decimal-string constants parsed at
+// call time, same convention `tss::curve`'s `BLS12_381_ORDER_DECIMAL` uses
+// for a curve constant the backing crate doesn't expose directly.
+const ED25519_FIELD_PRIME_DECIMAL: &str =
+    "57896044618658097711785492504343953926634992332820282019728792003956564819949";
+const ED25519_D_DECIMAL: &str =
+    "37095705934669439343138083508754565189542113879843219016388785533085940283555";
+const ED25519_SQRT_M1_DECIMAL: &str =
+    "19681161376707505956807079304988542015446066515923890162744021073123829784752";
+
+/// Recovers the affine x-coordinate for an Ed25519 point given its y
+/// coordinate and the sign bit carried in the compressed encoding's top bit,
+/// following RFC 8032 §5.1.3: `x^2 = (y^2-1) / (d*y^2+1) mod p`, then a
+/// candidate square root (the field has `p ≡ 5 mod 8`, so a single modpow
+/// finds it, optionally adjusted by `sqrt(-1)`) is parity-matched to `sign`.
+/// Returns `None` if `y` isn't the y-coordinate of any point on the curve.
+fn recover_ed25519_x(y: &BigInt, sign: bool) -> Option<BigInt> {
+    let p: BigInt = ED25519_FIELD_PRIME_DECIMAL.parse().expect("ED25519_FIELD_PRIME_DECIMAL is a valid decimal integer literal");
+    let d: BigInt = ED25519_D_DECIMAL.parse().expect("ED25519_D_DECIMAL is a valid decimal integer literal");
+    let sqrt_m1: BigInt = ED25519_SQRT_M1_DECIMAL.parse().expect("ED25519_SQRT_M1_DECIMAL is a valid decimal integer literal");
+
+    let y2 = y.modpow(&BigInt::from(2u32), &p);
+    let u = (&y2 - BigInt::one()).mod_floor(&p);
+    let v = (&d * &y2 + BigInt::one()).mod_floor(&p);
+    let v_inv = v.modinv(&p)?;
+    let x2 = (&u * &v_inv).mod_floor(&p);
+
+    let exp = (&p + BigInt::from(3u32)) / BigInt::from(8u32);
+    let mut x = x2.modpow(&exp, &p);
+    if (&x * &x).mod_floor(&p) != x2 {
+        x = (&x * &sqrt_m1).mod_floor(&p);
+        if (&x * &x).mod_floor(&p) != x2 {
+            return None;
+        }
+    }
+    if x.is_zero() && sign {
+        // x == 0 only has one square root (itself); a sign bit of 1 with no
+        // negative counterpart to pick instead means y wasn't a valid point.
+        return None;
+    }
+    if x.test_bit(0) != sign {
+        x = (&p - &x).mod_floor(&p);
+    }
+    Some(x)
+}
+
+/// Decompresses a stored Ed25519 `y` into a `curve25519-dalek` `EdwardsPoint`.
+/// `ECPoint::new`/`is_on_curve` already establish the convention that `y`
+/// holds the full 32-byte compressed encoding (sign bit included) loaded via
+/// `BigInt::from_bytes_be`, so reproducing that same byte layout here (rather
+/// than reinterpreting endianness) is what makes this the inverse of that
+/// convention.
+fn ecpoint_to_edwards(p: &ECPoint) -> Result<EdwardsPoint, String> {
+    if p.curve != ECCurve::Ed25519 {
+        return Err("Not an Ed25519 point".to_string());
+    }
+    let y_bytes = p.y.to_bytes_be().1;
+    if y_bytes.len() > 32 {
+        return Err("Coordinate too large".to_string());
+    }
+    let mut arr = [0u8; 32];
+    arr[32 - y_bytes.len()..].copy_from_slice(&y_bytes);
+    CompressedEdwardsY(arr).decompress().ok_or_else(|| "Invalid Ed25519 point encoding".to_string())
+}
+
+/// Rebuilds an `ECPoint` from an `EdwardsPoint`, recovering the affine x
+/// coordinate from the compressed encoding's sign bit so callers that
+/// inspect `x` (mirroring `to_secp256k1_affine`-style full affine access)
+/// still see a consistent pair, while keeping `y` in the same
+/// full-compressed-encoding-as-`BigInt` form `ECPoint::new` expects.
+fn edwards_to_ecpoint(point: &EdwardsPoint) -> Result<ECPoint, String> {
+    let compressed = point.compress();
+    let raw = compressed.to_bytes();
+    let y = BigInt::from_bytes_be(num_bigint::Sign::Plus, &raw);
+
+    let sign = (raw[31] & 0x80) != 0;
+    let mut canonical_le = raw;
+    canonical_le[31] &= 0x7f;
+    let canonical_y = BigInt::from_bytes_le(num_bigint::Sign::Plus, &canonical_le);
+    let x = recover_ed25519_x(&canonical_y, sign).ok_or("Decompressed point has no valid x-coordinate")?;
+    ECPoint::new(ECCurve::Ed25519, x, y)
+}
+
+/// Reduces a `BigInt` scalar mod the Ed25519 group order ℓ and encodes it as
+/// the little-endian bytes `curve25519-dalek`'s `Scalar` expects.
+fn bigint_to_ed25519_scalar(k: &BigInt) -> Ed25519Scalar {
+    let order = crate::tss::curve::ed25519_params().order().clone();
+    let reduced = k.mod_floor(&order);
+    let mut le_bytes = reduced.to_bytes_be().1;
+    le_bytes.reverse();
+    let mut arr = [0u8; 32];
+    arr[..le_bytes.len()].copy_from_slice(&le_bytes);
+    Ed25519Scalar::from_bytes_mod_order(arr)
+}
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum ECCurve {
     Secp256k1,
     Ed25519,
+    P256,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -20,6 +127,14 @@ pub struct ECPoint {
     pub curve: ECCurve,
     pub x: BigInt,
     pub y: BigInt,
+    /// Marks this as the group identity (point at infinity) rather than an
+    /// affine point. `x`/`y` carry no meaning when this is set -- Weierstrass
+    /// curves have no affine encoding for infinity, so `identity()` stores
+    /// zeroes; Ed25519's identity is a real affine point (`x = 0, y = 1`), so
+    /// `identity()` stores `x = 0` and its compressed encoding in `y` anyway,
+    /// and callers can test the flag instead of comparing coordinates.
+    #[serde(default)]
+    pub is_infinity: bool,
 }
 
 impl ECPoint {
@@ -40,7 +155,7 @@ impl ECPoint {
                 if affine.is_none().into() {
                     return Err("Point is not on the curve".to_string());
                 }
-                Ok(ECPoint { curve, x, y })
+                Ok(ECPoint { curve, x, y, is_infinity: false })
             }
             ECCurve::Ed25519 => {
                 // Ed25519 public keys are 32 bytes, y is encoded, x is recovered
@@ -55,20 +170,163 @@ impl ECPoint {
                 if pk.is_err() {
                     return Err("Invalid Ed25519 public key encoding".to_string());
                 }
-                Ok(ECPoint { curve, x, y })
+                Ok(ECPoint { curve, x, y, is_infinity: false })
+            }
+            ECCurve::P256 => {
+                let x_bytes = x.to_bytes_be().1;
+                let y_bytes = y.to_bytes_be().1;
+                let mut x_arr = [0u8; 32];
+                let mut y_arr = [0u8; 32];
+                if x_bytes.len() > 32 || y_bytes.len() > 32 {
+                    return Err("Coordinate too large".to_string());
+                }
+                x_arr[32 - x_bytes.len()..].copy_from_slice(&x_bytes);
+                y_arr[32 - y_bytes.len()..].copy_from_slice(&y_bytes);
+                let encoded = EncodedPoint::<NistP256>::from_affine_coordinates(&x_arr.into(), &y_arr.into(), false);
+                let affine = k256::elliptic_curve::AffinePoint::<NistP256>::from_encoded_point(&encoded);
+                if affine.is_none().into() {
+                    return Err("Point is not on the curve".to_string());
+                }
+                Ok(ECPoint { curve, x, y, is_infinity: false })
+            }
+        }
+    }
+
+    /// The group identity (point at infinity) for `curve`. `add` treats this
+    /// as the neutral element, and `scalar_mult`/`add` return it whenever a
+    /// group operation cancels out -- e.g. `P.add(&P.scalar_mult(&(-1))`.
+    pub fn identity(curve: ECCurve) -> ECPoint {
+        match curve {
+            ECCurve::Secp256k1 | ECCurve::P256 => {
+                ECPoint { curve, x: BigInt::zero(), y: BigInt::zero(), is_infinity: true }
+            }
+            ECCurve::Ed25519 => {
+                let mut p = edwards_to_ecpoint(&EdwardsPoint::identity())
+                    .expect("Ed25519's identity point always has a valid compressed encoding");
+                p.is_infinity = true;
+                p
+            }
+        }
+    }
+
+    /// The standard base point `G` for `curve`, as an affine `ECPoint`.
+    /// Ed25519's generator isn't offered here since nothing in this module
+    /// needs it yet -- `eddsa::` code works with `curve25519_dalek::EdwardsPoint`
+    /// directly rather than through this struct.
+    pub fn generator(curve: ECCurve) -> Result<ECPoint, String> {
+        match curve {
+            ECCurve::Secp256k1 => {
+                let affine = ProjectivePoint::GENERATOR.to_affine();
+                let encoded = affine.to_encoded_point(false);
+                let x = BigInt::from_bytes_be(num_bigint::Sign::Plus, encoded.x().unwrap());
+                let y = BigInt::from_bytes_be(num_bigint::Sign::Plus, encoded.y().unwrap());
+                ECPoint::new(ECCurve::Secp256k1, x, y)
+            }
+            ECCurve::P256 => {
+                let affine = P256ProjectivePoint::GENERATOR.to_affine();
+                let encoded = affine.to_encoded_point(false);
+                let x = BigInt::from_bytes_be(num_bigint::Sign::Plus, encoded.x().unwrap());
+                let y = BigInt::from_bytes_be(num_bigint::Sign::Plus, encoded.y().unwrap());
+                ECPoint::new(ECCurve::P256, x, y)
+            }
+            ECCurve::Ed25519 => Err("generator is not implemented for Ed25519 on this struct".to_string()),
+        }
+    }
+
+    /// Scalar-multiplies `curve`'s generator `G` by `k`: `G^k` (or `k*G` in
+    /// additive notation).
+    pub fn scalar_base_mult(curve: ECCurve, k: &BigInt) -> Result<ECPoint, String> {
+        ECPoint::generator(curve)?.scalar_mult(k)
+    }
+
+    /// Derives a second generator `H` with unknown discrete log relative to
+    /// `curve`'s generator `G`, via try-and-increment: hash `label` with a
+    /// little-endian counter through SHA-256 to get a candidate x, test
+    /// whether `x^3 + a*x + b mod p` is a quadratic residue by attempting its
+    /// modular square root (both curves supported have `p ≡ 3 mod 4`, so a
+    /// single `modpow` finds it when one exists), and decompress with the
+    /// even root as the fixed sign-bit convention. Retries on a non-residue
+    /// or on landing on `G` itself. Only defined for the short-Weierstrass
+    /// curves (`y^2 = x^3 + a*x + b`); Ed25519 needs an Edwards-specific
+    /// hash-to-curve method this try-and-increment approach doesn't cover.
+    pub fn hash_to_curve(curve: ECCurve, label: &[u8]) -> Result<ECPoint, String> {
+        let (p, a, b): (BigInt, BigInt, BigInt) = match curve {
+            ECCurve::Secp256k1 => (
+                "115792089237316195423570985008687907853269984665640564039457584007908834671663"
+                    .parse().expect("secp256k1 field prime is a valid decimal integer literal"),
+                BigInt::zero(),
+                BigInt::from(7u32),
+            ),
+            ECCurve::P256 => (
+                "115792089210356248762697446949407573530086143415290314195533631308867097853951"
+                    .parse().expect("P-256 field prime is a valid decimal integer literal"),
+                "115792089210356248762697446949407573530086143415290314195533631308867097853948"
+                    .parse().expect("P-256 curve coefficient a is a valid decimal integer literal"),
+                "41058363725152142129326129780047268409114441015993725554835256314039467401291"
+                    .parse().expect("P-256 curve coefficient b is a valid decimal integer literal"),
+            ),
+            ECCurve::Ed25519 => return Err("hash_to_curve is not defined for Ed25519's Edwards form".to_string()),
+        };
+
+        let generator = ECPoint::generator(curve)?;
+
+        const MAX_ATTEMPTS: u32 = 10_000;
+        for counter in 0..MAX_ATTEMPTS {
+            let mut hasher = Sha256::new();
+            hasher.update(label);
+            hasher.update(counter.to_le_bytes());
+            let digest = hasher.finalize();
+            let x = BigInt::from_bytes_be(num_bigint::Sign::Plus, &digest).mod_floor(&p);
+
+            let rhs = (&x * &x * &x + &a * &x + &b).mod_floor(&p);
+            if rhs.is_zero() {
+                continue;
+            }
+            let sqrt_exp = (&p + BigInt::one()) / BigInt::from(4u32);
+            let candidate = rhs.modpow(&sqrt_exp, &p);
+            if candidate.modpow(&BigInt::from(2u32), &p) != rhs {
+                continue; // rhs is not a quadratic residue mod p
             }
+            let y = if candidate.is_even() { candidate } else { &p - candidate };
+
+            let point = match ECPoint::new(curve, x, y) {
+                Ok(point) => point,
+                Err(_) => continue,
+            };
+            if point == generator {
+                continue;
+            }
+            return Ok(point);
         }
+        Err("hash_to_curve: exhausted attempts without finding a valid point".to_string())
+    }
+
+    /// The canonical second generator `H` used for Pedersen-style
+    /// commitments on `curve`: `hash_to_curve` under a fixed
+    /// domain-separation label, so every party derives the identical
+    /// nothing-up-my-sleeve point without needing to exchange it.
+    pub fn base_point2(curve: ECCurve) -> Result<ECPoint, String> {
+        ECPoint::hash_to_curve(curve, b"tss-lib/ecpoint/base_point2")
     }
 
     pub fn add(&self, other: &ECPoint) -> Result<ECPoint, String> {
         if self.curve != other.curve {
             return Err("Curve mismatch".to_string());
         }
+        if self.is_infinity {
+            return Ok(other.clone());
+        }
+        if other.is_infinity {
+            return Ok(self.clone());
+        }
         match self.curve {
             ECCurve::Secp256k1 => {
                 let p1 = self.to_secp256k1_affine()?;
                 let p2 = other.to_secp256k1_affine()?;
                 let sum = ProjectivePoint::from(p1) + ProjectivePoint::from(p2);
+                if bool::from(sum.is_identity()) {
+                    return Ok(ECPoint::identity(ECCurve::Secp256k1));
+                }
                 let sum_affine = sum.to_affine();
                 let encoded = sum_affine.to_encoded_point(false);
                 let x = BigInt::from_bytes_be(num_bigint::Sign::Plus, encoded.x().unwrap());
@@ -76,12 +334,34 @@ impl ECPoint {
                 ECPoint::new(ECCurve::Secp256k1, x, y)
             }
             ECCurve::Ed25519 => {
-                Err("Ed25519 point addition not implemented".to_string())
+                let p1 = ecpoint_to_edwards(self)?;
+                let p2 = ecpoint_to_edwards(other)?;
+                let sum = p1 + p2;
+                if sum.is_identity() {
+                    return Ok(ECPoint::identity(ECCurve::Ed25519));
+                }
+                edwards_to_ecpoint(&sum)
+            }
+            ECCurve::P256 => {
+                let p1 = self.to_p256_affine()?;
+                let p2 = other.to_p256_affine()?;
+                let sum = P256ProjectivePoint::from(p1) + P256ProjectivePoint::from(p2);
+                if bool::from(sum.is_identity()) {
+                    return Ok(ECPoint::identity(ECCurve::P256));
+                }
+                let sum_affine = sum.to_affine();
+                let encoded = sum_affine.to_encoded_point(false);
+                let x = BigInt::from_bytes_be(num_bigint::Sign::Plus, encoded.x().unwrap());
+                let y = BigInt::from_bytes_be(num_bigint::Sign::Plus, encoded.y().unwrap());
+                ECPoint::new(ECCurve::P256, x, y)
             }
         }
     }
 
     pub fn scalar_mult(&self, k: &BigInt) -> Result<ECPoint, String> {
+        if self.is_infinity {
+            return Ok(ECPoint::identity(self.curve.clone()));
+        }
         match self.curve {
             ECCurve::Secp256k1 => {
                 let p = self.to_secp256k1_affine()?;
@@ -95,6 +375,9 @@ impl ECPoint {
                 if scalar_ct.is_some().into() {
                     let scalar = scalar_ct.unwrap();
                     let res = ProjectivePoint::from(p) * scalar;
+                    if bool::from(res.is_identity()) {
+                        return Ok(ECPoint::identity(ECCurve::Secp256k1));
+                    }
                     let res_affine = res.to_affine();
                     let encoded = res_affine.to_encoded_point(false);
                     let x = BigInt::from_bytes_be(num_bigint::Sign::Plus, encoded.x().unwrap());
@@ -105,12 +388,46 @@ impl ECPoint {
                 }
             }
             ECCurve::Ed25519 => {
-                Err("Ed25519 scalar multiplication not implemented".to_string())
+                let p = ecpoint_to_edwards(self)?;
+                let scalar = bigint_to_ed25519_scalar(k);
+                let res = p * scalar;
+                if res.is_identity() {
+                    return Ok(ECPoint::identity(ECCurve::Ed25519));
+                }
+                edwards_to_ecpoint(&res)
+            }
+            ECCurve::P256 => {
+                let p = self.to_p256_affine()?;
+                let k = k.mod_floor(crate::tss::curve::secp256r1_params().order());
+                let k_bytes = k.to_bytes_be().1;
+                let mut scalar_bytes = [0u8; 32];
+                if k_bytes.len() > 32 {
+                    return Err("Scalar too large".to_string());
+                }
+                scalar_bytes[32 - k_bytes.len()..].copy_from_slice(&k_bytes);
+                let scalar_ct = P256Scalar::from_repr(scalar_bytes.into());
+                if scalar_ct.is_some().into() {
+                    let scalar = scalar_ct.unwrap();
+                    let res = P256ProjectivePoint::from(p) * scalar;
+                    if bool::from(res.is_identity()) {
+                        return Ok(ECPoint::identity(ECCurve::P256));
+                    }
+                    let res_affine = res.to_affine();
+                    let encoded = res_affine.to_encoded_point(false);
+                    let x = BigInt::from_bytes_be(num_bigint::Sign::Plus, encoded.x().unwrap());
+                    let y = BigInt::from_bytes_be(num_bigint::Sign::Plus, encoded.y().unwrap());
+                    ECPoint::new(ECCurve::P256, x, y)
+                } else {
+                    Err("Invalid scalar".to_string())
+                }
             }
         }
     }
 
     pub fn is_on_curve(&self) -> bool {
+        if self.is_infinity {
+            return true;
+        }
         match self.curve {
             ECCurve::Secp256k1 => self.to_secp256k1_affine().is_ok(),
             ECCurve::Ed25519 => {
@@ -122,6 +439,7 @@ impl ECPoint {
                 y_arr[32 - y_bytes.len()..].copy_from_slice(&y_bytes);
                 Ed25519PublicKey::from_bytes(&y_arr).is_ok()
             }
+            ECCurve::P256 => self.to_p256_affine().is_ok(),
         }
     }
 
@@ -129,6 +447,9 @@ impl ECPoint {
         if self.curve != ECCurve::Secp256k1 {
             return Err("Not a secp256k1 point".to_string());
         }
+        if self.is_infinity {
+            return Err("Cannot convert the point at infinity to affine coordinates".to_string());
+        }
         let x_bytes = self.x.to_bytes_be().1;
         let y_bytes = self.y.to_bytes_be().1;
         let mut x_arr = [0u8; 32];
@@ -146,6 +467,189 @@ impl ECPoint {
             Err("Invalid point encoding".to_string())
         }
     }
+
+    pub fn to_p256_affine(&self) -> Result<k256::elliptic_curve::AffinePoint<NistP256>, String> {
+        if self.curve != ECCurve::P256 {
+            return Err("Not a P-256 point".to_string());
+        }
+        if self.is_infinity {
+            return Err("Cannot convert the point at infinity to affine coordinates".to_string());
+        }
+        let x_bytes = self.x.to_bytes_be().1;
+        let y_bytes = self.y.to_bytes_be().1;
+        let mut x_arr = [0u8; 32];
+        let mut y_arr = [0u8; 32];
+        if x_bytes.len() > 32 || y_bytes.len() > 32 {
+            return Err("Coordinate too large".to_string());
+        }
+        x_arr[32 - x_bytes.len()..].copy_from_slice(&x_bytes);
+        y_arr[32 - y_bytes.len()..].copy_from_slice(&y_bytes);
+        let encoded = EncodedPoint::<NistP256>::from_affine_coordinates(&x_arr.into(), &y_arr.into(), false);
+        let affine = k256::elliptic_curve::AffinePoint::<NistP256>::from_encoded_point(&encoded);
+        if affine.is_some().into() {
+            Ok(affine.unwrap())
+        } else {
+            Err("Invalid point encoding".to_string())
+        }
+    }
+
+    /// Encodes this point as a SEC1 octet string for the Weierstrass curves
+    /// (`0x02`/`0x03` prefix + 32-byte x when `compressed`, `0x04` + x||y
+    /// otherwise), or Ed25519's native 32-byte compressed encoding
+    /// regardless of `compressed` -- there's no uncompressed Ed25519 wire
+    /// format to opt into. The point at infinity has no affine coordinates,
+    /// so SEC1's single `0x00` byte stands in for it on the Weierstrass
+    /// curves; Ed25519's identity is a real affine point and round-trips
+    /// through its usual 32-byte encoding instead.
+    pub fn to_bytes(&self, compressed: bool) -> Vec<u8> {
+        match self.curve {
+            ECCurve::Secp256k1 | ECCurve::P256 if self.is_infinity => vec![0u8],
+            ECCurve::Secp256k1 => {
+                let affine = self.to_secp256k1_affine().expect("ECPoint is only constructed from on-curve coordinates");
+                affine.to_encoded_point(compressed).as_bytes().to_vec()
+            }
+            ECCurve::P256 => {
+                let affine = self.to_p256_affine().expect("ECPoint is only constructed from on-curve coordinates");
+                affine.to_encoded_point(compressed).as_bytes().to_vec()
+            }
+            ECCurve::Ed25519 => {
+                let y_bytes = self.y.to_bytes_be().1;
+                let mut arr = [0u8; 32];
+                arr[32 - y_bytes.len()..].copy_from_slice(&y_bytes);
+                arr.to_vec()
+            }
+        }
+    }
+
+    /// Decodes a SEC1 octet string (compressed or uncompressed, detected
+    /// from the prefix byte) into a Weierstrass `ECPoint`, or a native
+    /// 32-byte compressed encoding into an Ed25519 `ECPoint` -- the inverse
+    /// of [`to_bytes`](Self::to_bytes). This is also how a compressed point
+    /// gets decompressed: SEC1's `from_bytes` recovers `y` from `x` and the
+    /// prefix's sign bit for the Weierstrass curves, same as
+    /// `recover_ed25519_x` does for Ed25519. A lone `0x00` byte is SEC1's
+    /// encoding of the point at infinity on the Weierstrass curves.
+    pub fn from_bytes(curve: ECCurve, bytes: &[u8]) -> Result<ECPoint, String> {
+        match curve {
+            ECCurve::Secp256k1 | ECCurve::P256 if bytes == [0u8] => Ok(ECPoint::identity(curve)),
+            ECCurve::Secp256k1 => {
+                let encoded = EncodedPoint::<Secp256k1>::from_bytes(bytes).map_err(|_| "Invalid SEC1 encoding".to_string())?;
+                let affine = k256::elliptic_curve::AffinePoint::<Secp256k1>::from_encoded_point(&encoded);
+                if affine.is_none().into() {
+                    return Err("Point is not on the curve".to_string());
+                }
+                let full = affine.unwrap().to_encoded_point(false);
+                let x = BigInt::from_bytes_be(num_bigint::Sign::Plus, full.x().unwrap());
+                let y = BigInt::from_bytes_be(num_bigint::Sign::Plus, full.y().unwrap());
+                ECPoint::new(ECCurve::Secp256k1, x, y)
+            }
+            ECCurve::P256 => {
+                let encoded = EncodedPoint::<NistP256>::from_bytes(bytes).map_err(|_| "Invalid SEC1 encoding".to_string())?;
+                let affine = k256::elliptic_curve::AffinePoint::<NistP256>::from_encoded_point(&encoded);
+                if affine.is_none().into() {
+                    return Err("Point is not on the curve".to_string());
+                }
+                let full = affine.unwrap().to_encoded_point(false);
+                let x = BigInt::from_bytes_be(num_bigint::Sign::Plus, full.x().unwrap());
+                let y = BigInt::from_bytes_be(num_bigint::Sign::Plus, full.y().unwrap());
+                ECPoint::new(ECCurve::P256, x, y)
+            }
+            ECCurve::Ed25519 => {
+                let arr: [u8; 32] = bytes.try_into().map_err(|_| "Ed25519 compressed encoding must be 32 bytes".to_string())?;
+                let point = CompressedEdwardsY(arr).decompress().ok_or("Invalid Ed25519 point encoding")?;
+                edwards_to_ecpoint(&point)
+            }
+        }
+    }
+
+    /// Encodes this point as an RFC 7518/8037 JSON Web Key: `"EC"`/`x`/`y`
+    /// (32-byte big-endian coordinates) for the Weierstrass curves, `"OKP"`/
+    /// `x` (32-byte compressed encoding, no `y`) for Ed25519. All coordinate
+    /// fields are base64url, unpadded, per RFC 7518 §6.2.1.
+    pub fn to_jwk(&self) -> Result<Jwk, String> {
+        if self.is_infinity {
+            return Err("The point at infinity has no affine coordinates to encode as a JWK".to_string());
+        }
+        match self.curve {
+            ECCurve::Secp256k1 | ECCurve::P256 => {
+                let uncompressed = self.to_bytes(false);
+                let (x, y) = uncompressed[1..].split_at(32);
+                Ok(Jwk {
+                    kty: "EC".to_string(),
+                    crv: jwk_crv_name(self.curve).to_string(),
+                    x: URL_SAFE_NO_PAD.encode(x),
+                    y: Some(URL_SAFE_NO_PAD.encode(y)),
+                })
+            }
+            ECCurve::Ed25519 => Ok(Jwk {
+                kty: "OKP".to_string(),
+                crv: jwk_crv_name(self.curve).to_string(),
+                x: URL_SAFE_NO_PAD.encode(self.to_bytes(true)),
+                y: None,
+            }),
+        }
+    }
+
+    /// Decodes an RFC 7518/8037 JSON Web Key back into an `ECPoint`, the
+    /// inverse of [`to_jwk`](Self::to_jwk). Validates `kty`/`crv` match a
+    /// supported curve and that decoded coordinates are exactly 32 bytes
+    /// before reconstructing the point.
+    pub fn from_jwk(jwk: &Jwk) -> Result<ECPoint, String> {
+        let curve = jwk_curve_from_names(&jwk.kty, &jwk.crv)?;
+        let x = URL_SAFE_NO_PAD.decode(&jwk.x).map_err(|_| "Invalid base64url in JWK x coordinate".to_string())?;
+        match curve {
+            ECCurve::Secp256k1 | ECCurve::P256 => {
+                let y_field = jwk.y.as_ref().ok_or("JWK is missing the y coordinate required for an EC key")?;
+                let y = URL_SAFE_NO_PAD.decode(y_field).map_err(|_| "Invalid base64url in JWK y coordinate".to_string())?;
+                if x.len() != 32 || y.len() != 32 {
+                    return Err("JWK EC coordinates must each be 32 bytes".to_string());
+                }
+                let mut uncompressed = Vec::with_capacity(65);
+                uncompressed.push(0x04);
+                uncompressed.extend_from_slice(&x);
+                uncompressed.extend_from_slice(&y);
+                ECPoint::from_bytes(curve, &uncompressed)
+            }
+            ECCurve::Ed25519 => {
+                if x.len() != 32 {
+                    return Err("JWK OKP x coordinate must be 32 bytes".to_string());
+                }
+                ECPoint::from_bytes(ECCurve::Ed25519, &x)
+            }
+        }
+    }
+}
+
+/// The RFC 7518/8037 `crv` name for `curve`.
+fn jwk_crv_name(curve: ECCurve) -> &'static str {
+    match curve {
+        ECCurve::Secp256k1 => "secp256k1",
+        ECCurve::P256 => "P-256",
+        ECCurve::Ed25519 => "Ed25519",
+    }
+}
+
+/// Maps a JWK's `kty`/`crv` pair back to an `ECCurve`, rejecting any
+/// combination this crate doesn't support (e.g. an `"EC"` key wouldn't be
+/// `crv: "Ed25519"`, which is `"OKP"`-only under RFC 8037).
+fn jwk_curve_from_names(kty: &str, crv: &str) -> Result<ECCurve, String> {
+    match (kty, crv) {
+        ("EC", "secp256k1") => Ok(ECCurve::Secp256k1),
+        ("EC", "P-256") => Ok(ECCurve::P256),
+        ("OKP", "Ed25519") => Ok(ECCurve::Ed25519),
+        _ => Err(format!("Unsupported JWK kty/crv combination: {kty}/{crv}")),
+    }
+}
+
+/// An RFC 7518/8037 JSON Web Key for an `ECPoint` public key. `y` is `None`
+/// for Ed25519's `"OKP"` key type, which has no second coordinate.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Jwk {
+    pub kty: String,
+    pub crv: String,
+    pub x: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub y: Option<String>,
 }
 
 pub fn flatten_ecpoints(points: &[ECPoint]) -> Result<Vec<BigInt>, String> {
@@ -214,6 +718,98 @@ mod tests {
         assert!(res_point.is_on_curve());
     }
 
+    fn p256_affine_to_bigints(affine: k256::elliptic_curve::AffinePoint<NistP256>) -> (BigInt, BigInt) {
+        let encoded = affine.to_encoded_point(false);
+        let x = BigInt::from_bytes_be(num_bigint::Sign::Plus, encoded.x().unwrap());
+        let y = BigInt::from_bytes_be(num_bigint::Sign::Plus, encoded.y().unwrap());
+        (x, y)
+    }
+
+    #[test]
+    fn test_p256_ecpoint_add() {
+        let g = P256ProjectivePoint::GENERATOR;
+        let affine = g.to_affine();
+        let (x, y) = p256_affine_to_bigints(affine);
+        let p1 = ECPoint::new(ECCurve::P256, x.clone(), y.clone()).unwrap();
+        let p2 = ECPoint::new(ECCurve::P256, x, y).unwrap();
+        let sum = p1.add(&p2);
+        assert!(sum.is_ok());
+        let sum_point = sum.unwrap();
+        assert!(sum_point.is_on_curve());
+    }
+
+    #[test]
+    fn test_p256_ecpoint_scalar_mult() {
+        let g = P256ProjectivePoint::GENERATOR;
+        let affine = g.to_affine();
+        let (x, y) = p256_affine_to_bigints(affine);
+        let p = ECPoint::new(ECCurve::P256, x, y).unwrap();
+        let k = 2.to_bigint().unwrap();
+        let res = p.scalar_mult(&k);
+        assert!(res.is_ok());
+        let res_point = res.unwrap();
+        assert!(res_point.is_on_curve());
+    }
+
+    #[test]
+    fn test_p256_ecpoint_rejects_secp256k1_coordinates() {
+        let g = ProjectivePoint::GENERATOR;
+        let affine = g.to_affine();
+        let (x, y) = affine_to_bigints(affine);
+        // Secp256k1's generator coordinates are vanishingly unlikely to also
+        // satisfy the P-256 curve equation.
+        assert!(ECPoint::new(ECCurve::P256, x, y).is_err());
+    }
+
+    #[test]
+    fn test_secp256k1_sec1_round_trip_compressed_and_uncompressed() {
+        let g = ProjectivePoint::GENERATOR;
+        let (x, y) = affine_to_bigints(g.to_affine());
+        let p = ECPoint::new(ECCurve::Secp256k1, x, y).unwrap();
+
+        let compressed = p.to_bytes(true);
+        assert_eq!(compressed.len(), 33);
+        assert_eq!(ECPoint::from_bytes(ECCurve::Secp256k1, &compressed).unwrap(), p);
+
+        let uncompressed = p.to_bytes(false);
+        assert_eq!(uncompressed.len(), 65);
+        assert_eq!(ECPoint::from_bytes(ECCurve::Secp256k1, &uncompressed).unwrap(), p);
+    }
+
+    #[test]
+    fn test_p256_sec1_round_trip_compressed_and_uncompressed() {
+        let g = P256ProjectivePoint::GENERATOR;
+        let (x, y) = p256_affine_to_bigints(g.to_affine());
+        let p = ECPoint::new(ECCurve::P256, x, y).unwrap();
+
+        let compressed = p.to_bytes(true);
+        assert_eq!(compressed.len(), 33);
+        assert_eq!(ECPoint::from_bytes(ECCurve::P256, &compressed).unwrap(), p);
+
+        let uncompressed = p.to_bytes(false);
+        assert_eq!(uncompressed.len(), 65);
+        assert_eq!(ECPoint::from_bytes(ECCurve::P256, &uncompressed).unwrap(), p);
+    }
+
+    #[test]
+    fn test_ed25519_bytes_round_trip() {
+        let g = curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+        let p = edwards_to_ecpoint(&g).unwrap();
+
+        let bytes = p.to_bytes(true);
+        assert_eq!(bytes.len(), 32);
+        assert_eq!(ECPoint::from_bytes(ECCurve::Ed25519, &bytes).unwrap(), p);
+        // `compressed` has no effect on Ed25519's native encoding.
+        assert_eq!(p.to_bytes(false), bytes);
+    }
+
+    #[test]
+    fn test_from_bytes_rejects_malformed_encoding() {
+        assert!(ECPoint::from_bytes(ECCurve::Secp256k1, &[0u8; 10]).is_err());
+        assert!(ECPoint::from_bytes(ECCurve::P256, &[0u8; 10]).is_err());
+        assert!(ECPoint::from_bytes(ECCurve::Ed25519, &[0u8; 10]).is_err());
+    }
+
     #[test]
     fn test_ed25519_ecpoint_is_on_curve() {
         use ed25519_dalek::SigningKey;
@@ -230,6 +826,50 @@ mod tests {
         assert!(p.is_on_curve());
     }
 
+    fn ed25519_point_from_edwards(point: &EdwardsPoint) -> ECPoint {
+        edwards_to_ecpoint(point).unwrap()
+    }
+
+    #[test]
+    fn test_ed25519_ecpoint_add() {
+        let g = curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+        let p1 = ed25519_point_from_edwards(&g);
+        let p2 = ed25519_point_from_edwards(&g);
+        let sum = p1.add(&p2).unwrap();
+        assert!(sum.is_on_curve());
+        assert_eq!(sum, ed25519_point_from_edwards(&(g + g)));
+    }
+
+    #[test]
+    fn test_ed25519_ecpoint_scalar_mult() {
+        let g = curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+        let p = ed25519_point_from_edwards(&g);
+        let k = 2.to_bigint().unwrap();
+        let res = p.scalar_mult(&k).unwrap();
+        assert!(res.is_on_curve());
+        assert_eq!(res, ed25519_point_from_edwards(&(g + g)));
+    }
+
+    #[test]
+    fn test_ed25519_ecpoint_scalar_mult_reduces_mod_group_order() {
+        let g = curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+        let p = ed25519_point_from_edwards(&g);
+        let order = crate::tss::curve::ed25519_params().order().clone();
+        let k = &order + 2.to_bigint().unwrap();
+        let res = p.scalar_mult(&k).unwrap();
+        assert_eq!(res, p.scalar_mult(&2.to_bigint().unwrap()).unwrap());
+    }
+
+    #[test]
+    fn test_ecpoint_add_rejects_mixed_curve() {
+        let g = ProjectivePoint::GENERATOR;
+        let affine = g.to_affine();
+        let (x, y) = affine_to_bigints(affine);
+        let secp_point = ECPoint::new(ECCurve::Secp256k1, x, y).unwrap();
+        let ed_point = ed25519_point_from_edwards(&curve25519_dalek::constants::ED25519_BASEPOINT_POINT);
+        assert!(secp_point.add(&ed_point).is_err());
+    }
+
     #[test]
     fn test_flatten_unflatten() {
         let g = ProjectivePoint::GENERATOR;
@@ -242,6 +882,18 @@ mod tests {
         assert_eq!(points, unflat);
     }
 
+    #[test]
+    fn test_flatten_unflatten_p256() {
+        let g = P256ProjectivePoint::GENERATOR;
+        let affine = g.to_affine();
+        let (x, y) = p256_affine_to_bigints(affine);
+        let p = ECPoint::new(ECCurve::P256, x, y).unwrap();
+        let points = vec![p.clone(), p.clone()];
+        let flat = flatten_ecpoints(&points).unwrap();
+        let unflat = unflatten_ecpoints(ECCurve::P256, &flat).unwrap();
+        assert_eq!(points, unflat);
+    }
+
     #[test]
     fn test_serde_json() {
         let g = ProjectivePoint::GENERATOR;
@@ -252,4 +904,189 @@ mod tests {
         let p2: ECPoint = serde_json::from_str(&json).unwrap();
         assert_eq!(p, p2);
     }
+
+    #[test]
+    fn test_secp256k1_add_cancellation_yields_identity() {
+        let g = ProjectivePoint::GENERATOR;
+        let affine = g.to_affine();
+        let (x, y) = affine_to_bigints(affine);
+        let p = ECPoint::new(ECCurve::Secp256k1, x, y).unwrap();
+        let neg_p = p.scalar_mult(&(-BigInt::one())).unwrap();
+        let sum = p.add(&neg_p).unwrap();
+        assert!(sum.is_infinity);
+        assert!(sum.is_on_curve());
+    }
+
+    #[test]
+    fn test_secp256k1_identity_is_neutral_for_add() {
+        let g = ProjectivePoint::GENERATOR;
+        let affine = g.to_affine();
+        let (x, y) = affine_to_bigints(affine);
+        let p = ECPoint::new(ECCurve::Secp256k1, x, y).unwrap();
+        let identity = ECPoint::identity(ECCurve::Secp256k1);
+        assert_eq!(p.add(&identity).unwrap(), p);
+        assert_eq!(identity.add(&p).unwrap(), p);
+    }
+
+    #[test]
+    fn test_secp256k1_scalar_mult_by_order_yields_identity() {
+        let g = ProjectivePoint::GENERATOR;
+        let affine = g.to_affine();
+        let (x, y) = affine_to_bigints(affine);
+        let p = ECPoint::new(ECCurve::Secp256k1, x, y).unwrap();
+        let order = crate::tss::curve::s256k1_params().order().clone();
+        let res = p.scalar_mult(&order).unwrap();
+        assert!(res.is_infinity);
+    }
+
+    #[test]
+    fn test_p256_add_cancellation_yields_identity() {
+        let g = P256ProjectivePoint::GENERATOR;
+        let affine = g.to_affine();
+        let (x, y) = p256_affine_to_bigints(affine);
+        let p = ECPoint::new(ECCurve::P256, x, y).unwrap();
+        let neg_p = p.scalar_mult(&(-BigInt::one())).unwrap();
+        let sum = p.add(&neg_p).unwrap();
+        assert!(sum.is_infinity);
+        assert!(sum.is_on_curve());
+    }
+
+    #[test]
+    fn test_ed25519_add_cancellation_yields_identity() {
+        let g = curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+        let p = ed25519_point_from_edwards(&g);
+        let neg_p = p.scalar_mult(&(-BigInt::one())).unwrap();
+        let sum = p.add(&neg_p).unwrap();
+        assert!(sum.is_infinity);
+    }
+
+    #[test]
+    fn test_secp256k1_identity_sec1_round_trip() {
+        let identity = ECPoint::identity(ECCurve::Secp256k1);
+        let bytes = identity.to_bytes(true);
+        assert_eq!(bytes, vec![0u8]);
+        let decoded = ECPoint::from_bytes(ECCurve::Secp256k1, &bytes).unwrap();
+        assert!(decoded.is_infinity);
+    }
+
+    #[test]
+    fn test_to_secp256k1_affine_rejects_infinity() {
+        let identity = ECPoint::identity(ECCurve::Secp256k1);
+        assert!(identity.to_secp256k1_affine().is_err());
+    }
+
+    #[test]
+    fn test_hash_to_curve_secp256k1_is_on_curve_and_not_generator() {
+        let h = ECPoint::hash_to_curve(ECCurve::Secp256k1, b"test-label").unwrap();
+        assert!(h.is_on_curve());
+        let g = ProjectivePoint::GENERATOR.to_affine();
+        let (gx, gy) = affine_to_bigints(g);
+        assert_ne!((h.x.clone(), h.y.clone()), (gx, gy));
+    }
+
+    #[test]
+    fn test_hash_to_curve_is_deterministic() {
+        let h1 = ECPoint::hash_to_curve(ECCurve::Secp256k1, b"test-label").unwrap();
+        let h2 = ECPoint::hash_to_curve(ECCurve::Secp256k1, b"test-label").unwrap();
+        assert_eq!(h1, h2);
+    }
+
+    #[test]
+    fn test_hash_to_curve_different_labels_differ() {
+        let h1 = ECPoint::hash_to_curve(ECCurve::Secp256k1, b"label-a").unwrap();
+        let h2 = ECPoint::hash_to_curve(ECCurve::Secp256k1, b"label-b").unwrap();
+        assert_ne!(h1, h2);
+    }
+
+    #[test]
+    fn test_hash_to_curve_p256_is_on_curve() {
+        let h = ECPoint::hash_to_curve(ECCurve::P256, b"test-label").unwrap();
+        assert!(h.is_on_curve());
+    }
+
+    #[test]
+    fn test_hash_to_curve_rejects_ed25519() {
+        assert!(ECPoint::hash_to_curve(ECCurve::Ed25519, b"test-label").is_err());
+    }
+
+    #[test]
+    fn test_base_point2_is_deterministic_and_on_curve() {
+        let h1 = ECPoint::base_point2(ECCurve::Secp256k1).unwrap();
+        let h2 = ECPoint::base_point2(ECCurve::Secp256k1).unwrap();
+        assert_eq!(h1, h2);
+        assert!(h1.is_on_curve());
+    }
+
+    #[test]
+    fn test_secp256k1_jwk_round_trip() {
+        let g = ProjectivePoint::GENERATOR;
+        let affine = g.to_affine();
+        let (x, y) = affine_to_bigints(affine);
+        let p = ECPoint::new(ECCurve::Secp256k1, x, y).unwrap();
+
+        let jwk = p.to_jwk().unwrap();
+        assert_eq!(jwk.kty, "EC");
+        assert_eq!(jwk.crv, "secp256k1");
+        assert!(jwk.y.is_some());
+
+        let recovered = ECPoint::from_jwk(&jwk).unwrap();
+        assert_eq!(p, recovered);
+    }
+
+    #[test]
+    fn test_p256_jwk_round_trip() {
+        let g = P256ProjectivePoint::GENERATOR;
+        let affine = g.to_affine();
+        let (x, y) = p256_affine_to_bigints(affine);
+        let p = ECPoint::new(ECCurve::P256, x, y).unwrap();
+
+        let jwk = p.to_jwk().unwrap();
+        assert_eq!(jwk.kty, "EC");
+        assert_eq!(jwk.crv, "P-256");
+
+        let recovered = ECPoint::from_jwk(&jwk).unwrap();
+        assert_eq!(p, recovered);
+    }
+
+    #[test]
+    fn test_ed25519_jwk_round_trip() {
+        let g = curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+        let p = ed25519_point_from_edwards(&g);
+
+        let jwk = p.to_jwk().unwrap();
+        assert_eq!(jwk.kty, "OKP");
+        assert_eq!(jwk.crv, "Ed25519");
+        assert!(jwk.y.is_none());
+
+        let recovered = ECPoint::from_jwk(&jwk).unwrap();
+        assert_eq!(p, recovered);
+    }
+
+    #[test]
+    fn test_from_jwk_rejects_mismatched_kty_crv() {
+        let jwk = Jwk {
+            kty: "EC".to_string(),
+            crv: "Ed25519".to_string(),
+            x: URL_SAFE_NO_PAD.encode([0u8; 32]),
+            y: None,
+        };
+        assert!(ECPoint::from_jwk(&jwk).is_err());
+    }
+
+    #[test]
+    fn test_from_jwk_rejects_wrong_coordinate_length() {
+        let jwk = Jwk {
+            kty: "OKP".to_string(),
+            crv: "Ed25519".to_string(),
+            x: URL_SAFE_NO_PAD.encode([0u8; 16]),
+            y: None,
+        };
+        assert!(ECPoint::from_jwk(&jwk).is_err());
+    }
+
+    #[test]
+    fn test_to_jwk_rejects_infinity() {
+        let identity = ECPoint::identity(ECCurve::Secp256k1);
+        assert!(identity.to_jwk().is_err());
+    }
 }
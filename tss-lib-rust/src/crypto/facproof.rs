@@ -1,74 +1,289 @@
+// Factorization proof (CGGMP21 Π_fac): proves an RSA modulus `N0` has no
+// small factors, without revealing `p`/`q`. Keygen runs this against each
+// party's Paillier modulus so a malicious party can't submit an `N0` with a
+// small prime factor, which would let other parties' encrypted shares leak
+// through a Paillier structural weakness.
+//
+// `ProofFac::new` used to be a stub -- `q` hardcoded to `BigInt::one()` and
+// every blind fixed to `one()` -- so it "verified" without binding anything.
+// This fills in real ring-Pedersen-committed blinds and a matching `verify`,
+// following the same `ProofTranscript`/error-enum shape as `ProofFac`'s
+// sibling sigma-protocol proofs in this crate.
+
 use num_bigint::BigInt;
-use num_traits::One;
-use crate::common::hash::sha512_256i;
+use num_integer::Integer;
+use num_traits::{One, Signed, Zero};
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::common::int::{is_in_interval, ModInt};
+use crate::common::random::get_random_positive_int;
+use crate::common::secret::SecretBigInt;
+use crate::common::slice::{bigints_to_bytes, multi_bytes_to_bigints};
+use crate::crypto::transcript::ProofTranscript;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum FacProofError {
+    #[error("invalid parameters: {0}")]
+    InvalidParameters(String),
+    #[error("byte conversion error: expected {expected} parts, got {got}")]
+    ByteConversionError { expected: usize, got: usize },
+}
+
+const PROOF_FAC_BYTES_PARTS: usize = 11;
 
+/// Proof that `N0 = p*q` has no small factors (CGGMP21 Π_fac).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ProofFac {
+    #[serde(with = "crate::serde_support::bigint_bytes")]
     pub p: BigInt,
+    #[serde(with = "crate::serde_support::bigint_bytes")]
     pub q: BigInt,
+    #[serde(with = "crate::serde_support::bigint_bytes")]
     pub a: BigInt,
+    #[serde(with = "crate::serde_support::bigint_bytes")]
     pub b: BigInt,
+    #[serde(with = "crate::serde_support::bigint_bytes")]
     pub t: BigInt,
+    #[serde(with = "crate::serde_support::bigint_bytes")]
     pub sigma: BigInt,
+    #[serde(with = "crate::serde_support::bigint_bytes")]
     pub z1: BigInt,
+    #[serde(with = "crate::serde_support::bigint_bytes")]
     pub z2: BigInt,
+    #[serde(with = "crate::serde_support::bigint_bytes")]
     pub w1: BigInt,
+    #[serde(with = "crate::serde_support::bigint_bytes")]
     pub w2: BigInt,
+    #[serde(with = "crate::serde_support::bigint_bytes")]
     pub v: BigInt,
 }
 
 impl ProofFac {
-    pub fn new(session: &[u8], n0: &BigInt, ncap: &BigInt, s: &BigInt, t: &BigInt, n0p: &BigInt, n0q: &BigInt) -> Result<Self, String> {
-        let q = BigInt::one(); // Placeholder for actual curve order
-        let q3 = &q * &q * &q;
-        let qncap = &q * ncap;
+    /// Creates a `ProofFac` that `n0 = n0p * n0q` has no small factors.
+    ///
+    /// `curve_q` is the EC curve order (`l`/`eps` are folded into the `q3`
+    /// bound the same way the MtA proofs in this crate fold them into their
+    /// own blind bounds), and `(ncap, s, t)` are ring-Pedersen parameters
+    /// with `ncap` strictly larger than `n0`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new<R: CryptoRng + RngCore>(
+        session: &[u8],
+        curve_q: &BigInt,
+        n0: &BigInt,
+        ncap: &BigInt,
+        s: &BigInt,
+        t: &BigInt,
+        n0p: &BigInt,
+        n0q: &BigInt,
+        rng: &mut R,
+    ) -> Result<Self, FacProofError> {
+        if n0.sign() != num_bigint::Sign::Plus || ncap.sign() != num_bigint::Sign::Plus {
+            return Err(FacProofError::InvalidParameters("N0 and Ncap must be positive".to_string()));
+        }
+        if n0p * n0q != *n0 {
+            return Err(FacProofError::InvalidParameters("N0 != n0p * n0q".to_string()));
+        }
+
+        let mod_ncap = ModInt::new(ncap.clone());
+        let sqrt_n0 = n0.sqrt();
+
+        let q3 = curve_q * curve_q * curve_q;
+        let qncap = curve_q * ncap;
         let qn0ncap = &qncap * n0;
         let q3ncap = &q3 * ncap;
         let q3n0ncap = &q3ncap * n0;
-        let sqrtn0 = n0.sqrt();
-        let q3sqrtn0 = &q3 * &sqrtn0;
-
-        let alpha = BigInt::one(); // Placeholder for random value
-        let beta = BigInt::one(); // Placeholder for random value
-        let mu = BigInt::one(); // Placeholder for random value
-        let nu = BigInt::one(); // Placeholder for random value
-        let sigma = BigInt::one(); // Placeholder for random value
-        let r = BigInt::one(); // Placeholder for random value
-        let x = BigInt::one(); // Placeholder for random value
-        let y = BigInt::one(); // Placeholder for random value
-
-        let modncap = ncap.clone(); // Placeholder for modular arithmetic
-        let p = &modncap * s.modpow(n0p, ncap) * t.modpow(&mu, ncap);
-        let q = &modncap * s.modpow(n0q, ncap) * t.modpow(&nu, ncap);
-        let a = &modncap * s.modpow(&alpha, ncap) * t.modpow(&x, ncap);
-        let b = &modncap * s.modpow(&beta, ncap) * t.modpow(&y, ncap);
-        let t = &modncap * q.modpow(&alpha, ncap) * t.modpow(&r, ncap);
-
-        let e = sha512_256i(&[n0, ncap, s, &t, &p, &q, &a, &b, &t, &sigma]);
-
-        let z1 = e.clone() * n0p + alpha;
-        let z2 = e.clone() * n0q + beta;
-        let w1 = e.clone() * mu + x;
-        let w2 = e.clone() * nu.clone() + y;
-        let v = e * (nu * n0p - sigma.clone()) + r;
-
-        Ok(ProofFac { p, q, a, b, t, sigma, z1, z2, w1, w2, v })
+        let q3sqrtn0 = &q3 * &sqrt_n0;
+
+        // Ephemeral sigma-protocol randomness: scrubbed on drop so it
+        // doesn't linger in freed heap pages after this call returns.
+        let alpha = SecretBigInt::new(get_random_positive_int(rng, &q3sqrtn0));
+        let beta = SecretBigInt::new(get_random_positive_int(rng, &q3sqrtn0));
+        let mu = SecretBigInt::new(get_random_positive_int(rng, &qncap));
+        let nu = SecretBigInt::new(get_random_positive_int(rng, &qncap));
+        let sigma = SecretBigInt::new(get_random_positive_int(rng, &qn0ncap));
+        let r = SecretBigInt::new(get_random_positive_int(rng, &q3n0ncap));
+        let x = SecretBigInt::new(get_random_positive_int(rng, &q3ncap));
+        let y = SecretBigInt::new(get_random_positive_int(rng, &q3ncap));
+
+        let p = mod_ncap.mul(&mod_ncap.exp(s, n0p), &mod_ncap.exp(t, &mu));
+        let q = mod_ncap.mul(&mod_ncap.exp(s, n0q), &mod_ncap.exp(t, &nu));
+        let a = mod_ncap.mul(&mod_ncap.exp(s, &alpha), &mod_ncap.exp(t, &x));
+        let b = mod_ncap.mul(&mod_ncap.exp(s, &beta), &mod_ncap.exp(t, &y));
+        let t_proof = mod_ncap.mul(&mod_ncap.exp(&q, &alpha), &mod_ncap.exp(t, &r));
+
+        // Bind every public input and commitment under its own label, in a
+        // fixed order, instead of one flattened sha512_256i call: prover and
+        // verifier only derive the same challenge when they agree on every
+        // value *and* its position.
+        let mut transcript = ProofTranscript::new(b"ProofFac", session);
+        transcript.append_bigint(b"N0", n0);
+        transcript.append_bigint(b"Ncap", ncap);
+        transcript.append_bigint(b"s", s);
+        transcript.append_bigint(b"t", t);
+        transcript.append_bigint(b"P", &p);
+        transcript.append_bigint(b"Q", &q);
+        transcript.append_bigint(b"A", &a);
+        transcript.append_bigint(b"B", &b);
+        transcript.append_bigint(b"T", &t_proof);
+        transcript.append_bigint(b"sigma", &sigma);
+        let e = transcript.challenge_bigint(b"e", curve_q);
+
+        let z1 = &e * n0p + alpha.into_inner();
+        let z2 = &e * n0q + beta.into_inner();
+        let w1 = &e * mu.into_inner() + x.into_inner();
+        let nu = nu.into_inner();
+        let w2 = &e * &nu + y.into_inner();
+        let sigma = sigma.into_inner();
+        let v = &e * (&sigma - &nu * n0p) + r.into_inner();
+
+        Ok(ProofFac { p, q, a, b, t: t_proof, sigma, z1, z2, w1, w2, v })
+    }
+
+    /// Verifies a `ProofFac` against the public `(curve_q, n0, ncap, s, t)`.
+    pub fn verify(&self, session: &[u8], curve_q: &BigInt, n0: &BigInt, ncap: &BigInt, s: &BigInt, t: &BigInt) -> bool {
+        if n0.sign() != num_bigint::Sign::Plus || ncap.sign() != num_bigint::Sign::Plus {
+            return false;
+        }
+
+        let q3 = curve_q * curve_q * curve_q;
+        let sqrt_n0 = n0.sqrt();
+        let q3sqrtn0 = &q3 * &sqrt_n0;
+        if !is_in_interval(&self.z1.abs(), &q3sqrtn0) || !is_in_interval(&self.z2.abs(), &q3sqrtn0) {
+            return false;
+        }
+
+        let mod_ncap = ModInt::new(ncap.clone());
+
+        let mut transcript = ProofTranscript::new(b"ProofFac", session);
+        transcript.append_bigint(b"N0", n0);
+        transcript.append_bigint(b"Ncap", ncap);
+        transcript.append_bigint(b"s", s);
+        transcript.append_bigint(b"t", t);
+        transcript.append_bigint(b"P", &self.p);
+        transcript.append_bigint(b"Q", &self.q);
+        transcript.append_bigint(b"A", &self.a);
+        transcript.append_bigint(b"B", &self.b);
+        transcript.append_bigint(b"T", &self.t);
+        transcript.append_bigint(b"sigma", &self.sigma);
+        let e = transcript.challenge_bigint(b"e", curve_q);
+
+        // s^z1 * t^w1 == A * P^e (mod Ncap)
+        let lhs1 = mod_ncap.mul(&mod_ncap.exp(s, &self.z1), &mod_ncap.exp(t, &self.w1));
+        let rhs1 = mod_ncap.mul(&self.a, &mod_ncap.exp(&self.p, &e));
+        if lhs1 != rhs1 {
+            return false;
+        }
+
+        // s^z2 * t^w2 == B * Q^e (mod Ncap)
+        let lhs2 = mod_ncap.mul(&mod_ncap.exp(s, &self.z2), &mod_ncap.exp(t, &self.w2));
+        let rhs2 = mod_ncap.mul(&self.b, &mod_ncap.exp(&self.q, &e));
+        if lhs2 != rhs2 {
+            return false;
+        }
+
+        // Q^z1 * t^v == T * R^e (mod Ncap), where R = s^N0 * t^sigma.
+        let lhs3 = mod_ncap.mul(&mod_ncap.exp(&self.q, &self.z1), &mod_ncap.exp(t, &self.v));
+        let r_val = mod_ncap.mul(&mod_ncap.exp(s, n0), &mod_ncap.exp(t, &self.sigma));
+        let rhs3 = mod_ncap.mul(&self.t, &mod_ncap.exp(&r_val, &e));
+        if lhs3 != rhs3 {
+            return false;
+        }
+
+        true
+    }
+
+    /// Converts the proof to a vector of byte vectors, one per component.
+    pub fn to_bytes(&self) -> Vec<Vec<u8>> {
+        bigints_to_bytes(&[
+            self.p.clone(), self.q.clone(), self.a.clone(), self.b.clone(), self.t.clone(),
+            self.sigma.clone(), self.z1.clone(), self.z2.clone(), self.w1.clone(), self.w2.clone(), self.v.clone(),
+        ])
+    }
+
+    /// Reconstructs a `ProofFac` from `to_bytes`' output.
+    pub fn from_bytes(bzs: &[Vec<u8>]) -> Result<Self, FacProofError> {
+        if bzs.len() != PROOF_FAC_BYTES_PARTS {
+            return Err(FacProofError::ByteConversionError { expected: PROOF_FAC_BYTES_PARTS, got: bzs.len() });
+        }
+        let ints = multi_bytes_to_bigints(bzs);
+        Ok(ProofFac {
+            p: ints[0].clone(), q: ints[1].clone(), a: ints[2].clone(), b: ints[3].clone(), t: ints[4].clone(),
+            sigma: ints[5].clone(), z1: ints[6].clone(), z2: ints[7].clone(), w1: ints[8].clone(),
+            w2: ints[9].clone(), v: ints[10].clone(),
+        })
     }
 }
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use num_bigint::ToBigInt;
+    use rand::thread_rng;
+
+    fn setup() -> (BigInt, BigInt, BigInt, BigInt, BigInt, BigInt, BigInt) {
+        // Small, non-cryptographic-sized values so the test runs quickly;
+        // N0 deliberately has no small factors relative to these toy primes.
+        let curve_q = 65537.to_bigint().unwrap();
+        let n0p = 1_000_003.to_bigint().unwrap();
+        let n0q = 1_000_033.to_bigint().unwrap();
+        let n0 = &n0p * &n0q;
+        let ncap = 999_999_999_989u64.to_bigint().unwrap();
+        let s = 7.to_bigint().unwrap();
+        let t = 11.to_bigint().unwrap();
+        (curve_q, n0, ncap, s, t, n0p, n0q)
+    }
+
+    #[test]
+    fn test_proof_fac_new_and_verify_round_trips() {
+        let mut rng = thread_rng();
+        let (curve_q, n0, ncap, s, t, n0p, n0q) = setup();
+        let session = b"session";
+
+        let proof = ProofFac::new(session, &curve_q, &n0, &ncap, &s, &t, &n0p, &n0q, &mut rng).unwrap();
+        assert!(proof.verify(session, &curve_q, &n0, &ncap, &s, &t));
+    }
 
     #[test]
-    fn test_proof_fac_new() {
+    fn test_proof_fac_verify_rejects_wrong_session() {
+        let mut rng = thread_rng();
+        let (curve_q, n0, ncap, s, t, n0p, n0q) = setup();
+        let proof = ProofFac::new(b"session", &curve_q, &n0, &ncap, &s, &t, &n0p, &n0q, &mut rng).unwrap();
+        assert!(!proof.verify(b"wrong-session", &curve_q, &n0, &ncap, &s, &t));
+    }
+
+    #[test]
+    fn test_proof_fac_verify_rejects_tampered_response() {
+        let mut rng = thread_rng();
+        let (curve_q, n0, ncap, s, t, n0p, n0q) = setup();
         let session = b"session";
-        let n0 = 1.to_bigint().unwrap();
-        let ncap = 2.to_bigint().unwrap();
-        let s = 3.to_bigint().unwrap();
-        let t = 4.to_bigint().unwrap();
-        let n0p = 5.to_bigint().unwrap();
-        let n0q = 6.to_bigint().unwrap();
-        let proof = ProofFac::new(session, &n0, &ncap, &s, &t, &n0p, &n0q);
-        assert!(proof.is_ok());
+        let mut proof = ProofFac::new(session, &curve_q, &n0, &ncap, &s, &t, &n0p, &n0q, &mut rng).unwrap();
+        proof.z1 += BigInt::one();
+        assert!(!proof.verify(session, &curve_q, &n0, &ncap, &s, &t));
+    }
+
+    #[test]
+    fn test_proof_fac_new_rejects_factor_mismatch() {
+        let mut rng = thread_rng();
+        let (curve_q, n0, ncap, s, t, n0p, n0q) = setup();
+        let wrong_q = n0q + BigInt::one();
+        assert!(matches!(
+            ProofFac::new(b"session", &curve_q, &n0, &ncap, &s, &t, &n0p, &wrong_q, &mut rng),
+            Err(FacProofError::InvalidParameters(_))
+        ));
+    }
+
+    #[test]
+    fn test_proof_fac_bytes_round_trip() {
+        let mut rng = thread_rng();
+        let (curve_q, n0, ncap, s, t, n0p, n0q) = setup();
+        let session = b"session";
+        let proof = ProofFac::new(session, &curve_q, &n0, &ncap, &s, &t, &n0p, &n0q, &mut rng).unwrap();
+
+        let bzs = proof.to_bytes();
+        assert_eq!(bzs.len(), PROOF_FAC_BYTES_PARTS);
+        let decoded = ProofFac::from_bytes(&bzs).unwrap();
+        assert_eq!(proof, decoded);
     }
 }
@@ -1,14 +1,23 @@
 pub mod ecpoint;
+pub mod msm;
 pub mod utils;
 pub mod commitments;
+pub mod commitment_builder;
 pub mod paillier;
 pub mod vss;
 pub mod schnorr;
 pub mod mta;
 pub mod ckd;
 pub mod dlnproof;
+pub mod dln;
 pub mod facproof;
 pub mod modproof;
+pub mod range_proof;
+pub mod schnorr_pop;
+pub mod transcript;
+pub mod p2p_seal;
+pub mod vrf;
+pub mod bls;
 
 // Add other modules from the 'crypto' package here as they are converted
 // pub mod modproof;
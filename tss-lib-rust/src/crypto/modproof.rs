@@ -1,6 +1,16 @@
-use num_bigint::BigInt;
+use num_bigint::{BigInt, Sign};
+use num_integer::Integer;
+use num_traits::{One, Zero};
 use crate::common::hash::sha512_256i;
+use crate::common::random::{is_probable_prime, jacobi_symbol, get_random_positive_int, passes_small_prime_sieve};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
+// Statistical security parameter: number of Fiat-Shamir challenges, matching
+// the Go implementation.
+const ITERATIONS: usize = 80;
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct ProofMod {
     pub w: BigInt,
     pub x: Vec<BigInt>,
@@ -10,32 +20,253 @@ pub struct ProofMod {
 }
 
 impl ProofMod {
-    pub fn new(session: &[u8], n: &BigInt, p: &BigInt, q: &BigInt) -> Result<Self, String> {
-        let phi = (p - 1) * (q - 1);
-        let w = BigInt::one(); // Placeholder for random value
+    /// Produces a Paillier-Blum modulus proof for `n = p * q`: a verifier who
+    /// only knows `n` can check it is an RSA modulus built from two primes
+    /// `≡ 3 mod 4` with `gcd(n, phi(n)) = 1`, without learning `p` or `q`.
+    pub fn new<R: rand::RngCore>(session: &[u8], n: &BigInt, p: &BigInt, q: &BigInt, rng: &mut R) -> Result<Self, String> {
+        let four = BigInt::from(4u32);
+        if p.mod_floor(&four) != BigInt::from(3u32) || q.mod_floor(&four) != BigInt::from(3u32) {
+            return Err("p and q must each be ≡ 3 mod 4".to_string());
+        }
+        let phi = (p - BigInt::one()) * (q - BigInt::one());
+        let n_inv_phi = n.modinv(&phi).ok_or("N has no inverse mod phi(N): gcd(N, phi(N)) != 1")?;
+
+        // Find a quadratic non-residue w with Jacobi symbol -1.
+        let w = loop {
+            let candidate = get_random_positive_int(rng, n);
+            if candidate.is_zero() {
+                continue;
+            }
+            if jacobi_symbol(&candidate, n) == -1 {
+                break candidate;
+            }
+        };
 
-        let y: Vec<BigInt> = vec![BigInt::one(); 80]; // Placeholder for random values
+        let ys = derive_challenges(session, n, ITERATIONS);
 
-        let x: Vec<BigInt> = vec![BigInt::one(); 80]; // Placeholder for computed values
-        let a = BigInt::one(); // Placeholder for computed value
-        let b = BigInt::one(); // Placeholder for computed value
-        let z: Vec<BigInt> = vec![BigInt::one(); 80]; // Placeholder for computed values
+        let mut x = Vec::with_capacity(ITERATIONS);
+        let mut z = Vec::with_capacity(ITERATIONS);
+        let mut a_bits = Vec::with_capacity(ITERATIONS);
+        let mut b_bits = Vec::with_capacity(ITERATIONS);
 
-        Ok(ProofMod { w, x, a, b, z })
+        for y in &ys {
+            let (a_i, b_i, root) = fourth_root(y, &w, p, q, n)
+                .ok_or("Failed to find a 4th root; p and q are not a valid Blum modulus")?;
+            a_bits.push(a_i);
+            b_bits.push(b_i);
+            x.push(root);
+            z.push(y.modpow(&n_inv_phi, n));
+        }
+
+        Ok(ProofMod {
+            w,
+            x,
+            a: bits_to_bigint(&a_bits),
+            b: bits_to_bigint(&b_bits),
+            z,
+        })
     }
+
+    /// A placeholder proof for when `Parameters::no_proof_mod()` opts out of
+    /// generating (and therefore verifying) this proof.
+    pub fn empty_proof() -> Self {
+        ProofMod {
+            w: BigInt::zero(),
+            x: Vec::new(),
+            a: BigInt::zero(),
+            b: BigInt::zero(),
+            z: Vec::new(),
+        }
+    }
+
+    /// Verifies this proof against `n` alone; the verifier never sees `p`/`q`.
+    pub fn verify(&self, session: &[u8], n: &BigInt) -> bool {
+        if n.sign() != Sign::Plus || n.is_even() || is_probable_prime(n, 40) {
+            return false;
+        }
+        // N must be a product of (at least) two large primes: if it were
+        // divisible by any prime below 2000, it couldn't be the safe-prime
+        // Paillier modulus this proof claims, and rejecting it here avoids
+        // 80 rounds of modpow over a modulus we already know is wrong.
+        if !passes_small_prime_sieve(n) {
+            return false;
+        }
+        if self.x.len() != ITERATIONS || self.z.len() != ITERATIONS {
+            return false;
+        }
+        if jacobi_symbol(&self.w, n) != -1 {
+            return false;
+        }
+
+        let ys = derive_challenges(session, n, ITERATIONS);
+        (0..ITERATIONS).all(|i| self.check_iteration(i, n, &ys[i]))
+    }
+
+    /// Like [`verify`](Self::verify), but spreads the `ITERATIONS`
+    /// per-iteration checks across `pool` instead of running them on the
+    /// calling thread. Intended for callers that already sized a pool for a
+    /// batch of proofs (e.g. verifying every party's keygen round 2 message)
+    /// and have spare workers for a single proof's 80 independent iterations.
+    #[cfg(feature = "parallel")]
+    pub fn verify_on_pool(&self, session: &[u8], n: &BigInt, pool: &rayon::ThreadPool) -> bool {
+        if n.sign() != Sign::Plus || n.is_even() || is_probable_prime(n, 40) {
+            return false;
+        }
+        if !passes_small_prime_sieve(n) {
+            return false;
+        }
+        if self.x.len() != ITERATIONS || self.z.len() != ITERATIONS {
+            return false;
+        }
+        if jacobi_symbol(&self.w, n) != -1 {
+            return false;
+        }
+
+        let ys = derive_challenges(session, n, ITERATIONS);
+        pool.install(|| (0..ITERATIONS).into_par_iter().all(|i| self.check_iteration(i, n, &ys[i])))
+    }
+
+    /// Checks iteration `i`'s pair of equations: `z_i^n == y_i (mod n)`
+    /// (proves `gcd(n, phi(n)) == 1`) and `x_i^4 == (-1)^a_i * w^b_i * y_i
+    /// (mod n)` (proves `y_i` has a 4th root, i.e. `n` is a Blum modulus).
+    fn check_iteration(&self, i: usize, n: &BigInt, y: &BigInt) -> bool {
+        if self.z[i].modpow(n, n) != y.mod_floor(n) {
+            return false;
+        }
+
+        let mut rhs = y.clone();
+        if bit(&self.a, i) {
+            rhs = (n - &rhs).mod_floor(n);
+        }
+        if bit(&self.b, i) {
+            rhs = (&rhs * &self.w).mod_floor(n);
+        }
+        self.x[i].modpow(&BigInt::from(4u32), n) == rhs
+    }
+}
+
+/// Derives the `count` Fiat-Shamir challenges `y_i = H(session, N, i) mod N`.
+fn derive_challenges(session: &[u8], n: &BigInt, count: usize) -> Vec<BigInt> {
+    let session_int = BigInt::from_bytes_be(Sign::Plus, session);
+    (0..count)
+        .map(|i| sha512_256i(&[&session_int, n, &BigInt::from(i as u64)]).mod_floor(n))
+        .collect()
+}
+
+/// Finds bits `(a, b)` and a witness `x` such that `x^4 == (-1)^a * w^b * y (mod n)`.
+/// Exactly one of the four sign/w combinations is a quadratic residue when `n`
+/// is a Blum integer (`p, q ≡ 3 mod 4`), so this always succeeds for a valid modulus.
+fn fourth_root(y: &BigInt, w: &BigInt, p: &BigInt, q: &BigInt, n: &BigInt) -> Option<(bool, bool, BigInt)> {
+    for a_i in [false, true] {
+        for b_i in [false, true] {
+            let mut candidate = y.mod_floor(n);
+            if a_i {
+                candidate = (n - &candidate).mod_floor(n);
+            }
+            if b_i {
+                candidate = (&candidate * w).mod_floor(n);
+            }
+            if let Some(sqrt) = sqrt_mod_pq(&candidate, p, q, n) {
+                if let Some(root) = sqrt_mod_pq(&sqrt, p, q, n) {
+                    return Some((a_i, b_i, root));
+                }
+            }
+        }
+    }
+    None
 }
+
+/// Square root of `v` mod `n = p * q`, valid when `v` is a quadratic residue
+/// and `p, q ≡ 3 mod 4` (so `v^((p+1)/4) mod p` is directly a square root mod
+/// `p`, likewise mod `q`), combined via CRT. Returns `None` if `v` was not
+/// actually a quadratic residue.
+fn sqrt_mod_pq(v: &BigInt, p: &BigInt, q: &BigInt, n: &BigInt) -> Option<BigInt> {
+    let v = v.mod_floor(n);
+    let exp_p = (p + BigInt::one()) / 4;
+    let exp_q = (q + BigInt::one()) / 4;
+    let sqrt_p = v.modpow(&exp_p, p);
+    let sqrt_q = v.modpow(&exp_q, q);
+
+    let p_inv_q = p.modinv(q)?;
+    let h = ((&sqrt_q - &sqrt_p) * p_inv_q).mod_floor(q);
+    let root = (&sqrt_p + p * h).mod_floor(n);
+
+    if root.modpow(&BigInt::from(2u32), n) == v {
+        Some(root)
+    } else {
+        None
+    }
+}
+
+fn bits_to_bigint(bits: &[bool]) -> BigInt {
+    let mut acc = BigInt::zero();
+    for (i, set) in bits.iter().enumerate() {
+        if *set {
+            acc.set_bit(i as u64, true);
+        }
+    }
+    acc
+}
+
+fn bit(n: &BigInt, i: usize) -> bool {
+    n.test_bit(i as u64)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use num_bigint::ToBigInt;
+    use crate::crypto::paillier::generate_safe_keypair;
+
+    #[test]
+    fn test_prove_and_verify_modulus() {
+        let mut rng = rand::thread_rng();
+        let (sk, pk) = generate_safe_keypair(&mut rng, 64);
+        let session = b"session";
+
+        let proof = ProofMod::new(session, &pk.n, sk.p(), sk.q(), &mut rng).unwrap();
+        assert!(proof.verify(session, &pk.n));
+    }
+
+    #[test]
+    fn test_verify_rejects_wrong_session() {
+        let mut rng = rand::thread_rng();
+        let (sk, pk) = generate_safe_keypair(&mut rng, 64);
+
+        let proof = ProofMod::new(b"session-a", &pk.n, sk.p(), sk.q(), &mut rng).unwrap();
+        assert!(!proof.verify(b"session-b", &pk.n));
+    }
+
+    #[test]
+    fn test_verify_rejects_prime_n() {
+        let session = b"session";
+        // N itself prime is not a valid RSA modulus.
+        let n = BigInt::from(103u32);
+        let proof = ProofMod::empty_proof();
+        assert!(!proof.verify(session, &n));
+    }
 
     #[test]
-    fn test_proof_mod_new() {
+    fn test_verify_rejects_n_with_small_factor() {
         let session = b"session";
-        let n = 1.to_bigint().unwrap();
-        let p = 2.to_bigint().unwrap();
-        let q = 3.to_bigint().unwrap();
-        let proof = ProofMod::new(session, &n, &p, &q);
-        assert!(proof.is_ok());
+        // Composite, but divisible by a small prime: not a valid two-large-prime modulus.
+        let n = BigInt::from(3u32) * BigInt::from(1000003u32);
+        let proof = ProofMod::empty_proof();
+        assert!(!proof.verify(session, &n));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_verify_on_pool_matches_verify() {
+        let mut rng = rand::thread_rng();
+        let (sk, pk) = generate_safe_keypair(&mut rng, 64);
+        let session = b"session";
+        let proof = ProofMod::new(session, &pk.n, sk.p(), sk.q(), &mut rng).unwrap();
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+
+        assert!(proof.verify_on_pool(session, &pk.n, &pool));
+
+        let mut tampered = proof.clone();
+        tampered.x[0] += BigInt::one();
+        assert!(!tampered.verify_on_pool(session, &pk.n, &pool));
     }
 }
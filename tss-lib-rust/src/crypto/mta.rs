@@ -1,5 +1,21 @@
-use num_bigint::BigInt;
-use crate::common::hash::sha512_256i;
+// `ProofBob` (GG18/GG20 Fig. 11) and `ProofBobWC` (Fig. 10, the "with check"
+// extension binding an elliptic-curve point `X = g^x`) are Bob's side of the
+// MtA range proof: the counterpart to `RangeProofAlice` that lets Bob prove
+// his own response `c2 = c1^x * Enc(y, r) mod N^2` was built honestly from a
+// multiplier `x` (and, for the WC variant, that `x` matches a public key `X`
+// he's already committed to elsewhere), without revealing `x`, `y`, or `r`.
+// `ProofBob::new` used to return all-`BigInt::one()` placeholders with no
+// `verify` at all, making every MtA this crate runs unsound.
+
+use num_bigint::{BigInt, RandBigInt};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand::{CryptoRng, RngCore};
+use crate::common::hash::{rejection_sample, sha512_256i};
+use crate::common::int::ModInt;
+use crate::common::random::get_random_positive_int;
+use crate::crypto::ecpoint::ECPoint;
+use crate::crypto::paillier::{PrivateKey, PublicKey};
 
 pub struct ProofBob {
     pub z: BigInt,
@@ -14,40 +30,550 @@ pub struct ProofBob {
     pub t2: BigInt,
 }
 
+/// Ephemeral blinds shared by the "with check" and "without check" proofs,
+/// plus the commitments `new` derives from them. Factored out so
+/// `ProofBob::new` and `ProofBobWC::new` (which is everything `ProofBob::new`
+/// does, plus `u = g^alpha`) can't drift apart.
+struct BobCommitments {
+    alpha: BigInt,
+    rho: BigInt,
+    rho_prm: BigInt,
+    sigma: BigInt,
+    tau: BigInt,
+    gamma: BigInt,
+    beta: BigInt,
+    z: BigInt,
+    zprm: BigInt,
+    t: BigInt,
+    v: BigInt,
+    w: BigInt,
+}
+
+/// Paillier encryption `Enc_pk(m, r) = (N+1)^m * r^N mod N^2`, with `r`
+/// supplied by the caller rather than drawn fresh -- `ProofBob` needs this to
+/// re-derive `Enc(gamma, beta)` and `Enc(t1, s)` under specific blinds, which
+/// `PublicKey::encrypt`'s own random-`r` sampling doesn't allow.
+fn encrypt_with(pk: &PublicKey, m: &BigInt, r: &BigInt, n_square: &BigInt) -> BigInt {
+    let mod_n_square = ModInt::new(n_square.clone());
+    let g = &pk.n + BigInt::one();
+    mod_n_square.mul(&mod_n_square.exp(&g, m), &mod_n_square.exp(r, &pk.n))
+}
+
+/// Samples a value in `[1, n)` coprime to `n`, as Paillier encryption
+/// randomness must be.
+fn random_coprime_to<R: RngCore + CryptoRng>(rng: &mut R, n: &BigInt) -> BigInt {
+    loop {
+        let candidate = get_random_positive_int(rng, n);
+        if candidate.is_zero() {
+            continue;
+        }
+        if candidate.gcd(n) == BigInt::one() {
+            return candidate;
+        }
+    }
+}
+
 impl ProofBob {
-    pub fn new(session: &[u8], pk: &BigInt, ntilde: &BigInt, h1: &BigInt, h2: &BigInt, c1: &BigInt, c2: &BigInt, x: &BigInt, y: &BigInt, r: &BigInt) -> Result<Self, String> {
-        let z = BigInt::one(); // Placeholder for computed value
-        let zprm = BigInt::one(); // Placeholder for computed value
-        let t = BigInt::one(); // Placeholder for computed value
-        let v = BigInt::one(); // Placeholder for computed value
-        let w = BigInt::one(); // Placeholder for computed value
-        let s = BigInt::one(); // Placeholder for computed value
-        let s1 = BigInt::one(); // Placeholder for computed value
-        let s2 = BigInt::one(); // Placeholder for computed value
-        let t1 = BigInt::one(); // Placeholder for computed value
-        let t2 = BigInt::one(); // Placeholder for computed value
+    /// Steps 1-10 of Figs. 10/11: samples the seven blinds and derives the
+    /// five commitments `z`/`zprm`/`t`/`v`/`w`, shared by `ProofBob::new` and
+    /// `ProofBobWC::new`.
+    fn commit<R: RngCore + CryptoRng>(pk: &PublicKey, ntilde: &BigInt, h1: &BigInt, h2: &BigInt, c1: &BigInt, x: &BigInt, y: &BigInt, q: &BigInt, rng: &mut R) -> BobCommitments {
+        let n_square = &pk.n * &pk.n;
+        let mod_ntilde = ModInt::new(ntilde.clone());
+        let mod_n_square = ModInt::new(n_square.clone());
+
+        let q3 = q * q * q;
+        let q_ntilde = q * ntilde;
+        let q3_ntilde = &q3 * ntilde;
+        let q7 = &q3 * &q3 * q;
+
+        let alpha = get_random_positive_int(rng, &q3);
+        let rho = get_random_positive_int(rng, &q_ntilde);
+        let rho_prm = get_random_positive_int(rng, &q3_ntilde);
+        let sigma = get_random_positive_int(rng, &q_ntilde);
+        let tau = get_random_positive_int(rng, &q3_ntilde);
+        let gamma = get_random_positive_int(rng, &q7);
+        let beta = random_coprime_to(rng, &pk.n);
+
+        let z = mod_ntilde.mul(&mod_ntilde.exp(h1, x), &mod_ntilde.exp(h2, &rho));
+        let zprm = mod_ntilde.mul(&mod_ntilde.exp(h1, &alpha), &mod_ntilde.exp(h2, &rho_prm));
+        let t = mod_ntilde.mul(&mod_ntilde.exp(h1, y), &mod_ntilde.exp(h2, &sigma));
+        let w = mod_ntilde.mul(&mod_ntilde.exp(h1, &gamma), &mod_ntilde.exp(h2, &tau));
+        let c1_alpha = mod_n_square.exp(c1, &alpha);
+        let enc_gamma = encrypt_with(pk, &gamma, &beta, &n_square);
+        let v = mod_n_square.mul(&c1_alpha, &enc_gamma);
+
+        BobCommitments { alpha, rho, rho_prm, sigma, tau, gamma, beta, z, zprm, t, v, w }
+    }
+
+    /// Hashes the public inputs and commitments into the Fiat-Shamir
+    /// challenge `e`, optionally folding in `X = g^x` and `u = g^alpha` for
+    /// the "with check" variant.
+    #[allow(clippy::too_many_arguments)]
+    fn challenge(session: &[u8], pk: &PublicKey, ntilde: &BigInt, h1: &BigInt, h2: &BigInt, c1: &BigInt, c2: &BigInt, com: &BobCommitments, x_pub_and_u: Option<(&ECPoint, &ECPoint)>, q: &BigInt) -> BigInt {
+        let session_int = BigInt::from_bytes_be(num_bigint::Sign::Plus, session);
+        let mut inputs = vec![&session_int, &pk.n, ntilde, h1, h2];
+        if let Some((x_pub, u)) = x_pub_and_u {
+            inputs.push(&x_pub.x);
+            inputs.push(&x_pub.y);
+            inputs.push(&u.x);
+            inputs.push(&u.y);
+        }
+        inputs.push(c1);
+        inputs.push(c2);
+        inputs.push(&com.z);
+        inputs.push(&com.zprm);
+        inputs.push(&com.t);
+        inputs.push(&com.v);
+        inputs.push(&com.w);
+        rejection_sample(q, &sha512_256i(&inputs))
+    }
+
+    /// Generates Bob's MtA range proof "without check" (Fig. 11): proves
+    /// that `c2 = c1^x * Enc_pk(y, r) mod N^2` (the "Bob" ciphertext sent
+    /// back to Alice in an MtA) really was built from a multiplier `x`
+    /// bounded by the curve order `q`, without revealing `x`, `y`, or `r`.
+    /// `pk` is Alice's Paillier key (the one `c1`/`c2` are encrypted
+    /// under, as in `mta_bob_side`); `ntilde`/`h1`/`h2` are the verifier's Paillier-Blum
+    /// ring-Pedersen parameters.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new<R: RngCore + CryptoRng>(
+        session: &[u8],
+        pk: &PublicKey,
+        ntilde: &BigInt,
+        h1: &BigInt,
+        h2: &BigInt,
+        c1: &BigInt,
+        c2: &BigInt,
+        x: &BigInt,
+        y: &BigInt,
+        r: &BigInt,
+        q: &BigInt,
+        rng: &mut R,
+    ) -> Result<Self, String> {
+        let com = Self::commit(pk, ntilde, h1, h2, c1, x, y, q, rng);
+        let e = Self::challenge(session, pk, ntilde, h1, h2, c1, c2, &com, None, q);
+
+        let mod_n = ModInt::new(pk.n.clone());
+        let s = mod_n.mul(&mod_n.exp(r, &e), &com.beta);
+        let s1 = &e * x + &com.alpha;
+        let s2 = &e * &com.rho + &com.rho_prm;
+        let t1 = &e * y + &com.gamma;
+        let t2 = &e * &com.sigma + &com.tau;
+
+        Ok(ProofBob { z: com.z, zprm: com.zprm, t: com.t, v: com.v, w: com.w, s, s1, s2, t1, t2 })
+    }
+
+    /// Verifies Bob's MtA range proof "without check" (Fig. 11) against the
+    /// same public inputs `new` was called with.
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify(&self, session: &[u8], pk: &PublicKey, ntilde: &BigInt, h1: &BigInt, h2: &BigInt, c1: &BigInt, c2: &BigInt, q: &BigInt) -> bool {
+        self.verify_core(session, pk, ntilde, h1, h2, c1, c2, q, None)
+    }
+
+    fn verify_core(&self, session: &[u8], pk: &PublicKey, ntilde: &BigInt, h1: &BigInt, h2: &BigInt, c1: &BigInt, c2: &BigInt, q: &BigInt, x_pub_and_u: Option<(&ECPoint, &ECPoint)>) -> bool {
+        let q3 = q * q * q;
+        if self.s1 < BigInt::zero() || self.s1 > q3 {
+            return false;
+        }
+
+        let n_square = &pk.n * &pk.n;
+        let mod_ntilde = ModInt::new(ntilde.clone());
+        let mod_n_square = ModInt::new(n_square.clone());
+
+        let session_int = BigInt::from_bytes_be(num_bigint::Sign::Plus, session);
+        let mut inputs = vec![&session_int, &pk.n, ntilde, h1, h2];
+        if let Some((x_pub, u)) = x_pub_and_u {
+            inputs.push(&x_pub.x);
+            inputs.push(&x_pub.y);
+            inputs.push(&u.x);
+            inputs.push(&u.y);
+        }
+        inputs.push(c1);
+        inputs.push(c2);
+        inputs.push(&self.z);
+        inputs.push(&self.zprm);
+        inputs.push(&self.t);
+        inputs.push(&self.v);
+        inputs.push(&self.w);
+        let e = rejection_sample(q, &sha512_256i(&inputs));
 
-        Ok(ProofBob { z, zprm, t, v, w, s, s1, s2, t1, t2 })
+        // h1^s1 * h2^s2 == z^e * zprm (mod ntilde)
+        let lhs1 = mod_ntilde.mul(&mod_ntilde.exp(h1, &self.s1), &mod_ntilde.exp(h2, &self.s2));
+        let rhs1 = mod_ntilde.mul(&mod_ntilde.exp(&self.z, &e), &self.zprm);
+        if lhs1 != rhs1 {
+            return false;
+        }
+
+        // h1^t1 * h2^t2 == t^e * w (mod ntilde)
+        let lhs2 = mod_ntilde.mul(&mod_ntilde.exp(h1, &self.t1), &mod_ntilde.exp(h2, &self.t2));
+        let rhs2 = mod_ntilde.mul(&mod_ntilde.exp(&self.t, &e), &self.w);
+        if lhs2 != rhs2 {
+            return false;
+        }
+
+        // c1^s1 * Enc(t1, s) == v * c2^e (mod N^2)
+        let c1_s1 = mod_n_square.exp(c1, &self.s1);
+        let enc_t1 = encrypt_with(pk, &self.t1, &self.s, &n_square);
+        let lhs3 = mod_n_square.mul(&c1_s1, &enc_t1);
+        let rhs3 = mod_n_square.mul(&self.v, &mod_n_square.exp(c2, &e));
+        if lhs3 != rhs3 {
+            return false;
+        }
+
+        if let Some((x_pub, u)) = x_pub_and_u {
+            let g_s1 = match ECPoint::scalar_base_mult(x_pub.curve.clone(), &self.s1) {
+                Ok(p) => p,
+                Err(_) => return false,
+            };
+            let x_e = match x_pub.scalar_mult(&e) {
+                Ok(p) => p,
+                Err(_) => return false,
+            };
+            let u_plus_x_e = match u.add(&x_e) {
+                Ok(p) => p,
+                Err(_) => return false,
+            };
+            if g_s1 != u_plus_x_e {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Batch-verifies many `ProofBob`s that all share the same Paillier key,
+    /// Paillier-Blum ring-Pedersen parameters, and curve order `q` (the usual
+    /// case: one Bob verifying proofs from many MtAs run under a single
+    /// keygen/signing session). Folds the three expensive modexp equalities
+    /// in `verify_core` into one randomized linear combination per equation
+    /// -- sound except with probability `1/q` per bad proof -- and falls
+    /// back to per-proof `verify` to name the culprits if the combined check
+    /// fails.
+    pub fn verify_batch(
+        session: &[u8],
+        pk: &PublicKey,
+        ntilde: &BigInt,
+        h1: &BigInt,
+        h2: &BigInt,
+        q: &BigInt,
+        statements: &[(&BigInt, &BigInt, &ProofBob)],
+    ) -> Result<(), Vec<usize>> {
+        if statements.is_empty() {
+            return Ok(());
+        }
+
+        let q3 = q * q * q;
+        if statements.iter().any(|&(_, _, proof)| proof.s1 < BigInt::zero() || proof.s1 > q3) {
+            return Self::find_bad_proofs(session, pk, ntilde, h1, h2, q, statements);
+        }
+
+        let n_square = &pk.n * &pk.n;
+        let mod_ntilde = ModInt::new(ntilde.clone());
+        let mod_n_square = ModInt::new(n_square.clone());
+
+        let mut rng = rand::rngs::OsRng;
+        let deltas: Vec<BigInt> = (0..statements.len()).map(|_| get_random_positive_int(&mut rng, q)).collect();
+        let challenges: Vec<BigInt> = statements
+            .iter()
+            .map(|&(c1, c2, proof)| Self::challenge_from_proof(session, pk, ntilde, h1, h2, c1, c2, proof, q))
+            .collect();
+
+        // h1^(sum delta_i*s1_i) * h2^(sum delta_i*s2_i) == prod (z_i^e_i * zprm_i)^delta_i (mod ntilde)
+        let mut sum_s1 = BigInt::zero();
+        let mut sum_s2 = BigInt::zero();
+        let mut rhs1 = BigInt::one();
+        let mut sum_t1 = BigInt::zero();
+        let mut sum_t2 = BigInt::zero();
+        let mut rhs2 = BigInt::one();
+        let mut lhs3 = BigInt::one();
+        let mut rhs3 = BigInt::one();
+        for (&(c1, c2, proof), (delta, e)) in statements.iter().zip(deltas.iter().zip(challenges.iter())) {
+            sum_s1 += delta * &proof.s1;
+            sum_s2 += delta * &proof.s2;
+            let zi_e_zprm = mod_ntilde.mul(&mod_ntilde.exp(&proof.z, e), &proof.zprm);
+            rhs1 = mod_ntilde.mul(&rhs1, &mod_ntilde.exp(&zi_e_zprm, delta));
+
+            sum_t1 += delta * &proof.t1;
+            sum_t2 += delta * &proof.t2;
+            let ti_e_w = mod_ntilde.mul(&mod_ntilde.exp(&proof.t, e), &proof.w);
+            rhs2 = mod_ntilde.mul(&rhs2, &mod_ntilde.exp(&ti_e_w, delta));
+
+            let c1_s1 = mod_n_square.exp(c1, &proof.s1);
+            let enc_t1 = encrypt_with(pk, &proof.t1, &proof.s, &n_square);
+            let lhs3_i = mod_n_square.mul(&c1_s1, &enc_t1);
+            lhs3 = mod_n_square.mul(&lhs3, &mod_n_square.exp(&lhs3_i, delta));
+            let rhs3_i = mod_n_square.mul(&proof.v, &mod_n_square.exp(c2, e));
+            rhs3 = mod_n_square.mul(&rhs3, &mod_n_square.exp(&rhs3_i, delta));
+        }
+        let lhs1 = mod_ntilde.mul(&mod_ntilde.exp(h1, &sum_s1), &mod_ntilde.exp(h2, &sum_s2));
+        let lhs2 = mod_ntilde.mul(&mod_ntilde.exp(h1, &sum_t1), &mod_ntilde.exp(h2, &sum_t2));
+
+        if lhs1 == rhs1 && lhs2 == rhs2 && lhs3 == rhs3 {
+            Ok(())
+        } else {
+            Self::find_bad_proofs(session, pk, ntilde, h1, h2, q, statements)
+        }
+    }
+
+    fn find_bad_proofs(
+        session: &[u8],
+        pk: &PublicKey,
+        ntilde: &BigInt,
+        h1: &BigInt,
+        h2: &BigInt,
+        q: &BigInt,
+        statements: &[(&BigInt, &BigInt, &ProofBob)],
+    ) -> Result<(), Vec<usize>> {
+        let bad: Vec<usize> = statements
+            .iter()
+            .enumerate()
+            .filter(|&(_, &(c1, c2, proof))| !proof.verify(session, pk, ntilde, h1, h2, c1, c2, q))
+            .map(|(idx, _)| idx)
+            .collect();
+        Err(bad)
     }
+
+    /// Re-derives the Fiat-Shamir challenge `e` for an already-assembled
+    /// proof, as `verify_batch` needs to recompute it per statement without
+    /// re-running `verify_core`'s other checks.
+    #[allow(clippy::too_many_arguments)]
+    fn challenge_from_proof(session: &[u8], pk: &PublicKey, ntilde: &BigInt, h1: &BigInt, h2: &BigInt, c1: &BigInt, c2: &BigInt, proof: &ProofBob, q: &BigInt) -> BigInt {
+        let session_int = BigInt::from_bytes_be(num_bigint::Sign::Plus, session);
+        let inputs = vec![&session_int, &pk.n, ntilde, h1, h2, c1, c2, &proof.z, &proof.zprm, &proof.t, &proof.v, &proof.w];
+        rejection_sample(q, &sha512_256i(&inputs))
+    }
+}
+
+/// Proof structure for Bob's MtA proof "with check" (Fig. 10): same as
+/// `ProofBob`, plus `u = g^alpha` binding the proof to a public key
+/// `X = g^x` the respondent has committed to elsewhere (e.g. its own ECDSA
+/// key share), so the verifier learns the proof's `x` really is the
+/// discrete log of `X` and not merely some bounded value.
+pub struct ProofBobWC {
+    pub proof_bob: ProofBob,
+    pub u: ECPoint,
 }
+
+impl ProofBobWC {
+    /// Same as `ProofBob::new`, but additionally binds the proof to
+    /// `x_pub = g^x`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new<R: RngCore + CryptoRng>(
+        session: &[u8],
+        pk: &PublicKey,
+        ntilde: &BigInt,
+        h1: &BigInt,
+        h2: &BigInt,
+        c1: &BigInt,
+        c2: &BigInt,
+        x: &BigInt,
+        y: &BigInt,
+        r: &BigInt,
+        q: &BigInt,
+        x_pub: &ECPoint,
+        rng: &mut R,
+    ) -> Result<Self, String> {
+        let com = ProofBob::commit(pk, ntilde, h1, h2, c1, x, y, q, rng);
+        let u = ECPoint::scalar_base_mult(x_pub.curve.clone(), &com.alpha)?;
+        let e = ProofBob::challenge(session, pk, ntilde, h1, h2, c1, c2, &com, Some((x_pub, &u)), q);
+
+        let mod_n = ModInt::new(pk.n.clone());
+        let s = mod_n.mul(&mod_n.exp(r, &e), &com.beta);
+        let s1 = &e * x + &com.alpha;
+        let s2 = &e * &com.rho + &com.rho_prm;
+        let t1 = &e * y + &com.gamma;
+        let t2 = &e * &com.sigma + &com.tau;
+
+        let proof_bob = ProofBob { z: com.z, zprm: com.zprm, t: com.t, v: com.v, w: com.w, s, s1, s2, t1, t2 };
+        Ok(ProofBobWC { proof_bob, u })
+    }
+
+    /// Same as `ProofBob::verify`, but additionally checks `g^s1 == u * X^e`,
+    /// confirming this proof's `x` is the discrete log of `x_pub`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify(&self, session: &[u8], pk: &PublicKey, ntilde: &BigInt, h1: &BigInt, h2: &BigInt, c1: &BigInt, c2: &BigInt, q: &BigInt, x_pub: &ECPoint) -> bool {
+        self.proof_bob.verify_core(session, pk, ntilde, h1, h2, c1, c2, q, Some((x_pub, &self.u)))
+    }
+}
+
+/// Bob's side of the multiplicative-to-additive (MtA) conversion: given Alice's
+/// Paillier encryption of `a` and Bob's share `b`, picks a random `beta_prime`,
+/// returns the ciphertext `c = Enc_A(a*b + beta_prime)` to send back to Alice,
+/// and keeps `beta = -beta_prime mod q` as Bob's additive share of `a*b`.
+pub fn mta_bob_side<R: rand::RngCore>(
+    pk_a: &PublicKey,
+    enc_a: &BigInt,
+    b: &BigInt,
+    q: &BigInt,
+    rng: &mut R,
+) -> Result<(BigInt, BigInt), String> {
+    let beta_prime = rng.gen_bigint_range(&BigInt::from(0), q);
+    let c = pk_a.add_const(&pk_a.mul_const(enc_a, b)?, &beta_prime)?;
+    let beta = (-&beta_prime).mod_floor(q);
+    Ok((c, beta))
+}
+
+/// Alice's side of the MtA conversion: decrypts Bob's ciphertext to recover
+/// `alpha = a*b + beta_prime mod q`, her additive share of `a*b`.
+pub fn mta_alice_side(sk_a: &PrivateKey, c: &BigInt, q: &BigInt) -> Result<BigInt, String> {
+    let alpha = sk_a.decrypt(c)?;
+    Ok(alpha.mod_floor(q))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::crypto::ecpoint::ECCurve;
+    use crate::crypto::paillier::generate_keypair;
     use num_bigint::ToBigInt;
+    use rand::thread_rng;
+
+    const SESSION: &[u8] = b"session";
+
+    fn setup() -> (PublicKey, BigInt, BigInt, BigInt, BigInt) {
+        let (_sk, pk) = generate_keypair(512);
+        let mut rng = thread_rng();
+        let ntilde = get_random_positive_int(&mut rng, &pk.n);
+        let h1 = random_coprime_to(&mut rng, &ntilde);
+        let h2 = random_coprime_to(&mut rng, &ntilde);
+        let q = 1_000_003.to_bigint().unwrap(); // a small prime standing in for the curve order
+        (pk, ntilde, h1, h2, q)
+    }
+
+    /// Builds `c1 = Enc(a)` and `c2 = c1^x * Enc(y, r) mod N^2`, the pair of
+    /// ciphertexts `ProofBob` attests to.
+    fn setup_ciphertexts<R: RngCore + CryptoRng>(pk: &PublicKey, q: &BigInt, rng: &mut R) -> (BigInt, BigInt, BigInt, BigInt, BigInt) {
+        let n_square = &pk.n * &pk.n;
+        let mod_n_square = ModInt::new(n_square.clone());
+
+        let a = get_random_positive_int(rng, q);
+        let r_a = random_coprime_to(rng, &pk.n);
+        let c1 = encrypt_with(pk, &a, &r_a, &n_square);
+
+        let x = get_random_positive_int(rng, q);
+        let y = get_random_positive_int(rng, q);
+        let r = random_coprime_to(rng, &pk.n);
+        let c2 = mod_n_square.mul(&mod_n_square.exp(&c1, &x), &encrypt_with(pk, &y, &r, &n_square));
+
+        (c1, c2, x, y, r)
+    }
+
+    #[test]
+    fn test_proof_bob_round_trip() {
+        let (pk, ntilde, h1, h2, q) = setup();
+        let mut rng = thread_rng();
+        let (c1, c2, x, y, r) = setup_ciphertexts(&pk, &q, &mut rng);
+
+        let proof = ProofBob::new(SESSION, &pk, &ntilde, &h1, &h2, &c1, &c2, &x, &y, &r, &q, &mut rng).unwrap();
+        assert!(proof.verify(SESSION, &pk, &ntilde, &h1, &h2, &c1, &c2, &q));
+    }
+
+    #[test]
+    fn test_proof_bob_rejects_tampered_proof() {
+        let (pk, ntilde, h1, h2, q) = setup();
+        let mut rng = thread_rng();
+        let (c1, c2, x, y, r) = setup_ciphertexts(&pk, &q, &mut rng);
+
+        let mut proof = ProofBob::new(SESSION, &pk, &ntilde, &h1, &h2, &c1, &c2, &x, &y, &r, &q, &mut rng).unwrap();
+        proof.z += BigInt::one();
+        assert!(!proof.verify(SESSION, &pk, &ntilde, &h1, &h2, &c1, &c2, &q));
+    }
 
     #[test]
-    fn test_proof_bob_new() {
-        let session = b"session";
-        let pk = 1.to_bigint().unwrap();
-        let ntilde = 2.to_bigint().unwrap();
-        let h1 = 3.to_bigint().unwrap();
-        let h2 = 4.to_bigint().unwrap();
-        let c1 = 5.to_bigint().unwrap();
-        let c2 = 6.to_bigint().unwrap();
-        let x = 7.to_bigint().unwrap();
-        let y = 8.to_bigint().unwrap();
-        let r = 9.to_bigint().unwrap();
-        let proof = ProofBob::new(session, &pk, &ntilde, &h1, &h2, &c1, &c2, &x, &y, &r);
-        assert!(proof.is_ok());
+    fn test_proof_bob_rejects_wrong_ciphertext() {
+        let (pk, ntilde, h1, h2, q) = setup();
+        let mut rng = thread_rng();
+        let (c1, c2, x, y, r) = setup_ciphertexts(&pk, &q, &mut rng);
+
+        let proof = ProofBob::new(SESSION, &pk, &ntilde, &h1, &h2, &c1, &c2, &x, &y, &r, &q, &mut rng).unwrap();
+        let (_, c2_wrong, ..) = setup_ciphertexts(&pk, &q, &mut rng);
+        assert!(!proof.verify(SESSION, &pk, &ntilde, &h1, &h2, &c1, &c2_wrong, &q));
+    }
+
+    #[test]
+    fn test_proof_bob_verify_batch_accepts_all_valid() {
+        let (pk, ntilde, h1, h2, q) = setup();
+        let mut rng = thread_rng();
+
+        let mut ciphertexts = Vec::new();
+        let mut proofs = Vec::new();
+        for _ in 0..3 {
+            let (c1, c2, x, y, r) = setup_ciphertexts(&pk, &q, &mut rng);
+            let proof = ProofBob::new(SESSION, &pk, &ntilde, &h1, &h2, &c1, &c2, &x, &y, &r, &q, &mut rng).unwrap();
+            ciphertexts.push((c1, c2));
+            proofs.push(proof);
+        }
+        let statements: Vec<(&BigInt, &BigInt, &ProofBob)> = ciphertexts
+            .iter()
+            .zip(proofs.iter())
+            .map(|((c1, c2), proof)| (c1, c2, proof))
+            .collect();
+
+        assert!(ProofBob::verify_batch(SESSION, &pk, &ntilde, &h1, &h2, &q, &statements).is_ok());
+    }
+
+    #[test]
+    fn test_proof_bob_verify_batch_names_the_bad_proof() {
+        let (pk, ntilde, h1, h2, q) = setup();
+        let mut rng = thread_rng();
+
+        let mut ciphertexts = Vec::new();
+        let mut proofs = Vec::new();
+        for _ in 0..3 {
+            let (c1, c2, x, y, r) = setup_ciphertexts(&pk, &q, &mut rng);
+            let proof = ProofBob::new(SESSION, &pk, &ntilde, &h1, &h2, &c1, &c2, &x, &y, &r, &q, &mut rng).unwrap();
+            ciphertexts.push((c1, c2));
+            proofs.push(proof);
+        }
+        proofs[1].z += BigInt::one();
+        let statements: Vec<(&BigInt, &BigInt, &ProofBob)> = ciphertexts
+            .iter()
+            .zip(proofs.iter())
+            .map(|((c1, c2), proof)| (c1, c2, proof))
+            .collect();
+
+        let bad = ProofBob::verify_batch(SESSION, &pk, &ntilde, &h1, &h2, &q, &statements).unwrap_err();
+        assert_eq!(bad, vec![1]);
+    }
+
+    #[test]
+    fn test_proof_bob_wc_round_trip() {
+        let (pk, ntilde, h1, h2, q) = setup();
+        let mut rng = thread_rng();
+        let (c1, c2, x, y, r) = setup_ciphertexts(&pk, &q, &mut rng);
+        let x_pub = ECPoint::scalar_base_mult(ECCurve::Secp256k1, &x).unwrap();
+
+        let proof = ProofBobWC::new(SESSION, &pk, &ntilde, &h1, &h2, &c1, &c2, &x, &y, &r, &q, &x_pub, &mut rng).unwrap();
+        assert!(proof.verify(SESSION, &pk, &ntilde, &h1, &h2, &c1, &c2, &q, &x_pub));
+    }
+
+    #[test]
+    fn test_proof_bob_wc_rejects_wrong_x_pub() {
+        let (pk, ntilde, h1, h2, q) = setup();
+        let mut rng = thread_rng();
+        let (c1, c2, x, y, r) = setup_ciphertexts(&pk, &q, &mut rng);
+        let x_pub = ECPoint::scalar_base_mult(ECCurve::Secp256k1, &x).unwrap();
+
+        let proof = ProofBobWC::new(SESSION, &pk, &ntilde, &h1, &h2, &c1, &c2, &x, &y, &r, &q, &x_pub, &mut rng).unwrap();
+        let wrong_x_pub = ECPoint::scalar_base_mult(ECCurve::Secp256k1, &(&x + BigInt::one())).unwrap();
+        assert!(!proof.verify(SESSION, &pk, &ntilde, &h1, &h2, &c1, &c2, &q, &wrong_x_pub));
+    }
+
+    #[test]
+    fn test_mta_round_trip() {
+        let (sk_a, pk_a) = generate_keypair(128);
+        let mut rng = rand::thread_rng();
+        let q = 1_000_003.to_bigint().unwrap(); // a small prime standing in for the curve order
+        let a = 17.to_bigint().unwrap();
+        let b = 23.to_bigint().unwrap();
+
+        let enc_a = pk_a.encrypt(&mut rng, &a).unwrap();
+        let (c, beta) = mta_bob_side(&pk_a, &enc_a, &b, &q, &mut rng).unwrap();
+        let alpha = mta_alice_side(&sk_a, &c, &q).unwrap();
+
+        let sum = (alpha + beta).mod_floor(&q);
+        let expected = (&a * &b).mod_floor(&q);
+        assert_eq!(sum, expected);
     }
 }
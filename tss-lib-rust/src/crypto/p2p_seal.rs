@@ -0,0 +1,125 @@
+// Per-recipient authenticated encryption for P2P round messages.
+//
+// `BaseParty::send_p2p` used to hand the encoded content straight to the
+// transport in the clear, so any relay carrying messages between parties
+// -- not just the addressed recipient -- could read or silently tamper
+// with the VSS shares and other secrets a keygen P2P round carries.
+// `seal`/`open` derive a per-(session, recipient) ChaCha20-Poly1305 key via
+// HKDF-SHA512/256 from a session secret shared out of band by the parties
+// and the recipient's public key bytes, so only someone who already knows
+// the session secret can read the content, and any tampering on the wire
+// is caught as an AEAD authentication failure rather than silently
+// accepted.
+
+use chacha20poly1305::aead::Aead;
+use chacha20poly1305::{ChaCha20Poly1305, KeyInit, Nonce};
+use hkdf::Hkdf;
+use rand::RngCore;
+use sha2::Sha512_256;
+
+const NONCE_LEN: usize = 12;
+const HKDF_INFO: &[u8] = b"tss-lib-rust p2p seal v1";
+
+/// `seal`/`open` failed: the sealed payload was too short to have come from
+/// `seal`, or the AEAD authentication tag didn't match -- either the wrong
+/// key was derived (wrong session secret or recipient) or the ciphertext
+/// was tampered with in transit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SealError {
+    Truncated,
+    AuthenticationFailed,
+}
+
+impl std::fmt::Display for SealError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SealError::Truncated => write!(f, "sealed payload shorter than a nonce"),
+            SealError::AuthenticationFailed => write!(f, "AEAD authentication failed"),
+        }
+    }
+}
+
+impl std::error::Error for SealError {}
+
+fn derive_key(session_secret: &[u8], recipient_key: &[u8]) -> [u8; 32] {
+    let hk = Hkdf::<Sha512_256>::new(Some(recipient_key), session_secret);
+    let mut key = [0u8; 32];
+    hk.expand(HKDF_INFO, &mut key)
+        .expect("32 bytes is a valid ChaCha20-Poly1305 key length");
+    key
+}
+
+/// Seals `plaintext` to `recipient_key` under `session_secret`, returning
+/// `nonce || ciphertext` ready to go on the wire as the message payload.
+pub fn seal<R: RngCore>(
+    session_secret: &[u8],
+    recipient_key: &[u8],
+    plaintext: &[u8],
+    rng: &mut R,
+) -> Vec<u8> {
+    let key = derive_key(session_secret, recipient_key);
+    let cipher = ChaCha20Poly1305::new((&key).into());
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    rng.fill_bytes(&mut nonce_bytes);
+    let nonce = Nonce::from_slice(&nonce_bytes);
+    let ciphertext = cipher
+        .encrypt(nonce, plaintext)
+        .expect("plaintext within ChaCha20-Poly1305's length limit");
+    let mut out = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+    out.extend_from_slice(&nonce_bytes);
+    out.extend_from_slice(&ciphertext);
+    out
+}
+
+/// Inverse of `seal`: recovers the plaintext sealed to `recipient_key` under
+/// `session_secret`, or a `SealError` if `sealed` was truncated, sealed
+/// under a different session secret/recipient, or tampered with.
+pub fn open(session_secret: &[u8], recipient_key: &[u8], sealed: &[u8]) -> Result<Vec<u8>, SealError> {
+    if sealed.len() < NONCE_LEN {
+        return Err(SealError::Truncated);
+    }
+    let (nonce_bytes, ciphertext) = sealed.split_at(NONCE_LEN);
+    let key = derive_key(session_secret, recipient_key);
+    let cipher = ChaCha20Poly1305::new((&key).into());
+    let nonce = Nonce::from_slice(nonce_bytes);
+    cipher
+        .decrypt(nonce, ciphertext)
+        .map_err(|_| SealError::AuthenticationFailed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+
+    #[test]
+    fn test_seal_then_open_round_trips() {
+        let sealed = seal(b"session-secret", b"recipient-key", b"share payload", &mut thread_rng());
+        assert_eq!(open(b"session-secret", b"recipient-key", &sealed).unwrap(), b"share payload");
+    }
+
+    #[test]
+    fn test_open_rejects_wrong_session_secret() {
+        let sealed = seal(b"session-a", b"recipient-key", b"share payload", &mut thread_rng());
+        assert_eq!(open(b"session-b", b"recipient-key", &sealed), Err(SealError::AuthenticationFailed));
+    }
+
+    #[test]
+    fn test_open_rejects_wrong_recipient_key() {
+        let sealed = seal(b"session-secret", b"recipient-a", b"share payload", &mut thread_rng());
+        assert_eq!(open(b"session-secret", b"recipient-b", &sealed), Err(SealError::AuthenticationFailed));
+    }
+
+    #[test]
+    fn test_open_rejects_tampered_ciphertext() {
+        let mut sealed = seal(b"session-secret", b"recipient-key", b"share payload", &mut thread_rng());
+        let last = sealed.len() - 1;
+        sealed[last] ^= 0x01;
+        assert_eq!(open(b"session-secret", b"recipient-key", &sealed), Err(SealError::AuthenticationFailed));
+    }
+
+    #[test]
+    fn test_open_rejects_truncated_payload() {
+        assert_eq!(open(b"session-secret", b"recipient-key", &[0u8; 4]), Err(SealError::Truncated));
+    }
+}
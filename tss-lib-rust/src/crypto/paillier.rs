@@ -1,19 +1,33 @@
 use num_bigint::{BigInt, RandBigInt, ToBigInt};
+use num_integer::Integer;
 use num_traits::{One, Zero};
 use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
 use std::fmt;
 use num_primes::Generator;
 
+use crate::common::secret::SecretBigInt;
+
 pub struct PublicKey {
     pub n: BigInt,
 }
 
+/// `lambda_n`, `phi_n`, `p`, and `q` are wrapped in `SecretBigInt` (the same
+/// zeroize-on-drop discipline `ProofFac`/`RangeProofAlice` use for ephemeral
+/// sigma-protocol witnesses) so this key's factorization doesn't linger in
+/// freed heap pages once the key is dropped. `hp`/`hq`/`p_inv_mod_q` are the
+/// CRT decryption coefficients `decrypt` needs every call; precomputing them
+/// once here means `decrypt` never has to exponentiate by the full `lambda_n`
+/// mod `n^2`.
 pub struct PrivateKey {
     pub public_key: PublicKey,
-    pub lambda_n: BigInt,
-    pub phi_n: BigInt,
-    pub p: BigInt,
-    pub q: BigInt,
+    lambda_n: SecretBigInt,
+    phi_n: SecretBigInt,
+    p: SecretBigInt,
+    q: SecretBigInt,
+    hp: SecretBigInt,
+    hq: SecretBigInt,
+    p_inv_mod_q: SecretBigInt,
 }
 
 impl PublicKey {
@@ -35,16 +49,157 @@ impl PublicKey {
         let rn = r.modpow(n, &n2);
         Ok((gm * rn) % &n2)
     }
+
+    /// Homomorphically adds two ciphertexts: `Dec(c1 * c2 mod n^2) == Dec(c1) + Dec(c2) mod n`.
+    pub fn add(&self, c1: &BigInt, c2: &BigInt) -> Result<BigInt, String> {
+        let n2 = &self.n * &self.n;
+        if c1 < &BigInt::zero() || c1 >= &n2 || c2 < &BigInt::zero() || c2 >= &n2 {
+            return Err("Ciphertext is too large or < 0".to_string());
+        }
+        Ok((c1 * c2) % &n2)
+    }
+
+    /// Homomorphically multiplies a ciphertext by a plaintext constant:
+    /// `Dec(c.modpow(k, n^2)) == k * Dec(c) mod n`.
+    pub fn mul_const(&self, c: &BigInt, k: &BigInt) -> Result<BigInt, String> {
+        let n2 = &self.n * &self.n;
+        if c < &BigInt::zero() || c >= &n2 {
+            return Err("Ciphertext is too large or < 0".to_string());
+        }
+        Ok(c.modpow(k, &n2))
+    }
+
+    /// Homomorphically adds a plaintext constant to a ciphertext:
+    /// `Dec(c * (n+1).modpow(k, n^2) mod n^2) == Dec(c) + k mod n`.
+    pub fn add_const(&self, c: &BigInt, k: &BigInt) -> Result<BigInt, String> {
+        let n2 = &self.n * &self.n;
+        if c < &BigInt::zero() || c >= &n2 {
+            return Err("Ciphertext is too large or < 0".to_string());
+        }
+        let gk = (&self.n + BigInt::one()).modpow(k, &n2);
+        Ok((c * gk) % &n2)
+    }
+
+    /// Homomorphically subtracts `c2` from `c1`: `Dec(c1 * c2^-1 mod n^2) ==
+    /// Dec(c1) - Dec(c2) mod n`.
+    pub fn homo_sub(&self, c1: &BigInt, c2: &BigInt) -> Result<BigInt, String> {
+        let n2 = &self.n * &self.n;
+        if c1 < &BigInt::zero() || c1 >= &n2 || c2 < &BigInt::zero() || c2 >= &n2 {
+            return Err("Ciphertext is too large or < 0".to_string());
+        }
+        let c2_inv = c2
+            .modinv(&n2)
+            .ok_or_else(|| "c2 has no inverse mod n^2 (gcd(c2, n^2) != 1)".to_string())?;
+        Ok((c1 * c2_inv) % &n2)
+    }
+
+    /// Re-randomizes `c` into a different, uniformly random ciphertext that
+    /// decrypts to the same plaintext: `c' = c * r^n mod n^2` for a fresh
+    /// random `r`. Lives on `PublicKey` (unlike `decrypt`) since
+    /// re-randomizing only needs the public modulus, not the factorization.
+    pub fn rerandomize<R: RngCore + CryptoRng>(&self, rng: &mut R, c: &BigInt) -> Result<BigInt, String> {
+        let n2 = &self.n * &self.n;
+        if c < &BigInt::zero() || c >= &n2 {
+            return Err("Ciphertext is too large or < 0".to_string());
+        }
+        let mut r;
+        loop {
+            r = rng.gen_bigint_range(&BigInt::one(), &self.n);
+            if num_integer::gcd(r.clone(), self.n.clone()) == BigInt::one() {
+                break;
+            }
+        }
+        let rn = r.modpow(&self.n, &n2);
+        Ok((c * rn) % &n2)
+    }
 }
 
 impl PrivateKey {
+    /// `p`, as stored by key generation. Exposed read-only since factors are
+    /// needed outside this module (e.g. `crypto::modproof`'s Paillier-Blum
+    /// witnesses), while keeping the field itself wrapped in `SecretBigInt`
+    /// so ordinary field access can't accidentally outlive a zeroize.
+    pub fn p(&self) -> &BigInt {
+        &self.p
+    }
+
+    /// `q`, as stored by key generation. See [`p`](Self::p).
+    pub fn q(&self) -> &BigInt {
+        &self.q
+    }
+
+    /// `lambda(n) = lcm(p-1, q-1)`, the Carmichael function of `n`.
+    pub fn lambda_n(&self) -> &BigInt {
+        &self.lambda_n
+    }
+
+    /// `phi(n) = (p-1)(q-1)`, Euler's totient of `n`.
+    pub fn phi_n(&self) -> &BigInt {
+        &self.phi_n
+    }
+
+    /// Builds a `PrivateKey`/`PublicKey` pair from two primes, precomputing
+    /// the CRT decryption coefficients `decrypt` needs on every call.
+    fn from_primes(p: BigInt, q: BigInt) -> (PrivateKey, PublicKey) {
+        let n = &p * &q;
+        let lambda_n = num_integer::lcm(p.clone() - 1u32, q.clone() - 1u32);
+        let phi_n = (&p - 1u32) * (&q - 1u32);
+
+        let g = &n + BigInt::one();
+        let p2 = &p * &p;
+        let q2 = &q * &q;
+        let hp = crt_decryption_coefficient(&g, &p, &p2);
+        let hq = crt_decryption_coefficient(&g, &q, &q2);
+        let p_inv_mod_q = p.modinv(&q).expect("p and q are distinct primes and therefore coprime");
+
+        let pk = PublicKey { n: n.clone() };
+        let sk = PrivateKey {
+            public_key: PublicKey { n },
+            lambda_n: SecretBigInt::new(lambda_n),
+            phi_n: SecretBigInt::new(phi_n),
+            p: SecretBigInt::new(p),
+            q: SecretBigInt::new(q),
+            hp: SecretBigInt::new(hp),
+            hq: SecretBigInt::new(hq),
+            p_inv_mod_q: SecretBigInt::new(p_inv_mod_q),
+        };
+        (sk, pk)
+    }
+
+    /// Decrypts `c` via the CRT optimization (Paillier's original paper,
+    /// §7): reduces the expensive `c^lambda_n mod n^2` exponentiation to two
+    /// independent exponentiations over `p^2`/`q^2` (each about half the bit
+    /// length of `n^2`) and recombines with Garner's formula, instead of one
+    /// exponentiation over the full `n^2`.
     pub fn decrypt(&self, c: &BigInt) -> Result<BigInt, String> {
-        let n2 = &self.public_key.n * &self.public_key.n;
-        let lc = (c.modpow(&self.lambda_n, &n2) - 1) / &self.public_key.n;
-        let lg = ((self.public_key.n.clone() + 1u32).modpow(&self.lambda_n, &n2) - 1u32.clone()) / &self.public_key.n;
-        let inv_lg = lg.modinv(&self.public_key.n).ok_or("No modular inverse")?;
-        Ok((lc * inv_lg) % &self.public_key.n)
+        let p = &*self.p;
+        let q = &*self.q;
+        let p2 = p * p;
+        let q2 = q * q;
+
+        let mp = l_function(&c.modpow(&(p - 1u32), &p2), p) * &*self.hp % p;
+        let mq = l_function(&c.modpow(&(q - 1u32), &q2), q) * &*self.hq % q;
+
+        // Garner's formula: the unique m (mod n) with m == mp (mod p) and
+        // m == mq (mod q).
+        let diff = (&mq - &mp).mod_floor(q);
+        let m = (&mp + p * (&diff * &*self.p_inv_mod_q).mod_floor(q)).mod_floor(&self.public_key.n);
+        Ok(m)
     }
+
+}
+
+/// `L(x) = (x - 1) / n`, the function Paillier decryption applies before
+/// the final CRT recombination (or, outside the CRT path, directly mod
+/// `n^2`).
+fn l_function(x: &BigInt, n: &BigInt) -> BigInt {
+    (x - BigInt::one()) / n
+}
+
+/// `hp`/`hq` in the CRT decryption formula: `(L_p(g^(p-1) mod p^2))^-1 mod p`.
+fn crt_decryption_coefficient(g: &BigInt, p: &BigInt, p2: &BigInt) -> BigInt {
+    let l = l_function(&g.modpow(&(p - 1u32), p2), p);
+    l.modinv(p).expect("prime factor is coprime to itself minus structure, so L(...) is invertible mod p")
 }
 
 // Minimal key generation for testing (not constant-time, not for production)
@@ -53,18 +208,24 @@ pub fn generate_keypair(bits: usize) -> (PrivateKey, PublicKey) {
     let q_biguint = Generator::new_prime(bits / 2);
     let p = BigInt::from_bytes_be(num_bigint::Sign::Plus, &p_biguint.to_bytes_be());
     let q = BigInt::from_bytes_be(num_bigint::Sign::Plus, &q_biguint.to_bytes_be());
-    let n = &p * &q;
-    let lambda_n = num_integer::lcm(p.clone() - 1u32, q.clone() - 1u32);
-    let phi_n = (&p - 1u32) * (&q - 1u32);
-    let pk = PublicKey { n: n.clone() };
-    let sk = PrivateKey {
-        public_key: PublicKey { n },
-        lambda_n,
-        phi_n,
-        p,
-        q,
-    };
-    (sk, pk)
+    PrivateKey::from_primes(p, q)
+}
+
+// Threshold-ECDSA key generation: p and q are each safe primes (p = 2p' + 1,
+// q = 2q' + 1 with p', q' also prime), rejection-sampled via
+// `GermainSafePrime::generate`. This resists small-subgroup attacks on the
+// Paillier modulus and, since safe primes of this form are always ≡ 3 mod 4,
+// lets `crypto::modproof` extract 4th-root witnesses for N.
+pub fn generate_safe_keypair<R: RngCore + CryptoRng>(rng: &mut R, bits: usize) -> (PrivateKey, PublicKey) {
+    let half_bits = bits / 2;
+    // GermainSafePrime::generate(bits) samples q' of `bits` bits and returns
+    // p = 2q' + 1, which has one extra bit, so ask for one fewer to land on
+    // the target modulus size.
+    let p_prime = crate::common::safe_prime::GermainSafePrime::generate(rng, half_bits - 1);
+    let q_prime = crate::common::safe_prime::GermainSafePrime::generate(rng, half_bits - 1);
+    let p = p_prime.safe_prime().clone();
+    let q = q_prime.safe_prime().clone();
+    PrivateKey::from_primes(p, q)
 }
 
 impl fmt::Display for PublicKey {
@@ -75,7 +236,7 @@ impl fmt::Display for PublicKey {
 
 impl fmt::Display for PrivateKey {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "PrivateKey {{ n: {}, lambda_n: {}, phi_n: {}, p: {}, q: {} }}", self.public_key.n, self.lambda_n, self.phi_n, self.p, self.q)
+        write!(f, "PrivateKey {{ n: {}, lambda_n: REDACTED, phi_n: REDACTED, p: REDACTED, q: REDACTED }}", self.public_key.n)
     }
 }
 
@@ -95,4 +256,80 @@ mod tests {
         let cipher = result.unwrap();
         assert_ne!(cipher, BigInt::zero());
     }
+
+    #[test]
+    fn test_add_decrypts_to_sum() {
+        let (sk, pk) = generate_keypair(128);
+        let mut rng = rand::thread_rng();
+        let m1 = 7.to_bigint().unwrap();
+        let m2 = 35.to_bigint().unwrap();
+        let c1 = pk.encrypt(&mut rng, &m1).unwrap();
+        let c2 = pk.encrypt(&mut rng, &m2).unwrap();
+        let c_sum = pk.add(&c1, &c2).unwrap();
+        assert_eq!(sk.decrypt(&c_sum).unwrap(), m1 + m2);
+    }
+
+    #[test]
+    fn test_mul_const_decrypts_to_product() {
+        let (sk, pk) = generate_keypair(128);
+        let mut rng = rand::thread_rng();
+        let m = 9.to_bigint().unwrap();
+        let k = 11.to_bigint().unwrap();
+        let c = pk.encrypt(&mut rng, &m).unwrap();
+        let c_mul = pk.mul_const(&c, &k).unwrap();
+        assert_eq!(sk.decrypt(&c_mul).unwrap(), m * k);
+    }
+
+    #[test]
+    fn test_add_const_decrypts_to_sum() {
+        let (sk, pk) = generate_keypair(128);
+        let mut rng = rand::thread_rng();
+        let m = 13.to_bigint().unwrap();
+        let k = 4.to_bigint().unwrap();
+        let c = pk.encrypt(&mut rng, &m).unwrap();
+        let c_add = pk.add_const(&c, &k).unwrap();
+        assert_eq!(sk.decrypt(&c_add).unwrap(), m + k);
+    }
+
+    #[test]
+    fn test_homo_sub_decrypts_to_difference() {
+        let (sk, pk) = generate_keypair(128);
+        let mut rng = rand::thread_rng();
+        let m1 = 35.to_bigint().unwrap();
+        let m2 = 7.to_bigint().unwrap();
+        let c1 = pk.encrypt(&mut rng, &m1).unwrap();
+        let c2 = pk.encrypt(&mut rng, &m2).unwrap();
+        let c_diff = pk.homo_sub(&c1, &c2).unwrap();
+        assert_eq!(sk.decrypt(&c_diff).unwrap(), m1 - m2);
+    }
+
+    #[test]
+    fn test_rerandomize_preserves_plaintext_but_changes_ciphertext() {
+        let (sk, pk) = generate_keypair(128);
+        let mut rng = rand::thread_rng();
+        let m = 17.to_bigint().unwrap();
+        let c = pk.encrypt(&mut rng, &m).unwrap();
+        let c_rerand = pk.rerandomize(&mut rng, &c).unwrap();
+        assert_ne!(c, c_rerand);
+        assert_eq!(sk.decrypt(&c_rerand).unwrap(), m);
+    }
+
+    #[test]
+    fn test_generate_safe_keypair_factors_are_safe_primes_and_blum() {
+        let (sk, _pk) = generate_safe_keypair(&mut OsRng, 64);
+        assert!(crate::common::safe_prime::GermainSafePrime::new((sk.p() - 1u32) / 2, sk.p().clone()).validate());
+        assert!(crate::common::safe_prime::GermainSafePrime::new((sk.q() - 1u32) / 2, sk.q().clone()).validate());
+        // p, q ≡ 3 mod 4, as required by the Paillier-Blum modulus proof.
+        assert_eq!(sk.p() % 4.to_bigint().unwrap(), 3.to_bigint().unwrap());
+        assert_eq!(sk.q() % 4.to_bigint().unwrap(), 3.to_bigint().unwrap());
+    }
+
+    #[test]
+    fn test_generate_safe_keypair_encrypts_and_decrypts() {
+        let (sk, pk) = generate_safe_keypair(&mut OsRng, 64);
+        let mut rng = rand::thread_rng();
+        let m = 42.to_bigint().unwrap();
+        let c = pk.encrypt(&mut rng, &m).unwrap();
+        assert_eq!(sk.decrypt(&c).unwrap(), m);
+    }
 }
@@ -1,6 +1,18 @@
 use num_bigint::BigInt;
-use crate::common::hash::sha512_256i;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand::{CryptoRng, RngCore};
 
+use crate::common::int::ModInt;
+use crate::common::random::get_random_positive_int;
+use crate::common::secret::SecretBigInt;
+use crate::crypto::paillier::PublicKey;
+use crate::crypto::transcript::ProofTranscript;
+
+/// Alice's range proof for the MtA protocol (GG18 Fig. 9): proves that a
+/// Paillier ciphertext `c = (1+N)^m * r^N mod N^2` encrypts a value `m` in
+/// the range implied by the curve order `q`, without revealing `m` or `r`.
+#[derive(Clone)]
 pub struct RangeProofAlice {
     pub z: BigInt,
     pub u: BigInt,
@@ -10,33 +22,345 @@ pub struct RangeProofAlice {
     pub s2: BigInt,
 }
 
+/// Binds the public inputs and commitments into the Fiat-Shamir challenge
+/// `e`, under `ProofTranscript`'s per-label domain separation -- shared by
+/// `RangeProofAlice::new`, `verify`, and `verify_batch` so they can never
+/// derive `e` differently from one another.
+fn challenge(session: &[u8], n: &BigInt, c: &BigInt, z: &BigInt, u: &BigInt, w: &BigInt, q: &BigInt) -> BigInt {
+    let mut transcript = ProofTranscript::new(b"RangeProofAlice", session);
+    transcript.append_bigint(b"N", n);
+    transcript.append_bigint(b"c", c);
+    transcript.append_bigint(b"z", z);
+    transcript.append_bigint(b"u", u);
+    transcript.append_bigint(b"w", w);
+    transcript.challenge_bigint(b"e", q)
+}
+
 impl RangeProofAlice {
-    pub fn new(pk: &BigInt, c: &BigInt, ntilde: &BigInt, h1: &BigInt, h2: &BigInt, m: &BigInt, r: &BigInt) -> Result<Self, String> {
-        let z = BigInt::one(); // Placeholder for computed value
-        let u = BigInt::one(); // Placeholder for computed value
-        let w = BigInt::one(); // Placeholder for computed value
-        let s = BigInt::one(); // Placeholder for computed value
-        let s1 = BigInt::one(); // Placeholder for computed value
-        let s2 = BigInt::one(); // Placeholder for computed value
+    /// Generates Alice's range proof that `pk`'s ciphertext `c` encrypts `m`
+    /// (with randomness `r`) in the range implied by curve order `q`, using
+    /// the Paillier-Blum modulus `ntilde` and generators `h1`, `h2` shared by
+    /// the verifier. `rng` must be a CSPRNG (e.g. `OsRng`) — this proof's
+    /// soundness depends on `alpha`, `beta`, `gamma` and `rho` being
+    /// unpredictable to the verifier. `session` binds the proof to the
+    /// calling protocol run, the same way `ProofFac`/`ProofMod` do, so a
+    /// proof generated for one session can't be replayed into another.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new<R: RngCore + CryptoRng>(
+        session: &[u8],
+        pk: &PublicKey,
+        c: &BigInt,
+        ntilde: &BigInt,
+        h1: &BigInt,
+        h2: &BigInt,
+        q: &BigInt,
+        m: &BigInt,
+        r: &BigInt,
+        rng: &mut R,
+    ) -> Result<Self, String> {
+        let n = &pk.n;
+        let n_square = n * n;
+        let mod_ntilde = ModInt::new(ntilde.clone());
+        let mod_nsquare = ModInt::new(n_square);
+        let mod_n = ModInt::new(n.clone());
+
+        let q3 = q * q * q;
+        let q_ntilde = q * ntilde;
+        let q3_ntilde = &q3 * ntilde;
+
+        // 1-4. alpha <- Z_(q^3), beta <- Z*_N, gamma <- Z_(q^3*Ntilde), rho <- Z_(q*Ntilde)
+        // Wrapped in `SecretBigInt` so each blind is overwritten with zero as
+        // soon as it goes out of scope, rather than lingering in freed heap
+        // pages until the allocator reuses them.
+        let alpha = SecretBigInt::new(get_random_positive_int(rng, &q3));
+        let beta = SecretBigInt::new(random_coprime_to(rng, n));
+        let gamma = SecretBigInt::new(get_random_positive_int(rng, &q3_ntilde));
+        let rho = SecretBigInt::new(get_random_positive_int(rng, &q_ntilde));
+
+        // 5. z = h1^m * h2^rho mod Ntilde
+        let z = mod_ntilde.exp2(h1, m, h2, &rho);
+
+        // 6. u = (1+N)^alpha * beta^N mod N^2
+        let g = n + BigInt::one();
+        let u = mod_nsquare.exp2(&g, &alpha, &beta, n);
+
+        // 7. w = h1^alpha * h2^gamma mod Ntilde
+        let w = mod_ntilde.exp2(h1, &alpha, h2, &gamma);
+
+        // 8-9. e = H(N, c, z, u, w) mod q
+        let e = challenge(session, n, c, &z, &u, &w, q);
+
+        // 10. s = beta * r^e mod N
+        let s = mod_n.mul(&beta, &mod_n.exp(r, &e));
+
+        // 11-12. s1 = e*m + alpha, s2 = e*rho + gamma
+        let s1 = &e * m + alpha.into_inner();
+        let s2 = &e * rho.into_inner() + gamma.into_inner();
 
         Ok(RangeProofAlice { z, u, w, s, s1, s2 })
     }
+
+    /// Verifies this proof against the same public parameters `new` was
+    /// called with.
+    pub fn verify(&self, session: &[u8], pk: &PublicKey, ntilde: &BigInt, h1: &BigInt, h2: &BigInt, q: &BigInt, c: &BigInt) -> bool {
+        let n = &pk.n;
+        let n_square = n * n;
+        let mod_ntilde = ModInt::new(ntilde.clone());
+        let mod_nsquare = ModInt::new(n_square.clone());
+
+        if !self.validate_basic(q) {
+            return false;
+        }
+
+        let e = challenge(session, n, c, &self.z, &self.u, &self.w, q);
+
+        // u == (1+N)^s1 * s^N * c^-e mod N^2
+        let c_inv = match c.modinv(&n_square) {
+            Some(inv) => inv,
+            None => return false,
+        };
+        let g = n + BigInt::one();
+        let u_check = mod_nsquare.mul(
+            &mod_nsquare.exp2(&g, &self.s1, &self.s, n),
+            &mod_nsquare.exp(&c_inv, &e),
+        );
+        if u_check != self.u {
+            return false;
+        }
+
+        // w == h1^s1 * h2^s2 * z^-e mod Ntilde
+        let z_inv = match self.z.modinv(ntilde) {
+            Some(inv) => inv,
+            None => return false,
+        };
+        let w_check = mod_ntilde.mul(&mod_ntilde.exp2(h1, &self.s1, h2, &self.s2), &mod_ntilde.exp(&z_inv, &e));
+        w_check == self.w
+    }
+
+    fn validate_basic(&self, q: &BigInt) -> bool {
+        let q3 = q * q * q;
+        self.s1 >= BigInt::zero() && self.s1 <= q3
+    }
+
+    /// Batch-verifies many `RangeProofAlice`s sharing the same Paillier key,
+    /// Paillier-Blum ring-Pedersen parameters, and curve order `q` (the usual
+    /// case: one verifier checking a batch of range proofs from a single
+    /// signing session). Folds the `u`/`w` equality checks across all
+    /// statements into one randomized linear combination each -- sound
+    /// except with probability `1/q` per bad proof -- and falls back to
+    /// per-proof `verify` to name the culprits if the combined check fails.
+    pub fn verify_batch(
+        session: &[u8],
+        pk: &PublicKey,
+        ntilde: &BigInt,
+        h1: &BigInt,
+        h2: &BigInt,
+        q: &BigInt,
+        statements: &[(&BigInt, &RangeProofAlice)],
+    ) -> Result<(), Vec<usize>> {
+        if statements.is_empty() {
+            return Ok(());
+        }
+        if statements.iter().any(|&(_, proof)| !proof.validate_basic(q)) {
+            return Self::find_bad_proofs(session, pk, ntilde, h1, h2, q, statements);
+        }
+
+        let n = &pk.n;
+        let n_square = n * n;
+        let mod_ntilde = ModInt::new(ntilde.clone());
+        let mod_nsquare = ModInt::new(n_square.clone());
+        let g = n + BigInt::one();
+
+        let mut rng = rand::rngs::OsRng;
+        let deltas: Vec<BigInt> = (0..statements.len()).map(|_| get_random_positive_int(&mut rng, q)).collect();
+        let challenges: Vec<BigInt> = statements
+            .iter()
+            .map(|&(c, proof)| challenge(session, n, c, &proof.z, &proof.u, &proof.w, q))
+            .collect();
+
+        // `s_i` appears as a *base* raised to the fixed exponent `N`, unlike
+        // `s1_i`/`s2_i`/`h1`/`h2` where the base is fixed and the exponent
+        // varies per proof -- so the batched `s_i^N` term is
+        // `(prod_i s_i^delta_i)^N`, not `(1+N)^(sum delta_i*s_i)`.
+        let mut sum_s1 = BigInt::zero();
+        let mut prod_s = BigInt::one();
+        let mut rhs_u = BigInt::one();
+        let mut sum_s2 = BigInt::zero();
+        let mut rhs_w = BigInt::one();
+        for ((c, proof), (delta, e)) in statements.iter().zip(deltas.iter().zip(challenges.iter())) {
+            sum_s1 += delta * &proof.s1;
+            prod_s = mod_nsquare.mul(&prod_s, &mod_nsquare.exp(&proof.s, delta));
+            let c_inv = match c.modinv(&n_square) {
+                Some(inv) => inv,
+                None => return Self::find_bad_proofs(session, pk, ntilde, h1, h2, q, statements),
+            };
+            let rhs_u_i = mod_nsquare.exp(&c_inv, e);
+            rhs_u = mod_nsquare.mul(&rhs_u, &mod_nsquare.exp(&rhs_u_i, delta));
+
+            sum_s2 += delta * &proof.s2;
+            let z_inv = match proof.z.modinv(ntilde) {
+                Some(inv) => inv,
+                None => return Self::find_bad_proofs(session, pk, ntilde, h1, h2, q, statements),
+            };
+            let rhs_w_i = mod_ntilde.exp(&z_inv, e);
+            rhs_w = mod_ntilde.mul(&rhs_w, &mod_ntilde.exp(&rhs_w_i, delta));
+        }
+
+        let lhs_u = mod_nsquare.mul(&mod_nsquare.mul(&mod_nsquare.exp(&g, &sum_s1), &mod_nsquare.exp(&prod_s, n)), &rhs_u);
+        let rhs_u_total = statements
+            .iter()
+            .zip(deltas.iter())
+            .fold(BigInt::one(), |acc, (&(_, proof), delta)| mod_nsquare.mul(&acc, &mod_nsquare.exp(&proof.u, delta)));
+        let lhs_w = mod_ntilde.mul(&mod_ntilde.exp2(h1, &sum_s1, h2, &sum_s2), &rhs_w);
+        let rhs_w_total = statements
+            .iter()
+            .zip(deltas.iter())
+            .fold(BigInt::one(), |acc, (&(_, proof), delta)| mod_ntilde.mul(&acc, &mod_ntilde.exp(&proof.w, delta)));
+
+        if lhs_u == rhs_u_total && lhs_w == rhs_w_total {
+            Ok(())
+        } else {
+            Self::find_bad_proofs(session, pk, ntilde, h1, h2, q, statements)
+        }
+    }
+
+    fn find_bad_proofs(
+        session: &[u8],
+        pk: &PublicKey,
+        ntilde: &BigInt,
+        h1: &BigInt,
+        h2: &BigInt,
+        q: &BigInt,
+        statements: &[(&BigInt, &RangeProofAlice)],
+    ) -> Result<(), Vec<usize>> {
+        let bad: Vec<usize> = statements
+            .iter()
+            .enumerate()
+            .filter(|&(_, &(c, proof))| !proof.verify(session, pk, ntilde, h1, h2, q, c))
+            .map(|(idx, _)| idx)
+            .collect();
+        Err(bad)
+    }
 }
+
+/// Samples a value in `[1, n)` coprime to `n`, as Paillier encryption
+/// randomness must be.
+fn random_coprime_to<R: RngCore + CryptoRng>(rng: &mut R, n: &BigInt) -> BigInt {
+    loop {
+        let candidate = get_random_positive_int(rng, n);
+        if candidate.is_zero() {
+            continue;
+        }
+        if candidate.gcd(n) == BigInt::one() {
+            return candidate;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::crypto::paillier::generate_keypair;
     use num_bigint::ToBigInt;
+    use rand::thread_rng;
+
+    fn setup() -> (PublicKey, BigInt, BigInt, BigInt, BigInt) {
+        let (_sk, pk) = generate_keypair(512);
+        let mut rng = thread_rng();
+        let ntilde = get_random_positive_int(&mut rng, &pk.n);
+        let h1 = random_coprime_to(&mut rng, &ntilde);
+        let h2 = random_coprime_to(&mut rng, &ntilde);
+        let q = 1_000_003.to_bigint().unwrap(); // a small prime standing in for the curve order
+        (pk, ntilde, h1, h2, q)
+    }
+
+    fn encrypt_with(pk: &PublicKey, m: &BigInt, r: &BigInt) -> BigInt {
+        let n2 = &pk.n * &pk.n;
+        let gm = (&pk.n + BigInt::one()).modpow(m, &n2);
+        let rn = r.modpow(&pk.n, &n2);
+        (gm * rn) % &n2
+    }
+
+    const SESSION: &[u8] = b"session";
+
+    #[test]
+    fn test_range_proof_alice_round_trip() {
+        let (pk, ntilde, h1, h2, q) = setup();
+        let mut rng = thread_rng();
+        let m = get_random_positive_int(&mut rng, &q);
+        let r = random_coprime_to(&mut rng, &pk.n);
+        let c = encrypt_with(&pk, &m, &r);
+
+        let proof = RangeProofAlice::new(SESSION, &pk, &c, &ntilde, &h1, &h2, &q, &m, &r, &mut rng).unwrap();
+        assert!(proof.verify(SESSION, &pk, &ntilde, &h1, &h2, &q, &c));
+    }
+
+    #[test]
+    fn test_range_proof_alice_rejects_tampered_proof() {
+        let (pk, ntilde, h1, h2, q) = setup();
+        let mut rng = thread_rng();
+        let m = get_random_positive_int(&mut rng, &q);
+        let r = random_coprime_to(&mut rng, &pk.n);
+        let c = encrypt_with(&pk, &m, &r);
+
+        let mut proof = RangeProofAlice::new(SESSION, &pk, &c, &ntilde, &h1, &h2, &q, &m, &r, &mut rng).unwrap();
+        proof.z += BigInt::one();
+        assert!(!proof.verify(SESSION, &pk, &ntilde, &h1, &h2, &q, &c));
+    }
+
+    #[test]
+    fn test_range_proof_alice_rejects_wrong_ciphertext() {
+        let (pk, ntilde, h1, h2, q) = setup();
+        let mut rng = thread_rng();
+        let m = get_random_positive_int(&mut rng, &q);
+        let r = random_coprime_to(&mut rng, &pk.n);
+        let c = encrypt_with(&pk, &m, &r);
+
+        let proof = RangeProofAlice::new(SESSION, &pk, &c, &ntilde, &h1, &h2, &q, &m, &r, &mut rng).unwrap();
+        let m_wrong = &m + BigInt::one();
+        let c_wrong = encrypt_with(&pk, &m_wrong, &r);
+        assert!(!proof.verify(SESSION, &pk, &ntilde, &h1, &h2, &q, &c_wrong));
+    }
 
     #[test]
-    fn test_range_proof_alice_new() {
-        let pk = 1.to_bigint().unwrap();
-        let c = 2.to_bigint().unwrap();
-        let ntilde = 3.to_bigint().unwrap();
-        let h1 = 4.to_bigint().unwrap();
-        let h2 = 5.to_bigint().unwrap();
-        let m = 6.to_bigint().unwrap();
-        let r = 7.to_bigint().unwrap();
-        let proof = RangeProofAlice::new(&pk, &c, &ntilde, &h1, &h2, &m, &r);
-        assert!(proof.is_ok());
+    fn test_range_proof_alice_verify_batch_accepts_all_valid() {
+        let (pk, ntilde, h1, h2, q) = setup();
+        let mut rng = thread_rng();
+
+        let mut ciphertexts = Vec::new();
+        let mut proofs = Vec::new();
+        for _ in 0..3 {
+            let m = get_random_positive_int(&mut rng, &q);
+            let r = random_coprime_to(&mut rng, &pk.n);
+            let c = encrypt_with(&pk, &m, &r);
+            let proof = RangeProofAlice::new(SESSION, &pk, &c, &ntilde, &h1, &h2, &q, &m, &r, &mut rng).unwrap();
+            ciphertexts.push(c);
+            proofs.push(proof);
+        }
+        let statements: Vec<(&BigInt, &RangeProofAlice)> = ciphertexts.iter().zip(proofs.iter()).collect();
+
+        assert!(RangeProofAlice::verify_batch(SESSION, &pk, &ntilde, &h1, &h2, &q, &statements).is_ok());
+    }
+
+    #[test]
+    fn test_range_proof_alice_verify_batch_names_the_bad_proof() {
+        let (pk, ntilde, h1, h2, q) = setup();
+        let mut rng = thread_rng();
+
+        let mut ciphertexts = Vec::new();
+        let mut proofs = Vec::new();
+        for _ in 0..3 {
+            let m = get_random_positive_int(&mut rng, &q);
+            let r = random_coprime_to(&mut rng, &pk.n);
+            let c = encrypt_with(&pk, &m, &r);
+            let proof = RangeProofAlice::new(SESSION, &pk, &c, &ntilde, &h1, &h2, &q, &m, &r, &mut rng).unwrap();
+            ciphertexts.push(c);
+            proofs.push(proof);
+        }
+        proofs[2].z += BigInt::one();
+        let statements: Vec<(&BigInt, &RangeProofAlice)> = ciphertexts.iter().zip(proofs.iter()).collect();
+
+        let bad = RangeProofAlice::verify_batch(SESSION, &pk, &ntilde, &h1, &h2, &q, &statements).unwrap_err();
+        assert_eq!(bad, vec![2]);
     }
 }
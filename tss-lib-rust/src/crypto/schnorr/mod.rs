@@ -0,0 +1 @@
+pub mod schnorr_proof;
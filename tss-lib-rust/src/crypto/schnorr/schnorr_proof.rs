@@ -1,411 +1,486 @@
-// Copyright © 2019 Binance
-//
-// This file is part of Binance. The full Binance copyright notice, including
-// terms governing use, modification, and redistribution, is contained in the
-// file LICENSE at the root of the source code distribution tree.
-
 // Translation of tss-lib-go/crypto/schnorr/schnorr_proof.go
 
 use crate::{
-    common::{
-        hash::sha512_256i_tagged,
-        int::ModInt,
-        random::get_random_positive_int,
-        hash_utils::rejection_sample,
-    },
-    crypto::ecpoint::{ECPoint, PointError},
-    tss::Curve, // Assuming trait for curve operations & params
+    common::int::ModInt,
+    common::random::get_random_positive_int,
+    crypto::ecpoint::ECPoint,
+    crypto::transcript::ProofTranscript,
 };
 
-use elliptic_curve::CurveArithmetic;
-use elliptic_curve::scalar::Scalar;
-use num_bigint_dig::{BigInt};
+use num_bigint::{BigInt, Sign};
 use num_traits::Zero;
 use rand::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
-use thiserror::Error;
-use log::error;
-
-#[derive(Error, Debug, Clone, PartialEq, Eq)]
-pub enum SchnorrError {
-    #[error("invalid parameters: {0}")]
-    InvalidParameters(String),
-    #[error("point operation failed: {0}")]
-    PointError(String),
-    #[error("internal error: {0}")]
-    InternalError(String),
-}
 
-impl From<PointError> for SchnorrError {
-    fn from(err: PointError) -> Self {
-        SchnorrError::PointError(err.to_string())
-    }
+fn zkp_challenge(session: &[u8], x_pub: &ECPoint, g: &ECPoint, alpha: &ECPoint, q: &BigInt) -> BigInt {
+    let mut transcript = ProofTranscript::new(b"ZkProof", session);
+    transcript.append_bigint(b"X.x", &x_pub.x);
+    transcript.append_bigint(b"X.y", &x_pub.y);
+    transcript.append_bigint(b"g.x", &g.x);
+    transcript.append_bigint(b"g.y", &g.y);
+    transcript.append_bigint(b"alpha.x", &alpha.x);
+    transcript.append_bigint(b"alpha.y", &alpha.y);
+    transcript.challenge_bigint(b"c", q)
 }
 
-/// Schnorr ZK proof of knowledge of the discrete logarithm `x` such that `X = g^x`.
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct ZkProof<C: Curve + CurveArithmetic> {
-     // Need to handle ECPoint serialization
-    #[serde(bound(serialize = "ECPoint<C>: Serialize", deserialize = "ECPoint<C>: Deserialize<'de>"))]
-    pub alpha: ECPoint<C>,
-    #[serde(with = "crate::serde_support::bigint_bytes")]
+/// Schnorr ZK proof of knowledge of the discrete logarithm `x` such that
+/// `X = g^x`. (GG18Spec Fig. 16)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ZkProof {
+    pub alpha: ECPoint,
     pub t: BigInt,
 }
 
-/// Schnorr ZK proof of knowledge `s`, `l` such that `V = R^s * g^l`.
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct ZkvProof<C: Curve + CurveArithmetic> {
-    // Need to handle ECPoint serialization
-    #[serde(bound(serialize = "ECPoint<C>: Serialize", deserialize = "ECPoint<C>: Deserialize<'de>"))]
-    pub alpha: ECPoint<C>,
-     #[serde(with = "crate::serde_support::bigint_bytes")]
-    pub t: BigInt,
-     #[serde(with = "crate::serde_support::bigint_bytes")]
-    pub u: BigInt,
-}
-
-impl<C> ZkProof<C>
-where
-    C: Curve + CurveArithmetic,
-    // Add bounds needed for ECPoint ops
-     ECPoint<C>: Clone + PartialEq + Serialize + for<'de> Deserialize<'de>,
-    // Assuming methods exist & BigInt can be converted to Scalar
-{
-    /// Creates a new Schnorr ZK proof `(α, t)` for `X = g^x`. (GG18Spec Fig. 16)
-    /// `α = g^a`
-    /// `c = H(session, X, g, α)`
-    /// `t = a + c*x mod q`
-    pub fn new<
-        R: CryptoRng + RngCore
-    >(
+impl ZkProof {
+    /// Creates a new Schnorr ZK proof `(α, t)` for `X = g^x`.
+    /// `α = g^a`, `c = H(session, X, g, α)`, `t = a + c*x mod q`.
+    pub fn new<R: CryptoRng + RngCore>(
         session: &[u8],
-        x_priv: &BigInt,       // The secret x
-        x_pub: &ECPoint<C>, // The public point X = g^x
+        x_priv: &BigInt,
+        x_pub: &ECPoint,
+        q: &BigInt,
         rng: &mut R,
-    ) -> Result<Self, SchnorrError> {
-         if x_priv.sign() == num_bigint_dig::Sign::Minus || !x_pub.validate_basic() {
-             return Err(SchnorrError::InvalidParameters("x or X are invalid".to_string()));
-         }
-        let q = C::ORDER_BIGINT; // Assuming Curve trait provides this
+    ) -> Result<Self, String> {
+        if x_priv.sign() == Sign::Minus {
+            return Err("x is negative".to_string());
+        }
         let mod_q = ModInt::new(q.clone());
-        let g = ECPoint::<C>::generator(); // Assuming generator access
-
-        // a <- Zq
-        let a = get_random_positive_int(rng, &q)
-            .ok_or_else(|| SchnorrError::InternalError("Failed to generate random 'a'".to_string()))?;
+        let g = ECPoint::generator(x_pub.curve)?;
 
-        // α = g^a
-        let alpha = ECPoint::<C>::scalar_base_mult(&a);
+        let a = get_random_positive_int(rng, q);
+        let alpha = ECPoint::scalar_base_mult(x_pub.curve, &a)?;
 
-        // c = H(session, X, g, α)
-        let (x_pub_x, x_pub_y) = x_pub.coords();
-        let (g_x, g_y) = g.coords();
-        let (alpha_x, alpha_y) = alpha.coords();
+        let c = zkp_challenge(session, x_pub, &g, &alpha, q);
+        let t = mod_q.add(&a, &mod_q.mul(&c, x_priv));
 
-        let c_hash = sha512_256i_tagged(
-            session,
-            &[&x_pub_x, &x_pub_y, &g_x, &g_y, &alpha_x, &alpha_y],
-        ).ok_or_else(|| SchnorrError::InternalError("Failed to compute challenge hash c".to_string()))?;
+        Ok(Self { alpha, t })
+    }
 
-        // Rejection sample c
-        let c = rejection_sample(&q, &c_hash);
+    /// Verifies a Schnorr ZK proof: checks `g^t == α * X^c`.
+    pub fn verify(&self, session: &[u8], x_pub: &ECPoint, q: &BigInt) -> bool {
+        let g = match ECPoint::generator(x_pub.curve) {
+            Ok(g) => g,
+            Err(_) => return false,
+        };
+        let c = zkp_challenge(session, x_pub, &g, &self.alpha, q);
 
-        // t = a + c*x mod q
-        let cx = mod_q.mul(&c, x_priv);
-        let t = mod_q.add(&a, &cx);
+        let gt = match ECPoint::scalar_base_mult(x_pub.curve, &self.t) {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
+        let xc = match x_pub.scalar_mult(&c) {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
+        let alpha_plus_xc = match self.alpha.add(&xc) {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
 
-        Ok(Self { alpha, t })
+        gt == alpha_plus_xc
     }
 
-    /// Verifies a Schnorr ZK proof. (GG18Spec Fig. 16)
-    /// Checks if `g^t == α * X^c`
-    pub fn verify(
-        &self,
+    /// Verifies a batch of `(proof, X)` pairs against one combined check
+    /// instead of `n` independent verifications.
+    ///
+    /// Draws a nonzero random blinder `rho_i` per proof and checks the
+    /// single relation `g^(sum rho_i*t_i) == sum rho_i*alpha_i + sum
+    /// (rho_i*c_i)*X_i`. A forged proof only survives this with probability
+    /// `1/q` over the verifier's choice of `rho_i` (Schwartz-Zippel), so a
+    /// combined failure only proves *some* proof is invalid -- callers who
+    /// need to localize which one should fall back to `verify` per proof.
+    pub fn verify_batch<R: CryptoRng + RngCore>(
         session: &[u8],
-        x_pub: &ECPoint<C>, // The public point X = g^x
+        proofs: &[(&Self, &ECPoint)],
+        q: &BigInt,
+        rng: &mut R,
     ) -> bool {
-         if !self.validate_basic() || !x_pub.validate_basic() {
-             return false;
-         }
-        let q = C::ORDER_BIGINT;
+        if proofs.is_empty() {
+            return true;
+        }
+        let curve = proofs[0].1.curve;
+        let g = match ECPoint::generator(curve) {
+            Ok(g) => g,
+            Err(_) => return false,
+        };
         let mod_q = ModInt::new(q.clone());
-        let g = ECPoint::<C>::generator();
-
-        // Recalculate c = H(session, X, g, α)
-        let (x_pub_x, x_pub_y) = x_pub.coords();
-        let (g_x, g_y) = g.coords();
-        let (alpha_x, alpha_y) = self.alpha.coords();
-
-         let c_hash = match sha512_256i_tagged(
-             session,
-             &[&x_pub_x, &x_pub_y, &g_x, &g_y, &alpha_x, &alpha_y],
-         ) {
-             Some(h) => h,
-             None => {
-                 error!("ZKProof verify: failed to compute challenge hash c");
-                 return false;
-             }
-         };
-
-        let c = rejection_sample(&q, &c_hash);
-
-        // Left side: g^t
-        let gt = ECPoint::<C>::scalar_base_mult(&self.t);
-
-        // Right side: α * X^c
-        let xc = x_pub.scalar_mul(&c);
-        let alpha_plus_xc = match self.alpha.add(&xc) {
-            Ok(p) => p,
-            Err(_) => {
-                 error!("ZKProof verify: point addition failed for alpha * X^c");
-                 return false;
-            }
-         };
 
-        // Check g^t == α * X^c
-        gt == alpha_plus_xc
-    }
+        let mut t_acc = BigInt::zero();
+        let mut rhs = ECPoint::identity(curve);
+        for (proof, x_pub) in proofs {
+            let c = zkp_challenge(session, x_pub, &g, &proof.alpha, q);
+            let rho = loop {
+                let candidate = get_random_positive_int(rng, q);
+                if !candidate.is_zero() {
+                    break candidate;
+                }
+            };
+
+            t_acc = mod_q.add(&t_acc, &mod_q.mul(&rho, &proof.t));
+
+            let rho_alpha = match proof.alpha.scalar_mult(&rho) {
+                Ok(p) => p,
+                Err(_) => return false,
+            };
+            let rho_c_x = match x_pub.scalar_mult(&mod_q.mul(&rho, &c)) {
+                Ok(p) => p,
+                Err(_) => return false,
+            };
+            rhs = match rhs.add(&rho_alpha).and_then(|p| p.add(&rho_c_x)) {
+                Ok(p) => p,
+                Err(_) => return false,
+            };
+        }
 
-    /// Basic validation of proof components.
-    pub fn validate_basic(&self) -> bool {
-        self.alpha.validate_basic() // t is BigInt, always valid
+        let lhs = match ECPoint::scalar_base_mult(curve, &t_acc) {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
+        lhs == rhs
     }
 }
 
-impl<C> ZkvProof<C>
-where
-    C: Curve + CurveArithmetic,
-    // Add bounds needed for ECPoint ops
-     ECPoint<C>: Clone + PartialEq + Serialize + for<'de> Deserialize<'de>,
-{
-    /// Creates a new Schnorr ZK proof `(α, t, u)` for `V = R^s * g^l`. (GG18Spec Fig. 17)
-    /// `α = R^a * g^b`
-    /// `c = H(session, V, R, g, α)`
-    /// `t = a + c*s mod q`
-    /// `u = b + c*l mod q`
-    pub fn new<
-        R: CryptoRng + RngCore
-    >(
+fn zkv_challenge(session: &[u8], v_pub: &ECPoint, r_pub: &ECPoint, g: &ECPoint, alpha: &ECPoint, q: &BigInt) -> BigInt {
+    let mut transcript = ProofTranscript::new(b"ZkvProof", session);
+    transcript.append_bigint(b"V.x", &v_pub.x);
+    transcript.append_bigint(b"V.y", &v_pub.y);
+    transcript.append_bigint(b"R.x", &r_pub.x);
+    transcript.append_bigint(b"R.y", &r_pub.y);
+    transcript.append_bigint(b"g.x", &g.x);
+    transcript.append_bigint(b"g.y", &g.y);
+    transcript.append_bigint(b"alpha.x", &alpha.x);
+    transcript.append_bigint(b"alpha.y", &alpha.y);
+    transcript.challenge_bigint(b"c", q)
+}
+
+/// Schnorr ZK proof of knowledge of `s`, `l` such that `V = R^s * g^l`.
+/// (GG18Spec Fig. 17)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ZkvProof {
+    pub alpha: ECPoint,
+    pub t: BigInt,
+    pub u: BigInt,
+}
+
+impl ZkvProof {
+    /// Creates a new Schnorr ZK proof `(α, t, u)` for `V = R^s * g^l`.
+    /// `α = R^a * g^b`, `c = H(session, V, R, g, α)`, `t = a + c*s mod q`,
+    /// `u = b + c*l mod q`.
+    pub fn new<R: CryptoRng + RngCore>(
         session: &[u8],
-        s: &BigInt, // Secret s
-        l: &BigInt, // Secret l
-        v_pub: &ECPoint<C>, // Public V = R^s * g^l
-        r_pub: &ECPoint<C>, // Public R
+        s: &BigInt,
+        l: &BigInt,
+        v_pub: &ECPoint,
+        r_pub: &ECPoint,
+        q: &BigInt,
         rng: &mut R,
-    ) -> Result<Self, SchnorrError> {
-         if s.sign() == num_bigint_dig::Sign::Minus ||
-            l.sign() == num_bigint_dig::Sign::Minus ||
-            !v_pub.validate_basic() ||
-            !r_pub.validate_basic()
-         {
-             return Err(SchnorrError::InvalidParameters("s, l, V, or R are invalid".to_string()));
-         }
-        let q = C::ORDER_BIGINT;
+    ) -> Result<Self, String> {
+        if s.sign() == Sign::Minus || l.sign() == Sign::Minus {
+            return Err("s or l is negative".to_string());
+        }
         let mod_q = ModInt::new(q.clone());
-        let g = ECPoint::<C>::generator();
-
-        // a, b <- Zq
-        let a = get_random_positive_int(rng, &q)
-            .ok_or_else(|| SchnorrError::InternalError("Failed to generate random 'a'".to_string()))?;
-        let b = get_random_positive_int(rng, &q)
-            .ok_or_else(|| SchnorrError::InternalError("Failed to generate random 'b'".to_string()))?;
-
-        // α = R^a * g^b
-        let ra = r_pub.scalar_mul(&a);
-        let gb = ECPoint::<C>::scalar_base_mult(&b);
-        let alpha = ra.add(&gb)?; // Handle potential point error
-
-        // c = H(session, V, R, g, α)
-        let (v_x, v_y) = v_pub.coords();
-        let (r_x, r_y) = r_pub.coords();
-        let (g_x, g_y) = g.coords();
-        let (alpha_x, alpha_y) = alpha.coords();
-
-        let c_hash = sha512_256i_tagged(
-            session,
-            &[&v_x, &v_y, &r_x, &r_y, &g_x, &g_y, &alpha_x, &alpha_y],
-        ).ok_or_else(|| SchnorrError::InternalError("Failed to compute challenge hash c".to_string()))?;
-        let c = rejection_sample(&q, &c_hash);
-
-        // t = a + c*s mod q
-        let cs = mod_q.mul(&c, s);
-        let t = mod_q.add(&a, &cs);
-
-        // u = b + c*l mod q
-        let cl = mod_q.mul(&c, l);
-        let u = mod_q.add(&b, &cl);
+        let g = ECPoint::generator(v_pub.curve)?;
+
+        let a = get_random_positive_int(rng, q);
+        let b = get_random_positive_int(rng, q);
+
+        let ra = r_pub.scalar_mult(&a)?;
+        let gb = ECPoint::scalar_base_mult(v_pub.curve, &b)?;
+        let alpha = ra.add(&gb)?;
+
+        let c = zkv_challenge(session, v_pub, r_pub, &g, &alpha, q);
+        let t = mod_q.add(&a, &mod_q.mul(&c, s));
+        let u = mod_q.add(&b, &mod_q.mul(&c, l));
 
         Ok(Self { alpha, t, u })
     }
 
-    /// Verifies a Schnorr ZK proof `(α, t, u)`. (GG18Spec Fig. 17)
-    /// Checks if `R^t * g^u == α * V^c`
-    pub fn verify(
-        &self,
-        session: &[u8],
-        v_pub: &ECPoint<C>,
-        r_pub: &ECPoint<C>,
-    ) -> bool {
-         if !self.validate_basic() || !v_pub.validate_basic() || !r_pub.validate_basic() {
-            return false;
-        }
-        let q = C::ORDER_BIGINT;
-        let mod_q = ModInt::new(q.clone());
-        let g = ECPoint::<C>::generator();
-
-        // Recalculate c = H(session, V, R, g, α)
-        let (v_x, v_y) = v_pub.coords();
-        let (r_x, r_y) = r_pub.coords();
-        let (g_x, g_y) = g.coords();
-        let (alpha_x, alpha_y) = self.alpha.coords();
-
-        let c_hash = match sha512_256i_tagged(
-            session,
-            &[&v_x, &v_y, &r_x, &r_y, &g_x, &g_y, &alpha_x, &alpha_y],
-         ) {
-            Some(h) => h,
-            None => {
-                error!("ZKVProof verify: failed to compute challenge hash c");
-                return false;
-            }
+    /// Verifies a Schnorr ZK proof `(α, t, u)`: checks `R^t * g^u == α * V^c`.
+    pub fn verify(&self, session: &[u8], v_pub: &ECPoint, r_pub: &ECPoint, q: &BigInt) -> bool {
+        let g = match ECPoint::generator(v_pub.curve) {
+            Ok(g) => g,
+            Err(_) => return false,
         };
-        let c = rejection_sample(&q, &c_hash);
+        let c = zkv_challenge(session, v_pub, r_pub, &g, &self.alpha, q);
 
-        // Left side: R^t * g^u
-        let rt = r_pub.scalar_mul(&self.t);
-        let gu = ECPoint::<C>::scalar_base_mult(&self.u);
+        let rt = match r_pub.scalar_mult(&self.t) {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
+        let gu = match ECPoint::scalar_base_mult(v_pub.curve, &self.u) {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
         let lhs = match rt.add(&gu) {
             Ok(p) => p,
-            Err(_) => {
-                 error!("ZKVProof verify: point addition failed for R^t * g^u");
-                 return false;
-            }
-         };
-
-        // Right side: α * V^c
-        let vc = v_pub.scalar_mul(&c);
+            Err(_) => return false,
+        };
+
+        let vc = match v_pub.scalar_mult(&c) {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
         let rhs = match self.alpha.add(&vc) {
             Ok(p) => p,
-             Err(_) => {
-                 error!("ZKVProof verify: point addition failed for alpha * V^c");
-                 return false;
-            }
-         };
+            Err(_) => return false,
+        };
 
-        // Check R^t * g^u == α * V^c
         lhs == rhs
     }
 
-    /// Basic validation of proof components.
-    pub fn validate_basic(&self) -> bool {
-        self.alpha.validate_basic() // t, u are BigInts, always valid
+    /// Batch counterpart of `verify`, in the same spirit as
+    /// `ZkProof::verify_batch`: recomputes each challenge `c_i`, draws a
+    /// nonzero random blinder `rho_i` per proof, and checks the single
+    /// combined relation `sum rho_i*(t_i*R_i) + g^(sum rho_i*u_i) == sum
+    /// rho_i*alpha_i + sum (rho_i*c_i)*V_i` instead of `n` independent
+    /// `R^t * g^u == alpha * V^c` checks.
+    pub fn verify_batch<R: CryptoRng + RngCore>(
+        session: &[u8],
+        proofs: &[(&Self, &ECPoint, &ECPoint)],
+        q: &BigInt,
+        rng: &mut R,
+    ) -> bool {
+        if proofs.is_empty() {
+            return true;
+        }
+        let curve = proofs[0].1.curve;
+        let g = match ECPoint::generator(curve) {
+            Ok(g) => g,
+            Err(_) => return false,
+        };
+        let mod_q = ModInt::new(q.clone());
+
+        let mut u_acc = BigInt::zero();
+        let mut lhs = ECPoint::identity(curve);
+        let mut rhs = ECPoint::identity(curve);
+
+        for (proof, v_pub, r_pub) in proofs {
+            let c = zkv_challenge(session, v_pub, r_pub, &g, &proof.alpha, q);
+            let rho = loop {
+                let candidate = get_random_positive_int(rng, q);
+                if !candidate.is_zero() {
+                    break candidate;
+                }
+            };
+
+            u_acc = mod_q.add(&u_acc, &mod_q.mul(&rho, &proof.u));
+
+            let rho_t = mod_q.mul(&rho, &proof.t);
+            let rho_t_r = match r_pub.scalar_mult(&rho_t) {
+                Ok(p) => p,
+                Err(_) => return false,
+            };
+            lhs = match lhs.add(&rho_t_r) {
+                Ok(p) => p,
+                Err(_) => return false,
+            };
+
+            let rho_alpha = match proof.alpha.scalar_mult(&rho) {
+                Ok(p) => p,
+                Err(_) => return false,
+            };
+            let rho_c_v = match v_pub.scalar_mult(&mod_q.mul(&rho, &c)) {
+                Ok(p) => p,
+                Err(_) => return false,
+            };
+            rhs = match rhs.add(&rho_alpha).and_then(|p| p.add(&rho_c_v)) {
+                Ok(p) => p,
+                Err(_) => return false,
+            };
+        }
+
+        let g_u = match ECPoint::scalar_base_mult(curve, &u_acc) {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
+        lhs = match lhs.add(&g_u) {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
+
+        lhs == rhs
     }
 }
 
-
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{crypto::ecpoint::ECPoint, tss::Secp256k1Curve};
-    use k256::Secp256k1;
+    use crate::crypto::ecpoint::ECCurve;
     use rand::thread_rng;
-    use elliptic_curve::group::Group;
 
-     // Helper to get curve order Q for K256
-     fn get_k256_q() -> BigInt {
-         let q_bytes = k256::Scalar::ORDER.to_be_bytes();
-         BigInt::from_bytes_be(num_bigint_dig::Sign::Plus, &q_bytes)
-     }
+    fn get_k256_q() -> BigInt {
+        let q_bytes = k256::Scalar::ORDER.to_be_bytes();
+        BigInt::from_bytes_be(Sign::Plus, &q_bytes)
+    }
 
     #[test]
     fn test_zkp_proof_verify() {
         let mut rng = thread_rng();
         let q = get_k256_q();
 
-        // Setup: secret x, public X = g^x
-        let x_priv = get_random_positive_int(&mut rng, &q).unwrap();
-        let x_pub = ECPoint::<Secp256k1>::scalar_base_mult(&x_priv);
+        let x_priv = get_random_positive_int(&mut rng, &q);
+        let x_pub = ECPoint::scalar_base_mult(ECCurve::Secp256k1, &x_priv).unwrap();
         let session = b"test_session_zkp";
 
-        // 1. Create proof
-        let proof = ZkProof::new(session, &x_priv, &x_pub, &mut rng).unwrap();
+        let proof = ZkProof::new(session, &x_priv, &x_pub, &q, &mut rng).unwrap();
 
-        // 2. Verify proof
-        assert!(proof.verify(session, &x_pub), "ZKProof verification failed");
+        assert!(proof.verify(session, &x_pub, &q), "ZKProof verification failed");
+        assert!(!proof.verify(b"wrong_session", &x_pub, &q), "ZKProof verification succeeded with wrong session");
 
-        // 3. Verify failure with wrong session
-        assert!(!proof.verify(b"wrong_session", &x_pub), "ZKProof verification succeeded with wrong session");
+        let x_priv_wrong = get_random_positive_int(&mut rng, &q);
+        let x_pub_wrong = ECPoint::scalar_base_mult(ECCurve::Secp256k1, &x_priv_wrong).unwrap();
+        assert!(!proof.verify(session, &x_pub_wrong, &q), "ZKProof verification succeeded with wrong X");
 
-        // 4. Verify failure with wrong public key X
-        let x_priv_wrong = get_random_positive_int(&mut rng, &q).unwrap();
-        let x_pub_wrong = ECPoint::<Secp256k1>::scalar_base_mult(&x_priv_wrong);
-        assert!(!proof.verify(session, &x_pub_wrong), "ZKProof verification succeeded with wrong X");
-
-        // 5. Verify failure with tampered proof `t`
         let mut tampered_proof_t = proof.clone();
-        tampered_proof_t.t += BigInt::one();
-        assert!(!tampered_proof_t.verify(session, &x_pub), "ZKProof verification succeeded with tampered t");
-
-         // 6. Verify failure with tampered proof `alpha`
-         let mut tampered_proof_alpha = proof.clone();
-         let random_scalar = get_random_positive_int(&mut rng, &q).unwrap();
-         let random_point = ECPoint::<Secp256k1>::scalar_base_mult(&random_scalar);
-         tampered_proof_alpha.alpha = tampered_proof_alpha.alpha.add(&random_point).unwrap();
-         assert!(!tampered_proof_alpha.verify(session, &x_pub), "ZKProof verification succeeded with tampered alpha");
+        tampered_proof_t.t += BigInt::from(1u32);
+        assert!(!tampered_proof_t.verify(session, &x_pub, &q), "ZKProof verification succeeded with tampered t");
+
+        let mut tampered_proof_alpha = proof.clone();
+        let random_scalar = get_random_positive_int(&mut rng, &q);
+        let random_point = ECPoint::scalar_base_mult(ECCurve::Secp256k1, &random_scalar).unwrap();
+        tampered_proof_alpha.alpha = tampered_proof_alpha.alpha.add(&random_point).unwrap();
+        assert!(!tampered_proof_alpha.verify(session, &x_pub, &q), "ZKProof verification succeeded with tampered alpha");
     }
 
     #[test]
     fn test_zkv_proof_verify() {
         let mut rng = thread_rng();
         let q = get_k256_q();
-        let g = ECPoint::<Secp256k1>::generator();
+        let g = ECPoint::generator(ECCurve::Secp256k1).unwrap();
 
-        // Setup: secrets s, l
-        let s = get_random_positive_int(&mut rng, &q).unwrap();
-        let l = get_random_positive_int(&mut rng, &q).unwrap();
+        let s = get_random_positive_int(&mut rng, &q);
+        let l = get_random_positive_int(&mut rng, &q);
 
-        // Public points R (random), V = R^s * g^l
-        let r_priv = get_random_positive_int(&mut rng, &q).unwrap();
-        let r_pub = ECPoint::<Secp256k1>::scalar_base_mult(&r_priv);
-        let rs = r_pub.scalar_mul(&s);
-        let gl = g.scalar_mul(&l);
+        let r_priv = get_random_positive_int(&mut rng, &q);
+        let r_pub = ECPoint::scalar_base_mult(ECCurve::Secp256k1, &r_priv).unwrap();
+        let rs = r_pub.scalar_mult(&s).unwrap();
+        let gl = g.scalar_mult(&l).unwrap();
         let v_pub = rs.add(&gl).unwrap();
 
         let session = b"test_session_zkv";
 
-        // 1. Create proof
-        let proof = ZkvProof::new(session, &s, &l, &v_pub, &r_pub, &mut rng).unwrap();
-
-        // 2. Verify proof
-        assert!(proof.verify(session, &v_pub, &r_pub), "ZKVProof verification failed");
+        let proof = ZkvProof::new(session, &s, &l, &v_pub, &r_pub, &q, &mut rng).unwrap();
 
-        // 3. Verify failure with wrong session
-        assert!(!proof.verify(b"wrong_session", &v_pub, &r_pub), "ZKVProof verification succeeded with wrong session");
+        assert!(proof.verify(session, &v_pub, &r_pub, &q), "ZKVProof verification failed");
+        assert!(!proof.verify(b"wrong_session", &v_pub, &r_pub, &q), "ZKVProof verification succeeded with wrong session");
 
-        // 4. Verify failure with wrong public key V
-        let s_wrong = get_random_positive_int(&mut rng, &q).unwrap();
-        let rs_wrong = r_pub.scalar_mul(&s_wrong);
+        let s_wrong = get_random_positive_int(&mut rng, &q);
+        let rs_wrong = r_pub.scalar_mult(&s_wrong).unwrap();
         let v_pub_wrong = rs_wrong.add(&gl).unwrap();
-        assert!(!proof.verify(session, &v_pub_wrong, &r_pub), "ZKVProof verification succeeded with wrong V");
+        assert!(!proof.verify(session, &v_pub_wrong, &r_pub, &q), "ZKVProof verification succeeded with wrong V");
 
-        // 5. Verify failure with wrong public key R
-        let r_priv_wrong = get_random_positive_int(&mut rng, &q).unwrap();
-        let r_pub_wrong = ECPoint::<Secp256k1>::scalar_base_mult(&r_priv_wrong);
-        assert!(!proof.verify(session, &v_pub, &r_pub_wrong), "ZKVProof verification succeeded with wrong R");
+        let r_priv_wrong = get_random_positive_int(&mut rng, &q);
+        let r_pub_wrong = ECPoint::scalar_base_mult(ECCurve::Secp256k1, &r_priv_wrong).unwrap();
+        assert!(!proof.verify(session, &v_pub, &r_pub_wrong, &q), "ZKVProof verification succeeded with wrong R");
 
-        // 6. Verify failure with tampered proof `t`
         let mut tampered_proof_t = proof.clone();
-        tampered_proof_t.t += BigInt::one();
-        assert!(!tampered_proof_t.verify(session, &v_pub, &r_pub), "ZKVProof verification succeeded with tampered t");
+        tampered_proof_t.t += BigInt::from(1u32);
+        assert!(!tampered_proof_t.verify(session, &v_pub, &r_pub, &q), "ZKVProof verification succeeded with tampered t");
 
-        // 7. Verify failure with tampered proof `u`
         let mut tampered_proof_u = proof.clone();
-        tampered_proof_u.u += BigInt::one();
-        assert!(!tampered_proof_u.verify(session, &v_pub, &r_pub), "ZKVProof verification succeeded with tampered u");
-
-         // 8. Verify failure with tampered proof `alpha`
-         let mut tampered_proof_alpha = proof.clone();
-         let random_scalar = get_random_positive_int(&mut rng, &q).unwrap();
-         let random_point = ECPoint::<Secp256k1>::scalar_base_mult(&random_scalar);
-         tampered_proof_alpha.alpha = tampered_proof_alpha.alpha.add(&random_point).unwrap();
- 
\ No newline at end of file
+        tampered_proof_u.u += BigInt::from(1u32);
+        assert!(!tampered_proof_u.verify(session, &v_pub, &r_pub, &q), "ZKVProof verification succeeded with tampered u");
+
+        let mut tampered_proof_alpha = proof.clone();
+        let random_scalar = get_random_positive_int(&mut rng, &q);
+        let random_point = ECPoint::scalar_base_mult(ECCurve::Secp256k1, &random_scalar).unwrap();
+        tampered_proof_alpha.alpha = tampered_proof_alpha.alpha.add(&random_point).unwrap();
+        assert!(!tampered_proof_alpha.verify(session, &v_pub, &r_pub, &q), "ZKVProof verification succeeded with tampered alpha");
+    }
+
+    #[test]
+    fn test_zkp_verify_batch_accepts_all_valid_proofs() {
+        let mut rng = thread_rng();
+        let q = get_k256_q();
+        let session = b"test_session_zkp_batch";
+
+        let mut proofs = Vec::new();
+        let mut pubs = Vec::new();
+        for _ in 0..4 {
+            let x_priv = get_random_positive_int(&mut rng, &q);
+            let x_pub = ECPoint::scalar_base_mult(ECCurve::Secp256k1, &x_priv).unwrap();
+            let proof = ZkProof::new(session, &x_priv, &x_pub, &q, &mut rng).unwrap();
+            proofs.push(proof);
+            pubs.push(x_pub);
+        }
+        let refs: Vec<(&ZkProof, &ECPoint)> = proofs.iter().zip(pubs.iter()).collect();
+
+        assert!(ZkProof::verify_batch(session, &refs, &q, &mut rng));
+    }
+
+    #[test]
+    fn test_zkp_verify_batch_rejects_one_bad_proof() {
+        let mut rng = thread_rng();
+        let q = get_k256_q();
+        let session = b"test_session_zkp_batch_bad";
+
+        let mut proofs = Vec::new();
+        let mut pubs = Vec::new();
+        for _ in 0..4 {
+            let x_priv = get_random_positive_int(&mut rng, &q);
+            let x_pub = ECPoint::scalar_base_mult(ECCurve::Secp256k1, &x_priv).unwrap();
+            let proof = ZkProof::new(session, &x_priv, &x_pub, &q, &mut rng).unwrap();
+            proofs.push(proof);
+            pubs.push(x_pub);
+        }
+        proofs[2].t += BigInt::from(1u32);
+        let refs: Vec<(&ZkProof, &ECPoint)> = proofs.iter().zip(pubs.iter()).collect();
+
+        assert!(!ZkProof::verify_batch(session, &refs, &q, &mut rng));
+    }
+
+    #[test]
+    fn test_zkv_verify_batch_accepts_all_valid_proofs() {
+        let mut rng = thread_rng();
+        let q = get_k256_q();
+        let g = ECPoint::generator(ECCurve::Secp256k1).unwrap();
+        let session = b"test_session_zkv_batch";
+
+        let mut proofs = Vec::new();
+        let mut vs = Vec::new();
+        let mut rs = Vec::new();
+        for _ in 0..4 {
+            let s = get_random_positive_int(&mut rng, &q);
+            let l = get_random_positive_int(&mut rng, &q);
+            let r_priv = get_random_positive_int(&mut rng, &q);
+            let r_pub = ECPoint::scalar_base_mult(ECCurve::Secp256k1, &r_priv).unwrap();
+            let v_pub = r_pub.scalar_mult(&s).unwrap().add(&g.scalar_mult(&l).unwrap()).unwrap();
+            let proof = ZkvProof::new(session, &s, &l, &v_pub, &r_pub, &q, &mut rng).unwrap();
+            proofs.push(proof);
+            vs.push(v_pub);
+            rs.push(r_pub);
+        }
+        let refs: Vec<(&ZkvProof, &ECPoint, &ECPoint)> =
+            proofs.iter().zip(vs.iter()).zip(rs.iter()).map(|((p, v), r)| (p, v, r)).collect();
+
+        assert!(ZkvProof::verify_batch(session, &refs, &q, &mut rng));
+    }
+
+    #[test]
+    fn test_zkv_verify_batch_rejects_one_bad_proof() {
+        let mut rng = thread_rng();
+        let q = get_k256_q();
+        let g = ECPoint::generator(ECCurve::Secp256k1).unwrap();
+        let session = b"test_session_zkv_batch_bad";
+
+        let mut proofs = Vec::new();
+        let mut vs = Vec::new();
+        let mut rs = Vec::new();
+        for _ in 0..4 {
+            let s = get_random_positive_int(&mut rng, &q);
+            let l = get_random_positive_int(&mut rng, &q);
+            let r_priv = get_random_positive_int(&mut rng, &q);
+            let r_pub = ECPoint::scalar_base_mult(ECCurve::Secp256k1, &r_priv).unwrap();
+            let v_pub = r_pub.scalar_mult(&s).unwrap().add(&g.scalar_mult(&l).unwrap()).unwrap();
+            let proof = ZkvProof::new(session, &s, &l, &v_pub, &r_pub, &q, &mut rng).unwrap();
+            proofs.push(proof);
+            vs.push(v_pub);
+            rs.push(r_pub);
+        }
+        proofs[1].u += BigInt::from(1u32);
+        let refs: Vec<(&ZkvProof, &ECPoint, &ECPoint)> =
+            proofs.iter().zip(vs.iter()).zip(rs.iter()).map(|((p, v), r)| (p, v, r)).collect();
+
+        assert!(!ZkvProof::verify_batch(session, &refs, &q, &mut rng));
+    }
+}
@@ -0,0 +1,118 @@
+// Schnorr proof of possession for a VSS dealer's constant-term commitment
+// `C_j = g^{a_j0}`. Without this, aggregated-key DKG is open to rogue-key
+// cancellation: a party that observes every other party's commitment before
+// publishing its own could pick `C_j` as (say) `g^{a_j0} = Y / Π_{k != j}
+// C_k0` for whatever group key `Y` it wants, without knowing `a_j0` at all.
+// Requiring every dealer to prove knowledge of its own `a_j0` before `C_j` is
+// folded into the combined commitment closes that window.
+
+use num_bigint::{BigInt, Sign};
+use num_integer::Integer;
+use rand::{CryptoRng, RngCore};
+
+use crate::common::{hash::sha512_256, random::get_random_positive_int};
+use crate::crypto::ecpoint::ECPoint;
+
+/// A Schnorr proof of knowledge of the discrete log of a VSS dealer's
+/// constant-term commitment: `R = g^r`, `z = r + c·a_j0 mod q` where `c =
+/// H(context ‖ C_j ‖ R)`. `R`'s curve (`r_point.curve`) is always the same
+/// as `c_j`'s, since it's derived from `c_j`'s own base point.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SchnorrPop {
+    pub r_point: ECPoint,
+    pub z: BigInt,
+}
+
+fn pop_challenge(context: &[u8], c_j: &ECPoint, r_point: &ECPoint) -> BigInt {
+    let digest = sha512_256(&[
+        context,
+        &c_j.x.to_bytes_be().1,
+        &c_j.y.to_bytes_be().1,
+        &r_point.x.to_bytes_be().1,
+        &r_point.y.to_bytes_be().1,
+    ]);
+    BigInt::from_bytes_be(Sign::Plus, &digest)
+}
+
+impl SchnorrPop {
+    /// Proves knowledge of `a_j0` such that `c_j = g^{a_j0}`: samples random
+    /// `r`, sends `R = g^r` and `z = r + c·a_j0 mod q`.
+    pub fn new<R: RngCore + CryptoRng>(
+        context: &[u8],
+        a_j0: &BigInt,
+        c_j: &ECPoint,
+        curve_order: &BigInt,
+        rng: &mut R,
+    ) -> Result<Self, String> {
+        let r = get_random_positive_int(rng, curve_order);
+        let r_point = ECPoint::scalar_base_mult(c_j.curve, &r)?;
+        let c = pop_challenge(context, c_j, &r_point).mod_floor(curve_order);
+        let z = (&r + &c * a_j0).mod_floor(curve_order);
+        Ok(SchnorrPop { r_point, z })
+    }
+
+    /// Verifies `g^z ?= R · C_j^c` where `c = H(context ‖ C_j ‖ R)`.
+    pub fn verify(&self, context: &[u8], c_j: &ECPoint, curve_order: &BigInt) -> bool {
+        let c = pop_challenge(context, c_j, &self.r_point).mod_floor(curve_order);
+        let lhs = match ECPoint::scalar_base_mult(c_j.curve, &self.z) {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
+        let c_j_to_c = match c_j.scalar_mult(&c) {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
+        let rhs = match self.r_point.add(&c_j_to_c) {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
+        lhs == rhs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::ecpoint::ECCurve;
+    use num_traits::One;
+    use rand::thread_rng;
+
+    fn get_k256_q() -> BigInt {
+        let q_bytes = k256::Scalar::ORDER.to_be_bytes();
+        BigInt::from_bytes_be(Sign::Plus, &q_bytes)
+    }
+
+    #[test]
+    fn test_schnorr_pop_prove_verify_round_trip() {
+        let mut rng = thread_rng();
+        let q = get_k256_q();
+        let a_j0 = BigInt::from(424242u64);
+        let c_j = ECPoint::scalar_base_mult(ECCurve::Secp256k1, &a_j0).unwrap();
+
+        let pop = SchnorrPop::new(b"context", &a_j0, &c_j, &q, &mut rng).unwrap();
+        assert!(pop.verify(b"context", &c_j, &q));
+    }
+
+    #[test]
+    fn test_schnorr_pop_rejects_wrong_context() {
+        let mut rng = thread_rng();
+        let q = get_k256_q();
+        let a_j0 = BigInt::from(424242u64);
+        let c_j = ECPoint::scalar_base_mult(ECCurve::Secp256k1, &a_j0).unwrap();
+
+        let pop = SchnorrPop::new(b"context-a", &a_j0, &c_j, &q, &mut rng).unwrap();
+        assert!(!pop.verify(b"context-b", &c_j, &q));
+    }
+
+    #[test]
+    fn test_schnorr_pop_rejects_wrong_commitment() {
+        let mut rng = thread_rng();
+        let q = get_k256_q();
+        let a_j0 = BigInt::from(424242u64);
+        let c_j = ECPoint::scalar_base_mult(ECCurve::Secp256k1, &a_j0).unwrap();
+        let wrong_c_j = ECPoint::scalar_base_mult(ECCurve::Secp256k1, &BigInt::one()).unwrap();
+
+        let pop = SchnorrPop::new(b"context", &a_j0, &c_j, &q, &mut rng).unwrap();
+        assert!(!pop.verify(b"context", &wrong_c_j, &q));
+    }
+}
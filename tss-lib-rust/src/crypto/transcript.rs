@@ -0,0 +1,133 @@
+// Shared Fiat-Shamir transcript for this crate's sigma-protocol proofs.
+//
+// `ProofFac` used to flatten its public inputs and commitments into one
+// `sha512_256i` call, which binds the challenge to the *set* of values but
+// is fragile to field-reordering/concatenation-ambiguity attacks (swapping
+// two same-length fields, or a value that happens to equal the
+// concatenation of two others, can otherwise produce the same digest).
+// `ProofTranscript` instead absorbs every value under its own label, in a
+// fixed order, via a STROBE-backed `merlin::Transcript`, so the squeezed
+// challenge only matches when both sides agree on every value *and* its
+// position. Other GG18/CGGMP proofs in this crate are expected to migrate
+// onto this instead of hand-rolling their own flattened hash.
+
+use merlin::Transcript;
+use num_bigint::{BigInt, Sign};
+use crate::common::hash::rejection_sample_unbiased;
+
+pub struct ProofTranscript(Transcript);
+
+impl ProofTranscript {
+    /// Starts a transcript under the fixed protocol label `protocol_label`
+    /// (e.g. `b"ProofFac"`) and binds `session` as a domain separator, so the
+    /// same public inputs replayed under a different session can never
+    /// reproduce the same challenge.
+    pub fn new(protocol_label: &'static [u8], session: &[u8]) -> Self {
+        let mut transcript = Transcript::new(protocol_label);
+        transcript.append_message(b"session", session);
+        ProofTranscript(transcript)
+    }
+
+    /// Absorbs `value`, big-endian encoded, under `label`.
+    pub fn append_bigint(&mut self, label: &'static [u8], value: &BigInt) {
+        self.0.append_message(label, &value.to_bytes_be().1);
+    }
+
+    /// Absorbs raw bytes under `label`, for domain-separation values that
+    /// aren't naturally a `BigInt` -- party indices, session tags -- or that
+    /// come from a different big-integer crate than this module's `BigInt`
+    /// (callers extract the magnitude bytes themselves, e.g. via that
+    /// crate's own `to_bytes_be()`).
+    pub fn append_bytes(&mut self, label: &'static [u8], value: &[u8]) {
+        self.0.append_message(label, value);
+    }
+
+    /// Squeezes a challenge under `label`, unbiasedly rejection-sampled
+    /// modulo `q` (see `rejection_sample_unbiased`): a squeezed block that
+    /// falls in the partial interval left over by `q` not dividing
+    /// `2^bits` is rejected and a fresh block squeezed in its place, rather
+    /// than wrapped back down and over-represented.
+    pub fn challenge_bigint(&mut self, label: &'static [u8], q: &BigInt) -> BigInt {
+        let byte_len = (q.bits() as usize) / 8 + 1;
+        let bits = (byte_len * 8) as u32;
+        let transcript = &mut self.0;
+        let mut attempt: u32 = 0;
+        rejection_sample_unbiased(q, bits, move || {
+            if attempt > 0 {
+                transcript.append_message(b"resample", &attempt.to_le_bytes());
+            }
+            attempt += 1;
+            let mut bytes = vec![0u8; byte_len];
+            transcript.challenge_bytes(label, &mut bytes);
+            BigInt::from_bytes_be(Sign::Plus, &bytes)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::ToBigInt;
+
+    #[test]
+    fn test_identical_transcripts_produce_identical_challenges() {
+        let q = 1_000_003.to_bigint().unwrap();
+
+        let mut t1 = ProofTranscript::new(b"Test", b"session");
+        t1.append_bigint(b"x", &7.to_bigint().unwrap());
+        let e1 = t1.challenge_bigint(b"e", &q);
+
+        let mut t2 = ProofTranscript::new(b"Test", b"session");
+        t2.append_bigint(b"x", &7.to_bigint().unwrap());
+        let e2 = t2.challenge_bigint(b"e", &q);
+
+        assert_eq!(e1, e2);
+    }
+
+    #[test]
+    fn test_reordering_appended_values_changes_the_challenge() {
+        let q = 1_000_003.to_bigint().unwrap();
+
+        let mut t1 = ProofTranscript::new(b"Test", b"session");
+        t1.append_bigint(b"x", &7.to_bigint().unwrap());
+        t1.append_bigint(b"y", &9.to_bigint().unwrap());
+        let e1 = t1.challenge_bigint(b"e", &q);
+
+        let mut t2 = ProofTranscript::new(b"Test", b"session");
+        t2.append_bigint(b"y", &9.to_bigint().unwrap());
+        t2.append_bigint(b"x", &7.to_bigint().unwrap());
+        let e2 = t2.challenge_bigint(b"e", &q);
+
+        assert_ne!(e1, e2);
+    }
+
+    #[test]
+    fn test_append_bytes_participates_like_append_bigint() {
+        let q = 1_000_003.to_bigint().unwrap();
+
+        let mut t1 = ProofTranscript::new(b"Test", b"session");
+        t1.append_bytes(b"x", &7u32.to_be_bytes());
+        let e1 = t1.challenge_bigint(b"e", &q);
+
+        let mut t2 = ProofTranscript::new(b"Test", b"session");
+        t2.append_bytes(b"x", &9u32.to_be_bytes());
+        let e2 = t2.challenge_bigint(b"e", &q);
+
+        assert_ne!(e1, e2);
+    }
+
+    #[test]
+    fn test_different_sessions_produce_different_challenges() {
+        let q = 1_000_003.to_bigint().unwrap();
+
+        let mut t1 = ProofTranscript::new(b"Test", b"session-a");
+        t1.append_bigint(b"x", &7.to_bigint().unwrap());
+        let e1 = t1.challenge_bigint(b"e", &q);
+
+        let mut t2 = ProofTranscript::new(b"Test", b"session-b");
+        t2.append_bigint(b"x", &7.to_bigint().unwrap());
+        let e2 = t2.challenge_bigint(b"e", &q);
+
+        assert_ne!(e1, e2);
+    }
+}
@@ -6,9 +6,11 @@
 
 // Translation of tss-lib-go/crypto/utils.go
 
-use crate::common::random::get_random_generator_of_quadratic_residue;
+use crate::common::hash::sha512_256;
 use num_bigint_dig::BigInt;
+use num_integer::Integer;
 use num_prime::{PrimalityTestConfig, nt_funcs};
+use num_traits::{One, Zero};
 use rand::{CryptoRng, RngCore};
 use thiserror::Error;
 
@@ -22,14 +24,107 @@ pub enum CryptoError {
     GeneratorError(String), // Or more specific error type if available
 }
 
-/// Generates N-tilde, h1, and h2 for Paillier based on two safe primes (p, q).
-/// N-tilde = p * q
-/// h1, h2 are random generators of the quadratic residues mod N-tilde.
-/// Requires p and q to be probable primes.
+/// Random positive integer strictly less than `bound`, rejection-sampled from
+/// the byte width of `bound` so the output isn't biased toward small values.
+fn random_below<R: CryptoRng + RngCore>(rng: &mut R, bound: &BigInt) -> BigInt {
+    let bits = bound.bits();
+    let bytes = ((bits + 7) / 8).max(1);
+    loop {
+        let mut buf = vec![0u8; bytes as usize];
+        rng.fill_bytes(&mut buf);
+        let candidate = BigInt::from_bytes_be(num_bigint_dig::Sign::Plus, &buf);
+        if &candidate < bound {
+            return candidate;
+        }
+    }
+}
+
+/// A Fiat-Shamir-batched Schnorr proof ("PiPrm") that `h1` and `h2` generate
+/// the same multiplicative subgroup of `Z*_n_tilde`, i.e. that whoever
+/// produced them knows `lambda` with `h2 = h1^lambda mod n_tilde`. Without
+/// this, a dealer could pick `h1, h2` with a known, hidden discrete-log
+/// relation and later forge the range/Paillier proofs that consume them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NTildeProof {
+    a: Vec<BigInt>,
+    z: Vec<BigInt>,
+}
+
+const N_TILDE_PROOF_ITERATIONS: usize = 128;
+
+fn n_tilde_challenge(n_tilde: &BigInt, h1: &BigInt, h2: &BigInt, commitments: &[BigInt]) -> Vec<bool> {
+    let mut inputs: Vec<Vec<u8>> = Vec::with_capacity(3 + commitments.len());
+    inputs.push(n_tilde.to_bytes_be().1);
+    inputs.push(h1.to_bytes_be().1);
+    inputs.push(h2.to_bytes_be().1);
+    for commitment in commitments {
+        inputs.push(commitment.to_bytes_be().1);
+    }
+    let digest = sha512_256(&inputs.iter().map(|v| v.as_slice()).collect::<Vec<_>>());
+    (0..N_TILDE_PROOF_ITERATIONS)
+        .map(|k| {
+            let byte = k / 8;
+            let bit = k % 8;
+            byte < digest.len() && (digest[digest.len() - 1 - byte] >> bit) & 1 == 1
+        })
+        .collect()
+}
+
+impl NTildeProof {
+    /// Proves knowledge of `lambda` with `h2 = h1^lambda mod n_tilde`, over
+    /// the subgroup of order `phi_n_tilde` (`p' * q'`, the product of the
+    /// safe primes' Sophie Germain factors).
+    fn prove<R: CryptoRng + RngCore>(
+        rng: &mut R,
+        n_tilde: &BigInt,
+        h1: &BigInt,
+        h2: &BigInt,
+        lambda: &BigInt,
+        phi_n_tilde: &BigInt,
+    ) -> Self {
+        let a: Vec<BigInt> = (0..N_TILDE_PROOF_ITERATIONS).map(|_| random_below(rng, phi_n_tilde)).collect();
+        let commitments: Vec<BigInt> = a.iter().map(|a_k| h1.modpow(a_k, n_tilde)).collect();
+        let challenge = n_tilde_challenge(n_tilde, h1, h2, &commitments);
+
+        let z: Vec<BigInt> = a
+            .iter()
+            .zip(challenge.iter())
+            .map(|(a_k, &e_k)| {
+                if e_k { (a_k + lambda).mod_floor(phi_n_tilde) } else { a_k.mod_floor(phi_n_tilde) }
+            })
+            .collect();
+
+        NTildeProof { a: commitments, z }
+    }
+
+    /// Verifies `h1^z_k == a_k * h2^e_k mod n_tilde` for every iteration `k`,
+    /// re-deriving the same Fiat-Shamir challenge bits the prover used.
+    pub fn verify(&self, n_tilde: &BigInt, h1: &BigInt, h2: &BigInt) -> bool {
+        if self.a.len() != N_TILDE_PROOF_ITERATIONS || self.z.len() != N_TILDE_PROOF_ITERATIONS {
+            return false;
+        }
+        let challenge = n_tilde_challenge(n_tilde, h1, h2, &self.a);
+        for k in 0..N_TILDE_PROOF_ITERATIONS {
+            let lhs = h1.modpow(&self.z[k], n_tilde);
+            let e_k = if challenge[k] { BigInt::one() } else { BigInt::zero() };
+            let rhs = (&self.a[k] * h2.modpow(&e_k, n_tilde)).mod_floor(n_tilde);
+            if lhs != rhs {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Generates N-tilde, h1, and h2 for Paillier based on two safe primes (p, q),
+/// along with an `NTildeProof` that h1 and h2 generate the same subgroup of
+/// `Z*_n_tilde` (see `NTildeProof`). Verify it with `NTildeProof::verify`
+/// before trusting `h1`/`h2` in any downstream range or Paillier proof.
+/// N-tilde = p * q. Requires p and q to be probable safe primes.
 pub fn generate_n_tilde_i<R: CryptoRng + RngCore>(
     rng: &mut R,
     safe_primes: [&BigInt; 2],
-) -> Result<(BigInt, BigInt, BigInt), CryptoError> {
+) -> Result<(BigInt, BigInt, BigInt, NTildeProof), CryptoError> {
     let p = safe_primes[0];
     let q = safe_primes[1];
 
@@ -41,14 +136,28 @@ pub fn generate_n_tilde_i<R: CryptoRng + RngCore>(
     }
 
     let n_tilde = p * q;
-
-    // Generate h1 and h2 as random generators of QR mod n_tilde
-    let h1 = get_random_generator_of_quadratic_residue(rng, &n_tilde)
-        .ok_or_else(|| CryptoError::GeneratorError("Failed to generate h1".to_string()))?;
-    let h2 = get_random_generator_of_quadratic_residue(rng, &n_tilde)
-        .ok_or_else(|| CryptoError::GeneratorError("Failed to generate h2".to_string()))?;
-
-    Ok((n_tilde, h1, h2))
+    // p, q are safe primes (p = 2p'+1, q = 2q'+1): their Sophie Germain
+    // factors give the order of the quadratic-residue subgroup mod n_tilde.
+    let p_prime = (p - BigInt::one()) / BigInt::from(2u32);
+    let q_prime = (q - BigInt::one()) / BigInt::from(2u32);
+    let phi_n_tilde = &p_prime * &q_prime;
+
+    // h1 is a random generator of the quadratic residues mod n_tilde; h2 is
+    // derived from it via a known exponent lambda, so the two are provably
+    // related instead of independently (and unverifiably) sampled.
+    let f1 = random_below(rng, &n_tilde);
+    let h1 = (&f1 * &f1).mod_floor(&n_tilde);
+    let lambda = loop {
+        let candidate = random_below(rng, &phi_n_tilde);
+        if !candidate.is_zero() {
+            break candidate;
+        }
+    };
+    let h2 = h1.modpow(&lambda, &n_tilde);
+
+    let proof = NTildeProof::prove(rng, &n_tilde, &h1, &h2, &lambda, &phi_n_tilde);
+
+    Ok((n_tilde, h1, h2, proof))
 }
 
 
@@ -77,11 +186,11 @@ mod tests {
             let p = safe_primes_pair[0].safe_prime(); // p = 2p'+1
             let q = safe_primes_pair[1].safe_prime(); // q = 2q'+1
 
-            // 2. Generate N-tilde, h1, h2
+            // 2. Generate N-tilde, h1, h2, and the companion proof
             let result = generate_n_tilde_i(&mut *rng_arc.lock().await, [&p, &q]);
 
             assert!(result.is_ok());
-            let (n_tilde, h1, h2) = result.unwrap();
+            let (n_tilde, h1, h2, proof) = result.unwrap();
 
             println!("p: {}", p);
             println!("q: {}", q);
@@ -99,6 +208,9 @@ mod tests {
             use jacobi::Symbol;
             assert_eq!(Symbol::new(&h1, &n_tilde), Symbol::One);
             assert_eq!(Symbol::new(&h2, &n_tilde), Symbol::One);
+
+            // The proof that h1, h2 generate the same subgroup must verify.
+            assert!(proof.verify(&n_tilde, &h1, &h2));
         });
     }
 
@@ -114,4 +226,20 @@ mod tests {
         let result2 = generate_n_tilde_i(&mut rng, [&non_prime, &prime]);
         assert!(matches!(result2, Err(CryptoError::NonPrimeInput)));
     }
+
+    #[test]
+    fn test_n_tilde_proof_rejects_wrong_h2() {
+        let mut rng = thread_rng();
+        // A tiny hand-picked safe-prime pair, just large enough to exercise
+        // the proof machinery without paying for real safe-prime generation.
+        let p = BigInt::from(23u64); // p' = 11
+        let q = BigInt::from(47u64); // q' = 23
+        let (n_tilde, h1, h2, proof) = generate_n_tilde_i(&mut rng, [&p, &q]).unwrap();
+
+        assert!(proof.verify(&n_tilde, &h1, &h2));
+
+        // Swapping in an unrelated h2 must fail verification.
+        let forged_h2 = (&h2 + BigInt::one()).mod_floor(&n_tilde);
+        assert!(!proof.verify(&n_tilde, &h1, &forged_h2));
+    }
 } 
\ No newline at end of file
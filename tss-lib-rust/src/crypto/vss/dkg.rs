@@ -0,0 +1,208 @@
+// Copyright © 2019 Binance
+//
+// This file is part of Binance. The full Binance copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+// Aggregated multi-dealer DKG (SimplPedPoP/Pedersen-DKG pattern) built on top
+// of `feldman_vss`. A single `feldman_vss::create` call lets one dealer deal
+// a secret it chose itself, which is fine for resharing but not for
+// generating a fresh key that no single party should know. Here, every
+// participant `i` independently deals its own random secret `s_i` via
+// `feldman_vss::create`, and the parties combine the n dealings into one
+// joint secret `sum_i s_i` that nobody ever assembles in the clear: each
+// party only ever sums the *shares* it was privately sent, never the
+// secrets.
+
+use crate::{
+    common::int::ModInt,
+    crypto::{
+        ecpoint::ECPoint,
+        vss::feldman_vss::{Share, VerificationVector, VssError},
+    },
+};
+
+use num_bigint::BigInt;
+use num_traits::Zero;
+
+/// The outcome of a completed aggregated DKG for one participant: its final
+/// share of the joint secret (its point on the summed polynomial, combined
+/// by Lagrange interpolation like any Feldman share), the aggregated (qualified-dealer)
+/// verification vector, and the resulting group public key.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DkgResult {
+    pub share: Share,
+    pub group_vv: VerificationVector,
+    pub group_public_key: ECPoint,
+}
+
+/// Combines verification vectors from the qualified dealer set component-wise
+/// (`V = Σ_i V_i`), so `V_j = g^(Σ_i a_i,j)` is the verification vector for
+/// the joint polynomial `Σ_i f_i(z)`. All inputs must share the same degree.
+pub fn aggregate_verification_vectors(
+    vvs: &[VerificationVector],
+) -> Result<VerificationVector, VssError> {
+    let (first, rest) = vvs
+        .split_first()
+        .ok_or_else(|| VssError::InvalidParameters("no verification vectors to aggregate".to_string()))?;
+
+    let degree = first.vector.len();
+    let mut combined = first.vector.clone();
+    for vv in rest {
+        if vv.vector.len() != degree {
+            return Err(VssError::InvalidParameters(
+                "verification vectors have mismatched degree".to_string(),
+            ));
+        }
+        for (acc, point) in combined.iter_mut().zip(vv.vector.iter()) {
+            *acc = acc.add(point).map_err(VssError::PointError)?;
+        }
+    }
+
+    Ok(VerificationVector { vector: combined })
+}
+
+/// Sums the shares one party privately received from every qualified dealer
+/// into its final aggregate share `σ_j = Σ_i f_i(id_j)`. Every input share
+/// must be for the same party (same `id`) and degree (same `threshold`).
+pub fn combine_shares(q: &BigInt, shares: &[Share]) -> Result<Share, VssError> {
+    let (first, rest) = shares
+        .split_first()
+        .ok_or_else(|| VssError::InvalidParameters("no shares to combine".to_string()))?;
+
+    for share in rest {
+        if share.id != first.id || share.threshold != first.threshold {
+            return Err(VssError::InvalidParameters(
+                "shares being combined must be for the same party and degree".to_string(),
+            ));
+        }
+    }
+
+    let mod_q = ModInt::new(q.clone());
+    let mut combined_value = BigInt::zero();
+    for share in shares {
+        combined_value = mod_q.add(&combined_value, &share.share);
+    }
+
+    Ok(Share { threshold: first.threshold, id: first.id.clone(), share: combined_value })
+}
+
+/// Runs one participant's side of the complaint/verification phase and
+/// combines the result: `contributions[i]` is the `i`-th dealer's broadcast
+/// verification vector paired with the share that dealer privately sent this
+/// party. Each contribution is checked with `Share::verify`; dealers that
+/// fail are excluded from the qualified set `Q` and reported back
+/// (index-aligned with `contributions`) so the caller can gossip the same
+/// complaints to the rest of the parties. The final share and group public
+/// key are derived solely from the qualified dealers in `Q`.
+pub fn dkg(
+    q: &BigInt,
+    contributions: &[(VerificationVector, Share)],
+) -> Result<(DkgResult, Vec<(usize, VssError)>), VssError> {
+    let mut qualified_vvs = Vec::with_capacity(contributions.len());
+    let mut qualified_shares = Vec::with_capacity(contributions.len());
+    let mut disqualified = Vec::new();
+
+    for (dealer_idx, (vv, share)) in contributions.iter().enumerate() {
+        if share.verify(q, vv) {
+            qualified_vvs.push(vv.clone());
+            qualified_shares.push(share.clone());
+        } else {
+            disqualified.push((dealer_idx, VssError::ShareVerificationError { id: share.id.clone() }));
+        }
+    }
+
+    if qualified_shares.is_empty() {
+        return Err(VssError::InvalidParameters("no dealer contribution passed verification".to_string()));
+    }
+
+    let share = combine_shares(q, &qualified_shares)?;
+    let group_vv = aggregate_verification_vectors(&qualified_vvs)?;
+    let group_public_key = group_vv.vector[0].clone();
+
+    Ok((DkgResult { share, group_vv, group_public_key }, disqualified))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::ecpoint::ECCurve;
+    use crate::crypto::vss::feldman_vss::create;
+    use num_bigint::Sign;
+    use num_traits::One;
+    use rand::thread_rng;
+
+    fn get_k256_q() -> BigInt {
+        let q_bytes = k256::Scalar::ORDER.to_be_bytes();
+        BigInt::from_bytes_be(Sign::Plus, &q_bytes)
+    }
+
+    #[test]
+    fn test_dkg_combines_honest_dealers_into_joint_secret() {
+        let mut rng = thread_rng();
+        let q = get_k256_q();
+        let threshold = 1;
+        let indexes = vec![BigInt::one(), BigInt::from(2u64), BigInt::from(3u64)];
+        let secrets = [BigInt::from(111u64), BigInt::from(222u64), BigInt::from(333u64)];
+
+        // Every dealer deals its own secret independently.
+        let dealings: Vec<(VerificationVector, Vec<Share>)> = secrets
+            .iter()
+            .map(|s| create(ECCurve::Secp256k1, &q, threshold, s, &indexes, &mut rng).unwrap())
+            .collect();
+
+        // Each party runs dkg() over the contributions addressed to it.
+        let results: Vec<DkgResult> = (0..indexes.len())
+            .map(|party_idx| {
+                let contributions: Vec<(VerificationVector, Share)> = dealings
+                    .iter()
+                    .map(|(vv, shares)| (vv.clone(), shares[party_idx].clone()))
+                    .collect();
+                let (result, disqualified) = dkg(&q, &contributions).unwrap();
+                assert!(disqualified.is_empty());
+                result
+            })
+            .collect();
+
+        // Every party agrees on the same group public key.
+        for result in &results[1..] {
+            assert_eq!(result.group_public_key, results[0].group_public_key);
+        }
+
+        let joint_secret: BigInt = secrets.iter().fold(BigInt::zero(), |acc, s| acc + s) % &q;
+        let expected_public_key = ECPoint::scalar_base_mult(ECCurve::Secp256k1, &joint_secret).unwrap();
+        assert_eq!(results[0].group_public_key, expected_public_key);
+
+        let shares: Vec<Share> = results.iter().map(|r| r.share.clone()).collect();
+        let reconstructed = crate::crypto::vss::feldman_vss::reconstruct_secret(&q, &shares[0..=threshold]).unwrap();
+        assert_eq!(reconstructed, joint_secret);
+    }
+
+    #[test]
+    fn test_dkg_disqualifies_dealer_with_tampered_share() {
+        let mut rng = thread_rng();
+        let q = get_k256_q();
+        let threshold = 1;
+        let indexes = vec![BigInt::one(), BigInt::from(2u64)];
+
+        let (good_vv, good_shares) = create(ECCurve::Secp256k1, &q, threshold, &BigInt::from(7u64), &indexes, &mut rng).unwrap();
+        let (bad_vv, mut bad_shares) = create(ECCurve::Secp256k1, &q, threshold, &BigInt::from(9u64), &indexes, &mut rng).unwrap();
+        bad_shares[0].share += BigInt::one();
+
+        let contributions = vec![(good_vv, good_shares[0].clone()), (bad_vv, bad_shares[0].clone())];
+        let (result, disqualified) = dkg(&q, &contributions).unwrap();
+
+        assert_eq!(disqualified.len(), 1);
+        assert_eq!(disqualified[0].0, 1);
+        assert!(matches!(disqualified[0].1, VssError::ShareVerificationError { .. }));
+        assert_eq!(result.share.share, good_shares[0].share);
+    }
+
+    #[test]
+    fn test_combine_shares_rejects_mismatched_party() {
+        let q = get_k256_q();
+        let a = Share { threshold: 1, id: BigInt::one(), share: BigInt::from(5u64) };
+        let b = Share { threshold: 1, id: BigInt::from(2u64), share: BigInt::from(7u64) };
+        assert!(matches!(combine_shares(&q, &[a, b]), Err(VssError::InvalidParameters(_))));
+    }
+}
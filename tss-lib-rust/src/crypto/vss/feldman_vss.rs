@@ -14,16 +14,14 @@ use crate::{
         int::ModInt,
         random::get_random_positive_int,
     },
-    crypto::ecpoint::{ECPoint, PointError}, // Assuming generic ECPoint
-    tss::Curve, // Assuming trait for curve operations & params
+    crypto::ecpoint::{ECCurve, ECPoint},
+    crypto::msm::msm,
 };
 
-use elliptic_curve::CurveArithmetic;
-use elliptic_curve::scalar::Scalar;
-use num_bigint_dig::{{BigInt, Sign}};
-use num_traits::{{Zero, One}};
-use rand::{{CryptoRng, RngCore}};
-use serde::{{Deserialize, Serialize}};
+use num_bigint::{BigInt, Sign};
+use num_traits::{Zero, One};
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use thiserror::Error;
 use log::warn;
@@ -45,12 +43,10 @@ pub enum VssError {
     ReconstructionError(String),
     #[error("point operation failed: {0}")]
     PointError(String),
-}
-
-impl From<PointError> for VssError {
-    fn from(err: PointError) -> Self {
-        VssError::PointError(err.to_string())
-    }
+    #[error("invalid hiding generator h: {0}")]
+    InvalidGenerator(String),
+    #[error("refresh/redistribution sub-share verification failed for share id {id}")]
+    RefreshVerificationError { id: BigInt },
 }
 
 /// Represents a VSS Share σᵢ for a party Pᵢ.
@@ -64,11 +60,21 @@ pub struct Share {
 }
 
 /// Represents the public verification vector V = [v₀, v₁, ..., vₜ] where vᵢ = g^aᵢ.
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct VerificationVector<C: Curve + CurveArithmetic> {
-     // Need to handle ECPoint serialization carefully
-     #[serde(bound(serialize = "ECPoint<C>: Serialize", deserialize = "ECPoint<C>: Deserialize<'de>"))]
-    pub vector: Vec<ECPoint<C>>,
+/// Every point's curve is carried on the point itself (see `crypto::ecpoint::ECPoint`);
+/// `curve()` reads it off `vector[0]` and every method here checks the rest agree.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VerificationVector {
+    pub vector: Vec<ECPoint>,
+}
+
+impl VerificationVector {
+    /// The curve every point in this vector is on, taken from `vector[0]`.
+    fn curve(&self) -> Result<ECCurve, VssError> {
+        self.vector
+            .first()
+            .map(|p| p.curve)
+            .ok_or_else(|| VssError::InvalidParameters("verification vector is empty".to_string()))
+    }
 }
 
 /// Convenience type for a slice of Shares.
@@ -76,9 +82,7 @@ pub type Shares<'a> = &'a [Share];
 
 /// Checks share IDs (indexes) for duplicates or zero values modulo the curve order `q`.
 /// Returns the original indexes if valid.
-pub fn check_indexes<
-    C: Curve + CurveArithmetic
->(q: &BigInt, indexes: &[BigInt]) -> Result<(), VssError> {
+pub fn check_indexes(q: &BigInt, indexes: &[BigInt]) -> Result<(), VssError> {
     if indexes.is_empty() {
          // Or should this be allowed?
          return Err(VssError::InvalidParameters("Indexes slice cannot be empty".to_string()));
@@ -106,43 +110,35 @@ pub fn check_indexes<
 /// Generates shares of the `secret` for parties identified by `indexes`,
 /// with a given `threshold`.
 /// Returns the verification vector `V` and the list of shares `σᵢ`.
-pub fn create<
-    C: Curve + CurveArithmetic,
-    R: CryptoRng + RngCore,
->(
+pub fn create<R: CryptoRng + RngCore>(
+    curve: ECCurve,
     q: &BigInt, // Curve order
     threshold: usize,
     secret: &BigInt,
     indexes: &[BigInt],
     rng: &mut R,
-) -> Result<(VerificationVector<C>, Vec<Share>), VssError>
-where
-     // Bounds needed for ECPoint operations
-     ECPoint<C>: Clone + PartialEq + Serialize + for<'de> Deserialize<'de>,
-     // Assuming ECPoint::scalar_base_mult exists and takes BigInt
-     // Assuming ECPoint implements Add and ScalarMult traits or methods
-{
+) -> Result<(VerificationVector, Vec<Share>), VssError> {
     if threshold < 1 {
         return Err(VssError::InvalidParameters("Threshold cannot be less than 1".to_string()));
     }
     if indexes.len() < threshold + 1 {
         return Err(VssError::NumSharesBelowThreshold(threshold + 1, indexes.len()));
     }
-    check_indexes::<C>(q, indexes)?;
+    check_indexes(q, indexes)?;
 
     // 1. Sample polynomial f(z) = a₀ + a₁z + ... + aₜzᵗ where a₀ = secret
     let poly = sample_polynomial(q, threshold, secret, rng);
 
     // 2. Compute verification vector V = [g^a₀, g^a₁, ..., g^aₜ]
-    let v_vec: Vec<ECPoint<C>> = poly.iter()
-        .map(|a_i| ECPoint::<C>::scalar_base_mult(a_i))
-        .collect();
+    let v_vec: Vec<ECPoint> = poly.iter()
+        .map(|a_i| ECPoint::scalar_base_mult(curve, a_i).map_err(VssError::PointError))
+        .collect::<Result<_, _>>()?;
     let verification_vector = VerificationVector { vector: v_vec };
 
     // 3. Compute shares σᵢ = f(idᵢ) for each party i
     let shares_vec: Vec<Share> = indexes.iter()
         .map(|id| {
-            let share_val = evaluate_polynomial(q, threshold, &poly, id);
+            let share_val = evaluate_polynomial(q, &poly, id);
             Share {
                 threshold,
                 id: id.clone(), // Use original ID provided
@@ -157,61 +153,190 @@ where
 impl Share {
     /// Verifies a share `σᵢ` against the public verification vector `V`.
     /// Checks if g^σᵢ = Π (vⱼ)^(idᵢ^j) for j = 0 to t.
-    pub fn verify<
-        C: Curve + CurveArithmetic
-    >(
-        &self,
-        q: &BigInt, // Curve order
-        verification_vector: &VerificationVector<C>,
-    ) -> bool
-    where
-         // Bounds needed for ECPoint operations
-         ECPoint<C>: Clone + PartialEq + Serialize + for<'de> Deserialize<'de>,
-         // Assuming ECPoint implements Add and ScalarMult traits or methods
-    {
+    pub fn verify(&self, q: &BigInt, verification_vector: &VerificationVector) -> bool {
         if self.threshold + 1 != verification_vector.vector.len() {
-             warn!("Share verify failed: threshold mismatch (share={}, vv={})", self.threshold, verification_vector.vector.len()-1);
+             warn!("Share verify failed: threshold mismatch (share={}, vv={})", self.threshold, verification_vector.vector.len().saturating_sub(1));
             return false;
         }
+        let curve = match verification_vector.curve() {
+            Ok(c) => c,
+            Err(_) => return false,
+        };
 
         let vs = &verification_vector.vector;
         let mod_q = ModInt::new(q.clone());
 
         // Calculate the right side of the equation: Π (vⱼ)^(idᵢ^j) mod N
-        // rhs = v₀ * v₁^id * v₂^(id²) * ... * vₜ^(idᵗ)
-
-        let mut rhs = vs[0].clone(); // Initialize with v₀ = g^a₀
+        // rhs = v₀ * v₁^id * v₂^(id²) * ... * vₜ^(idᵗ), computed as a single
+        // multi-scalar multiplication instead of a scalar_mult+add per term.
+        let mut id_powers = Vec::with_capacity(vs.len());
         let mut id_power_j = BigInt::one();
-
-        for j in 1..=self.threshold {
-            // id_power_j = id^j mod q
+        id_powers.push(id_power_j.clone());
+        for _ in 1..vs.len() {
             id_power_j = mod_q.mul(&id_power_j, &self.id);
-
-            // point_j = v_j ^ (id^j)
-            let point_j = vs[j].scalar_mul(&id_power_j);
-
-            // rhs = rhs + point_j (point addition)
-            match rhs.add(&point_j) {
-                 Ok(sum) => rhs = sum,
-                 Err(_) => {
-                     warn!("Share verify failed: point addition error during RHS calculation");
-                     return false; // Error during point addition
-                 }
-             }
+            id_powers.push(id_power_j.clone());
         }
 
+        let rhs = match msm(vs, &id_powers) {
+            Ok(point) => point,
+            Err(_) => {
+                warn!("Share verify failed: point addition error during RHS calculation");
+                return false;
+            }
+        };
+
         // Calculate the left side: g^σᵢ
-        let lhs = ECPoint::<C>::scalar_base_mult(&self.share);
+        let lhs = match ECPoint::scalar_base_mult(curve, &self.share) {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
 
         // Compare lhs == rhs
         lhs == rhs
     }
 }
 
+impl VerificationVector {
+    /// Verifies every share in `shares` against this vector with one
+    /// combined check instead of `shares.len()` independent `Share::verify`
+    /// calls: `O(t)` group operations total instead of `O(n*t)`. Draws a
+    /// random weight `rho_i` per share from `rng`, then checks
+    /// `g^(sum rho_i*sigma_i) == prod_j v_j^(sum_i rho_i*id_i^j)` -- a
+    /// forged share only survives with probability `1/q` over the choice
+    /// of weights. On a batch failure, falls back to per-share `verify` to
+    /// pinpoint and return the offending `ShareVerificationError`.
+    pub fn verify_batch<R: CryptoRng + RngCore>(
+        &self,
+        q: &BigInt,
+        shares: &[Share],
+        rng: &mut R,
+    ) -> Result<(), VssError> {
+        let threshold = self.vector.len().saturating_sub(1);
+        for share in shares {
+            if share.threshold != threshold {
+                return Err(VssError::ShareVerificationError { id: share.id.clone() });
+            }
+        }
+        let curve = self.curve()?;
+
+        let mod_q = ModInt::new(q.clone());
+        let weights: Vec<BigInt> = shares
+            .iter()
+            .map(|_| get_random_positive_int(rng, q))
+            .collect();
+
+        // Combined LHS scalar: sum_i rho_i * sigma_i mod q.
+        let mut combined_share = BigInt::zero();
+        for (rho_i, share) in weights.iter().zip(shares.iter()) {
+            let term = mod_q.mul(rho_i, &share.share);
+            combined_share = mod_q.add(&combined_share, &term);
+        }
+        let lhs = ECPoint::scalar_base_mult(curve, &combined_share).map_err(VssError::PointError)?;
+
+        // Combined RHS: for each v_j, c_j = sum_i rho_i * id_i^j, tracking
+        // each share's running id_i^j power as j increases, then fold every
+        // v_j^c_j into a single multi-scalar multiplication.
+        let mut id_powers: Vec<BigInt> = vec![BigInt::one(); shares.len()];
+        let mut c: Vec<BigInt> = Vec::with_capacity(self.vector.len());
+        for _ in &self.vector {
+            let mut c_j = BigInt::zero();
+            for (i, share) in shares.iter().enumerate() {
+                let weighted = mod_q.mul(&weights[i], &id_powers[i]);
+                c_j = mod_q.add(&c_j, &weighted);
+                id_powers[i] = mod_q.mul(&id_powers[i], &share.id);
+            }
+            c.push(c_j);
+        }
+
+        if !self.vector.is_empty() && lhs == msm(&self.vector, &c).map_err(VssError::PointError)? {
+            return Ok(());
+        }
+
+        // Combined check failed: fall back to per-share verification to
+        // name the actual culprit.
+        for share in shares {
+            if !share.verify(q, self) {
+                return Err(VssError::ShareVerificationError { id: share.id.clone() });
+            }
+        }
+        Err(VssError::InvalidParameters(
+            "batch verification failed but no individual share did".to_string(),
+        ))
+    }
+
+    /// Evaluates `Π vⱼ^(idʲ)`, i.e. `g^f(id)`, a party's public share point --
+    /// the same relation `Share::verify` checks a claimed scalar share
+    /// against, but usable when only the verification vector is available.
+    pub fn public_share(&self, q: &BigInt, id: &BigInt) -> Result<ECPoint, VssError> {
+        let mod_q = ModInt::new(q.clone());
+        let mut id_powers = Vec::with_capacity(self.vector.len());
+        let mut id_power_j = BigInt::one();
+        id_powers.push(id_power_j.clone());
+        for _ in 1..self.vector.len() {
+            id_power_j = mod_q.mul(&id_power_j, id);
+            id_powers.push(id_power_j.clone());
+        }
+        msm(&self.vector, &id_powers).map_err(VssError::PointError)
+    }
+
+    /// Returns `v₀ = g^a₀`, the group public key committed to by this
+    /// verification vector.
+    pub fn group_public_key(&self) -> ECPoint {
+        self.vector[0].clone()
+    }
+}
+
+/// Lagrange-interpolates `g^secret` in the exponent from `t+1` public share
+/// points `(idᵢ, Pᵢ = g^f(idᵢ))`, mirroring `reconstruct_secret`'s scalar
+/// interpolation but combining points instead of scalars. Useful when only
+/// public shares are available (e.g. to cross-check a reconstructed public
+/// key against a verification vector's `v₀`) since the scalar shares
+/// themselves need never be assembled.
+pub fn interpolate_public_point(
+    q: &BigInt,
+    points: &[(BigInt, ECPoint)],
+) -> Result<ECPoint, VssError> {
+    if points.is_empty() {
+        return Err(VssError::ReconstructionError("Cannot interpolate from empty points".to_string()));
+    }
+
+    let mod_q = ModInt::new(q.clone());
+    let mut coefficients = Vec::with_capacity(points.len());
+    let mut point_values = Vec::with_capacity(points.len());
+
+    for i in 0..points.len() {
+        let (id_i, point_i) = &points[i];
+        let mut lagrange_basis = BigInt::one();
+        for j in 0..points.len() {
+            if i == j {
+                continue;
+            }
+            let id_j = &points[j].0;
+            let denominator = mod_q.sub(id_j, id_i);
+            if denominator.is_zero() {
+                return Err(VssError::ReconstructionError(format!(
+                    "Lagrange denominator is zero for i={}, j={} (id_i={}, id_j={})",
+                    i, j, id_i, id_j
+                )));
+            }
+            let denominator_inv = mod_q.mod_inverse(&denominator).ok_or_else(|| {
+                VssError::ReconstructionError(format!(
+                    "Modular inverse failed for denominator (id_j - id_i) = {} mod {} for i={}, j={}",
+                    denominator, q, i, j
+                ))
+            })?;
+            let term = mod_q.mul(id_j, &denominator_inv);
+            lagrange_basis = mod_q.mul(&lagrange_basis, &term);
+        }
+        coefficients.push(lagrange_basis);
+        point_values.push(point_i.clone());
+    }
+
+    msm(&point_values, &coefficients).map_err(VssError::PointError)
+}
+
 /// Reconstructs the secret from a sufficient number of shares using Lagrange interpolation.
-pub fn reconstruct_secret<
-    C: Curve + CurveArithmetic
->(
+pub fn reconstruct_secret(
     q: &BigInt, // Curve order
     shares: Shares,
 ) -> Result<BigInt, VssError> {
@@ -269,13 +394,129 @@ pub fn reconstruct_secret<
     Ok(secret)
 }
 
+/// Deals a fresh degree-`threshold` VSS polynomial `delta(z)` with
+/// `delta(0) = 0`, i.e. `create` with the zero secret. One current
+/// shareholder calls this to drive a pure share refresh: it broadcasts the
+/// returned verification vector and privately sends each party `j` its
+/// sub-share `delta(idⱼ)`, to be combined via `apply_refresh`. Because every
+/// dealer's polynomial evaluates to 0 at the origin, summing all the
+/// resulting sub-shares into a live share rotates it without moving the
+/// secret it reconstructs to.
+pub fn reshare_zero<R: CryptoRng + RngCore>(
+    curve: ECCurve,
+    q: &BigInt,
+    threshold: usize,
+    indexes: &[BigInt],
+    rng: &mut R,
+) -> Result<(VerificationVector, Vec<Share>), VssError> {
+    create(curve, q, threshold, &BigInt::zero(), indexes, rng)
+}
+
+/// Folds a batch of `reshare_zero` sub-shares into `current_share`, verifying
+/// each against its dealer's zero verification vector first. Returns
+/// `VssError::RefreshVerificationError` at the first sub-share that doesn't
+/// check out; its `id` is that sub-share's own `id` (the receiving party's
+/// index, the same for every entry), so the error does not identify the
+/// dealer.
+pub fn apply_refresh(
+    q: &BigInt,
+    current_share: &Share,
+    zero_deltas: &[(Share, VerificationVector)],
+) -> Result<Share, VssError> {
+    let mod_q = ModInt::new(q.clone());
+    let mut new_share_value = current_share.share.clone();
+
+    for (delta, zero_vv) in zero_deltas {
+        if !delta.verify(q, zero_vv) {
+            return Err(VssError::RefreshVerificationError { id: delta.id.clone() });
+        }
+        new_share_value = mod_q.add(&new_share_value, &delta.share);
+    }
+
+    Ok(Share {
+        threshold: current_share.threshold,
+        id: current_share.id.clone(),
+        share: new_share_value,
+    })
+}
+
+/// Deals `own_share` (an existing shareholder's own share) as the secret of
+/// a fresh degree-`new_threshold` polynomial over a (possibly disjoint) new
+/// index set, to redistribute a threshold key to a different committee
+/// and/or threshold. One call per old shareholder; new parties combine the
+/// sub-shares they receive from every old shareholder with
+/// `combine_redistributed_share`.
+pub fn redistribute<R: CryptoRng + RngCore>(
+    curve: ECCurve,
+    q: &BigInt,
+    new_threshold: usize,
+    own_share: &Share,
+    new_indexes: &[BigInt],
+    rng: &mut R,
+) -> Result<(VerificationVector, Vec<Share>), VssError> {
+    create(curve, q, new_threshold, &own_share.share, new_indexes, rng)
+}
+
+/// Combines the sub-shares a new party received from every old shareholder
+/// (each produced by that shareholder's own `redistribute` call) into its
+/// new share, weighting each by the Lagrange coefficient of its dealer's
+/// index evaluated at 0 over the full old index set `old_indexes`. This is
+/// the receiving side of `redistribute`: it reconstructs `secret * l_i(0)`
+/// contributions without ever reconstructing `secret` itself.
+pub fn combine_redistributed_share(
+    q: &BigInt,
+    new_threshold: usize,
+    new_id: &BigInt,
+    // (old dealer's index, sub-share that dealer sent to `new_id`)
+    contributions: &[(BigInt, BigInt)],
+    old_indexes: &[BigInt],
+) -> Result<Share, VssError> {
+    if contributions.len() < old_indexes.len() {
+        return Err(VssError::NumSharesBelowThreshold(old_indexes.len(), contributions.len()));
+    }
+
+    let mod_q = ModInt::new(q.clone());
+    let mut new_share_value = BigInt::zero();
+
+    for (dealer_id, sub_share) in contributions {
+        let mut lagrange_basis = BigInt::one();
+        for other_id in old_indexes {
+            if other_id == dealer_id {
+                continue;
+            }
+            let denominator = mod_q.sub(other_id, dealer_id);
+            if denominator.is_zero() {
+                return Err(VssError::ReconstructionError(format!(
+                    "Lagrange denominator is zero for dealer {} vs {}",
+                    dealer_id, other_id
+                )));
+            }
+            let denominator_inv = mod_q.mod_inverse(&denominator).ok_or_else(|| {
+                VssError::ReconstructionError(format!(
+                    "Modular inverse failed for denominator {} mod {}",
+                    denominator, q
+                ))
+            })?;
+            let term = mod_q.mul(other_id, &denominator_inv);
+            lagrange_basis = mod_q.mul(&lagrange_basis, &term);
+        }
+
+        let term = mod_q.mul(sub_share, &lagrange_basis);
+        new_share_value = mod_q.add(&new_share_value, &term);
+    }
+
+    Ok(Share {
+        threshold: new_threshold,
+        id: new_id.clone(),
+        share: new_share_value,
+    })
+}
+
 // --- Private Helper Functions ---
 
 /// Samples a random polynomial f(z) = a₀ + a₁z + ... + aₜzᵗ of degree `threshold`,
 /// where a₀ = `secret` and other coefficients a₁, ..., aₜ are random values in Zq.
-fn sample_polynomial<
-    R: CryptoRng + RngCore
->(
+fn sample_polynomial<R: CryptoRng + RngCore>(
     q: &BigInt, // Curve order
     threshold: usize,
     secret: &BigInt,
@@ -288,9 +529,7 @@ fn sample_polynomial<
 
     // a₁, ..., aₜ = random in Zq
     for _ in 1..=threshold {
-        // Ensure coefficient is less than q
-        let ai = get_random_positive_int(rng, q).unwrap_or_else(BigInt::zero);
-        poly.push(ai);
+        poly.push(get_random_positive_int(rng, q));
     }
     poly
 }
@@ -300,7 +539,6 @@ fn sample_polynomial<
 /// result = a₀ + a₁*id + a₂*id² + ... + aₜ*idᵗ mod q
 fn evaluate_polynomial(
     q: &BigInt, // Curve order
-    _threshold: usize, // Not strictly needed if poly length implies it
     poly: &[BigInt],
     id: &BigInt,
 ) -> BigInt {
@@ -324,8 +562,6 @@ fn evaluate_polynomial(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tss::Secp256k1Curve; // Example curve
-    use elliptic_curve::Field;
     use rand::thread_rng;
 
      // Helper to get curve order Q for K256
@@ -347,42 +583,35 @@ mod tests {
         let indexes: Vec<BigInt> = (1..=num_parties).map(BigInt::from).collect();
 
         // 1. Create VSS shares
-        let create_result = create::<Secp256k1, _>(&q, threshold, &secret, &indexes, &mut rng);
+        let create_result = create(ECCurve::Secp256k1, &q, threshold, &secret, &indexes, &mut rng);
         assert!(create_result.is_ok());
         let (verification_vector, shares_vec) = create_result.unwrap();
 
         assert_eq!(verification_vector.vector.len(), threshold + 1);
         assert_eq!(shares_vec.len(), num_parties as usize);
 
-        println!("Secret: {}", secret);
-        println!("Threshold: {}", threshold);
-        println!("Verification Vector V[0]: {:?}", verification_vector.vector[0]); // g^a0 = g^secret
-        // println!("Shares: {:?}", shares_vec);
-
         // 2. Verify each share
         for share in &shares_vec {
-            println!("Verifying share for ID: {}", share.id);
-            assert!(share.verify::<Secp256k1>(&q, &verification_vector), "Share verification failed for ID {}", share.id);
+            assert!(share.verify(&q, &verification_vector), "Share verification failed for ID {}", share.id);
         }
 
         // 3. Reconstruct secret with enough shares (t+1)
         let shares_to_reconstruct = &shares_vec[0..=threshold];
-        let reconstructed_secret = reconstruct_secret::<Secp256k1>(&q, shares_to_reconstruct)
+        let reconstructed_secret = reconstruct_secret(&q, shares_to_reconstruct)
             .expect("Secret reconstruction failed");
 
-        println!("Reconstructed Secret: {}", reconstructed_secret);
         assert_eq!(secret, reconstructed_secret);
 
          // 4. Reconstruct with different set of t+1 shares
          let shares_to_reconstruct_alt = &shares_vec[num_parties as usize - threshold - 1..];
          assert_eq!(shares_to_reconstruct_alt.len(), threshold + 1);
-         let reconstructed_secret_alt = reconstruct_secret::<Secp256k1>(&q, shares_to_reconstruct_alt)
+         let reconstructed_secret_alt = reconstruct_secret(&q, shares_to_reconstruct_alt)
              .expect("Secret reconstruction (alt set) failed");
          assert_eq!(secret, reconstructed_secret_alt);
 
         // 5. Attempt reconstruction with insufficient shares (t)
         let shares_insufficient = &shares_vec[0..threshold];
-        let recon_insufficient = reconstruct_secret::<Secp256k1>(&q, shares_insufficient);
+        let recon_insufficient = reconstruct_secret(&q, shares_insufficient);
         assert!(matches!(recon_insufficient, Err(VssError::NumSharesBelowThreshold(_, _))));
     }
 
@@ -394,13 +623,13 @@ mod tests {
         let threshold = 1;
         let indexes = vec![BigInt::from(1), BigInt::from(2)];
 
-        let (vv, mut shares_vec) = create::<Secp256k1, _>(&q, threshold, &secret, &indexes, &mut rng).unwrap();
+        let (vv, mut shares_vec) = create(ECCurve::Secp256k1, &q, threshold, &secret, &indexes, &mut rng).unwrap();
 
         // Tamper with a share value
         shares_vec[0].share += BigInt::one();
 
-        assert!(!shares_vec[0].verify::<Secp256k1>(&q, &vv), "Tampered share verified successfully");
-        assert!(shares_vec[1].verify::<Secp256k1>(&q, &vv), "Untampered share failed verification");
+        assert!(!shares_vec[0].verify(&q, &vv), "Tampered share verified successfully");
+        assert!(shares_vec[1].verify(&q, &vv), "Untampered share failed verification");
     }
 
     #[test]
@@ -411,29 +640,29 @@ mod tests {
         let threshold = 1;
         let indexes = vec![BigInt::from(1), BigInt::from(2)];
 
-        let (vv1, shares1) = create::<Secp256k1, _>(&q, threshold, &secret, &indexes, &mut rng).unwrap();
+        let (vv1, shares1) = create(ECCurve::Secp256k1, &q, threshold, &secret, &indexes, &mut rng).unwrap();
         // Create shares/VV for a *different* secret
         let secret2 = BigInt::from(777u64);
-        let (vv2, _) = create::<Secp256k1, _>(&q, threshold, &secret2, &indexes, &mut rng).unwrap();
+        let (vv2, _) = create(ECCurve::Secp256k1, &q, threshold, &secret2, &indexes, &mut rng).unwrap();
 
         // Try to verify shares from secret1 against vv from secret2
-        assert!(!shares1[0].verify::<Secp256k1>(&q, &vv2), "Share verified against wrong VV");
-        assert!(shares1[0].verify::<Secp256k1>(&q, &vv1), "Share failed against correct VV");
+        assert!(!shares1[0].verify(&q, &vv2), "Share verified against wrong VV");
+        assert!(shares1[0].verify(&q, &vv1), "Share failed against correct VV");
     }
 
      #[test]
     fn test_check_indexes() {
         let q = get_k256_q();
-        assert!(check_indexes::<Secp256k1>(&q, &[BigInt::one(), BigInt::two()]).is_ok());
+        assert!(check_indexes(&q, &[BigInt::one(), BigInt::from(2u64)]).is_ok());
         // Zero index
-        assert!(matches!(check_indexes::<Secp256k1>(&q, &[BigInt::one(), BigInt::zero()]), Err(VssError::IndexIsZero)));
+        assert!(matches!(check_indexes(&q, &[BigInt::one(), BigInt::zero()]), Err(VssError::IndexIsZero)));
         // Duplicate index
-        assert!(matches!(check_indexes::<Secp256k1>(&q, &[BigInt::one(), BigInt::two(), BigInt::one()]), Err(VssError::DuplicateIndex(_))));
+        assert!(matches!(check_indexes(&q, &[BigInt::one(), BigInt::from(2u64), BigInt::one()]), Err(VssError::DuplicateIndex(_))));
          // Duplicate index (after mod q)
          let q_plus_1 = &q + BigInt::one();
-         assert!(matches!(check_indexes::<Secp256k1>(&q, &[BigInt::one(), q_plus_1]), Err(VssError::DuplicateIndex(_))));
+         assert!(matches!(check_indexes(&q, &[BigInt::one(), q_plus_1]), Err(VssError::DuplicateIndex(_))));
          // Empty
-         assert!(matches!(check_indexes::<Secp256k1>(&q, &[]), Err(VssError::InvalidParameters(_))));
+         assert!(matches!(check_indexes(&q, &[]), Err(VssError::InvalidParameters(_))));
 
     }
 
@@ -442,16 +671,168 @@ mod tests {
         let mut rng = thread_rng();
         let q = get_k256_q();
         let secret = BigInt::from(1u64);
-        let indexes = vec![BigInt::one(), BigInt::two()];
+        let indexes = vec![BigInt::one(), BigInt::from(2u64)];
 
         // Threshold too low
-        assert!(matches!(create::<Secp256k1, _>(&q, 0, &secret, &indexes, &mut rng), Err(VssError::InvalidParameters(_))));
+        assert!(matches!(create(ECCurve::Secp256k1, &q, 0, &secret, &indexes, &mut rng), Err(VssError::InvalidParameters(_))));
 
         // Not enough indexes for threshold
-        assert!(matches!(create::<Secp256k1, _>(&q, 2, &secret, &indexes, &mut rng), Err(VssError::NumSharesBelowThreshold(_, _))));
+        assert!(matches!(create(ECCurve::Secp256k1, &q, 2, &secret, &indexes, &mut rng), Err(VssError::NumSharesBelowThreshold(_, _))));
 
         // Invalid indexes
         let invalid_indexes = vec![BigInt::one(), BigInt::zero()];
-        assert!(matches!(create::<Secp256k1, _>(&q, 1, &secret, &invalid_indexes, &mut rng), Err(VssError::IndexIsZero)));
+        assert!(matches!(create(ECCurve::Secp256k1, &q, 1, &secret, &invalid_indexes, &mut rng), Err(VssError::IndexIsZero)));
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_refresh_rotates_shares_but_preserves_secret() {
+        let mut rng = thread_rng();
+        let q = get_k256_q();
+        let secret = BigInt::from(42424242_u64);
+        let threshold = 1;
+        let indexes = vec![BigInt::one(), BigInt::from(2u64), BigInt::from(3u64)];
+
+        let (_vv, shares) = create(ECCurve::Secp256k1, &q, threshold, &secret, &indexes, &mut rng).unwrap();
+
+        // Every current shareholder deals a fresh zero-share.
+        let zero_deals: Vec<(VerificationVector, Vec<Share>)> = (0..indexes.len())
+            .map(|_| reshare_zero(ECCurve::Secp256k1, &q, threshold, &indexes, &mut rng).unwrap())
+            .collect();
+
+        // Each party folds in the sub-share meant for it from every dealer.
+        let refreshed: Vec<Share> = shares
+            .iter()
+            .enumerate()
+            .map(|(party_idx, old_share)| {
+                let deltas: Vec<(Share, VerificationVector)> = zero_deals
+                    .iter()
+                    .map(|(vv, dealt_shares)| (dealt_shares[party_idx].clone(), vv.clone()))
+                    .collect();
+                apply_refresh(&q, old_share, &deltas).unwrap()
+            })
+            .collect();
+
+        assert_ne!(refreshed[0].share, shares[0].share, "refresh should rotate the share value");
+        let reconstructed = reconstruct_secret(&q, &refreshed[0..=threshold]).unwrap();
+        assert_eq!(secret, reconstructed);
+    }
+
+    #[test]
+    fn test_apply_refresh_rejects_bad_sub_share() {
+        let mut rng = thread_rng();
+        let q = get_k256_q();
+        let indexes = vec![BigInt::one(), BigInt::from(2u64)];
+        let (_vv, shares) = create(ECCurve::Secp256k1, &q, 1, &BigInt::from(7u64), &indexes, &mut rng).unwrap();
+        let (zero_vv, mut zero_shares) = reshare_zero(ECCurve::Secp256k1, &q, 1, &indexes, &mut rng).unwrap();
+        zero_shares[0].share += BigInt::one();
+
+        let result = apply_refresh(&q, &shares[0], &[(zero_shares[0].clone(), zero_vv)]);
+        assert!(matches!(result, Err(VssError::RefreshVerificationError { .. })));
+    }
+
+    #[test]
+    fn test_redistribute_to_new_committee_preserves_secret() {
+        let mut rng = thread_rng();
+        let q = get_k256_q();
+        let secret = BigInt::from(13371337_u64);
+        let old_threshold = 1;
+        let old_indexes = vec![BigInt::one(), BigInt::from(2u64), BigInt::from(3u64)];
+        let (_vv, old_shares) = create(ECCurve::Secp256k1, &q, old_threshold, &secret, &old_indexes, &mut rng).unwrap();
+        // Reconstruction needs t+1 = 2 old shareholders to redistribute.
+        let redistributing = &old_shares[0..=old_threshold];
+
+        let new_threshold = 2;
+        let new_indexes = vec![BigInt::from(10u64), BigInt::from(11u64), BigInt::from(12u64), BigInt::from(13u64)];
+        let old_dealer_indexes: Vec<BigInt> = redistributing.iter().map(|s| s.id.clone()).collect();
+
+        // Each redistributing old shareholder deals its own share as the secret.
+        let dealt: Vec<(VerificationVector, Vec<Share>)> = redistributing
+            .iter()
+            .map(|old_share| redistribute(ECCurve::Secp256k1, &q, new_threshold, old_share, &new_indexes, &mut rng).unwrap())
+            .collect();
+
+        // Each new party combines the sub-share it received from every old
+        // dealer, weighted by that dealer's own (old-committee) index.
+        let new_shares: Vec<Share> = (0..new_indexes.len())
+            .map(|new_idx| {
+                let contributions: Vec<(BigInt, BigInt)> = old_dealer_indexes
+                    .iter()
+                    .zip(dealt.iter())
+                    .map(|(old_id, (_, dealt_shares))| (old_id.clone(), dealt_shares[new_idx].share.clone()))
+                    .collect();
+                combine_redistributed_share(&q, new_threshold, &new_indexes[new_idx], &contributions, &old_dealer_indexes).unwrap()
+            })
+            .collect();
+
+        let reconstructed = reconstruct_secret(&q, &new_shares[0..=new_threshold]).unwrap();
+        assert_eq!(secret, reconstructed);
+    }
+
+    #[test]
+    fn test_verify_batch_accepts_all_honest_shares() {
+        let mut rng = thread_rng();
+        let q = get_k256_q();
+        let secret = BigInt::from(55555_u64);
+        let threshold = 2;
+        let indexes: Vec<BigInt> = (1..=5u64).map(BigInt::from).collect();
+
+        let (vv, shares) = create(ECCurve::Secp256k1, &q, threshold, &secret, &indexes, &mut rng).unwrap();
+        assert!(vv.verify_batch(&q, &shares, &mut rng).is_ok());
+    }
+
+    #[test]
+    fn test_verify_batch_pinpoints_tampered_share() {
+        let mut rng = thread_rng();
+        let q = get_k256_q();
+        let secret = BigInt::from(9999_u64);
+        let threshold = 1;
+        let indexes = vec![BigInt::one(), BigInt::from(2u64), BigInt::from(3u64)];
+
+        let (vv, mut shares) = create(ECCurve::Secp256k1, &q, threshold, &secret, &indexes, &mut rng).unwrap();
+        let tampered_id = shares[1].id.clone();
+        shares[1].share += BigInt::one();
+
+        match vv.verify_batch(&q, &shares, &mut rng) {
+            Err(VssError::ShareVerificationError { id }) => assert_eq!(id, tampered_id),
+            other => panic!("expected ShareVerificationError for the tampered share, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_public_share_matches_scalar_base_mult_of_share() {
+        let mut rng = thread_rng();
+        let q = get_k256_q();
+        let secret = BigInt::from(424242_u64);
+        let threshold = 2;
+        let indexes: Vec<BigInt> = (1..=4u64).map(BigInt::from).collect();
+
+        let (vv, shares) = create(ECCurve::Secp256k1, &q, threshold, &secret, &indexes, &mut rng).unwrap();
+
+        for share in &shares {
+            let public_share = vv.public_share(&q, &share.id).unwrap();
+            let expected = ECPoint::scalar_base_mult(ECCurve::Secp256k1, &share.share).unwrap();
+            assert_eq!(public_share, expected);
+        }
+
+        assert_eq!(vv.group_public_key(), vv.vector[0]);
+    }
+
+    #[test]
+    fn test_interpolate_public_point_recovers_group_public_key() {
+        let mut rng = thread_rng();
+        let q = get_k256_q();
+        let secret = BigInt::from(13131313_u64);
+        let threshold = 2;
+        let indexes: Vec<BigInt> = (1..=5u64).map(BigInt::from).collect();
+
+        let (vv, shares) = create(ECCurve::Secp256k1, &q, threshold, &secret, &indexes, &mut rng).unwrap();
+
+        let points: Vec<(BigInt, ECPoint)> = shares[0..=threshold]
+            .iter()
+            .map(|share| (share.id.clone(), vv.public_share(&q, &share.id).unwrap()))
+            .collect();
+
+        let recovered = interpolate_public_point(&q, &points).unwrap();
+        assert_eq!(recovered, vv.group_public_key());
+    }
+}
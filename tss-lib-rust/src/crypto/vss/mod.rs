@@ -0,0 +1,3 @@
+pub mod dkg;
+pub mod feldman_vss;
+pub mod pedersen_vss;
@@ -0,0 +1,295 @@
+// Copyright © 2019 Binance
+//
+// This file is part of Binance. The full Binance copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+// Pedersen VSS: a hiding alternative to `feldman_vss`. `feldman_vss::create`'s
+// verification vector V = [g^a0, ...] leaks g^secret (the dealt secret's
+// public point) to anyone who sees it, which is a problem whenever the
+// dealer needs to commit to shares before the corresponding public key is
+// meant to be revealed. Committing with a second generator `h` of unknown
+// discrete log w.r.t. `g` makes the commitments perfectly hiding instead.
+
+// Based on Torben Pryds Pedersen, 1991, "Non-Interactive and
+// Information-Theoretic Secure Verifiable Secret Sharing".
+
+use crate::{
+    common::{int::ModInt, random::get_random_positive_int},
+    crypto::ecpoint::ECPoint,
+    crypto::vss::feldman_vss::VssError,
+};
+
+use num_bigint::BigInt;
+use num_traits::{One, Zero};
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+/// A Pedersen share pair for party `id`: `sigma = f(id)` shares the secret
+/// like a Feldman share would, while `tau = f'(id)` is the corresponding
+/// blinding share and is discarded once shares are verified/reconstructed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Share {
+    pub threshold: usize,
+    #[serde(with = "crate::serde_support::bigint_bytes")]
+    pub id: BigInt,
+    #[serde(with = "crate::serde_support::bigint_bytes")]
+    pub sigma: BigInt,
+    #[serde(with = "crate::serde_support::bigint_bytes")]
+    pub tau: BigInt,
+}
+
+/// Public, perfectly-hiding verification vector `C = [C0, ..., Ct]` where
+/// `Cj = g^aj * h^bj`. Unlike `feldman_vss::VerificationVector`, this leaks
+/// nothing about the secret `a0`: `C0` is a Pedersen commitment to it, not
+/// `g^a0` itself.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VerificationVector {
+    pub vector: Vec<ECPoint>,
+}
+
+/// Creates a new Pedersen VSS instance. `h` must be a generator with unknown
+/// discrete log w.r.t. the curve's base point `g` (a nothing-up-my-sleeve
+/// point); callers are responsible for supplying one, e.g. hashed-to-curve
+/// from a fixed label, and passing the same `h` to every verifier. `h`'s
+/// curve (`h.curve`) is used for every group operation here.
+pub fn create<R: CryptoRng + RngCore>(
+    q: &BigInt,
+    threshold: usize,
+    secret: &BigInt,
+    indexes: &[BigInt],
+    h: &ECPoint,
+    rng: &mut R,
+) -> Result<(VerificationVector, Vec<Share>), VssError> {
+    if threshold < 1 {
+        return Err(VssError::InvalidParameters("Threshold cannot be less than 1".to_string()));
+    }
+    if indexes.len() < threshold + 1 {
+        return Err(VssError::NumSharesBelowThreshold(threshold + 1, indexes.len()));
+    }
+    if *h == ECPoint::identity(h.curve) {
+        return Err(VssError::InvalidGenerator("h must not be the point at infinity".to_string()));
+    }
+    super::feldman_vss::check_indexes(q, indexes)?;
+
+    let f = sample_polynomial(q, threshold, secret, rng);
+    let b0 = get_random_positive_int(rng, q);
+    let f_prime = sample_polynomial(q, threshold, &b0, rng);
+
+    // Cj = g^aj * h^bj
+    let commitments: Vec<ECPoint> = f
+        .iter()
+        .zip(f_prime.iter())
+        .map(|(a_j, b_j)| {
+            let g_term = ECPoint::scalar_base_mult(h.curve, a_j).map_err(VssError::PointError)?;
+            let h_term = h.scalar_mult(b_j).map_err(VssError::PointError)?;
+            g_term.add(&h_term).map_err(VssError::PointError)
+        })
+        .collect::<Result<Vec<_>, VssError>>()?;
+
+    let shares: Vec<Share> = indexes
+        .iter()
+        .map(|id| Share {
+            threshold,
+            id: id.clone(),
+            sigma: evaluate_polynomial(q, &f, id),
+            tau: evaluate_polynomial(q, &f_prime, id),
+        })
+        .collect();
+
+    Ok((VerificationVector { vector: commitments }, shares))
+}
+
+impl Share {
+    /// Verifies `g^sigma * h^tau == Prod_j Cj^(id^j)`.
+    pub fn verify(&self, q: &BigInt, h: &ECPoint, verification_vector: &VerificationVector) -> bool {
+        if self.threshold + 1 != verification_vector.vector.len() {
+            return false;
+        }
+
+        let cs = &verification_vector.vector;
+        let mod_q = ModInt::new(q.clone());
+
+        let mut rhs = cs[0].clone();
+        let mut id_power_j = BigInt::one();
+        for j in 1..=self.threshold {
+            id_power_j = mod_q.mul(&id_power_j, &self.id);
+            let term = match cs[j].scalar_mult(&id_power_j) {
+                Ok(t) => t,
+                Err(_) => return false,
+            };
+            match rhs.add(&term) {
+                Ok(sum) => rhs = sum,
+                Err(_) => return false,
+            }
+        }
+
+        let g_term = match ECPoint::scalar_base_mult(h.curve, &self.sigma) {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
+        let h_term = match h.scalar_mult(&self.tau) {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
+        let lhs = match g_term.add(&h_term) {
+            Ok(sum) => sum,
+            Err(_) => return false,
+        };
+
+        lhs == rhs
+    }
+}
+
+/// Reconstructs the secret from `sigma` shares by Lagrange interpolation,
+/// exactly as `feldman_vss::reconstruct_secret` does; `tau` is not needed
+/// once the `sigma` shares have each been verified and is discarded here.
+pub fn reconstruct_secret(q: &BigInt, shares: &[Share]) -> Result<BigInt, VssError> {
+    if shares.is_empty() {
+        return Err(VssError::ReconstructionError("Cannot reconstruct secret from empty shares".to_string()));
+    }
+    let threshold = shares[0].threshold;
+    if shares.len() <= threshold {
+        return Err(VssError::NumSharesBelowThreshold(threshold + 1, shares.len()));
+    }
+
+    let effective_shares = &shares[0..=threshold];
+    let mod_q = ModInt::new(q.clone());
+    let mut secret = BigInt::zero();
+
+    for i in 0..effective_shares.len() {
+        let id_i = &effective_shares[i].id;
+        let mut lagrange_basis = BigInt::one();
+        for j in 0..effective_shares.len() {
+            if i == j {
+                continue;
+            }
+            let id_j = &effective_shares[j].id;
+            let denominator = mod_q.sub(id_j, id_i);
+            if denominator.is_zero() {
+                return Err(VssError::ReconstructionError(format!(
+                    "Lagrange denominator is zero for i={}, j={} (id_i={}, id_j={})",
+                    i, j, id_i, id_j
+                )));
+            }
+            let denominator_inv = mod_q.mod_inverse(&denominator).ok_or_else(|| {
+                VssError::ReconstructionError(format!(
+                    "Modular inverse failed for denominator (id_j - id_i) = {} mod {} for i={}, j={}",
+                    denominator, q, i, j
+                ))
+            })?;
+            let term = mod_q.mul(id_j, &denominator_inv);
+            lagrange_basis = mod_q.mul(&lagrange_basis, &term);
+        }
+        let term_i = mod_q.mul(&effective_shares[i].sigma, &lagrange_basis);
+        secret = mod_q.add(&secret, &term_i);
+    }
+
+    Ok(secret)
+}
+
+fn sample_polynomial<R: CryptoRng + RngCore>(
+    q: &BigInt,
+    threshold: usize,
+    a0: &BigInt,
+    rng: &mut R,
+) -> Vec<BigInt> {
+    let mod_q = ModInt::new(q.clone());
+    let mut poly = Vec::with_capacity(threshold + 1);
+    poly.push(mod_q.add(a0, &BigInt::zero()));
+    for _ in 1..=threshold {
+        poly.push(get_random_positive_int(rng, q));
+    }
+    poly
+}
+
+fn evaluate_polynomial(q: &BigInt, poly: &[BigInt], id: &BigInt) -> BigInt {
+    let mod_q = ModInt::new(q.clone());
+    let mut result = poly.get(0).cloned().unwrap_or_else(BigInt::zero);
+    let mut id_power_i = BigInt::one();
+    for coeff in &poly[1..] {
+        id_power_i = mod_q.mul(&id_power_i, id);
+        let term = mod_q.mul(coeff, &id_power_i);
+        result = mod_q.add(&result, &term);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::ecpoint::ECCurve;
+    use num_bigint::Sign;
+    use rand::thread_rng;
+
+    fn get_k256_q() -> BigInt {
+        let q_bytes = k256::Scalar::ORDER.to_be_bytes();
+        BigInt::from_bytes_be(Sign::Plus, &q_bytes)
+    }
+
+    // Nothing-up-my-sleeve generator: not g itself, and with no known
+    // discrete log relation to it as far as this test is concerned.
+    fn nums_h() -> ECPoint {
+        ECPoint::scalar_base_mult(ECCurve::Secp256k1, &BigInt::from(424242u64)).unwrap()
+    }
+
+    #[test]
+    fn test_pedersen_vss_create_verify_reconstruct() {
+        let mut rng = thread_rng();
+        let q = get_k256_q();
+        let h = nums_h();
+
+        let secret = BigInt::from(123456789012345_u64);
+        let threshold = 2;
+        let indexes: Vec<BigInt> = (1..=5u64).map(BigInt::from).collect();
+
+        let (vv, shares) = create(&q, threshold, &secret, &indexes, &h, &mut rng).unwrap();
+        assert_eq!(vv.vector.len(), threshold + 1);
+
+        for share in &shares {
+            assert!(share.verify(&q, &h, &vv), "share {} failed to verify", share.id);
+        }
+
+        let reconstructed = reconstruct_secret(&q, &shares[0..=threshold]).unwrap();
+        assert_eq!(secret, reconstructed);
+    }
+
+    #[test]
+    fn test_pedersen_vss_does_not_leak_secret_point() {
+        // The key property over feldman_vss: C0 isn't g^secret, it's a
+        // hiding commitment to it, so two different secrets with the same
+        // blinding coefficient produce different C0's that don't reveal
+        // either secret's public point.
+        let mut rng = thread_rng();
+        let q = get_k256_q();
+        let h = nums_h();
+
+        let (vv, _) = create(&q, 1, &BigInt::from(5u64), &[BigInt::one(), BigInt::from(2u64)], &h, &mut rng).unwrap();
+        let leaked_point = ECPoint::scalar_base_mult(ECCurve::Secp256k1, &BigInt::from(5u64)).unwrap();
+        assert_ne!(vv.vector[0], leaked_point);
+    }
+
+    #[test]
+    fn test_verify_fails_on_tampered_share() {
+        let mut rng = thread_rng();
+        let q = get_k256_q();
+        let h = nums_h();
+        let indexes = vec![BigInt::one(), BigInt::from(2u64)];
+
+        let (vv, mut shares) = create(&q, 1, &BigInt::from(999u64), &indexes, &h, &mut rng).unwrap();
+        shares[0].sigma += BigInt::one();
+
+        assert!(!shares[0].verify(&q, &h, &vv));
+        assert!(shares[1].verify(&q, &h, &vv));
+    }
+
+    #[test]
+    fn test_create_rejects_identity_generator() {
+        let mut rng = thread_rng();
+        let q = get_k256_q();
+        let indexes = vec![BigInt::one(), BigInt::from(2u64)];
+
+        let result = create(&q, 1, &BigInt::from(5u64), &indexes, &ECPoint::identity(ECCurve::Secp256k1), &mut rng);
+        assert!(matches!(result, Err(VssError::InvalidGenerator(_))));
+    }
+}
@@ -1,325 +1,254 @@
-use crate::eddsa::keygen::messages::{KGRound1Message, DlnProof}; // Import message and placeholder proof
+use crate::eddsa::keygen::messages::{KGRound1Message, DlnProof};
 use num_bigint::BigInt;
-use std::error::Error;
-
-// --- Placeholder for Actual DLN Proof Verification Logic --- //
-// TODO: Replace this with the actual DLN proof type and its verification method
-impl DlnProof {
-    // Placeholder verification function
-    // The actual function signature will depend on the proof library used.
-    pub fn verify(&self, h1: &BigInt, h2: &BigInt, n: &BigInt) -> bool {
-        // Replace with actual verification logic
-        // For now, assume valid if data exists and parameters are non-zero
-        println!(
-            "Warning: Using placeholder DLN proof verification for H1={:?}, H2={:?}, N={:?}",
-            h1, h2, n
-        );
-        !self.0.is_empty() && h1 != &BigInt::from(0) && h2 != &BigInt::from(0) && n != &BigInt::from(0)
-    }
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
-    // Placeholder unmarshalling function
-    // In a real scenario, this might parse self.0 into a structured proof object
-    pub fn unmarshal(&self) -> Result<Self, Box<dyn Error>> {
-        // Replace with actual unmarshalling/parsing if needed
-        if self.0.is_empty() {
-            Err(From::from("Cannot unmarshal empty DLN proof bytes"))
-        } else {
-            // Return a clone or parsed version
-            Ok(self.clone())
-        }
-    }
-}
-// --- End Placeholder --- //
-
-// Trait defining that a type contains DLN proofs accessibly
-// Analogous to the Go `message` interface in dln_verifier.go
+/// A type that carries a pair of DLN proofs (`h1`/`h2` and `h2`/`h1`) in
+/// unmarshalled-or-raw form, so `DlnProofVerifier` can verify them without
+/// depending on the concrete message type.
+/// Analogous to the Go `message` interface in dln_verifier.go.
 pub trait HasDlnProofs {
-    // These methods return owned proofs, potentially after unmarshalling.
-    // Adjust return type if borrowing or references are more appropriate.
-    fn get_dln_proof_1(&self) -> Result<DlnProof, Box<dyn Error>>;
-    fn get_dln_proof_2(&self) -> Result<DlnProof, Box<dyn Error>>;
+    fn get_dln_proof_1(&self) -> Result<DlnProof, String>;
+    fn get_dln_proof_2(&self) -> Result<DlnProof, String>;
 }
 
-// Implement the trait for the message type that carries the proofs
 impl HasDlnProofs for KGRound1Message {
-    fn get_dln_proof_1(&self) -> Result<DlnProof, Box<dyn Error>> {
-        // Directly clone the proof or implement unmarshalling logic here
-        self.dln_proof_1.unmarshal()
+    fn get_dln_proof_1(&self) -> Result<DlnProof, String> {
+        self.unmarshal_dln_proof_1()
     }
 
-    fn get_dln_proof_2(&self) -> Result<DlnProof, Box<dyn Error>> {
-        // Directly clone the proof or implement unmarshalling logic here
-        self.dln_proof_2.unmarshal()
+    fn get_dln_proof_2(&self) -> Result<DlnProof, String> {
+        self.unmarshal_dln_proof_2()
     }
 }
 
-// Verifier struct. For now, it's synchronous.
-// Concurrency can be added later using libraries like `rayon` or `tokio`.
-pub struct DlnProofVerifier;
+/// Verifier that checks DLN proofs, optionally fanning a batch of messages
+/// (and, when there's spare capacity, the 128 iterations within a single
+/// proof) across a pool of `concurrency` worker threads.
+pub struct DlnProofVerifier {
+    concurrency: usize,
+}
 
 impl DlnProofVerifier {
-    // Creates a new verifier instance.
-    // `concurrency` parameter is ignored for now in the synchronous version.
-    pub fn new(_concurrency: usize) -> Self {
-        // if concurrency == 0 {
-        //     panic!("DlnProofVerifier::new: concurrency level must not be zero");
-        // }
-        DlnProofVerifier
+    /// Creates a new verifier that will use up to `concurrency` worker
+    /// threads for `verify_batch`.
+    pub fn new(concurrency: usize) -> Self {
+        if concurrency == 0 {
+            panic!("DlnProofVerifier::new: concurrency level must not be zero");
+        }
+        DlnProofVerifier { concurrency }
     }
 
-    // Verifies the first DLN proof from a message.
+    /// Verifies the first DLN proof from a message: proves `log_h1(h2)`.
+    /// `session_id` must match what the prover bound the proof to (see
+    /// `dln::Proof::new`); a mismatched or absent context makes this fail
+    /// even for an otherwise-valid proof, which is what blocks a proof
+    /// minted for one session from being replayed into another.
     pub fn verify_dln_proof_1<M: HasDlnProofs>(
         &self,
         msg: &M,
+        session_id: &[u8],
         h1: &BigInt,
         h2: &BigInt,
         n: &BigInt,
     ) -> bool {
-        // In an async version, this would spawn a task.
         match msg.get_dln_proof_1() {
-            Ok(proof) => proof.verify(h1, h2, n),
-            Err(_) => false, // Failed to get/unmarshal proof
+            Ok(proof) => proof.verify(session_id, h1, h2, n),
+            Err(_) => false, // Failed to unmarshal proof
         }
     }
 
-    // Verifies the second DLN proof from a message.
+    /// Verifies the second DLN proof from a message: proves `log_h2(h1)`, so
+    /// `h1`/`h2` are passed to the underlying check in swapped order.
     pub fn verify_dln_proof_2<M: HasDlnProofs>(
         &self,
         msg: &M,
+        session_id: &[u8],
         h1: &BigInt,
         h2: &BigInt,
         n: &BigInt,
     ) -> bool {
-        // In an async version, this would spawn a task.
         match msg.get_dln_proof_2() {
-            Ok(proof) => proof.verify(h1, h2, n),
-            Err(_) => false, // Failed to get/unmarshal proof
+            Ok(proof) => proof.verify(session_id, h2, h1, n),
+            Err(_) => false, // Failed to unmarshal proof
         }
     }
 
-    // TODO: Add batch verification methods if needed for efficiency.
-    // TODO: Refactor for concurrency using Rayon/Tokio if performance requires it.
+    /// Verifies a batch of `(message, h1, h2, n)` tuples, one pair of DLN
+    /// proofs per entry, against the shared `session_id` all of them were
+    /// proven under, returning `true` at position `i` iff both of
+    /// `msgs[i]`'s proofs verify. Spreads the batch across a thread pool
+    /// sized by `self.concurrency`. When the batch is smaller than the pool
+    /// (spare capacity), each message's two proofs are themselves verified
+    /// on the pool via `Proof::verify_on_pool`, which also short-circuits
+    /// its 128 per-iteration checks as soon as one fails.
+    #[cfg(feature = "parallel")]
+    pub fn verify_batch<M: HasDlnProofs + Sync>(&self, session_id: &[u8], msgs: &[(M, BigInt, BigInt, BigInt)]) -> Vec<bool> {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.concurrency)
+            .build()
+            .expect("failed to build DLN verification thread pool");
+
+        let spare_capacity = msgs.len() < self.concurrency;
+        pool.install(|| {
+            msgs.par_iter()
+                .map(|(msg, h1, h2, n)| {
+                    let (proof1, proof2) = match (msg.get_dln_proof_1(), msg.get_dln_proof_2()) {
+                        (Ok(p1), Ok(p2)) => (p1, p2),
+                        _ => return false,
+                    };
+                    if spare_capacity {
+                        proof1.verify_on_pool(session_id, h1, h2, n, &pool) && proof2.verify_on_pool(session_id, h2, h1, n, &pool)
+                    } else {
+                        proof1.verify(session_id, h1, h2, n) && proof2.verify(session_id, h2, h1, n)
+                    }
+                })
+                .collect()
+        })
+    }
+
+    /// Sequential fallback for `verify_batch` when the `parallel` feature is
+    /// disabled (e.g. WASM/enclave builds without a thread pool).
+    #[cfg(not(feature = "parallel"))]
+    pub fn verify_batch<M: HasDlnProofs>(&self, session_id: &[u8], msgs: &[(M, BigInt, BigInt, BigInt)]) -> Vec<bool> {
+        msgs.iter()
+            .map(|(msg, h1, h2, n)| {
+                self.verify_dln_proof_1(msg, session_id, h1, h2, n) && self.verify_dln_proof_2(msg, session_id, h1, h2, n)
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::eddsa::keygen::messages::KGRound1Message;
-    use crate::eddsa::keygen::test_utils::{load_keygen_test_fixtures, TEST_PARTICIPANTS}; // Assuming test fixtures are available
-    use crate::eddsa::keygen::save_data::{LocalPreParams, PaillierPublicKey};
-    use crate::eddsa::keygen::messages::DlnProof;
-    use num_bigint::BigInt;
+    use crate::crypto::dln::DlnParams;
     use num_traits::One;
-    use rand::rngs::OsRng;
-
-    // --- Placeholder for DLN Proof Generation (within tests) --- //
-    // TODO: Replace with actual proof generation from a crypto library
-    fn generate_dln_proof(pre_params: &LocalPreParams) -> Result<DlnProof, String> {
-        let h1i = pre_params.h1i.as_ref().ok_or("Missing h1i")?;
-        let h2i = pre_params.h2i.as_ref().ok_or("Missing h2i")?;
-        let alpha = pre_params.alpha.as_ref().ok_or("Missing alpha")?;
-        let p = pre_params.p.as_ref().ok_or("Missing p")?;
-        let q = pre_params.q.as_ref().ok_or("Missing q")?;
-        let n_tilde_i = pre_params.n_tilde_i.as_ref().ok_or("Missing n_tilde_i")?;
-        let mut rng = OsRng;
-
-        // Use the placeholder function defined elsewhere (e.g., round_1.rs or here)
-        println!("Warning: Using placeholder dlnproof::new for test proof generation.");
-        Ok(DlnProof(vec![1,2,3,4,5])) // Return a non-empty dummy proof
-        // Replace above with actual call when available:
-        // Ok(dlnproof::new(h1i, h2i, alpha, p, q, n_tilde_i, &mut rng))
-    }
-    // --- End Placeholder --- //
-
-    // Helper to prepare test data (load fixtures, generate proof)
-    fn prepare_data() -> Result<(LocalPreParams, DlnProof, DlnProof), String> {
-        // Load fixture data for the first party
-        // Using _eddsa_fixtures assuming they exist and match LocalPartySaveData format
-        let (fixtures, _) = load_keygen_test_fixtures(1, Some(0))
-            .map_err(|e| format!("Failed to load keygen fixtures: {}", e))?;
-        if fixtures.is_empty() {
-            return Err("No fixtures loaded".to_string());
-        }
-        let pre_params = fixtures[0].local_pre_params.clone();
-        if !pre_params.validate_with_proof() { // Use validation if available
-            return Err("Loaded pre-params failed validation".to_string());
-        }
+    use rand::thread_rng;
 
-        // Generate placeholder proofs
-        let proof1 = generate_dln_proof(&pre_params)?; // For H1, H2
-        let proof2 = generate_dln_proof(&pre_params)?; // For H2, H1 (using same placeholder for now)
+    const SESSION_ID: &[u8] = b"dln-verifier-test-session";
 
-        Ok((pre_params, proof1, proof2))
-    }
-
-    #[test]
-    fn test_verify_dln_proof1_success() {
-        let (pre_params, proof1, proof2) = prepare_data().expect("Failed to prepare test data");
+    fn build_message() -> (DlnParams, KGRound1Message) {
+        let mut rng = thread_rng();
+        let params = DlnParams::generate(&mut rng, 64);
+        let proof1 = params.prove_h1_to_h2(SESSION_ID, &mut rng);
+        let proof2 = params.prove_h2_to_h1(SESSION_ID, &mut rng);
 
-        // Create a KGRound1Message with the valid proof1
         let message = KGRound1Message {
-            commitment: crate::eddsa::keygen::messages::HashCommitment(vec![0]), // Dummy
-            paillier_pk: PaillierPublicKey { n: BigInt::one() }, // Dummy
-            n_tilde: pre_params.n_tilde_i.clone().unwrap(),
-            h1: pre_params.h1i.clone().unwrap(),
-            h2: pre_params.h2i.clone().unwrap(),
-            dln_proof_1: proof1,
-            dln_proof_2: proof2, // Include proof2 as well
+            commitment: vec![0],
+            n_tilde: params.n_tilde.to_bytes_be().1,
+            h1: params.h1.to_bytes_be().1,
+            h2: params.h2.to_bytes_be().1,
+            dln_proof_1: proof1.to_bytes(),
+            dln_proof_2: proof2.to_bytes(),
         };
+        (params, message)
+    }
 
+    #[test]
+    fn test_verify_dln_proof1_success() {
+        let (params, message) = build_message();
         let verifier = DlnProofVerifier::new(1);
-        let h1i = pre_params.h1i.as_ref().unwrap();
-        let h2i = pre_params.h2i.as_ref().unwrap();
-        let n_tilde_i = pre_params.n_tilde_i.as_ref().unwrap();
 
-        let result = verifier.verify_dln_proof_1(&message, h1i, h2i, n_tilde_i);
+        let result = verifier.verify_dln_proof_1(&message, SESSION_ID, &params.h1, &params.h2, &params.n_tilde);
         assert!(result, "DLNProof1 should verify successfully with correct data");
     }
 
     #[test]
     fn test_verify_dln_proof1_malformed_message() {
-        let (pre_params, mut proof1, proof2) = prepare_data().expect("Failed to prepare test data");
-
-        // Malform the proof (e.g., truncate)
-        if !proof1.0.is_empty() {
-            proof1.0.pop(); // Remove last byte
-        } else {
-            proof1.0 = vec![]; // Make it empty if it wasn't already
-        }
+        let (params, mut message) = build_message();
 
-        let message = KGRound1Message {
-            commitment: crate::eddsa::keygen::messages::HashCommitment(vec![0]),
-            paillier_pk: PaillierPublicKey { n: BigInt::one() },
-            n_tilde: pre_params.n_tilde_i.clone().unwrap(),
-            h1: pre_params.h1i.clone().unwrap(),
-            h2: pre_params.h2i.clone().unwrap(),
-            dln_proof_1: proof1,
-            dln_proof_2: proof2,
-        };
+        // Drop the serialized proof's last byte so from_bytes rejects it as
+        // a truncated body, instead of silently reading garbage.
+        message.dln_proof_1.pop();
 
         let verifier = DlnProofVerifier::new(1);
-        let h1i = pre_params.h1i.as_ref().unwrap();
-        let h2i = pre_params.h2i.as_ref().unwrap();
-        let n_tilde_i = pre_params.n_tilde_i.as_ref().unwrap();
-
-        // Verification might fail at unmarshalling or during verify itself
-        let result = verifier.verify_dln_proof_1(&message, h1i, h2i, n_tilde_i);
+        let result = verifier.verify_dln_proof_1(&message, SESSION_ID, &params.h1, &params.h2, &params.n_tilde);
         assert!(!result, "DLNProof1 should fail verification with malformed proof");
     }
 
     #[test]
     fn test_verify_dln_proof1_incorrect_parameters() {
-         let (pre_params, proof1, proof2) = prepare_data().expect("Failed to prepare test data");
-
-         let message = KGRound1Message {
-            commitment: crate::eddsa::keygen::messages::HashCommitment(vec![0]),
-            paillier_pk: PaillierPublicKey { n: BigInt::one() },
-            n_tilde: pre_params.n_tilde_i.clone().unwrap(),
-            h1: pre_params.h1i.clone().unwrap(),
-            h2: pre_params.h2i.clone().unwrap(),
-            dln_proof_1: proof1,
-            dln_proof_2: proof2,
-        };
+        let (params, message) = build_message();
+        let verifier = DlnProofVerifier::new(1);
 
-         let verifier = DlnProofVerifier::new(1);
-         let h1i = pre_params.h1i.as_ref().unwrap();
-         let h2i = pre_params.h2i.as_ref().unwrap();
-         let n_tilde_i = pre_params.n_tilde_i.as_ref().unwrap();
-
-         // Use incorrect parameters for verification
-         let wrong_h1i = h1i - BigInt::one();
-         let result = verifier.verify_dln_proof_1(&message, &wrong_h1i, h2i, n_tilde_i);
-         // Placeholder verify might pass, but real verification should fail
-         // assert!(!result, "DLNProof1 should fail verification with incorrect parameters");
-         println!("Note: Placeholder DLNProof verify() may not fail for incorrect params. Result: {}", result);
-         // For now, we assert based on the placeholder's behavior (which might just check non-emptiness)
-         if proof1.0.is_empty() { assert!(!result); } // Expect false if proof is empty
+        let wrong_h1 = &params.h1 - BigInt::one();
+        let result = verifier.verify_dln_proof_1(&message, SESSION_ID, &wrong_h1, &params.h2, &params.n_tilde);
+        assert!(!result, "DLNProof1 should fail verification with incorrect parameters");
     }
 
-    // --- Tests for DLNProof2 --- //
+    #[test]
+    fn test_verify_dln_proof1_rejects_wrong_session() {
+        let (params, message) = build_message();
+        let verifier = DlnProofVerifier::new(1);
+
+        let result = verifier.verify_dln_proof_1(&message, b"a-different-session", &params.h1, &params.h2, &params.n_tilde);
+        assert!(!result, "a proof bound to one session must not verify against another");
+    }
 
     #[test]
     fn test_verify_dln_proof2_success() {
-         let (pre_params, proof1, proof2) = prepare_data().expect("Failed to prepare test data");
-
-         let message = KGRound1Message {
-             commitment: crate::eddsa::keygen::messages::HashCommitment(vec![0]),
-             paillier_pk: PaillierPublicKey { n: BigInt::one() },
-             n_tilde: pre_params.n_tilde_i.clone().unwrap(),
-             h1: pre_params.h1i.clone().unwrap(),
-             h2: pre_params.h2i.clone().unwrap(),
-             dln_proof_1: proof1,
-             dln_proof_2: proof2,
-         };
-
-         let verifier = DlnProofVerifier::new(1);
-         let h1i = pre_params.h1i.as_ref().unwrap();
-         let h2i = pre_params.h2i.as_ref().unwrap();
-         let n_tilde_i = pre_params.n_tilde_i.as_ref().unwrap();
-
-         // Note: We pass H1, H2, N arguments, but the DLNProof2 inside message corresponds to H2, H1, N
-         let result = verifier.verify_dln_proof_2(&message, h1i, h2i, n_tilde_i);
-         assert!(result, "DLNProof2 should verify successfully with correct data");
+        let (params, message) = build_message();
+        let verifier = DlnProofVerifier::new(1);
+
+        // DLNProof2 proves log_h2(h1), so pass h1/h2 in the same order the
+        // caller uses for proof1; verify_dln_proof_2 swaps them internally.
+        let result = verifier.verify_dln_proof_2(&message, SESSION_ID, &params.h1, &params.h2, &params.n_tilde);
+        assert!(result, "DLNProof2 should verify successfully with correct data");
     }
 
     #[test]
     fn test_verify_dln_proof2_malformed_message() {
-         let (pre_params, proof1, mut proof2) = prepare_data().expect("Failed to prepare test data");
-
-         // Malform the proof
-         if !proof2.0.is_empty() {
-             proof2.0.pop();
-         } else {
-             proof2.0 = vec![];
-         }
-
-         let message = KGRound1Message {
-             commitment: crate::eddsa::keygen::messages::HashCommitment(vec![0]),
-             paillier_pk: PaillierPublicKey { n: BigInt::one() },
-             n_tilde: pre_params.n_tilde_i.clone().unwrap(),
-             h1: pre_params.h1i.clone().unwrap(),
-             h2: pre_params.h2i.clone().unwrap(),
-             dln_proof_1: proof1,
-             dln_proof_2: proof2,
-         };
-
-         let verifier = DlnProofVerifier::new(1);
-         let h1i = pre_params.h1i.as_ref().unwrap();
-         let h2i = pre_params.h2i.as_ref().unwrap();
-         let n_tilde_i = pre_params.n_tilde_i.as_ref().unwrap();
-
-         let result = verifier.verify_dln_proof_2(&message, h1i, h2i, n_tilde_i);
-         assert!(!result, "DLNProof2 should fail verification with malformed proof");
+        let (params, mut message) = build_message();
+        message.dln_proof_2.pop();
+
+        let verifier = DlnProofVerifier::new(1);
+        let result = verifier.verify_dln_proof_2(&message, SESSION_ID, &params.h1, &params.h2, &params.n_tilde);
+        assert!(!result, "DLNProof2 should fail verification with malformed proof");
+    }
+
+    #[test]
+    fn test_verify_dln_proof2_incorrect_parameters() {
+        let (params, message) = build_message();
+        let verifier = DlnProofVerifier::new(1);
+
+        let wrong_h2 = &params.h2 + BigInt::one();
+        let result = verifier.verify_dln_proof_2(&message, SESSION_ID, &params.h1, &wrong_h2, &params.n_tilde);
+        assert!(!result, "DLNProof2 should fail verification with incorrect parameters");
+    }
+
+    #[test]
+    #[should_panic(expected = "concurrency level must not be zero")]
+    fn test_new_panics_on_zero_concurrency() {
+        DlnProofVerifier::new(0);
     }
 
-     #[test]
-     fn test_verify_dln_proof2_incorrect_parameters() {
-         let (pre_params, proof1, proof2) = prepare_data().expect("Failed to prepare test data");
-
-         let message = KGRound1Message {
-             commitment: crate::eddsa::keygen::messages::HashCommitment(vec![0]),
-             paillier_pk: PaillierPublicKey { n: BigInt::one() },
-             n_tilde: pre_params.n_tilde_i.clone().unwrap(),
-             h1: pre_params.h1i.clone().unwrap(),
-             h2: pre_params.h2i.clone().unwrap(),
-             dln_proof_1: proof1,
-             dln_proof_2: proof2,
-         };
-
-         let verifier = DlnProofVerifier::new(1);
-         let h1i = pre_params.h1i.as_ref().unwrap();
-         let h2i = pre_params.h2i.as_ref().unwrap();
-         let n_tilde_i = pre_params.n_tilde_i.as_ref().unwrap();
-
-         // Use incorrect parameters for verification
-         let wrong_h2i = h2i + BigInt::one();
-         let result = verifier.verify_dln_proof_2(&message, h1i, &wrong_h2i, n_tilde_i);
-         // Placeholder verify might pass, but real verification should fail
-         // assert!(!result, "DLNProof2 should fail verification with incorrect parameters");
-          println!("Note: Placeholder DLNProof verify() may not fail for incorrect params. Result: {}", result);
-         // For now, we assert based on the placeholder's behavior
-         if proof2.0.is_empty() { assert!(!result); }
-     }
-
-    // TODO: Add benchmarks using Criterion.rs if needed, translating the logic from Go benchmarks.
-} 
\ No newline at end of file
+    #[test]
+    fn test_verify_batch_all_valid() {
+        let (params1, message1) = build_message();
+        let (params2, message2) = build_message();
+        let verifier = DlnProofVerifier::new(4);
+
+        let batch = vec![
+            (message1, params1.h1.clone(), params1.h2.clone(), params1.n_tilde.clone()),
+            (message2, params2.h1.clone(), params2.h2.clone(), params2.n_tilde.clone()),
+        ];
+        let results = verifier.verify_batch(SESSION_ID, &batch);
+        assert_eq!(results, vec![true, true]);
+    }
+
+    #[test]
+    fn test_verify_batch_reports_bad_entry() {
+        let (params1, message1) = build_message();
+        let (params2, mut message2) = build_message();
+        message2.dln_proof_1.pop();
+        let verifier = DlnProofVerifier::new(4);
+
+        let batch = vec![
+            (message1, params1.h1.clone(), params1.h2.clone(), params1.n_tilde.clone()),
+            (message2, params2.h1.clone(), params2.h2.clone(), params2.n_tilde.clone()),
+        ];
+        let results = verifier.verify_batch(SESSION_ID, &batch);
+        assert_eq!(results, vec![true, false]);
+    }
+}
@@ -21,9 +21,23 @@ pub enum TssError {
     // More general errors adapted from tss::Error concept
     BaseError { message: String },
     RoundError { message: String, round: u32, culprits: Vec<PartyID> },
+    /// A round-2 message failed cryptographic validation at store time (bad
+    /// VSS share, decommitment that doesn't open the round-1 commitment, or
+    /// an invalid Schnorr proof of possession) -- see
+    /// `messages::verify_round2_broadcast`/`verify_round2_share`. Unlike
+    /// `RoundError`, this always means the session cannot complete: a
+    /// dishonest peer was caught sending malformed cryptographic material,
+    /// not a transient conflict, so the only correct response is to abort
+    /// and report the culprits rather than retry.
+    KeygenAbort { culprits: Vec<PartyID>, reason: String },
     InternalError { message: String },
     LockPoisonError(String),
     ChannelSendError(String),
+    /// `BaseParty::poll_timeouts` found one or more expected senders whose
+    /// deadline for the current round passed without a message arriving.
+    /// Callers can use `missing` to abort the session and identify the
+    /// non-responsive participants instead of blocking forever.
+    Timeout { missing: Vec<PartyID> },
 
     // Add other variants as needed
 }
@@ -49,11 +63,14 @@ impl fmt::Display for TssError {
             TssError::UnexpectedMessageReceived => write!(f, "Received message unexpected in this round/state"),
             TssError::ProceedCalledWhenNotReady => write!(f, "Proceed called before round could proceed"),
             TssError::BaseError { message } => write!(f, "Base party error: {}", message),
-            TssError::RoundError { message, round, culprits } => 
+            TssError::RoundError { message, round, culprits } =>
                 write!(f, "Round {} error (culprits: {:?}): {}", round, culprits, message),
+            TssError::KeygenAbort { culprits, reason } =>
+                write!(f, "Keygen aborted (culprits: {:?}): {}", culprits, reason),
             TssError::InternalError { message } => write!(f, "Internal error: {}", message),
             TssError::LockPoisonError(s) => write!(f, "Mutex lock poison error: {}", s),
             TssError::ChannelSendError(s) => write!(f, "Channel send error: {}", s),
+            TssError::Timeout { missing } => write!(f, "Round timed out waiting for: {:?}", missing),
         }
     }
 }
@@ -37,6 +37,7 @@ use crate::eddsa::keygen::local_party::KeygenPartyTempData; // Use concrete temp
 use crate::eddsa::keygen::TssError; // Import keygen::TssError
 use crate::eddsa::keygen::rounds::KeygenRound; // Import the keygen trait
 use crate::tss::wire; // For parsing
+use crate::tss::transport::Transport;
 // --- End Keygen Specific Imports ---
 
 use std::collections::HashMap;
@@ -48,6 +49,14 @@ use crate::tss::wire; // Import wire helpers
 #[derive(Clone, Debug)]
 pub struct KeygenPartyTempData {
     // ... (fields as before)
+
+    /// The qualified set `Q` agreed by `tss::acs::Acs` over round-1
+    /// contributions: party indices every honest party agreed to include.
+    /// `None` until ACS terminates. `Round3` restricts VSS share
+    /// reconstruction and public-key derivation to exactly this set rather
+    /// than to "whichever round-2 messages happened to arrive locally",
+    /// so two honest parties can no longer diverge on the result.
+    pub qualified_set: Option<std::collections::HashSet<usize>>,
 }
 impl KeygenPartyTempData {
     // ... (new method as before)
@@ -65,12 +74,19 @@ pub struct LocalParty {
     base: BaseParty,
     // Add field to hold the current round
     current_round: Option<Box<dyn KeygenRound>>,
+    /// Messages that arrived tagged for a round later than the one
+    /// currently running, keyed by that round's number. Parked here instead
+    /// of being stored (the round that would validate/route them hasn't
+    /// started yet) and replayed through the normal
+    /// `store_message`/`can_proceed`/`proceed` path as soon as that round
+    /// starts, so an early-arriving peer message is never lost.
+    early_messages: HashMap<u32, Vec<ParsedMessage>>,
 }
 
 impl LocalParty {
     pub fn new(
         params: Parameters,
-        out_channel: Sender<TssMessage>,
+        transport: Arc<dyn Transport>,
         end_channel: Sender<KeygenPartySaveData>,
     ) -> Result<Self, TssError> {
         let party_id = params.party_id().clone();
@@ -91,7 +107,7 @@ impl LocalParty {
             shared_params.clone(),
             shared_data.clone(),
             shared_temp.clone(),
-            out_channel.clone(), // Clone for round
+            transport.clone(), // Clone for round
             end_channel.clone(),   // Clone for round
         );
 
@@ -100,7 +116,7 @@ impl LocalParty {
             shared_params.clone(),
             shared_temp.clone(),
             shared_data.clone(),
-            out_channel,
+            transport,
             1, // Starting round number
         ).with_end_channel(end_channel);
 
@@ -110,6 +126,7 @@ impl LocalParty {
             data: shared_data,
             base,
             current_round: Some(first_round), // Initialize with Round 1
+            early_messages: HashMap::new(),
         })
     }
 
@@ -130,44 +147,72 @@ impl LocalParty {
             return Err(TssError::BaseError{ message: "Cannot update party that is not running".to_string() });
         }
 
-        // 1. Parse message (using tss::wire, expect panic for now)
-        let parsed_msg = wire::parse_msg(wire_bytes, from, is_broadcast)
+        // 1. Parse message off the wire; the routing header now carries the
+        // round it was sent for (see `tss::message_pb::MessageWrapper::round_number`).
+        let parsed_msg = wire::parse_wire_message(wire_bytes, from, is_broadcast)
             .map_err(|e| self.base.wrap_base_error(format!("Wire parse error: {}", e)))?;
 
-        // Check if message is for the current round
-        // TODO: Need round info from parsed_msg or wire protocol
-        // if parsed_msg.round_number() != current_round_num { ... error ... }
-
         // 2. Validate sender
         self.validate_message_sender(&parsed_msg)?; // Call helper
 
-        // 3. Store message via the current round
+        // 3. Route by round: current-round messages are applied now,
+        // future-round messages are parked until that round starts, and
+        // messages for an already-completed round are dropped.
+        let msg_round = parsed_msg.round_number();
+        if msg_round > current_round_num {
+            log::warn!(
+                "parking message for round {} (currently on round {}) from {:?}",
+                msg_round, current_round_num, parsed_msg.from()
+            );
+            self.early_messages.entry(msg_round).or_insert_with(Vec::new).push(parsed_msg);
+            return Ok(());
+        }
+        if msg_round < current_round_num {
+            log::warn!(
+                "dropping message for already-completed round {} (currently on round {}) from {:?}",
+                msg_round, current_round_num, parsed_msg.from()
+            );
+            return Ok(());
+        }
+
+        self.apply_message(parsed_msg)
+    }
+
+    // Feeds one message for the current round through
+    // store_message/can_proceed/proceed, advancing (and replaying any
+    // parked messages for the new round) whenever the round completes.
+    fn apply_message(&mut self, parsed_msg: ParsedMessage) -> Result<(), TssError> {
         if let Some(round) = self.current_round.as_mut() {
             // store_message should validate content type and call base.set_ok
             round.store_message(parsed_msg)?;
 
-            // 4. Check if we can proceed
+            // Check if we can proceed
             if round.can_proceed() {
                 round.proceed()?; // Perform round logic
 
-                // 5. Advance to next round
+                // Advance to next round
                 // Take ownership of the current round Box to call next_round
                 if let Some(finished_round) = self.current_round.take() {
                     self.current_round = finished_round.next_round();
                     // Start the new round immediately if it exists
                     if let Some(new_round) = self.current_round.as_mut() {
                         new_round.start()?;
+                        let new_round_num = new_round.round_number();
+                        let parked = self.early_messages.remove(&new_round_num).unwrap_or_default();
+                        for parked_msg in parked {
+                            self.apply_message(parked_msg)?;
+                        }
                     }
                 } else {
                     // Should not happen if we just took it
                     return Err(TssError::InternalError{ message: "Failed to take ownership of round for advancing".to_string() });
                 }
             }
+            Ok(())
         } else {
             // Party finished, but received another message?
-            return Err(TssError::BaseError{ message: "Received message after party finished".to_string() });
+            Err(TssError::BaseError{ message: "Received message after party finished".to_string() })
         }
-        Ok(())
     }
 
     // Helper for validating message sender (subset of old validate_message)
@@ -195,6 +240,23 @@ impl LocalParty {
          self.current_round.as_ref().map(|r| r.base().waiting_for())
     }
 
+    /// Checks the current round's armed deadlines and reports any party
+    /// whose deadline has passed without a message arriving. Callers should
+    /// call this periodically (e.g. on a tick alongside the message router)
+    /// so a stalled or offline party can be detected deterministically
+    /// instead of blocking `update_from_bytes` forever.
+    pub fn poll_timeouts(&mut self) -> Result<(), TssError> {
+        let missing = match self.current_round.as_mut() {
+            Some(round) => round.base_mut().poll_timeouts(),
+            None => return Ok(()),
+        };
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(TssError::Timeout { missing })
+        }
+    }
+
     pub fn round_number(&self) -> Option<u32> {
         self.current_round.as_ref().map(|r| r.round_number())
     }
@@ -214,6 +276,7 @@ mod tests {
     use crate::tss::generate_test_party_ids;
     use crate::tss::new_peer_context;
     use crate::tss::curve::CurveName; // Import CurveName
+    use crate::tss::transport::ChannelTransport;
     use std::sync::mpsc::channel;
 
     // Helper uses keygen::Parameters
@@ -235,8 +298,9 @@ mod tests {
          let params = create_test_params("p1", 0, party_count, threshold);
          let (out_tx, _) = channel();
          let (end_tx, _) = channel();
+         let transport = Arc::new(ChannelTransport::new(out_tx));
 
-         let party_result = LocalParty::new(params, out_tx, end_tx);
+         let party_result = LocalParty::new(params, transport, end_tx);
 
          assert!(party_result.is_ok());
          let party = party_result.unwrap();
@@ -12,10 +12,16 @@ use crate::tss::message::{MessageContent}; // Use tss trait
 use crate::tss::party_id::PartyID;
 
 // Crypto imports
-use crate::crypto::commitments::hash_commit_decommit::{Commitment as HashCommitment, Decommitment as HashDeCommitment};
+use crate::crypto::commitments::{HashCommitDecommit, HashCommitment};
 use crate::crypto::vss::Share as VssShare; // Use actual VssShare type
-use crate::crypto::schnorr::Proof as SchnorrProof; // Use actual SchnorrProof type
-use ed25519_dalek::EdwardsPoint; // Use concrete point type
+pub use crate::crypto::dln::Proof as DlnProof; // Ring-Pedersen discrete-log-equality proof
+use num_bigint::Sign;
+
+// Round-2's decommitment and Schnorr proof are generic over the signing
+// curve (see `curve.rs`) instead of hardcoding `ed25519_dalek::EdwardsPoint`,
+// so a run can be conducted over Ristretto255 or Ed448-Goldilocks by
+// selecting a different `EdCurve` impl rather than forking this file.
+use crate::eddsa::keygen::curve::{verify_feldman_share, EdCurve, EdDecommitment, EdSchnorrProof};
 
 // --- Remove Placeholders --- //
 /*
@@ -32,17 +38,50 @@ use ed25519_dalek::EdwardsPoint; // Use concrete point type
 pub struct KGRound1Message {
     #[prost(bytes="vec", tag="1")]
     pub commitment: Vec<u8>,
+    // Ring-Pedersen (DLN) auxiliary parameters, restored so the range proofs
+    // used in later rounds have a Ntilde/h1/h2 to anchor to.
+    #[prost(bytes="vec", tag="2")]
+    pub n_tilde: Vec<u8>,
+    #[prost(bytes="vec", tag="3")]
+    pub h1: Vec<u8>,
+    #[prost(bytes="vec", tag="4")]
+    pub h2: Vec<u8>,
+    #[prost(bytes="vec", tag="5")]
+    pub dln_proof_1: Vec<u8>,
+    #[prost(bytes="vec", tag="6")]
+    pub dln_proof_2: Vec<u8>,
 }
 
 impl KGRound1Message {
     pub fn validate(&self) -> bool {
-        !self.commitment.is_empty()
+        !self.commitment.is_empty() && !self.n_tilde.is_empty() && !self.h1.is_empty() && !self.h2.is_empty()
+            && !self.dln_proof_1.is_empty() && !self.dln_proof_2.is_empty()
     }
 
     // Corresponds to Go UnmarshalCommitment()
     pub fn unmarshal_commitment(&self) -> BigInt {
         BigInt::from_bytes_be(num_bigint::Sign::Plus, &self.commitment)
     }
+
+    pub fn unmarshal_n_tilde(&self) -> BigInt {
+        BigInt::from_bytes_be(num_bigint::Sign::Plus, &self.n_tilde)
+    }
+
+    pub fn unmarshal_h1(&self) -> BigInt {
+        BigInt::from_bytes_be(num_bigint::Sign::Plus, &self.h1)
+    }
+
+    pub fn unmarshal_h2(&self) -> BigInt {
+        BigInt::from_bytes_be(num_bigint::Sign::Plus, &self.h2)
+    }
+
+    pub fn unmarshal_dln_proof_1(&self) -> Result<DlnProof, String> {
+        DlnProof::from_bytes(&self.dln_proof_1)
+    }
+
+    pub fn unmarshal_dln_proof_2(&self) -> Result<DlnProof, String> {
+        DlnProof::from_bytes(&self.dln_proof_2)
+    }
 }
 
 // Define trait implementation for MessageContent
@@ -80,8 +119,8 @@ impl MessageContent for KGRound2Message1 {
 #[derive(Clone, PartialEq, Message)]
 pub struct KGRound2Message2 {
     #[prost(bytes="vec", repeated, tag="1")]
-    pub decommitment: Vec<Vec<u8>>, // Assuming HashDeCommitment bytes
-    // Schnorr proof bytes (need concrete serialization for SchnorrProof)
+    pub decommitment: Vec<Vec<u8>>, // EdDecommitment<C>::to_bytes() output
+    // EdSchnorrProof<C>::to_bytes() output
     #[prost(bytes="vec", tag="2")]
     pub proof_bytes: Vec<u8>,
     // Removed separate proof fields
@@ -96,17 +135,19 @@ impl KGRound2Message2 {
         !self.proof_bytes.is_empty()
     }
 
-    // TODO: Update unmarshalling based on concrete types
-    pub fn unmarshal_decommitment(&self) -> Result<HashDeCommitment, TssError> {
-        // Assuming HashDeCommitment::from_bytes exists
-        unimplemented!("unmarshal_decommitment needs concrete HashDeCommitment type");
-        // Ok(HashDeCommitment::from_bytes(&self.decommitment)?)
+    /// Decodes `decommitment` into the Feldman commitment vector plus the
+    /// randomness it was bound with, over whichever curve `C` the run is
+    /// using. Only checks that the bytes parse -- does not re-derive and
+    /// compare against the round-1 hash commitment; use
+    /// `verify_round2_broadcast` for that.
+    pub fn unmarshal_decommitment<C: EdCurve>(&self) -> Result<EdDecommitment<C>, TssError> {
+        EdDecommitment::<C>::from_bytes(&self.decommitment)
+            .ok_or_else(|| TssError::MessageParseError("malformed round-2 decommitment".into()))
     }
 
-    pub fn unmarshal_zk_proof(&self) -> Result<SchnorrProof, TssError> {
-        // Assuming SchnorrProof::from_bytes exists
-        unimplemented!("unmarshal_zk_proof needs concrete SchnorrProof type");
-        // Ok(SchnorrProof::from_bytes(&self.proof_bytes)?)
+    pub fn unmarshal_zk_proof<C: EdCurve>(&self) -> Result<EdSchnorrProof<C>, TssError> {
+        EdSchnorrProof::<C>::from_bytes(&self.proof_bytes)
+            .ok_or_else(|| TssError::MessageParseError("malformed round-2 Schnorr proof".into()))
     }
 }
 
@@ -127,9 +168,19 @@ impl MessageContent for KGRound2Message2 {
 
 pub fn new_kg_round1_message(
     commitment: &HashCommitment, // Use actual HashCommitment
+    n_tilde: &BigInt,
+    h1: &BigInt,
+    h2: &BigInt,
+    dln_proof_1: &DlnProof,
+    dln_proof_2: &DlnProof,
 ) -> KGRound1Message {
     KGRound1Message {
         commitment: commitment.to_bytes(), // Assuming to_bytes() exists
+        n_tilde: n_tilde.to_bytes_be().1,
+        h1: h1.to_bytes_be().1,
+        h2: h2.to_bytes_be().1,
+        dln_proof_1: dln_proof_1.to_bytes(),
+        dln_proof_2: dln_proof_2.to_bytes(),
     }
 }
 
@@ -141,13 +192,13 @@ pub fn new_kg_round2_message1(
     }
 }
 
-pub fn new_kg_round2_message2(
-    decommitment: &HashDeCommitment,
-    proof: &SchnorrProof,
+pub fn new_kg_round2_message2<C: EdCurve>(
+    decommitment: &EdDecommitment<C>,
+    proof: &EdSchnorrProof<C>,
 ) -> KGRound2Message2 {
     KGRound2Message2 {
-        decommitment: decommitment.to_bytes(), // Assuming to_bytes() exists
-        proof_bytes: proof.to_bytes(), // Assuming to_bytes() exists
+        decommitment: decommitment.to_bytes(),
+        proof_bytes: proof.to_bytes(),
     }
 }
 
@@ -157,6 +208,194 @@ pub fn parse_message_from_payload<T: Message + Default>(payload: &[u8]) -> Resul
      T::decode(payload)
 }
 
+// --- Self-Describing Message Envelope --- //
+// Round 2 has two message types -- a P2P share and a broadcast commitment
+// reveal -- with no way to tell which one a given `wire_bytes` blob is
+// without attempting both decoders. `MessageEnvelope` stamps every
+// keygen message with its declared `KeygenMessageType` so `store_message`
+// can route deterministically and reject a message whose declared type
+// disagrees with how it actually arrived (broadcast vs P2P) before
+// attempting to decode, let alone cryptographically verify, its payload.
+// The commented-out `store_message` sketch below shows that routing.
+
+/// Which keygen content type a `MessageEnvelope::payload` holds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeygenMessageType {
+    Round1 = 0,
+    Round2P2PShare = 1,
+    Round2Broadcast = 2,
+}
+
+impl KeygenMessageType {
+    /// Whether this type is expected to arrive as a broadcast (`true`) or
+    /// P2P (`false`). `parse_envelope` compares this against how the
+    /// message actually arrived.
+    pub fn is_broadcast(self) -> bool {
+        matches!(self, KeygenMessageType::Round1 | KeygenMessageType::Round2Broadcast)
+    }
+
+    fn to_i32(self) -> i32 {
+        self as i32
+    }
+
+    fn from_i32(value: i32) -> Option<Self> {
+        match value {
+            0 => Some(KeygenMessageType::Round1),
+            1 => Some(KeygenMessageType::Round2P2PShare),
+            2 => Some(KeygenMessageType::Round2Broadcast),
+            _ => None,
+        }
+    }
+}
+
+/// A keygen content message tagged with its `KeygenMessageType`, so the
+/// receiver doesn't have to guess which decoder to try.
+#[derive(Clone, PartialEq, Message)]
+pub struct MessageEnvelope {
+    #[prost(int32, tag="1")]
+    pub msg_type: i32,
+    #[prost(bytes="vec", tag="2")]
+    pub payload: Vec<u8>,
+}
+
+impl MessageEnvelope {
+    pub fn wrap(msg_type: KeygenMessageType, content: &impl Message) -> Self {
+        MessageEnvelope {
+            msg_type: msg_type.to_i32(),
+            payload: content.encode_to_vec(),
+        }
+    }
+
+    pub fn message_type(&self) -> Result<KeygenMessageType, TssError> {
+        KeygenMessageType::from_i32(self.msg_type)
+            .ok_or_else(|| TssError::MessageParseError(format!("unknown keygen message type tag {}", self.msg_type)))
+    }
+}
+
+/// Stamps a `KGRound1Message` with its `KeygenMessageType`.
+pub fn new_kg_round1_envelope(
+    commitment: &HashCommitment,
+    n_tilde: &BigInt,
+    h1: &BigInt,
+    h2: &BigInt,
+    dln_proof_1: &DlnProof,
+    dln_proof_2: &DlnProof,
+) -> MessageEnvelope {
+    let content = new_kg_round1_message(commitment, n_tilde, h1, h2, dln_proof_1, dln_proof_2);
+    MessageEnvelope::wrap(KeygenMessageType::Round1, &content)
+}
+
+/// Stamps a `KGRound2Message1` (the P2P VSS share) with its `KeygenMessageType`.
+pub fn new_kg_round2_message1_envelope(share: &VssShare) -> MessageEnvelope {
+    MessageEnvelope::wrap(KeygenMessageType::Round2P2PShare, &new_kg_round2_message1(share))
+}
+
+/// Stamps a `KGRound2Message2` (the broadcast decommitment + proof) with its
+/// `KeygenMessageType`.
+pub fn new_kg_round2_message2_envelope<C: EdCurve>(
+    decommitment: &EdDecommitment<C>,
+    proof: &EdSchnorrProof<C>,
+) -> MessageEnvelope {
+    MessageEnvelope::wrap(KeygenMessageType::Round2Broadcast, &new_kg_round2_message2(decommitment, proof))
+}
+
+/// Decodes `wire_bytes` as a `MessageEnvelope` and checks its declared
+/// type's expected broadcast-ness against `is_broadcast` (how the message
+/// actually arrived), closing the gap where a P2P share could masquerade as
+/// round-2's broadcast message or vice versa. Returns the declared type
+/// plus the still-encoded inner payload for the caller to decode with
+/// `parse_message_from_payload` into the concrete content struct that type
+/// names.
+pub fn parse_envelope(wire_bytes: &[u8], is_broadcast: bool) -> Result<(KeygenMessageType, Vec<u8>), TssError> {
+    let envelope = MessageEnvelope::decode(wire_bytes)
+        .map_err(|e| TssError::MessageParseError(format!("failed to decode message envelope: {}", e)))?;
+    let msg_type = envelope.message_type()?;
+    if msg_type.is_broadcast() != is_broadcast {
+        return Err(TssError::MessageParseError(format!(
+            "message declared type {:?} but arrived with is_broadcast={}",
+            msg_type, is_broadcast
+        )));
+    }
+    Ok((msg_type, envelope.payload))
+}
+
+// --- Cryptographic Validation --- //
+// `validate()` above only checks that the wire fields are non-empty (a
+// malformed-message check); these functions do the actual cryptographic
+// verification a round must run before trusting a peer's content, matching
+// `simplpedpop_messages::verify_dealer_message`'s split between structural
+// and cryptographic validation. A failure here means a peer sent
+// cryptographically invalid material -- not a transient disagreement -- so
+// callers should treat it as fatal: abort the session with
+// `TssError::KeygenAbort { culprits: vec![sender], .. }` rather than retry.
+
+/// Re-derives the round-1 hash commitment from `decommitment` and checks it
+/// against `commitment`, opening the commit-reveal pair from round 1.
+fn verify_decommitment<C: EdCurve>(commitment: &HashCommitment, decommitment: &EdDecommitment<C>) -> bool {
+    let mut parts: Vec<BigInt> = Vec::with_capacity(decommitment.commitments.len() + 1);
+    parts.push(BigInt::from_bytes_be(Sign::Plus, &decommitment.randomness));
+    parts.extend(
+        decommitment
+            .commitments
+            .iter()
+            .map(|c| BigInt::from_bytes_be(Sign::Plus, &C::point_to_bytes(c))),
+    );
+    HashCommitDecommit { c: commitment.clone(), d: parts }.verify()
+}
+
+/// Verifies a sender's round-2 broadcast (`KGRound2Message2`) against their
+/// round-1 commitment (`KGRound1Message`): opens the hash commitment, then
+/// checks the Schnorr proof of possession of the vector's constant term
+/// `commitments[0]` (so a party can't broadcast a commitment vector it
+/// doesn't actually hold the secret for). On success, returns the opened
+/// Feldman commitment vector for `verify_round2_share` to check shares
+/// against.
+pub fn verify_round2_broadcast<C: EdCurve>(
+    sender: &PartyID,
+    round1: &KGRound1Message,
+    round2: &KGRound2Message2,
+    context: &[u8],
+) -> Result<Vec<C::Point>, TssError> {
+    let abort = |reason: String| TssError::KeygenAbort { culprits: vec![sender.clone()], reason };
+
+    let decommitment = round2.unmarshal_decommitment::<C>().map_err(|e| abort(e.to_string()))?;
+    let commitment = round1.unmarshal_commitment();
+    if !verify_decommitment(&commitment, &decommitment) {
+        return Err(abort("decommitment does not open the round-1 hash commitment".to_string()));
+    }
+
+    let proof = round2.unmarshal_zk_proof::<C>().map_err(|e| abort(e.to_string()))?;
+    let c0 = decommitment
+        .commitments
+        .first()
+        .ok_or_else(|| abort("empty Feldman commitment vector".to_string()))?;
+    if !proof.verify(c0, context) {
+        return Err(abort("Schnorr proof of possession failed to verify".to_string()));
+    }
+
+    Ok(decommitment.commitments)
+}
+
+/// Verifies a sender's round-2 P2P share (`KGRound2Message1`) against the
+/// (already-opened, via `verify_round2_broadcast`) Feldman commitment
+/// vector they broadcast, via the homomorphic check
+/// `g^share == Π commitments_j^{recipient_index^j}`.
+pub fn verify_round2_share<C: EdCurve>(
+    sender: &PartyID,
+    recipient_index: u32,
+    share_msg: &KGRound2Message1,
+    commitments: &[C::Point],
+) -> Result<(), TssError> {
+    let abort = |reason: String| TssError::KeygenAbort { culprits: vec![sender.clone()], reason };
+
+    let share = C::scalar_from_bytes(&share_msg.share)
+        .ok_or_else(|| abort("malformed round-2 share".to_string()))?;
+    if !verify_feldman_share::<C>(recipient_index, &share, commitments) {
+        return Err(abort("share fails Feldman verification against the broadcast commitment vector".to_string()));
+    }
+    Ok(())
+}
+
 
 // Example of how LocalParty::store_message might use this:
 /*
@@ -177,19 +416,24 @@ fn store_message(&mut self, msg: ParsedMessage) -> Result<(), TssError> {
             } else { return Err(TssError::InvalidMessage); }
         }
         2 => {
-            // Need a way to distinguish R2M1 from R2M2 from wire_bytes
-            // Maybe a type hint field in ParsedMessage/MessageRouting?
-            // Or try parsing both?
-            if let Ok(r2m1) = parse_message_from_payload::<KGRound2Message1>(&msg.wire_bytes) {
-                 if r2m1.validate_basic() && !msg.is_broadcast() {
-                     temp_guard.round_2_messages1.insert(from_id, r2m1);
-                 } else { return Err(TssError::InvalidMessage); }
-            } else if let Ok(r2m2) = parse_message_from_payload::<KGRound2Message2>(&msg.wire_bytes) {
-                 if r2m2.validate_basic() && msg.is_broadcast() {
-                     temp_guard.round_2_messages2.insert(from_id, r2m2);
-                 } else { return Err(TssError::InvalidMessage); }
-            } else {
-                 return Err(TssError::InvalidMessage); // Couldn't parse as R2M1 or R2M2
+            // `parse_envelope` rejects a message whose declared type
+            // disagrees with msg.is_broadcast() before we even look at
+            // which content struct to decode it as.
+            let (msg_type, payload) = parse_envelope(&msg.wire_bytes, msg.is_broadcast())?;
+            match msg_type {
+                KeygenMessageType::Round2P2PShare => {
+                    let r2m1: KGRound2Message1 = parse_message_from_payload(&payload)?;
+                    if r2m1.validate_basic() {
+                        temp_guard.round_2_messages1.insert(from_id, r2m1);
+                    } else { return Err(TssError::InvalidMessage); }
+                }
+                KeygenMessageType::Round2Broadcast => {
+                    let r2m2: KGRound2Message2 = parse_message_from_payload(&payload)?;
+                    if r2m2.validate_basic() {
+                        temp_guard.round_2_messages2.insert(from_id, r2m2);
+                    } else { return Err(TssError::InvalidMessage); }
+                }
+                KeygenMessageType::Round1 => return Err(TssError::InvalidMessage), // Wrong round
             }
         }
         _ => return Err(TssError::UnexpectedMessageReceived), // No messages expected later
@@ -198,3 +442,139 @@ fn store_message(&mut self, msg: ParsedMessage) -> Result<(), TssError> {
     Ok(())
 }
 */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eddsa::keygen::curve::Ed25519Curve;
+    use ed25519_dalek::{constants::ED25519_BASEPOINT_POINT, EdwardsPoint, Scalar};
+    use rand::rngs::OsRng;
+
+    fn test_sender() -> PartyID {
+        PartyID::new("1".to_string(), "party-1".to_string(), BigInt::from(1))
+    }
+
+    fn feldman_setup(threshold: usize) -> (Vec<Scalar>, Vec<EdwardsPoint>) {
+        let coeffs: Vec<Scalar> = (0..=threshold).map(|_| Scalar::random(&mut OsRng)).collect();
+        let commitments: Vec<EdwardsPoint> = coeffs.iter().map(|a| ED25519_BASEPOINT_POINT * a).collect();
+        (coeffs, commitments)
+    }
+
+    fn round2_broadcast_for(
+        coeffs: &[Scalar],
+        commitments: &[EdwardsPoint],
+        identity: &[u8],
+    ) -> (KGRound1Message, KGRound2Message2) {
+        let randomness = [7u8; 32];
+        let decommitment = EdDecommitment::<Ed25519Curve> { randomness, commitments: commitments.to_vec() };
+        let secrets: Vec<BigInt> = commitments
+            .iter()
+            .map(|c| BigInt::from_bytes_be(Sign::Plus, c.compress().as_bytes()))
+            .collect();
+        let commit = HashCommitDecommit::new_with_randomness(BigInt::from_bytes_be(Sign::Plus, &randomness), &secrets);
+        assert!(commit.verify());
+
+        let r_scalar = Scalar::random(&mut OsRng);
+        let r_point = ED25519_BASEPOINT_POINT * r_scalar;
+        let mut challenge_input = r_point.compress().as_bytes().to_vec();
+        challenge_input.extend(commitments[0].compress().as_bytes());
+        challenge_input.extend_from_slice(identity);
+        let c = Ed25519Curve::hash_to_scalar(&challenge_input);
+        let s = r_scalar + c * coeffs[0];
+        let proof = EdSchnorrProof::<Ed25519Curve> { r: r_point, s };
+
+        let round1 = KGRound1Message {
+            commitment: commit.c.to_bytes_be().1,
+            n_tilde: vec![1],
+            h1: vec![1],
+            h2: vec![1],
+            dln_proof_1: vec![],
+            dln_proof_2: vec![],
+        };
+        let round2 = new_kg_round2_message2(&decommitment, &proof);
+        (round1, round2)
+    }
+
+    #[test]
+    fn verify_round2_broadcast_accepts_honest_message() {
+        let (coeffs, commitments) = feldman_setup(2);
+        let identity = b"party-1";
+        let (round1, round2) = round2_broadcast_for(&coeffs, &commitments, identity);
+        let sender = test_sender();
+
+        let opened = verify_round2_broadcast::<Ed25519Curve>(&sender, &round1, &round2, identity).unwrap();
+        assert_eq!(opened, commitments);
+    }
+
+    #[test]
+    fn verify_round2_broadcast_rejects_tampered_decommitment() {
+        let (coeffs, commitments) = feldman_setup(2);
+        let identity = b"party-1";
+        let (round1, mut round2) = round2_broadcast_for(&coeffs, &commitments, identity);
+        round2.decommitment[1][0] ^= 0xFF;
+        let sender = test_sender();
+
+        let err = verify_round2_broadcast::<Ed25519Curve>(&sender, &round1, &round2, identity).unwrap_err();
+        match err {
+            TssError::KeygenAbort { culprits, .. } => assert_eq!(culprits, vec![sender]),
+            other => panic!("expected KeygenAbort, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn verify_round2_broadcast_rejects_wrong_identity() {
+        let (coeffs, commitments) = feldman_setup(2);
+        let (round1, round2) = round2_broadcast_for(&coeffs, &commitments, b"party-1");
+        let sender = test_sender();
+
+        let err = verify_round2_broadcast::<Ed25519Curve>(&sender, &round1, &round2, b"party-2").unwrap_err();
+        assert!(matches!(err, TssError::KeygenAbort { .. }));
+    }
+
+    #[test]
+    fn verify_round2_share_accepts_honest_share_and_rejects_wrong_index() {
+        let threshold = 2;
+        let (coeffs, commitments) = feldman_setup(threshold);
+        let index: u32 = 3;
+
+        let index_scalar = Scalar::from(index as u64);
+        let mut share = coeffs[threshold];
+        for a_k in coeffs[..threshold].iter().rev() {
+            share = share * index_scalar + a_k;
+        }
+        let share_msg = KGRound2Message1 { share: share.as_bytes().to_vec() };
+        let sender = test_sender();
+
+        assert!(verify_round2_share::<Ed25519Curve>(&sender, index, &share_msg, &commitments).is_ok());
+
+        let err = verify_round2_share::<Ed25519Curve>(&sender, index + 1, &share_msg, &commitments).unwrap_err();
+        assert!(matches!(err, TssError::KeygenAbort { .. }));
+    }
+
+    #[test]
+    fn parse_envelope_routes_by_declared_type() {
+        let (coeffs, commitments) = feldman_setup(2);
+        let (_, round2_broadcast) = round2_broadcast_for(&coeffs, &commitments, b"party-1");
+        let envelope = MessageEnvelope::wrap(KeygenMessageType::Round2Broadcast, &round2_broadcast);
+        let wire_bytes = envelope.encode_to_vec();
+
+        let (msg_type, payload) = parse_envelope(&wire_bytes, true).unwrap();
+        assert_eq!(msg_type, KeygenMessageType::Round2Broadcast);
+        let decoded: KGRound2Message2 = parse_message_from_payload(&payload).unwrap();
+        assert_eq!(decoded, round2_broadcast);
+    }
+
+    #[test]
+    fn parse_envelope_rejects_broadcast_ness_mismatch() {
+        let share_msg = KGRound2Message1 { share: vec![1, 2, 3] };
+        let envelope = MessageEnvelope::wrap(KeygenMessageType::Round2P2PShare, &share_msg);
+        let wire_bytes = envelope.encode_to_vec();
+
+        // A P2P share claiming to be a broadcast should be rejected before decoding.
+        let err = parse_envelope(&wire_bytes, true).unwrap_err();
+        assert!(matches!(err, TssError::MessageParseError(_)));
+
+        // Declared as P2P and arriving as P2P is accepted.
+        assert!(parse_envelope(&wire_bytes, false).is_ok());
+    }
+}
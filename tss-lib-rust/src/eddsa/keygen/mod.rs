@@ -2,6 +2,7 @@
 
 // TODO: Implement LocalParty, rounds, messages, and tests
 
+pub mod curve;
 pub mod error;
 pub mod local_party;
 pub mod rounds;
@@ -11,13 +12,20 @@ pub mod party_base;
 pub mod round_1;
 pub mod round_2;
 pub mod round_3;
+pub mod repair;
+pub mod reshare;
+pub mod resharing;
 pub mod save_data;
+pub mod simplpedpop;
+pub mod simplpedpop_messages;
 pub mod test_utils;
+pub mod dln_verifier;
 
 // Re-export key types for easier access
 pub use error::TssError;
 pub use params::Parameters;
 pub use party_base::BaseParty;
+pub use simplpedpop::KeygenFlavor;
 
 // Define keygen-specific traits/structs here if needed later,
 // e.g., KeygenRound trait
@@ -1,23 +1,49 @@
 // Parameters specific to the EDDSA keygen protocol
 
+use std::marker::PhantomData;
 use std::sync::Arc;
+use std::time::Duration;
+use crate::eddsa::keygen::curve::{Ed25519Curve, EdCurve};
 use crate::tss::{
     curve::CurveName,
     party_id::{PartyID, SortedPartyIDs},
 };
 use crate::tss::peers::PeerContext; // Keep PeerContext if needed for communication
 
+/// `C` selects the Edwards/Ristretto group (`curve.rs::EdCurve`) that
+/// round-2's decommitment/Schnorr-proof types (see `messages.rs`) are built
+/// over; it defaults to `Ed25519Curve` so existing call sites that just
+/// write `Parameters::new(...)` keep working unchanged. This is a separate
+/// axis from the `curve` field below: `curve` is the `CurveName` used for
+/// SSID derivation (`rounds::get_ssid`) and predates this generic, while `C`
+/// is the compile-time group selection `KGRound2Message2`'s point/proof
+/// handling is parameterized over.
 #[derive(Clone, Debug)] // Added Debug
-pub struct Parameters {
+pub struct Parameters<C: EdCurve = Ed25519Curve> {
     curve: CurveName,
     peer_ctx: Arc<PeerContext>, // Context for peer communication?
     party_id: PartyID,          // This party's ID
     parties: Arc<SortedPartyIDs>, // All parties, sorted
     party_count: usize,
     threshold: usize,
+    /// Secret shared out of band by every party in this run, used to key
+    /// P2P payload encryption (see `BaseParty::send_p2p`). `None` means P2P
+    /// payloads are sealed with an empty secret, so the sealing key depends
+    /// only on the recipient's public key: anyone who knows that key can
+    /// read them and re-seal altered content without detection.
+    session_secret: Option<Vec<u8>>,
+    /// How long a round waits for a non-responding party before
+    /// `BaseParty::poll_timeouts` reports it as missing. Defaults to
+    /// [`DEFAULT_ROUND_TIMEOUT`]; override with [`Self::set_round_timeout`]
+    /// for tests or deployments that need a tighter/looser bound.
+    round_timeout: Duration,
+    _group: PhantomData<C>,
 }
 
-impl Parameters {
+/// Default per-round deadline used when a caller doesn't set one explicitly.
+pub const DEFAULT_ROUND_TIMEOUT: Duration = Duration::from_secs(30);
+
+impl<C: EdCurve> Parameters<C> {
     pub fn new(
         curve: CurveName,
         peer_ctx: Arc<PeerContext>,
@@ -33,6 +59,9 @@ impl Parameters {
             parties,
             party_count,
             threshold,
+            session_secret: None,
+            round_timeout: DEFAULT_ROUND_TIMEOUT,
+            _group: PhantomData,
         }
     }
 
@@ -41,6 +70,12 @@ impl Parameters {
         self.curve
     }
 
+    /// The name of the generic group `C` this run's round-2 point/proof
+    /// types are built over (e.g. `"ed25519"`, `"ristretto255"`).
+    pub fn group_name(&self) -> &'static str {
+        C::NAME
+    }
+
     pub fn peer_ctx(&self) -> &Arc<PeerContext> {
         &self.peer_ctx
     }
@@ -65,4 +100,27 @@ impl Parameters {
     pub fn party_index(&self) -> Option<usize> {
         self.parties.find_by_id(&self.party_id)
     }
+
+    /// The out-of-band session secret used to key P2P payload encryption,
+    /// if one was set.
+    pub fn session_secret(&self) -> Option<&[u8]> {
+        self.session_secret.as_deref()
+    }
+
+    /// Binds this party's P2P payload encryption to a caller-chosen session
+    /// secret, shared out of band by every party in the run.
+    pub fn set_session_secret(&mut self, session_secret: Vec<u8>) {
+        self.session_secret = Some(session_secret);
+    }
+
+    /// How long a round will wait for each expected sender before it's
+    /// reported via `BaseParty::poll_timeouts`.
+    pub fn round_timeout(&self) -> Duration {
+        self.round_timeout
+    }
+
+    /// Overrides the per-round deadline used for timeout tracking.
+    pub fn set_round_timeout(&mut self, round_timeout: Duration) {
+        self.round_timeout = round_timeout;
+    }
 } 
\ No newline at end of file
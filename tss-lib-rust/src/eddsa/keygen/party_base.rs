@@ -2,6 +2,8 @@
 
 use std::sync::{Arc, Mutex, mpsc::Sender};
 use std::error::Error as StdError;
+use std::collections::{HashMap, VecDeque};
+use std::time::Instant;
 
 // Keygen specific imports
 use crate::eddsa::keygen::Parameters;
@@ -11,24 +13,54 @@ use crate::eddsa::keygen::rounds::PROTOCOL_NAME;
 
 // TSS core imports
 use crate::tss::party_id::{PartyID, SortedPartyIDs};
-use crate::tss::message::{TssMessage, MessageContent, MessageWrapper}; // Use tss::message
+use crate::tss::message::{MessageContent, MessageWrapper}; // Use tss::message
+use crate::tss::message_store::{MessageKey, MessageStore};
+use crate::tss::transport::Transport;
+use crate::tss::wire;
 
 // Crypto imports
 use prost::Message; // For MessageContent constraint and encoding
+use crate::crypto::p2p_seal;
 
-#[derive(Debug)] // Add Debug derive
+// `Transport` is a trait object and isn't `Debug`, so this is implemented by
+// hand instead of derived.
 pub struct BaseParty {
     pub(crate) params: Arc<Parameters>,
     pub(crate) temp_data: Arc<Mutex<KeygenPartyTempData>>,
     pub(crate) save_data: Arc<Mutex<KeygenPartySaveData>>,
-    pub(crate) out_channel: Sender<TssMessage>,
+    pub(crate) transport: Arc<dyn Transport>,
     pub(crate) end_channel: Option<Sender<KeygenPartySaveData>>, // Optional: only needed for final round
 
     pub(crate) round_number: u32,
     pub(crate) started: bool,
-    pub(crate) ok: Vec<bool>, // Received message flags
-    // TODO: Add message_store if BaseParty should manage raw message storage
-    // pub(crate) message_store: MessageStore, // Needs definition
+    pub(crate) message_store: MessageStore, // Indexed (round, sender, type) storage with dedup/replay protection
+
+    /// When `true`, `can_advance` finalizes the round as soon as `quorum_reached`
+    /// is true (`threshold + 1` messages) instead of waiting on every party.
+    /// Defaults to `false`: rounds that need all `n` messages (the historical
+    /// behavior) don't have to do anything to keep it.
+    pub(crate) quorum_advancement: bool,
+
+    /// Deadline for each party this round is still waiting on, armed by
+    /// `arm_round_timeouts` and cleared as "ok" messages arrive. Paired with
+    /// `deadline_queue` so `poll_timeouts` doesn't have to scan the whole
+    /// map to find expired entries.
+    pub(crate) round_deadlines: HashMap<PartyID, Instant>,
+    /// Deadlines in the order they were armed (all equal to `now +
+    /// round_timeout` at arm time, so insertion order is deadline order).
+    /// `poll_timeouts` pops from the front, discarding stale entries for
+    /// parties that already responded, giving amortized O(1) expiry checks.
+    pub(crate) deadline_queue: VecDeque<(Instant, PartyID)>,
+}
+
+impl std::fmt::Debug for BaseParty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BaseParty")
+            .field("party_id", self.params.party_id())
+            .field("round_number", &self.round_number)
+            .field("started", &self.started)
+            .finish()
+    }
 }
 
 // Add implementation block
@@ -37,22 +69,33 @@ impl BaseParty {
         params: Arc<Parameters>,
         temp_data: Arc<Mutex<KeygenPartyTempData>>,
         save_data: Arc<Mutex<KeygenPartySaveData>>,
-        out_channel: Sender<TssMessage>,
+        transport: Arc<dyn Transport>,
         round_number: u32,
     ) -> Self {
-        let party_count = params.party_count();
         Self {
             params,
             temp_data,
             save_data,
-            out_channel,
+            transport,
             end_channel: None, // Initialize as None, can be set later
             round_number,
             started: false,
-            ok: vec![false; party_count], // Initialize based on party count
+            message_store: MessageStore::new(),
+            quorum_advancement: false,
+            round_deadlines: HashMap::new(),
+            deadline_queue: VecDeque::new(),
         }
     }
 
+    /// Opts this round into threshold-based advancement: `can_advance`
+    /// finalizes once `quorum_reached` is true instead of waiting for every
+    /// party. Rounds that need all `n` messages (e.g. a round whose output
+    /// depends on every party's contribution) simply never call this.
+    pub fn enable_quorum_advancement(mut self) -> Self {
+        self.quorum_advancement = true;
+        self
+    }
+
     // Method to add the end channel (used in LocalParty::new)
     pub fn with_end_channel(mut self, end_channel: Sender<KeygenPartySaveData>) -> Self {
         self.end_channel = Some(end_channel);
@@ -113,18 +156,57 @@ impl BaseParty {
 
     // Reset the message received flags for the start of a round
     pub fn reset_ok(&mut self) {
-        for i in 0..self.party_count() {
-            self.ok[i] = false;
-        }
+        self.message_store.reset_round(self.round_number);
     }
 
-    // Mark a message as received from a party
+    // Mark a message as received from a party. Kept as a thin wrapper over
+    // `store_message` for call sites that only need a plain receipt flag,
+    // not replay protection over the message's actual content.
     pub fn set_ok(&mut self, party_index: usize) -> Result<(), KeygenTssError> {
-        if party_index >= self.party_count() {
-            return Err(self.wrap_base_error(format!("set_ok index out of bounds: {}", party_index)));
+        self.store_message(party_index, "ok", &[]).map(|_| ())
+    }
+
+    // Records `payload` as received from `from_index` for the current round
+    // under `message_type`, detecting replays: a byte-identical re-send is a
+    // harmless no-op (`Ok(false)`), while a conflicting second payload for
+    // the same slot is reported as a `RoundError` naming the sender as
+    // culprit. Returns `Ok(true)` when the slot is newly filled.
+    pub fn store_message(&mut self, from_index: usize, message_type: &'static str, payload: &[u8]) -> Result<bool, KeygenTssError> {
+        if from_index >= self.party_count() {
+            return Err(self.wrap_base_error(format!("store_message index out of bounds: {}", from_index)));
         }
-        self.ok[party_index] = true;
-        Ok(())
+        let key = MessageKey::new(self.round_number, from_index, message_type);
+        let stored = self.message_store.store_message(key, payload.to_vec()).map_err(|conflict| {
+            let culprits = self.params.parties().get(from_index).cloned().into_iter().collect();
+            KeygenTssError::RoundError {
+                message: format!(
+                    "party {} sent conflicting payloads for round {} message type \"{}\"",
+                    from_index, conflict.key.round_number, conflict.key.message_type
+                ),
+                round: self.round_number,
+                culprits,
+            }
+        })?;
+        if message_type == "ok" {
+            if let Some(party_id) = self.params.parties().get(from_index) {
+                self.round_deadlines.remove(party_id);
+            }
+        }
+        Ok(stored)
+    }
+
+    // Buffers a message that arrived for a round that hasn't started
+    // receiving yet, to be applied via `store_message` once that round
+    // calls `drain_early_messages`.
+    pub fn buffer_early_message(&mut self, round_number: u32, from_index: usize, message_type: &'static str, payload: Vec<u8>) {
+        self.message_store.buffer_early(MessageKey::new(round_number, from_index, message_type), payload);
+    }
+
+    // Returns every message buffered early for `round_number`, in arrival
+    // order, so the round beginning to receive can feed them through
+    // `store_message` as if they had just arrived.
+    pub fn drain_early_messages(&mut self, round_number: u32) -> Vec<(MessageKey, Vec<u8>)> {
+        self.message_store.drain_round(round_number)
     }
 
     // Return a list of parties from whom messages are still expected
@@ -132,7 +214,7 @@ impl BaseParty {
         let mut waiting_list = Vec::new();
         let parties = self.params.parties(); // Get Arc<SortedPartyIDs>
         for i in 0..self.party_count() {
-            if !self.ok[i] {
+            if !self.message_store.has(&MessageKey::new(self.round_number, i, "ok")) {
                 // Find the PartyID corresponding to index i
                 if let Some(party_id) = parties.get(i) {
                     waiting_list.push(party_id.clone());
@@ -145,7 +227,74 @@ impl BaseParty {
 
      // Simple count of received messages (may not be sufficient for rounds needing multiple message types)
     pub fn message_count(&self) -> usize {
-        self.ok.iter().filter(|&&ok_flag| ok_flag).count()
+        self.message_store.message_count(self.round_number)
+    }
+
+    /// `true` once `threshold + 1` valid "ok" messages have arrived for this
+    /// round -- the minimum needed to mathematically complete it, per the
+    /// same `t`-out-of-`n` bound the VSS/signing math is built on.
+    pub fn quorum_reached(&self) -> bool {
+        let ok_count = (0..self.party_count())
+            .filter(|&i| self.message_store.has(&MessageKey::new(self.round_number, i, "ok")))
+            .count();
+        ok_count >= self.params.threshold() + 1
+    }
+
+    /// Whether this round can finalize right now. Rounds opted into
+    /// `quorum_advancement` proceed as soon as `quorum_reached`; all others
+    /// keep the original all-`n` behavior (identical to
+    /// `message_count() == party_count()`).
+    pub fn can_advance(&self) -> bool {
+        if self.quorum_advancement {
+            self.quorum_reached()
+        } else {
+            self.message_count() == self.party_count()
+        }
+    }
+
+    /// Parties this round finalized without hearing from, once it advanced
+    /// on a quorum rather than waiting for all `n`. Empty for a round that
+    /// only ever finalizes once every party has responded.
+    pub fn pending_culprits(&self) -> Vec<PartyID> {
+        if self.quorum_advancement && self.quorum_reached() {
+            self.waiting_for()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Arms a fresh deadline of `now + params.round_timeout()` for every
+    /// party `waiting_for` still expects an "ok" from. Rounds call this
+    /// from `start()` (alongside `reset_ok`) so a stalled or offline peer
+    /// can be detected instead of hanging the session forever.
+    pub fn arm_round_timeouts(&mut self) {
+        let deadline = Instant::now() + self.params.round_timeout();
+        self.round_deadlines.clear();
+        self.deadline_queue.clear();
+        for party in self.waiting_for() {
+            self.round_deadlines.insert(party.clone(), deadline);
+            self.deadline_queue.push_back((deadline, party));
+        }
+    }
+
+    /// Pops every armed deadline that has passed, returning the parties
+    /// that are still missing. Entries for parties that already responded
+    /// are discarded for free as the queue is walked, so this stays
+    /// amortized O(1) per call rather than rescanning all of
+    /// `round_deadlines`.
+    pub fn poll_timeouts(&mut self) -> Vec<PartyID> {
+        let now = Instant::now();
+        let mut missing = Vec::new();
+        while let Some((deadline, _)) = self.deadline_queue.front() {
+            if *deadline > now {
+                break;
+            }
+            let (_, party) = self.deadline_queue.pop_front().expect("front just peeked");
+            if self.round_deadlines.remove(&party).is_some() {
+                missing.push(party);
+            }
+        }
+        missing
     }
 
     // --- Message Creation/Sending Methods --- //
@@ -156,13 +305,14 @@ impl BaseParty {
         to: &PartyID,
         content: Box<dyn MessageContent>,
     ) -> Result<MessageWrapper, KeygenTssError> {
-        Ok(MessageWrapper::new(
+        Ok(MessageWrapper::new_for_round(
             false, // is_broadcast
             false, // is_to_old_committee
             false, // is_to_old_and_new_committees
             self.party_id().clone(),
             vec![to.clone()],
             content,
+            self.round_number,
         ))
     }
 
@@ -177,42 +327,71 @@ impl BaseParty {
             .cloned()
             .collect();
 
-        Ok(MessageWrapper::new(
+        Ok(MessageWrapper::new_for_round(
             true, // is_broadcast
             false, // is_to_old_committee
             false, // is_to_old_and_new_committees
             self.party_id().clone(),
             recipients,
             content,
+            self.round_number,
         ))
     }
 
-    // Sends a P2P message
+    // Sends a P2P message. Unlike `send_broadcast`, the payload carries
+    // secrets (e.g. VSS shares) that only the addressed recipient should be
+    // able to read, so the content is sealed with `p2p_seal` before the
+    // wrapper is serialized and handed to `transport` -- a relay sees only
+    // ciphertext, and any tampering is caught as an AEAD failure on
+    // `open_p2p_message` rather than silently corrupting the round.
     pub fn send_p2p(&self, msg: MessageWrapper) -> Result<(), KeygenTssError> {
-        // Validation might happen within MessageWrapper::new or sending logic
-        // TODO: Update channel to accept MessageWrapper or serialize it
-        // Temporary: Convert wrapper to placeholder TssMessage for channel
-        let temp_msg = TssMessage {
-            payload: msg.message.encode_to_vec(), // Re-encode content
-            from: msg.from.clone(),
-            to: Some(msg.to().clone()),
-            is_broadcast: false,
-        };
-        self.out_channel.send(temp_msg)
+        let recipient = msg.to().first()
+            .ok_or_else(|| self.wrap_base_error("P2P message has no recipient".to_string()))?
+            .clone();
+        let plaintext = msg.message().encode_to_vec(); // Re-encode content
+        let sealed = p2p_seal::seal(
+            self.params.session_secret().unwrap_or(&[]),
+            &recipient.key().to_bytes_be().1,
+            &plaintext,
+            &mut rand::thread_rng(),
+        );
+        let wire_bytes = wire::encode_wire_message(&msg, sealed);
+        self.transport.send_p2p(&recipient, wire_bytes)
             .map_err(|e| self.wrap_base_error(format!("Failed to send P2P message: {}", e)))
     }
 
-    // Sends a broadcast message
+    // Opens a payload sealed by the sender's `send_p2p`, returning the
+    // re-encoded `MessageContent` bytes. An AEAD failure here means the
+    // payload was not sealed to this party under the run's session secret
+    // -- either `from_index` is lying about who sent it, or a relay
+    // tampered with the ciphertext -- so it is reported as a `RoundError`
+    // naming the claimed sender as culprit, the same way `store_message`
+    // reports a conflicting resend.
+    pub fn open_p2p_message(&self, from_index: usize, sealed: &[u8]) -> Result<Vec<u8>, KeygenTssError> {
+        if from_index >= self.party_count() {
+            return Err(self.wrap_base_error(format!("open_p2p_message index out of bounds: {}", from_index)));
+        }
+        p2p_seal::open(
+            self.params.session_secret().unwrap_or(&[]),
+            &self.party_id().key().to_bytes_be().1,
+            sealed,
+        ).map_err(|e| {
+            let culprits = self.params.parties().get(from_index).cloned().into_iter().collect();
+            KeygenTssError::RoundError {
+                message: format!("failed to decrypt P2P payload claimed to be from party {}: {}", from_index, e),
+                round: self.round_number,
+                culprits,
+            }
+        })
+    }
+
+    // Sends a broadcast message. Broadcast content isn't sealed: every party
+    // already receives the same bytes, so there's no per-recipient secret to
+    // protect the way there is for `send_p2p`.
     pub fn send_broadcast(&self, msg: MessageWrapper) -> Result<(), KeygenTssError> {
-        // TODO: Update channel to accept MessageWrapper or serialize it
-        // Temporary: Convert wrapper to placeholder TssMessage for channel
-        let temp_msg = TssMessage {
-            payload: msg.message.encode_to_vec(), // Re-encode content
-            from: msg.from.clone(),
-            to: None, // Broadcast might imply None for receiver
-            is_broadcast: true,
-        };
-        self.out_channel.send(temp_msg)
+        let content_bytes = msg.message().encode_to_vec(); // Re-encode content
+        let wire_bytes = wire::encode_wire_message(&msg, content_bytes);
+        self.transport.send_broadcast(wire_bytes)
              .map_err(|e| self.wrap_base_error(format!("Failed to send broadcast message: {}", e)))
     }
 
@@ -226,8 +405,6 @@ impl BaseParty {
         }
     }
 
-    // TODO: Add store_message method if BaseParty needs to manage received message state
-    // pub fn store_message(&mut self, msg: ParsedMessage) -> Result<(), TssError> { ... }
 }
 
 // Removed placeholder TssMessage definition
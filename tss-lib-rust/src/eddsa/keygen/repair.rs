@@ -0,0 +1,148 @@
+// Repairable recovery of a single lost share, without reconstructing the
+// secret.
+//
+// A helper set `T` of `t` current shareholders cooperatively recomputes the
+// lost party `l`'s share `s_l = Σ_{i in T} λ_i * s_i` (the Lagrange
+// interpolation of the existing polynomial at point `l`), but no individual
+// helper -- and no message on the wire -- ever carries the full value
+// `λ_i * s_i` in the clear: helper `i` splits it into `|T|` random additive
+// sub-shares that sum back to `λ_i * s_i` and sends one to each other helper.
+// Every helper then only ever sees a sum of sub-shares from every dealer,
+// never a single dealer's full contribution, before those per-helper sums are
+// forwarded to whoever is recovering the lost share and added together.
+
+use ed25519_dalek::{constants::ED25519_BASEPOINT_POINT, EdwardsPoint, Scalar};
+use rand::{CryptoRng, RngCore};
+
+/// The Lagrange coefficient for evaluating the sharing polynomial at the lost
+/// party's index `l`, given the helper set `T`: `λ_i^T(l) = Π_{k in T, k !=
+/// i} (l - k) / (i - k)`.
+pub fn lagrange_coefficient_at(i: u32, l: u32, t: &[u32]) -> Scalar {
+    let x_i = Scalar::from(i as u64);
+    let x_l = Scalar::from(l as u64);
+    let mut numerator = Scalar::ONE;
+    let mut denominator = Scalar::ONE;
+    for &k in t {
+        if k == i {
+            continue;
+        }
+        let x_k = Scalar::from(k as u64);
+        numerator *= x_l - x_k;
+        denominator *= x_i - x_k;
+    }
+    numerator * denominator.invert()
+}
+
+/// Helper `i`'s additive split of `λ_i^T(l) * s_i` into one random sub-share
+/// per member of the helper set `t` (indexed the same way as `t`), summing
+/// back to the full contribution.
+pub fn split_contribution<R: RngCore + CryptoRng>(
+    i: u32,
+    l: u32,
+    t: &[u32],
+    s_i: &Scalar,
+    rng: &mut R,
+) -> Vec<Scalar> {
+    let lambda_i = lagrange_coefficient_at(i, l, t);
+    let contribution = lambda_i * s_i;
+
+    let mut sub_shares: Vec<Scalar> = (0..t.len().saturating_sub(1)).map(|_| Scalar::random(rng)).collect();
+    let running_sum: Scalar = sub_shares.iter().sum();
+    sub_shares.push(contribution - running_sum);
+    sub_shares
+}
+
+/// One helper `j`'s running sum `σ_j = Σ_{i in T} δ_{i,j}` of the sub-shares
+/// it received from every dealer in the helper set.
+pub fn sum_received_sub_shares(received: &[Scalar]) -> Scalar {
+    received.iter().sum()
+}
+
+/// The recovering party's final step: `s_l = Σ_{j in T} σ_j`, the sum of every
+/// helper's partial sum. Equal to `Σ_{i in T} λ_i^T(l) * s_i` by construction,
+/// since each `σ_j` is itself a sum of one sub-share from every dealer.
+pub fn recover_share(helper_sums: &[Scalar]) -> Scalar {
+    helper_sums.iter().sum()
+}
+
+/// Verifies a recovered share against the lost party's stored public share
+/// `big_x_l = g^{s_l}` before it's accepted. A helper set with even one
+/// dishonest member (that lied about its own `s_i`, or about a sub-share it
+/// sent) produces a recovered value that fails this check instead of being
+/// silently adopted.
+pub fn verify_recovered_share(recovered: &Scalar, big_x_l: &EdwardsPoint) -> bool {
+    ED25519_BASEPOINT_POINT * recovered == *big_x_l
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::OsRng;
+
+    fn evaluate(coeffs: &[Scalar], x: u32) -> Scalar {
+        let x = Scalar::from(x as u64);
+        let mut value = Scalar::ZERO;
+        let mut x_pow = Scalar::ONE;
+        for a_k in coeffs {
+            value += a_k * x_pow;
+            x_pow *= x;
+        }
+        value
+    }
+
+    #[test]
+    fn test_lagrange_coefficient_at_reconstructs_polynomial_value() {
+        let mut rng = OsRng;
+        let coeffs = vec![Scalar::random(&mut rng), Scalar::random(&mut rng), Scalar::random(&mut rng)];
+        let t = [1u32, 2, 3];
+        let l = 4u32;
+
+        let expected_s_l = evaluate(&coeffs, l);
+        let shares: Vec<Scalar> = t.iter().map(|&x| evaluate(&coeffs, x)).collect();
+
+        let recovered: Scalar = t
+            .iter()
+            .zip(&shares)
+            .map(|(&i, s_i)| lagrange_coefficient_at(i, l, &t) * s_i)
+            .sum();
+
+        assert_eq!(recovered, expected_s_l);
+    }
+
+    #[test]
+    fn test_repair_protocol_recovers_lost_share_via_split_and_sum() {
+        let mut rng = OsRng;
+        let coeffs = vec![Scalar::random(&mut rng), Scalar::random(&mut rng)];
+        let t = [1u32, 2, 3];
+        let l = 4u32;
+        let shares: Vec<Scalar> = t.iter().map(|&x| evaluate(&coeffs, x)).collect();
+        let expected_s_l = evaluate(&coeffs, l);
+
+        // Each dealer i in T splits its lambda-weighted contribution into one
+        // sub-share per helper.
+        let splits: Vec<Vec<Scalar>> = t
+            .iter()
+            .zip(&shares)
+            .map(|(&i, s_i)| split_contribution(i, l, &t, s_i, &mut rng))
+            .collect();
+
+        // Every helper j sums the j-th sub-share from every dealer.
+        let helper_sums: Vec<Scalar> = (0..t.len())
+            .map(|j| sum_received_sub_shares(&splits.iter().map(|split| split[j]).collect::<Vec<_>>()))
+            .collect();
+
+        let recovered = recover_share(&helper_sums);
+        assert_eq!(recovered, expected_s_l);
+
+        let big_x_l = ED25519_BASEPOINT_POINT * expected_s_l;
+        assert!(verify_recovered_share(&recovered, &big_x_l));
+    }
+
+    #[test]
+    fn test_verify_recovered_share_rejects_wrong_value() {
+        let mut rng = OsRng;
+        let wrong = Scalar::random(&mut rng);
+        let real_public = ED25519_BASEPOINT_POINT * Scalar::random(&mut rng);
+        assert!(!verify_recovered_share(&wrong, &real_public));
+    }
+}
@@ -0,0 +1,330 @@
+// Proactive share refresh and threshold re-sharing.
+//
+// Reuses the same dealing/Feldman-verification shape as `simplpedpop`, but
+// instead of each dealer contributing a fresh random secret, every member of
+// the current qualified set `Q` deals a degree-`new_threshold`
+// polynomial whose constant term is its *existing* Shamir share `s_i`. A new
+// party `j` verifies every sub-share it receives against the dealer's
+// commitments (anchored to the dealer's already-known public share,
+// `commitments[0]`), then combines them with Lagrange coefficients at 0 over
+// `Q`: since `Σ_{i in Q} λ_i^Q(0) * s_i == secret`, the same linear
+// combination of sub-shares reconstructs to the same secret, so the group
+// public key is unchanged. Calling this with the same membership and a fresh
+// set of dealt polynomials is a refresh (defeats a mobile adversary that
+// learned old shares); calling it with a different `new_recipient_indices` /
+// `new_threshold` is a membership change.
+
+use ed25519_dalek::{constants::ED25519_BASEPOINT_POINT, EdwardsPoint, Scalar};
+use num_bigint::{BigInt, Sign};
+use rand::{CryptoRng, RngCore};
+
+use crate::eddsa::keygen::save_data::{LocalPartySaveData, LocalSecrets};
+
+/// One dealer's sub-share for a specific new recipient.
+#[derive(Clone)]
+pub struct SubShare {
+    pub recipient_index: u32,
+    pub value: Scalar,
+}
+
+/// A qualified-set member's contribution to a reshare: Feldman commitments
+/// to its re-sharing polynomial (`commitments[0]` must equal the dealer's
+/// already-known public share, `g^{s_i}`) and one sub-share per new
+/// recipient.
+pub struct DealerContribution {
+    pub dealer_index: u32,
+    pub commitments: Vec<EdwardsPoint>,
+    pub sub_shares: Vec<SubShare>,
+}
+
+/// Deals a fresh degree-`new_threshold` polynomial with constant term
+/// `old_share`, evaluating it at every index in `new_recipient_indices`.
+pub fn deal_subshares<R: RngCore + CryptoRng>(
+    dealer_index: u32,
+    old_share: &Scalar,
+    new_threshold: usize,
+    new_recipient_indices: &[u32],
+    rng: &mut R,
+) -> DealerContribution {
+    let mut coeffs = Vec::with_capacity(new_threshold + 1);
+    coeffs.push(*old_share);
+    coeffs.extend((0..new_threshold).map(|_| Scalar::random(rng)));
+
+    let commitments: Vec<EdwardsPoint> = coeffs.iter().map(|a_k| ED25519_BASEPOINT_POINT * a_k).collect();
+    let sub_shares = new_recipient_indices
+        .iter()
+        .map(|&j| SubShare { recipient_index: j, value: evaluate(&coeffs, j) })
+        .collect();
+
+    DealerContribution { dealer_index, commitments, sub_shares }
+}
+
+fn evaluate(coeffs: &[Scalar], x: u32) -> Scalar {
+    let x = Scalar::from(x as u64);
+    let mut value = Scalar::ZERO;
+    let mut x_pow = Scalar::ONE;
+    for a_k in coeffs {
+        value += a_k * x_pow;
+        x_pow *= x;
+    }
+    value
+}
+
+/// Verifies `g^sub_share == Π C_k^{j^k}` for the dealer's commitments.
+pub fn verify_sub_share(sub_share: &SubShare, commitments: &[EdwardsPoint]) -> bool {
+    let x = Scalar::from(sub_share.recipient_index as u64);
+    let mut rhs = EdwardsPoint::identity();
+    let mut x_pow = Scalar::ONE;
+    for c_k in commitments {
+        rhs += c_k * x_pow;
+        x_pow *= x;
+    }
+    ED25519_BASEPOINT_POINT * sub_share.value == rhs
+}
+
+/// Verifies that the dealer committed to its already-known public share as
+/// the constant term of its re-sharing polynomial.
+pub fn verify_dealer_contribution(contribution: &DealerContribution, known_public_share: &EdwardsPoint) -> bool {
+    contribution.commitments.first() == Some(known_public_share)
+}
+
+/// The Lagrange coefficient `λ_i^Q(0) = Π_{k in Q, k != i} (0 - k) / (i - k)`.
+pub fn lagrange_coefficient_at_zero(i: u32, q: &[u32]) -> Scalar {
+    let x_i = Scalar::from(i as u64);
+    let mut numerator = Scalar::ONE;
+    let mut denominator = Scalar::ONE;
+    for &k in q {
+        if k == i {
+            continue;
+        }
+        let x_k = Scalar::from(k as u64);
+        numerator *= -x_k;
+        denominator *= x_i - x_k;
+    }
+    numerator * denominator.invert()
+}
+
+/// Verifies every contribution from the qualified set `q` and combines them
+/// into this party's new share: `s'_j = Σ_{i in Q} λ_i^Q(0) * f_i(j)`.
+pub fn combine_new_share(
+    my_index: u32,
+    q: &[u32],
+    contributions: &[(EdwardsPoint, DealerContribution)],
+) -> Result<Scalar, String> {
+    let mut new_share = Scalar::ZERO;
+
+    for (known_public_share, contribution) in contributions {
+        if !verify_dealer_contribution(contribution, known_public_share) {
+            return Err(format!("dealer {} committed to the wrong constant term", contribution.dealer_index));
+        }
+
+        let sub_share = contribution
+            .sub_shares
+            .iter()
+            .find(|s| s.recipient_index == my_index)
+            .ok_or_else(|| format!("dealer {} did not send a sub-share for this party", contribution.dealer_index))?;
+
+        if !verify_sub_share(sub_share, &contribution.commitments) {
+            return Err(format!("dealer {} sent a sub-share that fails Feldman verification", contribution.dealer_index));
+        }
+
+        let lambda = lagrange_coefficient_at_zero(contribution.dealer_index, q);
+        new_share += lambda * sub_share.value;
+    }
+
+    Ok(new_share)
+}
+
+/// Evaluates a Feldman commitment vector at `x`: `Σ C_k * x^k`, accumulating
+/// successive powers of `x` as `simplpedpop::evaluate_commitments` does, over the
+/// new committee's Lagrange-weighted group commitment vector.
+fn evaluate_commitments(commitments: &[EdwardsPoint], x: u32) -> EdwardsPoint {
+    let x = Scalar::from(x as u64);
+    let mut value = EdwardsPoint::identity();
+    let mut x_pow = Scalar::ONE;
+    for c_k in commitments {
+        value += c_k * x_pow;
+        x_pow *= x;
+    }
+    value
+}
+
+/// Finishes a reshare for this party: verifies every dealer's contribution
+/// (as `combine_new_share` does), checks that the qualified set's
+/// Lagrange-weighted constant-term commitments still reconstruct
+/// `previous_eddsa_pub` -- so a reshare can never silently rotate the group's
+/// public key -- and builds the `LocalPartySaveData` this party ends up with,
+/// in the same shape the round-based and SimplPedPoP flows produce.
+pub fn to_save_data(
+    my_index: u32,
+    q: &[u32],
+    contributions: &[(EdwardsPoint, DealerContribution)],
+    previous_eddsa_pub: &EdwardsPoint,
+    new_party_indices: &[u32],
+) -> Result<LocalPartySaveData, String> {
+    let reconstructed_pub = contributions
+        .iter()
+        .fold(EdwardsPoint::identity(), |acc, (known_public_share, contribution)| {
+            acc + known_public_share * lagrange_coefficient_at_zero(contribution.dealer_index, q)
+        });
+    if &reconstructed_pub != previous_eddsa_pub {
+        return Err("reshare would change the group public key".to_string());
+    }
+
+    let new_share = combine_new_share(my_index, q, contributions)?;
+
+    // The new committee's group commitment vector: each dealer's Feldman
+    // commitments, Lagrange-weighted by the old qualified set and summed
+    // coordinate-wise, mirroring how the new share itself is a
+    // Lagrange-weighted sum of sub-shares.
+    let degree = contributions.first().map(|(_, c)| c.commitments.len()).unwrap_or(0);
+    let mut group_commitments = vec![EdwardsPoint::identity(); degree];
+    for (_, contribution) in contributions {
+        if contribution.commitments.len() != group_commitments.len() {
+            return Err(format!("dealer {} disagrees on polynomial degree", contribution.dealer_index));
+        }
+        let lambda = lagrange_coefficient_at_zero(contribution.dealer_index, q);
+        for (acc, c_k) in group_commitments.iter_mut().zip(&contribution.commitments) {
+            *acc += c_k * lambda;
+        }
+    }
+
+    let xi = BigInt::from_bytes_le(Sign::Plus, new_share.as_bytes());
+    let share_id = BigInt::from(my_index);
+    let mut save_data = LocalPartySaveData::new(new_party_indices.len(), LocalSecrets { xi, share_id });
+    for (j, &index) in new_party_indices.iter().enumerate() {
+        save_data.ks[j] = BigInt::from(index);
+        save_data.big_x_j[j] = evaluate_commitments(&group_commitments, index);
+    }
+    save_data.eddsa_pub = *previous_eddsa_pub;
+
+    Ok(save_data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::OsRng;
+
+    fn reconstruct_secret(indices: &[u32], shares: &[Scalar]) -> Scalar {
+        let mut secret = Scalar::ZERO;
+        for (idx, share) in indices.iter().zip(shares) {
+            secret += lagrange_coefficient_at_zero(*idx, indices) * share;
+        }
+        secret
+    }
+
+    #[test]
+    fn test_lagrange_coefficients_reconstruct_shamir_secret() {
+        let mut rng = OsRng;
+        let secret = Scalar::random(&mut rng);
+        let coeffs = vec![secret, Scalar::random(&mut rng), Scalar::random(&mut rng)];
+        let indices = [1u32, 2, 3, 4];
+        let shares: Vec<Scalar> = indices.iter().map(|&x| evaluate(&coeffs, x)).collect();
+
+        // Any 3-of-4 subset should reconstruct the same secret.
+        assert_eq!(reconstruct_secret(&indices[0..3], &shares[0..3]), secret);
+        assert_eq!(reconstruct_secret(&indices[1..4], &shares[1..4]), secret);
+    }
+
+    #[test]
+    fn test_reshare_preserves_secret_with_same_membership() {
+        let mut rng = OsRng;
+        let secret = Scalar::random(&mut rng);
+        let old_coeffs = vec![secret, Scalar::random(&mut rng)];
+        let old_indices = [1u32, 2, 3];
+        let old_shares: Vec<Scalar> = old_indices.iter().map(|&x| evaluate(&old_coeffs, x)).collect();
+        let public_shares: Vec<EdwardsPoint> = old_shares.iter().map(|s| ED25519_BASEPOINT_POINT * s).collect();
+
+        let new_threshold = 1;
+        let contributions: Vec<DealerContribution> = old_indices
+            .iter()
+            .zip(&old_shares)
+            .map(|(&i, s_i)| deal_subshares(i, s_i, new_threshold, &old_indices, &mut rng))
+            .collect();
+
+        let mut new_shares = Vec::new();
+        for &j in &old_indices {
+            let per_dealer: Vec<(EdwardsPoint, DealerContribution)> = contributions
+                .iter()
+                .enumerate()
+                .map(|(k, c)| {
+                    (public_shares[k], DealerContribution {
+                        dealer_index: c.dealer_index,
+                        commitments: c.commitments.clone(),
+                        sub_shares: c.sub_shares.clone(),
+                    })
+                })
+                .collect();
+            new_shares.push(combine_new_share(j, &old_indices, &per_dealer).unwrap());
+        }
+
+        assert_eq!(reconstruct_secret(&old_indices, &new_shares), secret);
+    }
+
+    #[test]
+    fn test_combine_new_share_rejects_wrong_public_share() {
+        let mut rng = OsRng;
+        let secret = Scalar::random(&mut rng);
+        let contribution = deal_subshares(1, &secret, 1, &[1, 2], &mut rng);
+        let wrong_public_share = ED25519_BASEPOINT_POINT * Scalar::random(&mut rng);
+
+        let result = combine_new_share(1, &[1], &[(wrong_public_share, contribution)]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_to_save_data_preserves_group_public_key() {
+        let mut rng = OsRng;
+        let secret = Scalar::random(&mut rng);
+        let old_coeffs = vec![secret, Scalar::random(&mut rng)];
+        let old_indices = [1u32, 2, 3];
+        let old_shares: Vec<Scalar> = old_indices.iter().map(|&x| evaluate(&old_coeffs, x)).collect();
+        let public_shares: Vec<EdwardsPoint> = old_shares.iter().map(|s| ED25519_BASEPOINT_POINT * s).collect();
+        let group_public_key = ED25519_BASEPOINT_POINT * secret;
+
+        let new_threshold = 1;
+        let new_indices = [10u32, 20, 30];
+        let contributions: Vec<DealerContribution> = old_indices
+            .iter()
+            .zip(&old_shares)
+            .map(|(&i, s_i)| deal_subshares(i, s_i, new_threshold, &new_indices, &mut rng))
+            .collect();
+        let per_dealer: Vec<(EdwardsPoint, DealerContribution)> = contributions
+            .iter()
+            .enumerate()
+            .map(|(k, c)| {
+                (public_shares[k], DealerContribution {
+                    dealer_index: c.dealer_index,
+                    commitments: c.commitments.clone(),
+                    sub_shares: c.sub_shares.clone(),
+                })
+            })
+            .collect();
+
+        let save_data_10 = to_save_data(10, &old_indices, &per_dealer, &group_public_key, &new_indices).unwrap();
+        let save_data_20 = to_save_data(20, &old_indices, &per_dealer, &group_public_key, &new_indices).unwrap();
+        assert_eq!(save_data_10.eddsa_pub, group_public_key);
+        assert_eq!(save_data_20.eddsa_pub, group_public_key);
+
+        let to_scalar = |xi: &BigInt| {
+            let mut bytes = [0u8; 32];
+            let (_, le) = xi.to_bytes_le();
+            bytes[..le.len()].copy_from_slice(&le);
+            Scalar::from_bytes_mod_order(bytes)
+        };
+        let new_shares = [to_scalar(&save_data_10.local_secrets.xi), to_scalar(&save_data_20.local_secrets.xi)];
+        assert_eq!(reconstruct_secret(&[10, 20], &new_shares), secret);
+    }
+
+    #[test]
+    fn test_to_save_data_rejects_changed_public_key() {
+        let mut rng = OsRng;
+        let secret = Scalar::random(&mut rng);
+        let contribution = deal_subshares(1, &secret, 1, &[10], &mut rng);
+        let wrong_public_key = ED25519_BASEPOINT_POINT * Scalar::random(&mut rng);
+
+        let result = to_save_data(10, &[1], &[(ED25519_BASEPOINT_POINT * secret, contribution)], &wrong_public_key, &[10]);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,485 @@
+// EdDSA key-resharing (proactive refresh / committee change) subsystem.
+//
+// `reshare` already carries the bare cryptographic core -- dealing
+// Lagrange-weighted sub-shares and combining them back into a new share --
+// but it hands `DealerContribution`s to callers as plain structs with no
+// wire format and no protection against a dealer changing its mind between
+// "this is my contribution" and "here is what I actually sent you". This
+// module wraps that core in the same commit -> input -> share -> output
+// shape `keygen`'s `round_1`/`round_2` uses: a round-1 broadcast commits to
+// each dealer's Feldman commitment vector before any sub-share goes out, a
+// round-2 P2P message carries the sub-share itself, and a round-2 broadcast
+// opens the commitment alongside a Schnorr proof of knowledge of the old
+// share so a recipient can catch a dealer that committed to one vector and
+// decommitted a different one. New parties reconstruct their share as the
+// Lagrange-weighted sum of every verified sub-share; old parties that are leaving the
+// committee (or refreshing in place) zeroize their old share once dealing
+// is done, per `zeroize_old_share`.
+
+use ed25519_dalek::{constants::ED25519_BASEPOINT_POINT, EdwardsPoint, Scalar};
+use num_traits::ToPrimitive;
+use rand::{CryptoRng, RngCore};
+use sha2::{Digest, Sha512};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::eddsa::keygen::reshare::{verify_dealer_contribution, verify_sub_share, DealerContribution, SubShare};
+use crate::eddsa::keygen::save_data::LocalPartySaveData;
+use crate::tss::party_id::{PartyID, SortedPartyIDs};
+
+/// Holds both committees a reshare runs between: the old committee (and its
+/// threshold) whose members deal sub-shares of their existing Shamir share,
+/// and the new committee (and its threshold) who receive them. Mirrors
+/// `keygen::Parameters`, but keygen only ever has one committee.
+#[derive(Clone, Debug)]
+pub struct ReSharingParameters {
+    old_committee: Arc<SortedPartyIDs>,
+    old_threshold: usize,
+    new_committee: Arc<SortedPartyIDs>,
+    new_threshold: usize,
+    party_id: PartyID,
+}
+
+impl ReSharingParameters {
+    pub fn new(
+        old_committee: Arc<SortedPartyIDs>,
+        old_threshold: usize,
+        new_committee: Arc<SortedPartyIDs>,
+        new_threshold: usize,
+        party_id: PartyID,
+    ) -> Self {
+        ReSharingParameters { old_committee, old_threshold, new_committee, new_threshold, party_id }
+    }
+
+    pub fn old_committee(&self) -> &Arc<SortedPartyIDs> {
+        &self.old_committee
+    }
+
+    pub fn old_threshold(&self) -> usize {
+        self.old_threshold
+    }
+
+    pub fn new_committee(&self) -> &Arc<SortedPartyIDs> {
+        &self.new_committee
+    }
+
+    pub fn new_threshold(&self) -> usize {
+        self.new_threshold
+    }
+
+    pub fn party_id(&self) -> &PartyID {
+        &self.party_id
+    }
+
+    /// Whether this party belongs to the old committee (and therefore deals).
+    pub fn is_old_committee_member(&self) -> bool {
+        self.old_committee.find_by_id(&self.party_id).is_some()
+    }
+
+    /// Whether this party belongs to the new committee (and therefore receives).
+    pub fn is_new_committee_member(&self) -> bool {
+        self.new_committee.find_by_id(&self.party_id).is_some()
+    }
+}
+
+/// Round-1 broadcast: a hash commitment to the dealer's Feldman commitment
+/// vector, sent before any sub-share so a later round-2 decommit can be
+/// checked against what was actually committed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReShareRound1Message {
+    pub commitment: [u8; 64],
+}
+
+/// Round-2 P2P message: the dealer's sub-share evaluated at one new party's
+/// index. Sent only to that recipient.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReShareRound2Message1 {
+    pub recipient_index: u32,
+    pub share: Scalar,
+}
+
+/// Round-2 broadcast: the decommitment of round 1's Feldman commitment
+/// vector, plus a Schnorr proof of knowledge of the dealer's old share (the
+/// discrete log of `commitments[0]`), binding the dealer's identity so a
+/// rogue-key substitution of another party's commitment can't succeed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReShareRound2Message2 {
+    pub randomness: [u8; 32],
+    pub commitments: Vec<EdwardsPoint>,
+    pub proof_r: EdwardsPoint,
+    pub proof_s: Scalar,
+}
+
+fn hash_commitment(randomness: &[u8; 32], commitments: &[EdwardsPoint]) -> [u8; 64] {
+    let mut hasher = Sha512::new();
+    hasher.update(randomness);
+    for c in commitments {
+        hasher.update(c.compress().as_bytes());
+    }
+    let mut out = [0u8; 64];
+    out.copy_from_slice(&hasher.finalize());
+    out
+}
+
+fn pok_challenge(r: &EdwardsPoint, c0: &EdwardsPoint, dealer_identity: &[u8]) -> Scalar {
+    let mut hasher = Sha512::new();
+    hasher.update(r.compress().as_bytes());
+    hasher.update(c0.compress().as_bytes());
+    hasher.update(dealer_identity);
+    let mut wide = [0u8; 64];
+    wide.copy_from_slice(&hasher.finalize());
+    Scalar::from_bytes_mod_order_wide(&wide)
+}
+
+/// What an old-committee dealer produces across both rounds: the round-1
+/// commitment to send immediately, the decommitment data it must hold onto
+/// for round 2, and the per-recipient sub-shares for round 2's P2P leg.
+pub struct DealerRoundState {
+    pub round1: ReShareRound1Message,
+    randomness: [u8; 32],
+    commitments: Vec<EdwardsPoint>,
+    pub sub_shares: Vec<ReShareRound2Message1>,
+}
+
+impl DealerRoundState {
+    /// Builds this dealer's round-2 broadcast, proving knowledge of
+    /// `old_share` (the discrete log of `commitments[0]`) bound to
+    /// `dealer_identity`.
+    pub fn round2_broadcast<R: RngCore + CryptoRng>(
+        &self,
+        old_share: &Scalar,
+        dealer_identity: &[u8],
+        rng: &mut R,
+    ) -> ReShareRound2Message2 {
+        let r_scalar = Scalar::random(rng);
+        let r_point = ED25519_BASEPOINT_POINT * r_scalar;
+        let c = pok_challenge(&r_point, &self.commitments[0], dealer_identity);
+        let s = r_scalar + c * old_share;
+
+        ReShareRound2Message2 {
+            randomness: self.randomness,
+            commitments: self.commitments.clone(),
+            proof_r: r_point,
+            proof_s: s,
+        }
+    }
+}
+
+/// Round 1: an old-committee member deals a fresh degree-`new_threshold`
+/// polynomial whose constant term is its existing share `old_share`, and
+/// commits to the resulting Feldman commitment vector.
+pub fn deal_round1<R: RngCore + CryptoRng>(
+    dealer_index: u32,
+    old_share: &Scalar,
+    new_threshold: usize,
+    new_recipient_indices: &[u32],
+    rng: &mut R,
+) -> DealerRoundState {
+    let contribution =
+        crate::eddsa::keygen::reshare::deal_subshares(dealer_index, old_share, new_threshold, new_recipient_indices, rng);
+
+    let mut randomness = [0u8; 32];
+    rng.fill_bytes(&mut randomness);
+    let commitment = hash_commitment(&randomness, &contribution.commitments);
+
+    let sub_shares = contribution
+        .sub_shares
+        .into_iter()
+        .map(|s| ReShareRound2Message1 { recipient_index: s.recipient_index, share: s.value })
+        .collect();
+
+    DealerRoundState {
+        round1: ReShareRound1Message { commitment },
+        randomness,
+        commitments: contribution.commitments,
+        sub_shares,
+    }
+}
+
+/// Verifies that `round2` decommits `round1`'s hash commitment.
+pub fn verify_decommitment(round1: &ReShareRound1Message, round2: &ReShareRound2Message2) -> bool {
+    hash_commitment(&round2.randomness, &round2.commitments) == round1.commitment
+}
+
+/// Verifies the round-2 broadcast's Schnorr proof of knowledge of the
+/// dealer's old share, bound to `dealer_identity`.
+pub fn verify_proof_of_knowledge(round2: &ReShareRound2Message2, dealer_identity: &[u8]) -> bool {
+    match round2.commitments.first() {
+        Some(c0) => {
+            let c = pok_challenge(&round2.proof_r, c0, dealer_identity);
+            ED25519_BASEPOINT_POINT * round2.proof_s == round2.proof_r + c0 * c
+        }
+        None => false,
+    }
+}
+
+/// A new-committee member's fully verified view of one dealer's
+/// contribution: both round-2 messages, checked against round 1's
+/// commitment and the dealer's already-known public share.
+pub struct VerifiedContribution {
+    pub dealer_index: u32,
+    pub known_public_share: EdwardsPoint,
+    pub commitments: Vec<EdwardsPoint>,
+    pub sub_share: Scalar,
+}
+
+/// Verifies one dealer's full round-1/round-2 exchange for this new party:
+/// the decommitment opens the round-1 commitment, the commitment vector's
+/// constant term matches the dealer's already-known public share, the
+/// proof of knowledge verifies, and this party's sub-share is consistent
+/// with the commitments. Returns the `VerifiedContribution` (holding the
+/// verified sub-share) on success, or the dealer's index as the culprit on
+/// failure.
+pub fn verify_contribution(
+    my_index: u32,
+    dealer_index: u32,
+    round1: &ReShareRound1Message,
+    round2: &ReShareRound2Message2,
+    my_sub_share: &ReShareRound2Message1,
+    known_public_share: &EdwardsPoint,
+    dealer_identity: &[u8],
+) -> Result<VerifiedContribution, u32> {
+    if !verify_decommitment(round1, round2) {
+        return Err(dealer_index);
+    }
+    let contribution = DealerContribution {
+        dealer_index,
+        commitments: round2.commitments.clone(),
+        sub_shares: vec![SubShare { recipient_index: my_index, value: my_sub_share.share }],
+    };
+    if !verify_dealer_contribution(&contribution, known_public_share) {
+        return Err(dealer_index);
+    }
+    if !verify_proof_of_knowledge(round2, dealer_identity) {
+        return Err(dealer_index);
+    }
+    let as_sub_share: SubShare = my_sub_share.into();
+    if my_sub_share.recipient_index != my_index || !verify_sub_share(&as_sub_share, &round2.commitments) {
+        return Err(dealer_index);
+    }
+
+    Ok(VerifiedContribution {
+        dealer_index,
+        known_public_share: *known_public_share,
+        commitments: round2.commitments.clone(),
+        sub_share: my_sub_share.share,
+    })
+}
+
+impl From<&ReShareRound2Message1> for SubShare {
+    fn from(msg: &ReShareRound2Message1) -> Self {
+        SubShare { recipient_index: msg.recipient_index, value: msg.share }
+    }
+}
+
+/// Finishes a reshare for a new-committee member: combines every verified
+/// contribution into this party's new share with `Σ λ_i^Q(0) * f_i(j)`,
+/// checks the reconstructed public key still matches `previous_eddsa_pub`,
+/// and builds the `LocalPartySaveData` this party ends up with. Thin
+/// wrapper over `reshare::to_save_data` taking the message-layer verified
+/// contributions instead of bare `DealerContribution`s.
+pub fn finish_new_party(
+    my_index: u32,
+    q: &[u32],
+    verified: &[VerifiedContribution],
+    previous_eddsa_pub: &EdwardsPoint,
+    new_party_indices: &[u32],
+) -> Result<LocalPartySaveData, String> {
+    let contributions: Vec<(EdwardsPoint, DealerContribution)> = verified
+        .iter()
+        .map(|v| {
+            (v.known_public_share, DealerContribution {
+                dealer_index: v.dealer_index,
+                commitments: v.commitments.clone(),
+                sub_shares: vec![SubShare { recipient_index: my_index, value: v.sub_share }],
+            })
+        })
+        .collect();
+
+    crate::eddsa::keygen::reshare::to_save_data(my_index, q, &contributions, previous_eddsa_pub, new_party_indices)
+}
+
+/// Overwrites an old-committee member's share with zero once dealing is
+/// done, so a proactive refresh (or an old party leaving the committee
+/// entirely) doesn't leave the retired share sitting in memory.
+pub fn zeroize_old_share(share: &mut Scalar) {
+    *share = Scalar::ZERO;
+}
+
+/// Converts a keygen secret share (`LocalSecrets::xi`, stored as a `BigInt`)
+/// back into the `Scalar` the resharing core works in -- the inverse of the
+/// `BigInt::from_bytes_le` conversion `reshare::to_save_data` applies to a
+/// freshly combined share.
+fn xi_to_scalar(xi: &num_bigint::BigInt) -> Scalar {
+    let mut bytes = [0u8; 32];
+    let (_, le) = xi.to_bytes_le();
+    bytes[..le.len()].copy_from_slice(&le);
+    Scalar::from_bytes_mod_order(bytes)
+}
+
+/// This party's existing share as the `Scalar` a reshare deals from, pulled
+/// directly out of a post-keygen `LocalPartySaveData` instead of requiring
+/// the caller to hand-roll the `BigInt`-to-`Scalar` conversion.
+pub fn old_share_from_save_data(save_data: &LocalPartySaveData) -> Scalar {
+    xi_to_scalar(&save_data.local_secrets.xi)
+}
+
+/// Everything a reshare needs out of an old-committee member's existing
+/// `LocalPartySaveData` besides its own share: every old-committee member's
+/// known public share, keyed by index (from the parallel `ks`/`big_x_j`
+/// vectors), and the group public key the reshare must preserve. Any one old
+/// party's save data works here -- every old-committee member agrees on
+/// `ks`, `big_x_j`, and `eddsa_pub`.
+pub fn old_committee_inputs_from_save_data(save_data: &LocalPartySaveData) -> Result<(HashMap<u32, EdwardsPoint>, EdwardsPoint), String> {
+    let mut public_shares = HashMap::with_capacity(save_data.ks.len());
+    for (k, x) in save_data.ks.iter().zip(&save_data.big_x_j) {
+        let index = k.to_u32().ok_or_else(|| "old committee index does not fit in a u32".to_string())?;
+        public_shares.insert(index, *x);
+    }
+    Ok((public_shares, save_data.eddsa_pub))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eddsa::keygen::reshare::lagrange_coefficient_at_zero;
+    use rand::rngs::OsRng;
+
+    fn reconstruct_secret(indices: &[u32], shares: &[Scalar]) -> Scalar {
+        let mut secret = Scalar::ZERO;
+        for (idx, share) in indices.iter().zip(shares) {
+            secret += lagrange_coefficient_at_zero(*idx, indices) * share;
+        }
+        secret
+    }
+
+    #[test]
+    fn test_round_trip_commit_decommit_and_pok() {
+        let mut rng = OsRng;
+        let old_share = Scalar::random(&mut rng);
+        let dealer_identity = b"dealer-1";
+        let state = deal_round1(1, &old_share, 1, &[10, 20, 30], &mut rng);
+
+        let round2 = state.round2_broadcast(&old_share, dealer_identity, &mut rng);
+        assert!(verify_decommitment(&state.round1, &round2));
+        assert!(verify_proof_of_knowledge(&round2, dealer_identity));
+    }
+
+    #[test]
+    fn test_tampered_decommitment_is_rejected() {
+        let mut rng = OsRng;
+        let old_share = Scalar::random(&mut rng);
+        let state = deal_round1(1, &old_share, 1, &[10], &mut rng);
+        let mut round2 = state.round2_broadcast(&old_share, b"dealer-1", &mut rng);
+        round2.commitments[0] = round2.commitments[0] + ED25519_BASEPOINT_POINT;
+
+        assert!(!verify_decommitment(&state.round1, &round2));
+    }
+
+    #[test]
+    fn test_proof_of_knowledge_rejects_wrong_identity() {
+        let mut rng = OsRng;
+        let old_share = Scalar::random(&mut rng);
+        let state = deal_round1(1, &old_share, 1, &[10], &mut rng);
+        let round2 = state.round2_broadcast(&old_share, b"dealer-1", &mut rng);
+
+        assert!(!verify_proof_of_knowledge(&round2, b"dealer-2"));
+    }
+
+    #[test]
+    fn test_full_reshare_preserves_secret_and_public_key() {
+        let mut rng = OsRng;
+        let secret = Scalar::random(&mut rng);
+        let old_indices = [1u32, 2, 3];
+        let old_coeffs = vec![secret, Scalar::random(&mut rng)];
+        let old_shares: Vec<Scalar> = old_indices
+            .iter()
+            .map(|&x| {
+                let x = Scalar::from(x as u64);
+                old_coeffs[0] + old_coeffs[1] * x
+            })
+            .collect();
+        let public_shares: Vec<EdwardsPoint> = old_shares.iter().map(|s| ED25519_BASEPOINT_POINT * s).collect();
+        let group_public_key = ED25519_BASEPOINT_POINT * secret;
+        let new_indices = [10u32, 20, 30];
+        let new_threshold = 1;
+
+        let dealer_states: Vec<DealerRoundState> = old_indices
+            .iter()
+            .zip(&old_shares)
+            .map(|(&i, s)| deal_round1(i, s, new_threshold, &new_indices, &mut rng))
+            .collect();
+        let dealer_round2s: Vec<ReShareRound2Message2> = dealer_states
+            .iter()
+            .zip(&old_shares)
+            .enumerate()
+            .map(|(k, (state, s))| state.round2_broadcast(s, format!("dealer-{k}").as_bytes(), &mut rng))
+            .collect();
+
+        let mut new_shares = Vec::new();
+        for &j in &new_indices {
+            let mut verified = Vec::new();
+            for (k, &dealer_idx) in old_indices.iter().enumerate() {
+                let my_sub_share = dealer_states[k]
+                    .sub_shares
+                    .iter()
+                    .find(|s| s.recipient_index == j)
+                    .unwrap();
+                let v = verify_contribution(
+                    j,
+                    dealer_idx,
+                    &dealer_states[k].round1,
+                    &dealer_round2s[k],
+                    my_sub_share,
+                    &public_shares[k],
+                    format!("dealer-{k}").as_bytes(),
+                )
+                .unwrap();
+                verified.push(v);
+            }
+            let save_data =
+                finish_new_party(j, &old_indices, &verified, &group_public_key, &new_indices).unwrap();
+            assert_eq!(save_data.eddsa_pub, group_public_key);
+
+            let mut bytes = [0u8; 32];
+            let (_, le) = save_data.local_secrets.xi.to_bytes_le();
+            bytes[..le.len()].copy_from_slice(&le);
+            new_shares.push(Scalar::from_bytes_mod_order(bytes));
+        }
+
+        assert_eq!(reconstruct_secret(&new_indices, &new_shares), secret);
+    }
+
+    #[test]
+    fn test_inputs_from_save_data_round_trip() {
+        use crate::eddsa::keygen::save_data::{LocalPartySaveData, LocalSecrets};
+        use num_bigint::{BigInt, Sign};
+
+        let mut rng = OsRng;
+        let share = Scalar::random(&mut rng);
+        let public_share = ED25519_BASEPOINT_POINT * share;
+        let group_public_key = ED25519_BASEPOINT_POINT * Scalar::random(&mut rng);
+
+        let mut save_data = LocalPartySaveData::new(
+            1,
+            LocalSecrets { xi: BigInt::from_bytes_le(Sign::Plus, share.as_bytes()), share_id: BigInt::from(1) },
+        );
+        save_data.ks[0] = BigInt::from(7u32);
+        save_data.big_x_j[0] = public_share;
+        save_data.eddsa_pub = group_public_key;
+
+        assert_eq!(old_share_from_save_data(&save_data), share);
+
+        let (public_shares, recovered_pub) = old_committee_inputs_from_save_data(&save_data).unwrap();
+        assert_eq!(public_shares.get(&7), Some(&public_share));
+        assert_eq!(recovered_pub, group_public_key);
+    }
+
+    #[test]
+    fn test_zeroize_old_share_clears_value() {
+        let mut rng = OsRng;
+        let mut share = Scalar::random(&mut rng);
+        zeroize_old_share(&mut share);
+        assert_eq!(share, Scalar::ZERO);
+    }
+}
@@ -21,6 +21,7 @@ use crate::crypto::paillier; // Import actual paillier
 use std::sync::{Arc, Mutex};
 use std::collections::HashMap;
 use crate::tss::{error::TssError, message::TssMessage};
+use crate::tss::transport::Transport;
 use crate::eddsa::keygen::{KeygenRound, PROTOCOL_NAME};
 use crate::tss::curve::{CurveName, get_curve_params, CurveParams};
 use crate::crypto::hashing::hash_bytes;
@@ -115,11 +116,11 @@ impl Round1 {
         params: Arc<Parameters>,
         save_data: Arc<Mutex<KeygenPartySaveData>>,
         temp_data: Arc<Mutex<KeygenPartyTempData>>,
-        out_channel: Sender<TssMessage>,
+        transport: Arc<dyn Transport>,
         end_channel: Sender<KeygenPartySaveData>, // Keep end_channel in signature for LocalParty
     ) -> Box<dyn TssRound> {
         // Create BaseParty instance
-        let base = BaseParty::new(params, temp_data, save_data, out_channel, 1)
+        let base = BaseParty::new(params, temp_data, save_data, transport, 1)
             .with_end_channel(end_channel); // Add end channel
 
         Box::new(Self { base })
@@ -149,6 +150,7 @@ impl KeygenRound for Round1 {
         }
         self.base.started = true;
         self.base.reset_ok();
+        self.base.arm_round_timeouts();
 
         let mut rng = OsRng;
         let curve_params = get_curve_params(self.base.params().curve())
@@ -166,7 +168,7 @@ impl KeygenRound for Round1 {
         save_guard.started = true;
         temp_guard.ssid_nonce = Some(BigInt::zero());
         let ssid = get_ssid(self.base.params(), 1, &temp_guard.ssid_nonce.as_ref().unwrap())?;
-        temp_guard.ssid = Some(ssid);
+        temp_guard.ssid = Some(ssid.clone());
 
         let ui_bigint = rng.gen_bigint_range(&BigInt::one(), curve_order);
         temp_guard.ui = Some(ui_bigint.clone());
@@ -198,7 +200,22 @@ impl KeygenRound for Round1 {
         let cmt_d = commit_decommit.d.clone();
         temp_guard.de_commit_poly_g = Some(cmt_d);
 
-        let msg_content = KGRound1Message { commitment: cmt_c.to_bytes_be().1 };
+        // Ring-Pedersen (DLN) auxiliary parameters: range proofs used in later
+        // rounds need an Ntilde/h1/h2 to anchor to, proven consistent in both
+        // directions so a receiving party can verify either one was derived
+        // honestly from the other.
+        let dln_params = crate::crypto::dln::DlnParams::generate(&mut rng, 2048);
+        let dln_proof_1 = dln_params.prove_h1_to_h2(&ssid, &mut rng);
+        let dln_proof_2 = dln_params.prove_h2_to_h1(&ssid, &mut rng);
+
+        let msg_content = KGRound1Message {
+            commitment: cmt_c.to_bytes_be().1,
+            n_tilde: dln_params.n_tilde.to_bytes_be().1,
+            h1: dln_params.h1.to_bytes_be().1,
+            h2: dln_params.h2.to_bytes_be().1,
+            dln_proof_1: dln_proof_1.to_bytes(),
+            dln_proof_2: dln_proof_2.to_bytes(),
+        };
         let msg_payload = msg_content.encode_to_vec();
 
         let broadcast_msg = self.base.new_broadcast_message(msg_payload)?;
@@ -239,7 +256,7 @@ impl KeygenRound for Round1 {
             self.base.params.clone(),
             self.base.save_data.clone(),
             self.base.temp_data.clone(),
-            self.base.out_channel.clone(),
+            self.base.transport.clone(),
             self.base.end_channel.clone().expect("End channel should be set for round 1"),
         ))
     }
@@ -24,6 +24,7 @@ use crate::eddsa::keygen::local_party::{KeygenPartyTempData, KeygenPartySaveData
 use crate::tss::party_id::PartyID;
 use crate::tss::message::ParsedMessage;
 use crate::tss::message::MessageContent; // Needed for store_message validation
+use crate::tss::transport::Transport;
 
 // Crypto imports
 use crate::crypto::vss::{ShareVec as Vs, Share as IndividualVssShare};
@@ -117,11 +118,11 @@ impl Round2 {
         params: Arc<Parameters>,
         save_data: Arc<Mutex<KeygenPartySaveData>>,
         temp_data: Arc<Mutex<KeygenPartyTempData>>,
-        out_channel: Sender<TssMessage>,
+        transport: Arc<dyn Transport>,
         end_channel: Sender<KeygenPartySaveData>,
     ) -> Box<dyn TssRound> {
         // Create BaseParty instance
-        let base = BaseParty::new(params, temp_data, save_data, out_channel, 2)
+        let base = BaseParty::new(params, temp_data, save_data, transport, 2)
             .with_end_channel(end_channel);
 
         Box::new(Self { base })
@@ -143,6 +144,7 @@ impl KeygenRound for Round2 {
         }
         self.base.started = true;
         self.base.reset_ok();
+        self.base.arm_round_timeouts();
 
         let party_id = self.base.party_id().clone();
         let i = self.base.party_index();
@@ -255,7 +257,7 @@ impl KeygenRound for Round2 {
             self.base.params.clone(),
             self.base.save_data.clone(),
             self.base.temp_data.clone(),
-            self.base.out_channel.clone(),
+            self.base.transport.clone(),
             self.base.end_channel.clone().expect("End channel should be set for round 2"),
         ))
     }
@@ -31,6 +31,7 @@ use crate::tss::{
     message::{ParsedMessage, TssMessage},
     party::{Round as TssRound, BaseParty}, // Added BaseParty
     party_id::{PartyID, SortedPartyIDs},
+    transport::Transport,
 };
 use crate::crypto::paillier; // Import actual paillier
 
@@ -122,10 +123,10 @@ impl Round3 {
         params: Arc<Parameters>,
         save_data: Arc<Mutex<KeygenPartySaveData>>,
         temp_data: Arc<Mutex<KeygenPartyTempData>>,
-        out_channel: Sender<TssMessage>,
+        transport: Arc<dyn Transport>,
         end_channel: Sender<KeygenPartySaveData>,
     ) -> Box<dyn TssRound> {
-        let base = BaseParty::new(params, temp_data, save_data, out_channel, 3)
+        let base = BaseParty::new(params, temp_data, save_data, transport, 3)
             .with_end_channel(end_channel);
 
         Box::new(Self { base })
@@ -156,12 +157,24 @@ impl Round3 {
             .map(|s| s.scalar.clone())
             .ok_or_else(|| TssError::InternalError{ message: "Missing own VSS share".into() })?;
 
+        // Reconstruct only from the ACS-agreed qualified set `Q`, not from
+        // whichever round-2 messages happen to have arrived locally -- see
+        // `tss::acs`. Falls back to every party when ACS hasn't run (e.g.
+        // this round executing ahead of ACS wiring into the round-1/2
+        // message flow), preserving today's all-`n` behavior.
+        let all_parties = self.base.params().parties().clone();
+        let qualified_parties: Vec<PartyID> = match temp_guard.qualified_set.as_ref() {
+            Some(q) => all_parties.iter().filter(|p| q.contains(&p.index())).cloned().collect(),
+            None => all_parties.iter().cloned().collect(),
+        };
+
         let r2m1_count = temp_guard.round_2_messages1.len();
-        if r2m1_count != party_count {
-             return Err(TssError::InternalError{ message: format!("Expected {} Round 2 Message 1, found {}", party_count, r2m1_count)});
+        if r2m1_count != qualified_parties.len() {
+             return Err(TssError::InternalError{ message: format!("Expected {} Round 2 Message 1 (qualified set), found {}", qualified_parties.len(), r2m1_count)});
         }
 
         for (from_party_id, r2msg1) in &temp_guard.round_2_messages1 {
+             if !qualified_parties.iter().any(|p| p.index() == from_party_id.index()) { continue; }
              if from_party_id.index() == i { continue; }
              let share_bytes = &r2msg1.share;
              let mut share_scalar_bytes = [0u8; 32];
@@ -182,12 +195,11 @@ impl Round3 {
         let mut error_accumulator: Option<TssError> = None;
 
          let r2m2_count = temp_guard.round_2_messages2.len();
-         if r2m2_count != party_count {
-              return Err(TssError::InternalError{ message: format!("Expected {} Round 2 Message 2, found {}", party_count, r2m2_count)});
+         if r2m2_count != qualified_parties.len() {
+              return Err(TssError::InternalError{ message: format!("Expected {} Round 2 Message 2 (qualified set), found {}", qualified_parties.len(), r2m2_count)});
          }
 
-        let all_parties = self.base.params().parties().clone();
-        for pj in all_parties.iter() {
+        for pj in qualified_parties.iter() {
             if pj.index() == i { continue; }
             let j = pj.index();
             let ssid_bytes = temp_guard.ssid.as_ref().ok_or_else(|| TssError::InternalError{ message: "Missing SSID".into() })?;
@@ -228,7 +240,7 @@ impl Round3 {
         if let Some(err) = error_accumulator { return Err(err); }
         println!("Round 3: VSS shares and proofs verified.");
 
-        for pj in all_parties.iter() {
+        for pj in qualified_parties.iter() {
              if pj.index() == i { continue; }
              let pj_vs = pj_vs_map.get(pj).unwrap();
              for c in 0..=threshold {
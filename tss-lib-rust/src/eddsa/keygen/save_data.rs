@@ -6,11 +6,11 @@
 
 // LocalPartySaveData defines the save data structure for the EdDSA keygen protocol.
 
-use num_bigint::BigInt;
-use serde::{Deserialize, Serialize};
+use num_bigint::{BigInt, Sign};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashMap;
 use std::error::Error;
-ed25519_dalek::{EdwardsPoint, Scalar as Ed25519Scalar};
+use ed25519_dalek::{CompressedEdwardsY, EdwardsPoint, Scalar as Ed25519Scalar};
 
 // TSS core imports
 use crate::tss::party_id::{PartyID, SortedPartyIDs};
@@ -37,8 +37,22 @@ pub struct LocalSecrets {
     pub share_id: BigInt, // Use BigInt directly for kj (party's VSS ID)
 }
 
-// Everything in LocalPartySaveData is saved locally when done
-#[derive(Debug, Clone, Serialize, Deserialize)] // Use derives if EdwardsPoint supports them (needs feature or wrapper)
+// BigInt doesn't expose its internal digit buffer, so this can't scrub the
+// heap bytes a prior allocation occupied, but it does guarantee a party's
+// long-lived key share isn't left sitting in a `LocalSecrets` that's simply
+// gone out of scope: dropping (including via a reshare/refresh that replaces
+// this party's save data) overwrites `xi` before freeing it.
+impl Drop for LocalSecrets {
+    fn drop(&mut self) {
+        self.xi = BigInt::default();
+        self.share_id = BigInt::default();
+    }
+}
+
+// Everything in LocalPartySaveData is saved locally when done.
+// Serialize/Deserialize are implemented by hand below (via LocalPartySaveDataWire)
+// rather than derived, since EdwardsPoint has no serde impl of its own.
+#[derive(Debug, Clone)]
 pub struct LocalPartySaveData {
     // Embed Secrets directly
     pub local_secrets: LocalSecrets,
@@ -111,6 +125,64 @@ impl LocalPartySaveData {
         Ok(new_data)
     }
 
+    // Shifts this save data by a BIP-32 non-hardened derivation delta
+    // (the sum of each path step's `I_L`, reduced mod `curve_order`).
+    // Only the party at
+    // `chosen_party_index` folds `delta` into its own secret share
+    // (`xi' = xi + delta mod curve_order`); every other party's `xi` is
+    // untouched. `delta_point` must be `delta*G` on the same curve, and gets
+    // added into every entry of `big_x_j` plus `eddsa_pub` so the public
+    // shares stay consistent with the shifted secret: since only the sharing
+    // polynomial's free term moves by `delta`, the threshold structure is
+    // preserved and the resulting signature verifies under the derived
+    // child public key.
+    //
+    // `delta` must already be reduced mod `curve_order`, and this is only
+    // valid for an all-normal-node derivation path -- a hardened node needs
+    // the private key inside the HMAC, which no single party holds.
+    pub fn apply_derivation_delta(
+        &mut self,
+        delta: &BigInt,
+        curve_order: &BigInt,
+        delta_point: &EdwardsPoint,
+        chosen_party_index: usize,
+    ) -> Result<(), String> {
+        if chosen_party_index >= self.big_x_j.len() {
+            return Err(format!(
+                "apply_derivation_delta: party index {} is out of range for {} parties",
+                chosen_party_index,
+                self.big_x_j.len()
+            ));
+        }
+        if self.original_index()? == chosen_party_index {
+            self.local_secrets.xi = (&self.local_secrets.xi + delta) % curve_order;
+        }
+        for x_j in self.big_x_j.iter_mut() {
+            *x_j = *x_j + delta_point;
+        }
+        self.eddsa_pub = self.eddsa_pub + delta_point;
+        Ok(())
+    }
+
+    // Folds a proactive-refresh delta into this party's own secret share:
+    // `xi' = xi + delta mod curve_order`. `delta` is the Lagrange-combined
+    // result of a round where every current shareholder deals a fresh
+    // zero-constant-term polynomial (see `reshare::deal_subshares` called
+    // with `Scalar::ZERO`, verified and combined via
+    // `reshare::combine_new_share` against each dealer's public share taken
+    // as `EdwardsPoint::identity()`). Because every dealer's polynomial
+    // satisfies `f_i(0) = 0`, the deltas the full committee adds sum to zero
+    // at `x = 0`, so `eddsa_pub` is unaffected by this call and stays fixed
+    // across the refresh -- only `xi` rotates, defeating an adversary that
+    // learned the pre-refresh shares. This does not update `big_x_j`; a
+    // caller that needs the public per-party shares to track the rotation
+    // must separately combine the dealers' Feldman commitments the same way
+    // and add the resulting delta points itself, the same way
+    // `apply_derivation_delta` takes `delta_point` from its caller.
+    pub fn apply_refresh_delta(&mut self, delta: &BigInt, curve_order: &BigInt) {
+        self.local_secrets.xi = (&self.local_secrets.xi + delta) % curve_order;
+    }
+
     // Add implementation for original_index if not already present
     // (It was added in local_party.rs previously, might be better placed here)
     pub fn original_index(&self) -> Result<usize, String> {
@@ -125,7 +197,238 @@ impl LocalPartySaveData {
     }
 }
 
-// Note: Serialization/Deserialization for LocalPartySaveData
-// needs handling for EdwardsPoint (e.g., using serde_bytes for compressed representation
-// or a wrapper struct that implements Serialize/Deserialize).
-// The derive(Serialize, Deserialize) might fail depending on EdwardsPoint implementation.
+// Current wire-format revision for `LocalPartySaveData`. Bump this and add a
+// new `LocalPartySaveDataWireVN` + a case in `Deserialize` whenever a field is
+// added or changed, so a save written by an older build still deserializes
+// (or fails with a clear "unsupported version" error instead of silently
+// misreading bytes).
+const SAVE_DATA_WIRE_VERSION: u16 = 1;
+
+// On-the-wire shape of `LocalPartySaveData`: `EdwardsPoint` has no serde impl,
+// so `big_x_j`/`eddsa_pub` are carried as their 32-byte compressed Edwards
+// encoding instead of the point type directly. `BigInt` already has a serde
+// impl (see `LocalSecrets`), so `ks` and `local_secrets` serialize as-is.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LocalPartySaveDataWire {
+    version: u16,
+    local_secrets: LocalSecrets,
+    ks: Vec<BigInt>,
+    big_x_j: Vec<[u8; 32]>,
+    eddsa_pub: [u8; 32],
+}
+
+fn decompress_point(bytes: [u8; 32]) -> Result<EdwardsPoint, String> {
+    CompressedEdwardsY(bytes)
+        .decompress()
+        .ok_or_else(|| "LocalPartySaveData: not a valid compressed Edwards point".to_string())
+}
+
+impl Serialize for LocalPartySaveData {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let wire = LocalPartySaveDataWire {
+            version: SAVE_DATA_WIRE_VERSION,
+            local_secrets: self.local_secrets.clone(),
+            ks: self.ks.clone(),
+            big_x_j: self.big_x_j.iter().map(|p| p.compress().to_bytes()).collect(),
+            eddsa_pub: self.eddsa_pub.compress().to_bytes(),
+        };
+        wire.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for LocalPartySaveData {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let wire = LocalPartySaveDataWire::deserialize(deserializer)?;
+        if wire.version != SAVE_DATA_WIRE_VERSION {
+            return Err(serde::de::Error::custom(format!(
+                "LocalPartySaveData: unsupported wire version {} (expected {})",
+                wire.version, SAVE_DATA_WIRE_VERSION
+            )));
+        }
+        let big_x_j = wire
+            .big_x_j
+            .into_iter()
+            .map(decompress_point)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(serde::de::Error::custom)?;
+        let eddsa_pub = decompress_point(wire.eddsa_pub).map_err(serde::de::Error::custom)?;
+        Ok(LocalPartySaveData {
+            local_secrets: wire.local_secrets,
+            ks: wire.ks,
+            big_x_j,
+            eddsa_pub,
+        })
+    }
+}
+
+// Converts a BigInt scalar already reduced mod the Ed25519 group order into
+// its canonical little-endian 32-byte encoding. `xi`/`share_id` are produced
+// that way throughout this module (see `simplpedpop::to_save_data`), so no
+// further reduction happens here -- an un-reduced BigInt would silently
+// truncate instead of round-tripping.
+fn scalar_bigint_to_bytes(n: &BigInt) -> [u8; 32] {
+    let (_, le_bytes) = n.to_bytes_le();
+    let mut bytes = [0u8; 32];
+    let len = le_bytes.len().min(32);
+    bytes[..len].copy_from_slice(&le_bytes[..len]);
+    bytes
+}
+
+fn scalar_bytes_to_bigint(bytes: &[u8; 32]) -> BigInt {
+    BigInt::from_bytes_le(Sign::Plus, bytes)
+}
+
+/// Plain byte-oriented mirror of the `frost-ed25519` crate's `KeyPackage`
+/// wire layout (identifier, signing share, verifying share, group verifying
+/// key). `frost-ed25519` isn't a dependency of this workspace, so this isn't
+/// `frost_ed25519::keys::KeyPackage` itself -- it reproduces the same field
+/// layout and encodings (32-byte compressed points, 32-byte little-endian
+/// scalars) so a value built here can be re-encoded into that crate's own
+/// types by whatever glue code links the two.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrostKeyPackage {
+    pub identifier: [u8; 32],
+    pub signing_share: [u8; 32],
+    pub verifying_share: [u8; 32],
+    pub verifying_key: [u8; 32],
+}
+
+/// Mirror of `frost-ed25519`'s `PublicKeyPackage`: every party's identifier
+/// and verifying share, plus the group verifying key. See `FrostKeyPackage`
+/// for why this is a plain byte-layout mirror rather than the crate's type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrostPublicKeyPackage {
+    pub verifying_shares: Vec<([u8; 32], [u8; 32])>, // (identifier, verifying_share)
+    pub verifying_key: [u8; 32],
+}
+
+impl LocalPartySaveData {
+    /// Exports this party's own key material in `frost-ed25519` `KeyPackage`
+    /// layout: identifier = `share_id`, signing share = `xi`, verifying share
+    /// = this party's own entry in `big_x_j`, verifying key = `eddsa_pub`.
+    pub fn to_frost_key_package(&self) -> Result<FrostKeyPackage, String> {
+        let idx = self.original_index()?;
+        Ok(FrostKeyPackage {
+            identifier: scalar_bigint_to_bytes(&self.local_secrets.share_id),
+            signing_share: scalar_bigint_to_bytes(&self.local_secrets.xi),
+            verifying_share: self.big_x_j[idx].compress().to_bytes(),
+            verifying_key: self.eddsa_pub.compress().to_bytes(),
+        })
+    }
+
+    /// Exports the group-wide public material in `frost-ed25519`
+    /// `PublicKeyPackage` layout: every party's (`share_id`, `big_x_j`) pair
+    /// plus the shared `eddsa_pub`.
+    pub fn to_frost_public_key_package(&self) -> FrostPublicKeyPackage {
+        FrostPublicKeyPackage {
+            verifying_shares: self
+                .ks
+                .iter()
+                .zip(self.big_x_j.iter())
+                .map(|(k, x_j)| (scalar_bigint_to_bytes(k), x_j.compress().to_bytes()))
+                .collect(),
+            verifying_key: self.eddsa_pub.compress().to_bytes(),
+        }
+    }
+
+    /// Imports a single party's `frost-ed25519` `KeyPackage`, combined with
+    /// the group's `PublicKeyPackage`, back into this module's save-data
+    /// layout. The resulting `LocalPartySaveData` holds exactly the fields a
+    /// `frost-ed25519`-generated key carries: no Paillier material, no VSS
+    /// commitments, since FROST keygen doesn't produce those.
+    pub fn from_frost_key_package(
+        key_package: &FrostKeyPackage,
+        public_key_package: &FrostPublicKeyPackage,
+    ) -> Result<Self, String> {
+        let party_count = public_key_package.verifying_shares.len();
+        let mut save_data = LocalPartySaveData::new_empty(party_count);
+        save_data.local_secrets = LocalSecrets {
+            xi: scalar_bytes_to_bigint(&key_package.signing_share),
+            share_id: scalar_bytes_to_bigint(&key_package.identifier),
+        };
+        for (j, (identifier, verifying_share)) in public_key_package.verifying_shares.iter().enumerate() {
+            save_data.ks[j] = scalar_bytes_to_bigint(identifier);
+            save_data.big_x_j[j] = decompress_point(*verifying_share)?;
+        }
+        save_data.eddsa_pub = decompress_point(public_key_package.verifying_key)?;
+        Ok(save_data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_point(seed: u8) -> EdwardsPoint {
+        let bytes = [seed; 32];
+        Ed25519Scalar::from_bytes_mod_order(bytes) * ed25519_dalek::constants::ED25519_BASEPOINT_POINT
+    }
+
+    fn sample_save_data() -> LocalPartySaveData {
+        let mut save_data = LocalPartySaveData::new(
+            2,
+            LocalSecrets {
+                xi: BigInt::from(7),
+                share_id: BigInt::from(1),
+            },
+        );
+        save_data.ks[0] = BigInt::from(1);
+        save_data.ks[1] = BigInt::from(2);
+        save_data.big_x_j[0] = sample_point(1);
+        save_data.big_x_j[1] = sample_point(2);
+        save_data.eddsa_pub = sample_point(3);
+        save_data
+    }
+
+    #[test]
+    fn test_wire_round_trip_preserves_points_and_secrets() {
+        let save_data = sample_save_data();
+        let encoded = serde_json::to_vec(&save_data).expect("serialize");
+        let decoded: LocalPartySaveData = serde_json::from_slice(&encoded).expect("deserialize");
+        assert_eq!(decoded.local_secrets.xi, save_data.local_secrets.xi);
+        assert_eq!(decoded.local_secrets.share_id, save_data.local_secrets.share_id);
+        assert_eq!(decoded.ks, save_data.ks);
+        assert_eq!(decoded.big_x_j, save_data.big_x_j);
+        assert_eq!(decoded.eddsa_pub, save_data.eddsa_pub);
+    }
+
+    #[test]
+    fn test_wire_round_trip_handles_identity_point() {
+        let mut save_data = sample_save_data();
+        save_data.big_x_j[0] = EdwardsPoint::default();
+        save_data.eddsa_pub = EdwardsPoint::default();
+        let encoded = serde_json::to_vec(&save_data).expect("serialize");
+        let decoded: LocalPartySaveData = serde_json::from_slice(&encoded).expect("deserialize");
+        assert_eq!(decoded.big_x_j[0], save_data.big_x_j[0]);
+        assert_eq!(decoded.eddsa_pub, save_data.eddsa_pub);
+    }
+
+    #[test]
+    fn test_wire_rejects_unknown_version() {
+        let wire = LocalPartySaveDataWire {
+            version: SAVE_DATA_WIRE_VERSION + 1,
+            local_secrets: LocalSecrets { xi: BigInt::from(1), share_id: BigInt::from(1) },
+            ks: vec![BigInt::from(1)],
+            big_x_j: vec![[0u8; 32]],
+            eddsa_pub: [0u8; 32],
+        };
+        let encoded = serde_json::to_vec(&wire).expect("serialize");
+        let decoded: Result<LocalPartySaveData, _> = serde_json::from_slice(&encoded);
+        assert!(decoded.is_err());
+    }
+
+    #[test]
+    fn test_frost_key_package_round_trips_through_save_data() {
+        let save_data = sample_save_data();
+        let key_package = save_data.to_frost_key_package().expect("key package");
+        let public_key_package = save_data.to_frost_public_key_package();
+
+        let restored = LocalPartySaveData::from_frost_key_package(&key_package, &public_key_package)
+            .expect("restore from frost key package");
+
+        assert_eq!(restored.local_secrets.xi, save_data.local_secrets.xi);
+        assert_eq!(restored.local_secrets.share_id, save_data.local_secrets.share_id);
+        assert_eq!(restored.big_x_j, save_data.big_x_j);
+        assert_eq!(restored.eddsa_pub, save_data.eddsa_pub);
+    }
+}
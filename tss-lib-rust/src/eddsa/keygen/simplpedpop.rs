@@ -0,0 +1,488 @@
+// Single-broadcast, publicly-verifiable DKG for EdDSA keygen (SimplPedPoP).
+//
+// Unlike the multi-round commit/decommit flow in round_1/round_2/round_3,
+// every party here acts as a dealer exactly once: it deals a degree-`t`
+// Shamir polynomial, broadcasts Feldman commitments to its coefficients plus
+// a Schnorr proof-of-possession of the constant term, and sends each other
+// party its evaluation of that polynomial. A recipient who has verified every
+// dealer's commitments, proof-of-possession, and its own share needs no
+// further rounds: the final secret share is the sum of the verified shares,
+// and the group public key is the sum of every dealer's constant-term
+// commitment. Share transport is assumed to ride over the same
+// point-to-point channel abstraction the rest of the party machinery uses;
+// this module only implements the cryptographic core (dealing, per-share
+// verification, and aggregation).
+
+use ed25519_dalek::{constants::ED25519_BASEPOINT_POINT, EdwardsPoint, Scalar};
+use num_bigint::{BigInt, Sign};
+use rand::{CryptoRng, RngCore};
+use sha2::{Digest, Sha512};
+
+use crate::eddsa::keygen::save_data::{LocalPartySaveData, LocalSecrets};
+
+/// One dealer's contribution: Feldman commitments to its polynomial's
+/// coefficients (`commitments[0]` is the dealer's public contribution to the
+/// group key), a proof of possession of the constant term, and one Shamir
+/// share per recipient.
+pub struct DealerOutput {
+    pub commitments: Vec<EdwardsPoint>,
+    pub proof_of_possession: SchnorrPop,
+    pub shares: Vec<DealerShare>,
+}
+
+/// The share a dealer sends to recipient `recipient_index` (the recipient's
+/// 1-based party index, matching the `x` coordinate used in VSS elsewhere in
+/// this crate).
+#[derive(Clone)]
+pub struct DealerShare {
+    pub recipient_index: u32,
+    pub value: Scalar,
+}
+
+/// A Schnorr proof of possession of the discrete log of `commitments[0]`,
+/// binding the dealer's identity so that a rogue-key attack (copying another
+/// party's commitment without knowing its discrete log) cannot succeed.
+pub struct SchnorrPop {
+    pub r: EdwardsPoint,
+    pub s: Scalar,
+}
+
+/// Deals a fresh degree-`threshold` polynomial and evaluates it at every
+/// index in `recipient_indices`. `dealer_identity` should uniquely identify
+/// the dealer (e.g. its party ID bytes) and is bound into the
+/// proof-of-possession.
+pub fn deal<R: RngCore + CryptoRng>(
+    threshold: usize,
+    recipient_indices: &[u32],
+    dealer_identity: &[u8],
+    rng: &mut R,
+) -> DealerOutput {
+    let coeffs: Vec<Scalar> = (0..=threshold).map(|_| Scalar::random(rng)).collect();
+    let commitments: Vec<EdwardsPoint> = coeffs.iter().map(|a_k| ED25519_BASEPOINT_POINT * a_k).collect();
+
+    let shares = recipient_indices
+        .iter()
+        .map(|&j| DealerShare { recipient_index: j, value: evaluate(&coeffs, j) })
+        .collect();
+
+    let proof_of_possession = prove_possession(&coeffs[0], &commitments[0], dealer_identity, rng);
+
+    DealerOutput { commitments, proof_of_possession, shares }
+}
+
+fn evaluate(coeffs: &[Scalar], x: u32) -> Scalar {
+    let x = Scalar::from(x as u64);
+    let mut value = Scalar::ZERO;
+    let mut x_pow = Scalar::ONE;
+    for a_k in coeffs {
+        value += a_k * x_pow;
+        x_pow *= x;
+    }
+    value
+}
+
+fn prove_possession<R: RngCore + CryptoRng>(
+    a0: &Scalar,
+    c0: &EdwardsPoint,
+    identity: &[u8],
+    rng: &mut R,
+) -> SchnorrPop {
+    let r_scalar = Scalar::random(rng);
+    let r_point = ED25519_BASEPOINT_POINT * r_scalar;
+    let c = pop_challenge(&r_point, c0, identity);
+    let s = r_scalar + c * a0;
+    SchnorrPop { r: r_point, s }
+}
+
+/// Verifies a dealer's proof of possession of the discrete log of `c0`
+/// (`commitments[0]`) bound to `identity`.
+pub fn verify_possession(pop: &SchnorrPop, c0: &EdwardsPoint, identity: &[u8]) -> bool {
+    let c = pop_challenge(&pop.r, c0, identity);
+    let lhs = ED25519_BASEPOINT_POINT * pop.s;
+    let rhs = pop.r + c0 * c;
+    lhs == rhs
+}
+
+fn pop_challenge(r: &EdwardsPoint, c0: &EdwardsPoint, identity: &[u8]) -> Scalar {
+    let mut hasher = Sha512::new();
+    hasher.update(r.compress().as_bytes());
+    hasher.update(c0.compress().as_bytes());
+    hasher.update(identity);
+    let mut wide = [0u8; 64];
+    wide.copy_from_slice(&hasher.finalize());
+    Scalar::from_bytes_mod_order_wide(&wide)
+}
+
+/// Evaluates a Feldman commitment vector at `x`: `Σ C_k * x^k`. This is the
+/// public-side counterpart of `evaluate()` -- the same accumulation of
+/// successive powers of `x`, but over commitments instead of scalars, since the
+/// coefficients are hidden behind `g^{a_k}` and addition in the exponent is
+/// all that's available.
+fn evaluate_commitments(commitments: &[EdwardsPoint], x: u32) -> EdwardsPoint {
+    let x = Scalar::from(x as u64);
+    let mut value = EdwardsPoint::identity();
+    let mut x_pow = Scalar::ONE;
+    for c_k in commitments {
+        value += c_k * x_pow;
+        x_pow *= x;
+    }
+    value
+}
+
+/// Verifies a single share against the dealer's Feldman commitments:
+/// `g^share == Π C_k^{j^k}`.
+pub fn verify_share(share: &DealerShare, commitments: &[EdwardsPoint]) -> bool {
+    ED25519_BASEPOINT_POINT * share.value == evaluate_commitments(commitments, share.recipient_index)
+}
+
+/// Why a SimplPedPoP round failed to aggregate: one or more dealers sent
+/// material that didn't verify. `culprits` lists the offending dealers'
+/// identities (the same bytes passed to `deal`/`verify_possession`) so a
+/// caller can identifiably abort instead of just failing the whole round
+/// anonymously -- mirroring `TssError::KeygenAbort`'s `culprits` field for
+/// the round-based keygen flow.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DkgError {
+    Aborted { culprits: Vec<Vec<u8>>, reason: String },
+}
+
+impl std::fmt::Display for DkgError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DkgError::Aborted { culprits, reason } => {
+                write!(f, "SimplPedPoP DKG aborted (culprits: {:?}): {}", culprits, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DkgError {}
+
+/// The outcome of a successful round: this party's aggregated secret share,
+/// the group's public key, and the aggregated Feldman commitment vector
+/// (`group_commitments[0] == group_public_key`), in the same shape
+/// `LocalPartySaveData` expects so that signing is unaffected by which
+/// keygen mode produced it. Use `to_save_data` to build that directly.
+pub struct DkgResult {
+    pub secret_share: Scalar,
+    pub group_public_key: EdwardsPoint,
+    pub group_commitments: Vec<EdwardsPoint>,
+}
+
+/// Verifies every dealer's proof of possession and this party's share, then
+/// aggregates: the secret share is the sum of the valid received shares, and
+/// the group commitment vector is every dealer's commitment vector summed
+/// coordinate-wise (an identity-initialized accumulator, each dealer's
+/// `CoefficientCommitment` added in at its own index -- FROST's
+/// group-commitment construction). The group public key is simply
+/// `group_commitments[0]`.
+///
+/// Unlike an early-return on the first bad dealer, every dealer is checked
+/// and every failure recorded in `DkgError::Aborted::culprits`, so a caller
+/// that wants to eject every misbehaving dealer in one pass (rather than
+/// retrying once per culprit) can do so.
+pub fn aggregate(
+    my_index: u32,
+    dealer_outputs: &[(Vec<u8>, DealerOutput)],
+) -> Result<DkgResult, DkgError> {
+    let mut secret_share = Scalar::ZERO;
+    let degree = dealer_outputs
+        .first()
+        .map(|(_, output)| output.commitments.len())
+        .unwrap_or(0);
+    let mut group_commitments = vec![EdwardsPoint::identity(); degree];
+    let mut culprits = Vec::new();
+
+    for (dealer_identity, output) in dealer_outputs {
+        let c0 = match output.commitments.first() {
+            Some(c0) => c0,
+            None => {
+                culprits.push(dealer_identity.clone());
+                continue;
+            }
+        };
+
+        if !verify_possession(&output.proof_of_possession, c0, dealer_identity) {
+            culprits.push(dealer_identity.clone());
+            continue;
+        }
+        if output.commitments.len() != group_commitments.len() {
+            culprits.push(dealer_identity.clone());
+            continue;
+        }
+
+        let share = match output.shares.iter().find(|s| s.recipient_index == my_index) {
+            Some(share) => share,
+            None => {
+                culprits.push(dealer_identity.clone());
+                continue;
+            }
+        };
+
+        if !verify_share(share, &output.commitments) {
+            culprits.push(dealer_identity.clone());
+            continue;
+        }
+
+        secret_share += share.value;
+        for (acc, c_k) in group_commitments.iter_mut().zip(&output.commitments) {
+            *acc += c_k;
+        }
+    }
+
+    if !culprits.is_empty() {
+        return Err(DkgError::Aborted { culprits, reason: "one or more dealers failed verification".to_string() });
+    }
+
+    let group_public_key = *group_commitments.first().ok_or_else(|| DkgError::Aborted {
+        culprits: vec![],
+        reason: "no dealers participated".to_string(),
+    })?;
+    Ok(DkgResult { secret_share, group_public_key, group_commitments })
+}
+
+/// Builds the `LocalPartySaveData` this party ends up with after a
+/// successful `aggregate()` call, in the exact shape the round-based
+/// round_1..round_4 flow produces -- this is the selectable entry point a
+/// caller uses in place of running those rounds when it doesn't need
+/// per-round identifiable aborts. `party_indices` must list every party's
+/// 1-based index in the same ascending order used for `ks`/`big_x_j`
+/// elsewhere (matching `SortedPartyIDs`), and each party's public share is
+/// recovered directly from the aggregated commitment vector via Feldman
+/// evaluation, with no further rounds required.
+pub fn to_save_data(
+    my_index: u32,
+    result: &DkgResult,
+    party_indices: &[u32],
+) -> LocalPartySaveData {
+    let xi = BigInt::from_bytes_le(Sign::Plus, result.secret_share.as_bytes());
+    let share_id = BigInt::from(my_index);
+
+    let mut save_data = LocalPartySaveData::new(party_indices.len(), LocalSecrets { xi, share_id });
+
+    for (j, &index) in party_indices.iter().enumerate() {
+        save_data.ks[j] = BigInt::from(index);
+        save_data.big_x_j[j] = evaluate_commitments(&result.group_commitments, index);
+    }
+    save_data.eddsa_pub = result.group_public_key;
+
+    save_data
+}
+
+/// Selects which EdDSA keygen flavor a `LocalParty` runs: the multi-round
+/// commit/decommit flow (`round_1`/`round_2`/`round_3`), or this module's
+/// single-broadcast SimplPedPoP. Both produce an identically-shaped
+/// `LocalPartySaveData`, so a deployment can pick whichever round-trip /
+/// consistency-check trade-off it wants without touching signing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeygenFlavor {
+    RoundBased,
+    SimplPedPoP,
+}
+
+/// The full SimplPedPoP flow for one local party: verify and aggregate every
+/// dealer's contribution (including this party's own, which must be present
+/// in `dealer_outputs` like any other dealer's) with `aggregate`, then build
+/// this party's `LocalPartySaveData` with `to_save_data`. This is the single
+/// entry point a caller uses in place of driving `round_1`/`round_2`/`round_3`
+/// when `KeygenFlavor::SimplPedPoP` is selected.
+pub fn keygen(
+    my_index: u32,
+    dealer_outputs: &[(Vec<u8>, DealerOutput)],
+    party_indices: &[u32],
+) -> Result<LocalPartySaveData, DkgError> {
+    let result = aggregate(my_index, dealer_outputs)?;
+    Ok(to_save_data(my_index, &result, party_indices))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn test_verify_share_accepts_honest_dealer() {
+        let mut rng = OsRng;
+        let output = deal(2, &[1, 2, 3], b"dealer-1", &mut rng);
+        for share in &output.shares {
+            assert!(verify_share(share, &output.commitments));
+        }
+    }
+
+    #[test]
+    fn test_verify_share_rejects_tampered_value() {
+        let mut rng = OsRng;
+        let output = deal(2, &[1, 2, 3], b"dealer-1", &mut rng);
+        let mut tampered = output.shares[0].clone();
+        tampered.value += Scalar::ONE;
+        assert!(!verify_share(&tampered, &output.commitments));
+    }
+
+    #[test]
+    fn test_proof_of_possession_round_trip() {
+        let mut rng = OsRng;
+        let output = deal(2, &[1, 2, 3], b"dealer-1", &mut rng);
+        assert!(verify_possession(&output.proof_of_possession, &output.commitments[0], b"dealer-1"));
+        assert!(!verify_possession(&output.proof_of_possession, &output.commitments[0], b"dealer-2"));
+    }
+
+    #[test]
+    fn test_aggregate_combines_shares_and_public_key() {
+        let mut rng = OsRng;
+        let recipients = [1, 2, 3];
+        let dealer1 = deal(1, &recipients, b"dealer-1", &mut rng);
+        let dealer2 = deal(1, &recipients, b"dealer-2", &mut rng);
+
+        let expected_pubkey = dealer1.commitments[0] + dealer2.commitments[0];
+
+        for &me in &recipients {
+            let share1 = dealer1.shares.iter().find(|s| s.recipient_index == me).unwrap().clone();
+            let share2 = dealer2.shares.iter().find(|s| s.recipient_index == me).unwrap().clone();
+            let expected_share = share1.value + share2.value;
+
+            let outputs = vec![
+                (b"dealer-1".to_vec(), DealerOutput {
+                    commitments: dealer1.commitments.clone(),
+                    proof_of_possession: SchnorrPop { r: dealer1.proof_of_possession.r, s: dealer1.proof_of_possession.s },
+                    shares: dealer1.shares.clone(),
+                }),
+                (b"dealer-2".to_vec(), DealerOutput {
+                    commitments: dealer2.commitments.clone(),
+                    proof_of_possession: SchnorrPop { r: dealer2.proof_of_possession.r, s: dealer2.proof_of_possession.s },
+                    shares: dealer2.shares.clone(),
+                }),
+            ];
+
+            let result = aggregate(me, &outputs).unwrap();
+            assert_eq!(result.secret_share, expected_share);
+            assert_eq!(result.group_public_key, expected_pubkey);
+            assert_eq!(result.group_commitments[0], expected_pubkey);
+        }
+    }
+
+    #[test]
+    fn test_keygen_entry_point_matches_separate_aggregate_and_save() {
+        let mut rng = OsRng;
+        let recipients = [1, 2, 3];
+        let dealer1 = deal(1, &recipients, b"dealer-1", &mut rng);
+        let dealer2 = deal(1, &recipients, b"dealer-2", &mut rng);
+
+        let outputs = vec![
+            (b"dealer-1".to_vec(), DealerOutput {
+                commitments: dealer1.commitments.clone(),
+                proof_of_possession: SchnorrPop { r: dealer1.proof_of_possession.r, s: dealer1.proof_of_possession.s },
+                shares: dealer1.shares.clone(),
+            }),
+            (b"dealer-2".to_vec(), DealerOutput {
+                commitments: dealer2.commitments.clone(),
+                proof_of_possession: SchnorrPop { r: dealer2.proof_of_possession.r, s: dealer2.proof_of_possession.s },
+                shares: dealer2.shares.clone(),
+            }),
+        ];
+
+        let save_data = keygen(1, &outputs, &recipients).unwrap();
+        let result = aggregate(1, &outputs).unwrap();
+        let expected = to_save_data(1, &result, &recipients);
+
+        assert_eq!(save_data.eddsa_pub, expected.eddsa_pub);
+        assert_eq!(save_data.local_secrets.xi, expected.local_secrets.xi);
+    }
+
+    #[test]
+    fn test_to_save_data_matches_per_party_aggregation() {
+        let mut rng = OsRng;
+        let recipients = [1, 2, 3];
+        let dealer1 = deal(1, &recipients, b"dealer-1", &mut rng);
+        let dealer2 = deal(1, &recipients, b"dealer-2", &mut rng);
+
+        let expected_pubkey = dealer1.commitments[0] + dealer2.commitments[0];
+
+        let outputs = vec![
+            (b"dealer-1".to_vec(), DealerOutput {
+                commitments: dealer1.commitments.clone(),
+                proof_of_possession: SchnorrPop { r: dealer1.proof_of_possession.r, s: dealer1.proof_of_possession.s },
+                shares: dealer1.shares.clone(),
+            }),
+            (b"dealer-2".to_vec(), DealerOutput {
+                commitments: dealer2.commitments.clone(),
+                proof_of_possession: SchnorrPop { r: dealer2.proof_of_possession.r, s: dealer2.proof_of_possession.s },
+                shares: dealer2.shares.clone(),
+            }),
+        ];
+
+        for &me in &recipients {
+            let result = aggregate(me, &outputs).unwrap();
+            let save_data = to_save_data(me, &result, &recipients);
+
+            assert_eq!(save_data.eddsa_pub, expected_pubkey);
+            assert_eq!(save_data.local_secrets.share_id, BigInt::from(me));
+            assert_eq!(
+                save_data.local_secrets.xi,
+                BigInt::from_bytes_le(Sign::Plus, result.secret_share.as_bytes())
+            );
+
+            // Every party's recovered public share must match the share the
+            // aggregated secret polynomial would actually produce for it.
+            for (j, &index) in recipients.iter().enumerate() {
+                let expected_x_j = evaluate_commitments(&result.group_commitments, index);
+                assert_eq!(save_data.big_x_j[j], expected_x_j);
+                assert_eq!(save_data.ks[j], BigInt::from(index));
+            }
+        }
+    }
+
+    #[test]
+    fn test_aggregate_rejects_bad_proof_of_possession() {
+        let mut rng = OsRng;
+        let recipients = [1, 2];
+        let dealer1 = deal(1, &recipients, b"dealer-1", &mut rng);
+
+        let outputs = vec![(b"wrong-identity".to_vec(), DealerOutput {
+            commitments: dealer1.commitments.clone(),
+            proof_of_possession: SchnorrPop { r: dealer1.proof_of_possession.r, s: dealer1.proof_of_possession.s },
+            shares: dealer1.shares.clone(),
+        })];
+
+        match aggregate(1, &outputs) {
+            Err(DkgError::Aborted { culprits, .. }) => assert_eq!(culprits, vec![b"wrong-identity".to_vec()]),
+            other => panic!("expected an Aborted error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_aggregate_reports_every_culprit_not_just_the_first() {
+        let mut rng = OsRng;
+        let recipients = [1, 2];
+        let dealer1 = deal(1, &recipients, b"dealer-1", &mut rng);
+        let dealer2 = deal(1, &recipients, b"dealer-2", &mut rng);
+        let honest = deal(1, &recipients, b"dealer-3", &mut rng);
+
+        // Both dealer-1 and dealer-2 are mislabeled, so their proofs of
+        // possession (bound to their real identities) fail under these
+        // names; dealer-3 is untouched and should still aggregate cleanly.
+        let outputs = vec![
+            (b"not-dealer-1".to_vec(), DealerOutput {
+                commitments: dealer1.commitments.clone(),
+                proof_of_possession: SchnorrPop { r: dealer1.proof_of_possession.r, s: dealer1.proof_of_possession.s },
+                shares: dealer1.shares.clone(),
+            }),
+            (b"not-dealer-2".to_vec(), DealerOutput {
+                commitments: dealer2.commitments.clone(),
+                proof_of_possession: SchnorrPop { r: dealer2.proof_of_possession.r, s: dealer2.proof_of_possession.s },
+                shares: dealer2.shares.clone(),
+            }),
+            (b"dealer-3".to_vec(), DealerOutput {
+                commitments: honest.commitments.clone(),
+                proof_of_possession: SchnorrPop { r: honest.proof_of_possession.r, s: honest.proof_of_possession.s },
+                shares: honest.shares.clone(),
+            }),
+        ];
+
+        match aggregate(1, &outputs) {
+            Err(DkgError::Aborted { culprits, .. }) => {
+                assert_eq!(culprits, vec![b"not-dealer-1".to_vec(), b"not-dealer-2".to_vec()]);
+            }
+            other => panic!("expected an Aborted error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,382 @@
+// Wire messages and aggregation certificate for the SimplPedPoP keygen
+// flavor. `simplpedpop` implements the bare dealing/aggregation math;
+// this module adds the wire format `KeygenFlavor::SimplPedPoP` actually
+// broadcasts (`KGRoundDealerMessage`, mirroring `KGRound1Message` et al. in
+// `messages.rs`) and a final `SPPOutput` certificate parties exchange once
+// dealing is done, recording that every participant aggregated the same
+// transcript -- useful for an operator auditing the keygen after the fact,
+// the same way a Go tss-lib deployment would log round messages for replay.
+//
+// Each dealer's per-recipient share is sealed with `p2p_seal`, but keyed by
+// an X25519 ECDH of the dealer's and recipient's long-term transport keys
+// (`StaticSecret`/`PublicKey`) rather than a secret shared out of band by
+// the whole cohort: only the two parties on either end of that ECDH can
+// derive the AEAD key, so a dealer's broadcast message is no more readable
+// by a third cohort member than it would be over a private channel, and no
+// private channel is actually required to carry it.
+
+use ed25519_dalek::{constants::ED25519_BASEPOINT_POINT, EdwardsPoint, Scalar};
+use prost::Message;
+use rand::{CryptoRng, RngCore};
+use sha2::{Digest, Sha512};
+use x25519_dalek::{PublicKey as XPublicKey, StaticSecret};
+
+use crate::crypto::p2p_seal;
+use crate::eddsa::keygen::simplpedpop::{verify_possession, DealerOutput, DealerShare, SchnorrPop};
+
+/// One dealer's broadcast: Feldman commitments to its polynomial
+/// coefficients, one Chacha20Poly1305-sealed share per recipient (sealed so
+/// a relay carrying the broadcast can't read another party's share), and a
+/// Schnorr proof of possession of the constant-term commitment.
+#[derive(Clone, PartialEq, Message)]
+pub struct KGRoundDealerMessage {
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    pub commitments: Vec<Vec<u8>>,
+    #[prost(bytes = "vec", repeated, tag = "2")]
+    pub encrypted_shares: Vec<Vec<u8>>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub pop_sig: Vec<u8>,
+}
+
+impl KGRoundDealerMessage {
+    pub fn validate(&self) -> bool {
+        !self.commitments.is_empty()
+            && self.commitments.iter().all(|c| c.len() == 32)
+            && !self.encrypted_shares.is_empty()
+            && self.pop_sig.len() == 64
+    }
+
+    pub fn unmarshal_commitments(&self) -> Result<Vec<EdwardsPoint>, String> {
+        self.commitments
+            .iter()
+            .map(|bytes| {
+                let arr: [u8; 32] = bytes.clone().try_into().map_err(|_| "commitment not 32 bytes".to_string())?;
+                ed25519_dalek::CompressedEdwardsY(arr)
+                    .decompress()
+                    .ok_or_else(|| "commitment is not a valid curve point".to_string())
+            })
+            .collect()
+    }
+
+    pub fn unmarshal_pop_sig(&self) -> Result<SchnorrPop, String> {
+        if self.pop_sig.len() != 64 {
+            return Err("pop_sig must be 64 bytes (32-byte R || 32-byte s)".to_string());
+        }
+        let mut r_bytes = [0u8; 32];
+        let mut s_bytes = [0u8; 32];
+        r_bytes.copy_from_slice(&self.pop_sig[..32]);
+        s_bytes.copy_from_slice(&self.pop_sig[32..]);
+        let r = ed25519_dalek::CompressedEdwardsY(r_bytes)
+            .decompress()
+            .ok_or_else(|| "pop_sig R is not a valid curve point".to_string())?;
+        let s: Option<Scalar> = Scalar::from_canonical_bytes(s_bytes).into();
+        let s = s.ok_or("pop_sig s is not canonical")?;
+        Ok(SchnorrPop { r, s })
+    }
+}
+
+/// Builds this dealer's wire message: encodes its commitments, seals one
+/// share per `recipient_keys` entry (`(recipient_index,
+/// recipient_transport_public)`) under the X25519 ECDH of
+/// `dealer_transport_secret` and that recipient's key, and encodes the
+/// proof of possession as `R || s`.
+pub fn new_kg_round_dealer_message<R: RngCore + CryptoRng>(
+    output: &DealerOutput,
+    dealer_transport_secret: &StaticSecret,
+    recipient_keys: &[(u32, XPublicKey)],
+    rng: &mut R,
+) -> KGRoundDealerMessage {
+    let commitments = output.commitments.iter().map(|c| c.compress().to_bytes().to_vec()).collect();
+
+    let encrypted_shares = recipient_keys
+        .iter()
+        .map(|(recipient_index, recipient_public)| {
+            let share = output
+                .shares
+                .iter()
+                .find(|s| s.recipient_index == *recipient_index)
+                .expect("recipient_keys must list exactly the recipients dealt to");
+            let shared_secret = dealer_transport_secret.diffie_hellman(recipient_public);
+            p2p_seal::seal(shared_secret.as_bytes(), recipient_public.as_bytes(), share.value.as_bytes(), rng)
+        })
+        .collect();
+
+    let mut pop_sig = Vec::with_capacity(64);
+    pop_sig.extend_from_slice(output.proof_of_possession.r.compress().as_bytes());
+    pop_sig.extend_from_slice(output.proof_of_possession.s.as_bytes());
+
+    KGRoundDealerMessage { commitments, encrypted_shares, pop_sig }
+}
+
+/// Opens the share this message sealed for `my_index` (the `n`-th entry of
+/// `encrypted_shares`, matching the `n`-th entry the sender built
+/// `recipient_keys` with), re-deriving the same ECDH key from
+/// `my_transport_secret` and the dealer's public key, and verifying the
+/// opened share against the message's commitments.
+pub fn open_share_for(
+    msg: &KGRoundDealerMessage,
+    recipient_position: usize,
+    my_index: u32,
+    my_transport_secret: &StaticSecret,
+    my_transport_public: &XPublicKey,
+    dealer_transport_public: &XPublicKey,
+) -> Result<DealerShare, String> {
+    let sealed = msg.encrypted_shares.get(recipient_position).ok_or("no sealed share at that position")?;
+    let shared_secret = my_transport_secret.diffie_hellman(dealer_transport_public);
+    let plaintext = p2p_seal::open(shared_secret.as_bytes(), my_transport_public.as_bytes(), sealed).map_err(|e| e.to_string())?;
+    let bytes: [u8; 32] = plaintext.try_into().map_err(|_| "decrypted share is not 32 bytes".to_string())?;
+    let value: Option<Scalar> = Scalar::from_canonical_bytes(bytes).into();
+    let value = value.ok_or("decrypted share is not canonical")?;
+
+    let share = DealerShare { recipient_index: my_index, value };
+    let commitments = msg.unmarshal_commitments()?;
+    if !crate::eddsa::keygen::simplpedpop::verify_share(&share, &commitments) {
+        return Err("decrypted share fails Feldman verification".to_string());
+    }
+    Ok(share)
+}
+
+/// Verifies `msg`'s proof of possession, keyed on `dealer_identity`.
+pub fn verify_dealer_message(msg: &KGRoundDealerMessage, dealer_identity: &[u8]) -> Result<(), String> {
+    if !msg.validate() {
+        return Err("malformed dealer message".to_string());
+    }
+    let commitments = msg.unmarshal_commitments()?;
+    let pop = msg.unmarshal_pop_sig()?;
+    if !verify_possession(&pop, &commitments[0], dealer_identity) {
+        return Err("proof of possession failed to verify".to_string());
+    }
+    Ok(())
+}
+
+/// The transcript every participant is attesting to: every dealer's
+/// commitment vector, in ascending dealer-index order, hashed together so a
+/// single `TranscriptSignature` commits to the whole cohort's contributions
+/// at once.
+pub fn transcript_hash(dealer_messages: &[(u32, KGRoundDealerMessage)]) -> [u8; 64] {
+    let mut ordered = dealer_messages.to_vec();
+    ordered.sort_by_key(|(idx, _)| *idx);
+
+    let mut hasher = Sha512::new();
+    for (dealer_index, msg) in &ordered {
+        hasher.update(dealer_index.to_be_bytes());
+        for c in &msg.commitments {
+            hasher.update(c);
+        }
+    }
+    let mut out = [0u8; 64];
+    out.copy_from_slice(&hasher.finalize());
+    out
+}
+
+/// One participant's Schnorr signature over the agreed transcript hash,
+/// proving knowledge of its long-term identity key `identity_priv` (distinct
+/// from any dealt share).
+#[derive(Clone, Debug, PartialEq)]
+pub struct TranscriptSignature {
+    pub party_index: u32,
+    pub r: EdwardsPoint,
+    pub s: Scalar,
+}
+
+fn transcript_challenge(r: &EdwardsPoint, identity_pub: &EdwardsPoint, transcript: &[u8; 64]) -> Scalar {
+    let mut hasher = Sha512::new();
+    hasher.update(b"simplpedpop-transcript-sig");
+    hasher.update(r.compress().as_bytes());
+    hasher.update(identity_pub.compress().as_bytes());
+    hasher.update(transcript);
+    let mut wide = [0u8; 64];
+    wide.copy_from_slice(&hasher.finalize());
+    Scalar::from_bytes_mod_order_wide(&wide)
+}
+
+/// Signs `transcript` with this participant's long-term identity key.
+pub fn sign_transcript<R: RngCore + CryptoRng>(
+    party_index: u32,
+    identity_priv: &Scalar,
+    identity_pub: &EdwardsPoint,
+    transcript: &[u8; 64],
+    rng: &mut R,
+) -> TranscriptSignature {
+    let r_scalar = Scalar::random(rng);
+    let r_point = ED25519_BASEPOINT_POINT * r_scalar;
+    let c = transcript_challenge(&r_point, identity_pub, transcript);
+    let s = r_scalar + c * identity_priv;
+    TranscriptSignature { party_index, r: r_point, s }
+}
+
+pub fn verify_transcript_signature(sig: &TranscriptSignature, transcript: &[u8; 64], identity_pub: &EdwardsPoint) -> bool {
+    let c = transcript_challenge(&sig.r, identity_pub, transcript);
+    ED25519_BASEPOINT_POINT * sig.s == sig.r + identity_pub * c
+}
+
+/// Audit certificate for one run of `KeygenFlavor::SimplPedPoP`: the
+/// transcript hash every signature below commits to, and one
+/// `TranscriptSignature` per participant proving it saw and agreed to that
+/// exact transcript. A caller who persists this (alongside the resulting
+/// `LocalPartySaveData`) can later prove the full cohort participated in
+/// producing a given group key, without needing to keep every
+/// `KGRoundDealerMessage` around.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SPPOutput {
+    pub transcript: [u8; 64],
+    pub signatures: Vec<TranscriptSignature>,
+}
+
+/// Verifies every signature in `signatures` against `transcript` using the
+/// matching entry in `identity_pubs`, and returns the assembled certificate
+/// only if all of them check out -- a single bad signature means that
+/// participant didn't actually agree to this transcript, so the cohort as a
+/// whole can't be certified.
+pub fn aggregate_output(
+    transcript: [u8; 64],
+    signatures: Vec<TranscriptSignature>,
+    identity_pubs: &[(u32, EdwardsPoint)],
+) -> Result<SPPOutput, String> {
+    for sig in &signatures {
+        let identity_pub = identity_pubs
+            .iter()
+            .find(|(idx, _)| *idx == sig.party_index)
+            .map(|(_, pk)| pk)
+            .ok_or_else(|| format!("no identity key on file for party {}", sig.party_index))?;
+        if !verify_transcript_signature(sig, &transcript, identity_pub) {
+            return Err(format!("party {} signature failed to verify", sig.party_index));
+        }
+    }
+    Ok(SPPOutput { transcript, signatures })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eddsa::keygen::simplpedpop::{aggregate, deal};
+    use rand::rngs::OsRng;
+
+    fn gen_transport_keypair(rng: &mut OsRng) -> (StaticSecret, XPublicKey) {
+        let secret = StaticSecret::random_from_rng(rng);
+        let public = XPublicKey::from(&secret);
+        (secret, public)
+    }
+
+    #[test]
+    fn test_dealer_message_round_trip_and_pop() {
+        let mut rng = OsRng;
+        let output = deal(1, &[1, 2], b"dealer-1", &mut rng);
+        let (dealer_secret, dealer_public) = gen_transport_keypair(&mut rng);
+        let (party1_secret, party1_public) = gen_transport_keypair(&mut rng);
+        let (party2_secret, party2_public) = gen_transport_keypair(&mut rng);
+        let recipient_keys = vec![(1, party1_public), (2, party2_public)];
+
+        let msg = new_kg_round_dealer_message(&output, &dealer_secret, &recipient_keys, &mut rng);
+        assert!(msg.validate());
+        verify_dealer_message(&msg, b"dealer-1").unwrap();
+        assert!(verify_dealer_message(&msg, b"dealer-2").is_err());
+
+        let share_1 = open_share_for(&msg, 0, 1, &party1_secret, &party1_public, &dealer_public).unwrap();
+        let share_2 = open_share_for(&msg, 1, 2, &party2_secret, &party2_public, &dealer_public).unwrap();
+        assert_eq!(share_1.value, output.shares.iter().find(|s| s.recipient_index == 1).unwrap().value);
+        assert_eq!(share_2.value, output.shares.iter().find(|s| s.recipient_index == 2).unwrap().value);
+    }
+
+    #[test]
+    fn test_open_share_fails_for_wrong_recipient_key() {
+        let mut rng = OsRng;
+        let output = deal(1, &[1, 2], b"dealer-1", &mut rng);
+        let (dealer_secret, dealer_public) = gen_transport_keypair(&mut rng);
+        let (_party1_secret, party1_public) = gen_transport_keypair(&mut rng);
+        let (_party2_secret, party2_public) = gen_transport_keypair(&mut rng);
+        let recipient_keys = vec![(1, party1_public), (2, party2_public)];
+        let msg = new_kg_round_dealer_message(&output, &dealer_secret, &recipient_keys, &mut rng);
+
+        let (wrong_secret, wrong_public) = gen_transport_keypair(&mut rng);
+        assert!(open_share_for(&msg, 0, 1, &wrong_secret, &wrong_public, &dealer_public).is_err());
+    }
+
+    #[test]
+    fn test_full_simplpedpop_via_wire_messages_matches_direct_aggregate() {
+        let mut rng = OsRng;
+        let recipients = [1u32, 2, 3];
+        let transport_keys: Vec<(u32, StaticSecret, XPublicKey)> = recipients
+            .iter()
+            .map(|&i| {
+                let (secret, public) = gen_transport_keypair(&mut rng);
+                (i, secret, public)
+            })
+            .collect();
+        let recipient_keys: Vec<(u32, XPublicKey)> = transport_keys.iter().map(|(i, _, pk)| (*i, *pk)).collect();
+
+        let dealer1 = deal(1, &recipients, b"dealer-1", &mut rng);
+        let dealer2 = deal(1, &recipients, b"dealer-2", &mut rng);
+        let (dealer1_secret, dealer1_public) = gen_transport_keypair(&mut rng);
+        let (dealer2_secret, dealer2_public) = gen_transport_keypair(&mut rng);
+        let msg1 = new_kg_round_dealer_message(&dealer1, &dealer1_secret, &recipient_keys, &mut rng);
+        let msg2 = new_kg_round_dealer_message(&dealer2, &dealer2_secret, &recipient_keys, &mut rng);
+
+        for (position, &me) in recipients.iter().enumerate() {
+            let (my_secret, my_public) = transport_keys.iter().find(|(i, _, _)| *i == me).map(|(_, s, p)| (s, *p)).unwrap();
+            verify_dealer_message(&msg1, b"dealer-1").unwrap();
+            verify_dealer_message(&msg2, b"dealer-2").unwrap();
+
+            let share1 = open_share_for(&msg1, position, me, my_secret, &my_public, &dealer1_public).unwrap();
+            let share2 = open_share_for(&msg2, position, me, my_secret, &my_public, &dealer2_public).unwrap();
+
+            let direct_outputs = vec![
+                (b"dealer-1".to_vec(), DealerOutput {
+                    commitments: dealer1.commitments.clone(),
+                    proof_of_possession: SchnorrPop { r: dealer1.proof_of_possession.r, s: dealer1.proof_of_possession.s },
+                    shares: vec![share1],
+                }),
+                (b"dealer-2".to_vec(), DealerOutput {
+                    commitments: dealer2.commitments.clone(),
+                    proof_of_possession: SchnorrPop { r: dealer2.proof_of_possession.r, s: dealer2.proof_of_possession.s },
+                    shares: vec![share2],
+                }),
+            ];
+            let result = aggregate(me, &direct_outputs).unwrap();
+            assert_eq!(result.group_public_key, dealer1.commitments[0] + dealer2.commitments[0]);
+        }
+    }
+
+    #[test]
+    fn test_transcript_signature_round_trip_and_tamper_detection() {
+        let mut rng = OsRng;
+        let identity_priv = Scalar::random(&mut rng);
+        let identity_pub = ED25519_BASEPOINT_POINT * identity_priv;
+        let transcript = [7u8; 64];
+
+        let sig = sign_transcript(1, &identity_priv, &identity_pub, &transcript, &mut rng);
+        assert!(verify_transcript_signature(&sig, &transcript, &identity_pub));
+
+        let other_transcript = [8u8; 64];
+        assert!(!verify_transcript_signature(&sig, &other_transcript, &identity_pub));
+    }
+
+    #[test]
+    fn test_aggregate_output_rejects_bad_signature() {
+        let mut rng = OsRng;
+        let priv1 = Scalar::random(&mut rng);
+        let pub1 = ED25519_BASEPOINT_POINT * priv1;
+        let transcript = [1u8; 64];
+        let mut sig = sign_transcript(1, &priv1, &pub1, &transcript, &mut rng);
+        sig.s += Scalar::ONE;
+
+        let result = aggregate_output(transcript, vec![sig], &[(1, pub1)]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_aggregate_output_accepts_every_valid_signature() {
+        let mut rng = OsRng;
+        let priv1 = Scalar::random(&mut rng);
+        let pub1 = ED25519_BASEPOINT_POINT * priv1;
+        let priv2 = Scalar::random(&mut rng);
+        let pub2 = ED25519_BASEPOINT_POINT * priv2;
+        let transcript = [2u8; 64];
+
+        let sig1 = sign_transcript(1, &priv1, &pub1, &transcript, &mut rng);
+        let sig2 = sign_transcript(2, &priv2, &pub2, &transcript, &mut rng);
+
+        let output = aggregate_output(transcript, vec![sig1, sig2], &[(1, pub1), (2, pub2)]).unwrap();
+        assert_eq!(output.signatures.len(), 2);
+    }
+}
@@ -0,0 +1,501 @@
+// `LocalParty` drives the commit/reveal resharing protocol `resharing.rs`
+// describes: every old-committee member deals a fresh Lagrange-weighted
+// sub-share of its existing key to every new-committee member, a new member
+// verifies each dealer's contribution against that dealer's already-known
+// public share, and combines a quorum of `old_threshold + 1` verified
+// contributions into its new share once it has them all. Reuses the
+// round-advancing shape `eddsa::keygen::local_party::LocalParty` uses
+// (`current_round`, `update_from_bytes`, driving itself forward as enough
+// messages arrive) but, since this protocol is a fixed two-message-type
+// exchange rather than an open-ended round sequence, tracks progress with a
+// plain two-state enum instead of a `Round` trait object.
+//
+// New parties pick a single canonical quorum -- the first `old_threshold + 1`
+// members of `old_committee` by index -- rather than whichever `old_threshold
+// + 1` dealers happen to report in first, so two honest new parties can't
+// diverge by combining a different set of contributions (the same problem
+// `eddsa::keygen`'s ACS-backed qualified set solves for fresh keygen, without
+// needing the full ACS machinery here since the old committee's membership is
+// already fixed and known in advance).
+
+use std::collections::HashMap;
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+
+use ed25519_dalek::{EdwardsPoint, Scalar};
+use num_traits::ToPrimitive;
+use rand::{CryptoRng, RngCore};
+
+use crate::eddsa::keygen::resharing::{self, ReSharingParameters, ReShareRound1Message, ReShareRound2Message1, ReShareRound2Message2};
+use crate::eddsa::keygen::save_data::LocalPartySaveData;
+use crate::eddsa::resharing::messages::ResharingMessage;
+use crate::tss::party_id::PartyID;
+use crate::tss::transport::Transport;
+
+fn party_index(id: &PartyID) -> u32 {
+    id.key().to_u32().expect("resharing: party index must fit in u32")
+}
+
+fn dealer_identity_bytes(dealer: &PartyID) -> Vec<u8> {
+    dealer.id().as_bytes().to_vec()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResharingRound {
+    /// Still dealing (if an old-committee member) and/or collecting and
+    /// verifying other dealers' contributions (if a new-committee member).
+    Collecting,
+    /// Finished: an old-only party has dealt and zeroized its old share, or
+    /// a new party has combined a quorum of contributions and reported its
+    /// new share on `end_channel`.
+    Done,
+}
+
+/// Drives one party's side of an `eddsa::keygen::resharing` run between an
+/// old committee/threshold and a new one. Construct one per party, call
+/// [`LocalParty::start`] once, then feed it every message the transport
+/// delivers via [`LocalParty::update_from_bytes`] until [`LocalParty::done`].
+pub struct LocalParty {
+    params: ReSharingParameters,
+    transport: Arc<dyn Transport>,
+    end_channel: Sender<LocalPartySaveData>,
+
+    /// This party's existing share, if it's an old-committee member. Zeroized
+    /// (not cleared) once dealing is done, whether or not this party is also
+    /// in the new committee -- the new share it ends up with, if any, is a
+    /// fresh value combined from other parties' sub-shares, not this one.
+    old_share: Option<Scalar>,
+    /// The old committee's public shares (`big_x_j` from the pre-reshare
+    /// `LocalPartySaveData`), keyed by each old party's index -- needed to
+    /// verify every dealer's contribution, including by a new party that was
+    /// never part of the old committee and so never had this on hand before.
+    /// Keyed rather than positional so a caller can't silently hand these in
+    /// out of step with `old_committee`'s own ordering.
+    old_committee_public_shares: HashMap<u32, EdwardsPoint>,
+    /// The group public key, unchanged by a reshare; every new share must
+    /// reconstruct to this.
+    group_public_key: EdwardsPoint,
+
+    round1_received: HashMap<u32, ReShareRound1Message>,
+    round2_broadcast_received: HashMap<u32, ReShareRound2Message2>,
+    round2_p2p_received: HashMap<u32, ReShareRound2Message1>,
+
+    current_round: ResharingRound,
+}
+
+impl LocalParty {
+    pub fn new(
+        params: ReSharingParameters,
+        transport: Arc<dyn Transport>,
+        old_share: Option<Scalar>,
+        old_committee_public_shares: HashMap<u32, EdwardsPoint>,
+        group_public_key: EdwardsPoint,
+        end_channel: Sender<LocalPartySaveData>,
+    ) -> Self {
+        LocalParty {
+            params,
+            transport,
+            end_channel,
+            old_share,
+            old_committee_public_shares,
+            group_public_key,
+            round1_received: HashMap::new(),
+            round2_broadcast_received: HashMap::new(),
+            round2_p2p_received: HashMap::new(),
+            current_round: ResharingRound::Collecting,
+        }
+    }
+
+    /// Convenience constructor that pulls round-1/round-2 verification
+    /// inputs directly out of existing post-keygen `LocalPartySaveData`
+    /// instead of requiring the caller to hand-extract `xi`/`big_x_j`/
+    /// `eddsa_pub` itself. `committee_save_data` is any one old-committee
+    /// member's save data (they all agree on `ks`, `big_x_j`, and
+    /// `eddsa_pub`); `own_save_data` is `Some` only for an old-committee
+    /// member dealing its own share.
+    pub fn from_previous_save_data(
+        params: ReSharingParameters,
+        transport: Arc<dyn Transport>,
+        own_save_data: Option<&LocalPartySaveData>,
+        committee_save_data: &LocalPartySaveData,
+        end_channel: Sender<LocalPartySaveData>,
+    ) -> Result<Self, String> {
+        let (old_committee_public_shares, group_public_key) = resharing::old_committee_inputs_from_save_data(committee_save_data)?;
+        let old_share = own_save_data.map(resharing::old_share_from_save_data);
+        Ok(LocalParty::new(params, transport, old_share, old_committee_public_shares, group_public_key, end_channel))
+    }
+
+    pub fn done(&self) -> bool {
+        self.current_round == ResharingRound::Done
+    }
+
+    /// Deals this party's sub-shares (if it's an old-committee member) and
+    /// broadcasts/send-P2Ps the round-1/round-2 messages. A new-only party
+    /// (not in the old committee) has nothing to send and just starts
+    /// collecting.
+    pub fn start<R: RngCore + CryptoRng>(&mut self, rng: &mut R) -> Result<(), String> {
+        if self.current_round != ResharingRound::Collecting {
+            return Err("resharing: party already started".to_string());
+        }
+        if !self.params.is_old_committee_member() && !self.params.is_new_committee_member() {
+            return Err("resharing: this party belongs to neither the old nor the new committee".to_string());
+        }
+
+        if self.params.is_old_committee_member() {
+            let old_share = self
+                .old_share
+                .ok_or_else(|| "resharing: old committee member is missing its existing share".to_string())?;
+            let dealer_index = party_index(self.params.party_id());
+            let new_indices: Vec<u32> = self.params.new_committee().iter().map(party_index).collect();
+
+            let state = resharing::deal_round1(dealer_index, &old_share, self.params.new_threshold(), &new_indices, rng);
+            self.transport
+                .send_broadcast(ResharingMessage::from_round1(&state.round1).to_bytes())
+                .map_err(|e| format!("resharing: {}", e))?;
+
+            let dealer_identity = dealer_identity_bytes(self.params.party_id());
+            let round2 = state.round2_broadcast(&old_share, &dealer_identity, rng);
+            self.transport
+                .send_broadcast(ResharingMessage::from_round2_broadcast(&round2).to_bytes())
+                .map_err(|e| format!("resharing: {}", e))?;
+
+            // A dealer that's also a new-committee member won't see its own
+            // broadcast/P2P come back over the transport, so it has to feed
+            // its own contribution into its collection state directly.
+            if self.params.is_new_committee_member() {
+                self.round1_received.insert(dealer_index, state.round1.clone());
+                self.round2_broadcast_received.insert(dealer_index, round2.clone());
+            }
+
+            for sub_share in &state.sub_shares {
+                if sub_share.recipient_index == dealer_index && self.params.is_new_committee_member() {
+                    self.round2_p2p_received.insert(dealer_index, sub_share.clone());
+                    continue;
+                }
+                let recipient = self
+                    .params
+                    .new_committee()
+                    .iter()
+                    .find(|p| party_index(p) == sub_share.recipient_index)
+                    .ok_or_else(|| format!("resharing: no new-committee party with index {}", sub_share.recipient_index))?;
+                self.transport
+                    .send_p2p(recipient, ResharingMessage::from_round2_p2p(sub_share).to_bytes())
+                    .map_err(|e| format!("resharing: {}", e))?;
+            }
+
+            if let Some(share) = self.old_share.as_mut() {
+                resharing::zeroize_old_share(share);
+            }
+        }
+
+        if !self.params.is_new_committee_member() {
+            self.current_round = ResharingRound::Done;
+            return Ok(());
+        }
+
+        self.try_finalize()
+    }
+
+    /// Feeds one message in off the wire: stores it, then tries to finalize
+    /// if this is a new-committee member waiting on a quorum of dealers.
+    pub fn update_from_bytes(&mut self, from: &PartyID, bytes: &[u8]) -> Result<(), String> {
+        if !self.params.is_new_committee_member() {
+            // An old-only party has nothing left to receive once it's dealt;
+            // checked before the "already finished" error below since an
+            // old-only party finishes during `start` and should keep silently
+            // ignoring messages after that, not start erroring on them.
+            return Ok(());
+        }
+        if self.current_round == ResharingRound::Done {
+            return Err("resharing: party already finished".to_string());
+        }
+
+        let dealer_index = party_index(from);
+        let message = ResharingMessage::from_bytes(bytes)?;
+        match &message {
+            ResharingMessage::Round1 { .. } => {
+                self.round1_received.insert(dealer_index, message.to_round1()?);
+            }
+            ResharingMessage::Round2Broadcast { .. } => {
+                self.round2_broadcast_received.insert(dealer_index, message.to_round2_broadcast()?);
+            }
+            ResharingMessage::Round2P2P { recipient_index, .. } => {
+                if *recipient_index == party_index(self.params.party_id()) {
+                    self.round2_p2p_received.insert(dealer_index, message.to_round2_p2p()?);
+                }
+                // Else: a sub-share addressed to a different recipient that
+                // the transport fanned out to everyone; not ours to keep.
+            }
+        }
+
+        self.try_finalize()
+    }
+
+    /// The canonical quorum every new party combines over: the first
+    /// `old_threshold + 1` old-committee members by index. Fixed in advance
+    /// (not "whichever dealers reported in first") so two new parties never
+    /// combine a different set of contributions into a different share.
+    fn canonical_quorum(&self) -> Vec<u32> {
+        self.params.old_committee().iter().map(party_index).take(self.params.old_threshold() + 1).collect()
+    }
+
+    fn known_public_share(&self, dealer_index: u32) -> Option<EdwardsPoint> {
+        self.old_committee_public_shares.get(&dealer_index).copied()
+    }
+
+    fn try_finalize(&mut self) -> Result<(), String> {
+        if self.current_round == ResharingRound::Done {
+            return Ok(());
+        }
+
+        let my_index = party_index(self.params.party_id());
+        let quorum = self.canonical_quorum();
+        let mut verified = Vec::with_capacity(quorum.len());
+        for &dealer_index in &quorum {
+            let (round1, round2, p2p) = match (
+                self.round1_received.get(&dealer_index),
+                self.round2_broadcast_received.get(&dealer_index),
+                self.round2_p2p_received.get(&dealer_index),
+            ) {
+                (Some(round1), Some(round2), Some(p2p)) => (round1, round2, p2p),
+                _ => return Ok(()), // still waiting on this dealer
+            };
+            let known_public_share = self
+                .known_public_share(dealer_index)
+                .ok_or_else(|| format!("resharing: no known public share on file for old party {}", dealer_index))?;
+            let dealer = self
+                .params
+                .old_committee()
+                .iter()
+                .find(|p| party_index(p) == dealer_index)
+                .ok_or_else(|| format!("resharing: old party {} is not in the old committee", dealer_index))?;
+
+            let v = resharing::verify_contribution(
+                my_index,
+                dealer_index,
+                round1,
+                round2,
+                p2p,
+                &known_public_share,
+                &dealer_identity_bytes(dealer),
+            )
+            .map_err(|culprit| format!("resharing: dealer {} failed contribution verification", culprit))?;
+            verified.push(v);
+        }
+
+        let new_indices: Vec<u32> = self.params.new_committee().iter().map(party_index).collect();
+        let save_data = resharing::finish_new_party(my_index, &quorum, &verified, &self.group_public_key, &new_indices)
+            .map_err(|e| format!("resharing: {}", e))?;
+
+        self.current_round = ResharingRound::Done;
+        self.end_channel.send(save_data).map_err(|_| "resharing: end channel closed before finishing".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tss::party_id::SortedPartyIDs;
+    use ed25519_dalek::constants::ED25519_BASEPOINT_POINT;
+    use num_bigint::BigInt;
+    use rand::rngs::OsRng;
+    use std::sync::mpsc::channel;
+
+    struct RecordingTransport {
+        broadcasts: std::sync::Mutex<Vec<Vec<u8>>>,
+        p2p: std::sync::Mutex<Vec<(PartyID, Vec<u8>)>>,
+    }
+
+    impl RecordingTransport {
+        fn new() -> Self {
+            RecordingTransport { broadcasts: std::sync::Mutex::new(Vec::new()), p2p: std::sync::Mutex::new(Vec::new()) }
+        }
+    }
+
+    impl Transport for RecordingTransport {
+        fn send_p2p(&self, to: &PartyID, bytes: Vec<u8>) -> Result<(), crate::tss::transport::TransportError> {
+            self.p2p.lock().unwrap().push((to.clone(), bytes));
+            Ok(())
+        }
+
+        fn send_broadcast(&self, bytes: Vec<u8>) -> Result<(), crate::tss::transport::TransportError> {
+            self.broadcasts.lock().unwrap().push(bytes);
+            Ok(())
+        }
+    }
+
+    fn party(index: u32) -> PartyID {
+        PartyID::new(format!("p{index}"), format!("party-{index}"), BigInt::from(index))
+    }
+
+    /// Runs a full 3-old/3-new reshare end to end by routing each dealer's
+    /// `RecordingTransport` output straight into every new party's
+    /// `update_from_bytes`, mirroring how `test_full_reshare_preserves_...`
+    /// in `resharing.rs` drives the bare crypto core.
+    #[test]
+    fn test_full_reshare_via_local_party_preserves_public_key() {
+        let mut rng = OsRng;
+        let secret = Scalar::random(&mut rng);
+        let old_ids: Vec<PartyID> = (1..=3).map(party).collect();
+        let new_ids: Vec<PartyID> = (10..=12).map(party).collect();
+        let old_coeffs = vec![secret, Scalar::random(&mut rng)];
+        let old_shares: Vec<Scalar> = old_ids
+            .iter()
+            .map(|p| {
+                let x = Scalar::from(party_index(p) as u64);
+                old_coeffs[0] + old_coeffs[1] * x
+            })
+            .collect();
+        let old_public_shares: HashMap<u32, EdwardsPoint> = old_ids
+            .iter()
+            .zip(old_shares.iter())
+            .map(|(p, s)| (party_index(p), ED25519_BASEPOINT_POINT * s))
+            .collect();
+        let group_public_key = ED25519_BASEPOINT_POINT * secret;
+
+        let old_committee = Arc::new(SortedPartyIDs::new(old_ids.clone()));
+        let new_committee = Arc::new(SortedPartyIDs::new(new_ids.clone()));
+
+        let mut dealer_parties: Vec<LocalParty> = Vec::new();
+        let mut dealer_transports: Vec<Arc<RecordingTransport>> = Vec::new();
+        for (i, id) in old_ids.iter().enumerate() {
+            let params = ReSharingParameters::new(old_committee.clone(), 2, new_committee.clone(), 1, id.clone());
+            let transport = Arc::new(RecordingTransport::new());
+            let (end_tx, _end_rx) = channel();
+            let lp = LocalParty::new(params, transport.clone(), Some(old_shares[i]), old_public_shares.clone(), group_public_key, end_tx);
+            dealer_parties.push(lp);
+            dealer_transports.push(transport);
+        }
+        for dealer in dealer_parties.iter_mut() {
+            dealer.start(&mut rng).unwrap();
+        }
+
+        let mut new_results = Vec::new();
+        for id in &new_ids {
+            let params = ReSharingParameters::new(old_committee.clone(), 2, new_committee.clone(), 1, id.clone());
+            let transport = Arc::new(RecordingTransport::new());
+            let (end_tx, end_rx) = channel();
+            let mut lp = LocalParty::new(params, transport, None, old_public_shares.clone(), group_public_key, end_tx);
+            lp.start(&mut rng).unwrap();
+
+            for (k, dealer_transport) in dealer_transports.iter().enumerate() {
+                for bytes in dealer_transport.broadcasts.lock().unwrap().iter() {
+                    lp.update_from_bytes(&old_ids[k], bytes).unwrap();
+                }
+                for (to, bytes) in dealer_transport.p2p.lock().unwrap().iter() {
+                    if to == id {
+                        lp.update_from_bytes(&old_ids[k], bytes).unwrap();
+                    }
+                }
+            }
+
+            assert!(lp.done());
+            new_results.push(end_rx.recv().unwrap());
+        }
+
+        for save_data in &new_results {
+            assert_eq!(save_data.eddsa_pub, group_public_key);
+        }
+    }
+
+    /// Same end-to-end run as `test_full_reshare_via_local_party_preserves_public_key`,
+    /// but old-committee members construct via `from_previous_save_data` out
+    /// of a real post-keygen `LocalPartySaveData` instead of hand-assembling
+    /// a `Scalar`/`HashMap` themselves.
+    #[test]
+    fn test_full_reshare_from_previous_save_data_preserves_public_key() {
+        use crate::eddsa::keygen::save_data::{LocalPartySaveData, LocalSecrets};
+        use num_bigint::Sign;
+
+        let mut rng = OsRng;
+        let secret = Scalar::random(&mut rng);
+        let old_ids: Vec<PartyID> = (1..=3).map(party).collect();
+        let new_ids: Vec<PartyID> = (10..=12).map(party).collect();
+        let old_coeffs = vec![secret, Scalar::random(&mut rng)];
+        let old_shares: Vec<Scalar> = old_ids
+            .iter()
+            .map(|p| {
+                let x = Scalar::from(party_index(p) as u64);
+                old_coeffs[0] + old_coeffs[1] * x
+            })
+            .collect();
+        let old_public_shares: Vec<EdwardsPoint> = old_shares.iter().map(|s| ED25519_BASEPOINT_POINT * s).collect();
+        let group_public_key = ED25519_BASEPOINT_POINT * secret;
+
+        let old_committee = Arc::new(SortedPartyIDs::new(old_ids.clone()));
+        let new_committee = Arc::new(SortedPartyIDs::new(new_ids.clone()));
+
+        // Any one old party's save data carries the full committee's
+        // `ks`/`big_x_j`/`eddsa_pub`, so it doubles as `committee_save_data`
+        // for every other old or new party too.
+        let mut committee_save_data = LocalPartySaveData::new(old_ids.len(), LocalSecrets { xi: BigInt::default(), share_id: BigInt::default() });
+        for (j, id) in old_ids.iter().enumerate() {
+            committee_save_data.ks[j] = BigInt::from(party_index(id));
+            committee_save_data.big_x_j[j] = old_public_shares[j];
+        }
+        committee_save_data.eddsa_pub = group_public_key;
+
+        let own_save_data: Vec<LocalPartySaveData> = old_shares
+            .iter()
+            .map(|s| {
+                let mut sd = committee_save_data.clone();
+                sd.local_secrets.xi = BigInt::from_bytes_le(Sign::Plus, s.as_bytes());
+                sd
+            })
+            .collect();
+
+        let mut dealer_parties: Vec<LocalParty> = Vec::new();
+        let mut dealer_transports: Vec<Arc<RecordingTransport>> = Vec::new();
+        for (i, id) in old_ids.iter().enumerate() {
+            let params = ReSharingParameters::new(old_committee.clone(), 2, new_committee.clone(), 1, id.clone());
+            let transport = Arc::new(RecordingTransport::new());
+            let (end_tx, _end_rx) = channel();
+            let lp = LocalParty::from_previous_save_data(params, transport.clone(), Some(&own_save_data[i]), &committee_save_data, end_tx).unwrap();
+            dealer_parties.push(lp);
+            dealer_transports.push(transport);
+        }
+        for dealer in dealer_parties.iter_mut() {
+            dealer.start(&mut rng).unwrap();
+        }
+
+        let mut new_results = Vec::new();
+        for id in &new_ids {
+            let params = ReSharingParameters::new(old_committee.clone(), 2, new_committee.clone(), 1, id.clone());
+            let transport = Arc::new(RecordingTransport::new());
+            let (end_tx, end_rx) = channel();
+            let mut lp = LocalParty::from_previous_save_data(params, transport, None, &committee_save_data, end_tx).unwrap();
+            lp.start(&mut rng).unwrap();
+
+            for (k, dealer_transport) in dealer_transports.iter().enumerate() {
+                for bytes in dealer_transport.broadcasts.lock().unwrap().iter() {
+                    lp.update_from_bytes(&old_ids[k], bytes).unwrap();
+                }
+                for (to, bytes) in dealer_transport.p2p.lock().unwrap().iter() {
+                    if to == id {
+                        lp.update_from_bytes(&old_ids[k], bytes).unwrap();
+                    }
+                }
+            }
+
+            assert!(lp.done());
+            new_results.push(end_rx.recv().unwrap());
+        }
+
+        for save_data in &new_results {
+            assert_eq!(save_data.eddsa_pub, group_public_key);
+        }
+    }
+
+    #[test]
+    fn test_neither_old_nor_new_member_is_rejected() {
+        let old_committee = Arc::new(SortedPartyIDs::new(vec![party(1), party(2)]));
+        let new_committee = Arc::new(SortedPartyIDs::new(vec![party(10), party(11)]));
+        let bystander = party(99);
+        let params = ReSharingParameters::new(old_committee, 1, new_committee, 1, bystander);
+        let transport = Arc::new(RecordingTransport::new());
+        let (end_tx, _end_rx) = channel();
+        let mut lp = LocalParty::new(params, transport, None, HashMap::new(), EdwardsPoint::default(), end_tx);
+
+        assert!(lp.start(&mut OsRng).is_err());
+    }
+}
@@ -0,0 +1,159 @@
+// Wire-format mirror of `eddsa::keygen::resharing`'s three round messages.
+//
+// `EdwardsPoint`/`Scalar` have no serde impl of their own, so each variant
+// here carries compressed/encoded bytes instead of the point/scalar types
+// directly -- the same approach `LocalPartySaveData` uses in `save_data.rs`.
+// `ResharingMessage` is the single enum `LocalParty::update_from_bytes`
+// deserializes off the wire; which variant it is also tells the round
+// machinery which leg of the protocol the message belongs to.
+
+use ed25519_dalek::{CompressedEdwardsY, EdwardsPoint, Scalar};
+use serde::{Deserialize, Serialize};
+
+use crate::eddsa::keygen::resharing::{ReShareRound1Message, ReShareRound2Message1, ReShareRound2Message2};
+
+const RESHARING_MESSAGE_WIRE_VERSION: u16 = 1;
+
+fn compress(point: &EdwardsPoint) -> [u8; 32] {
+    point.compress().to_bytes()
+}
+
+fn decompress(bytes: [u8; 32]) -> Result<EdwardsPoint, String> {
+    CompressedEdwardsY(bytes)
+        .decompress()
+        .ok_or_else(|| "resharing message: not a valid compressed Edwards point".to_string())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ResharingMessage {
+    /// `ReShareRound1Message`: a dealer's hash commitment to its Feldman vector.
+    Round1 { commitment: [u8; 64] },
+    /// `ReShareRound2Message1`: one dealer's sub-share, P2P to a single recipient.
+    Round2P2P { recipient_index: u32, share: [u8; 32] },
+    /// `ReShareRound2Message2`: a dealer's decommitment plus proof of knowledge.
+    Round2Broadcast { randomness: [u8; 32], commitments: Vec<[u8; 32]>, proof_r: [u8; 32], proof_s: [u8; 32] },
+}
+
+#[derive(Serialize, Deserialize)]
+struct ResharingMessageWire {
+    version: u16,
+    message: ResharingMessage,
+}
+
+impl ResharingMessage {
+    pub fn from_round1(msg: &ReShareRound1Message) -> Self {
+        ResharingMessage::Round1 { commitment: msg.commitment }
+    }
+
+    pub fn from_round2_p2p(msg: &ReShareRound2Message1) -> Self {
+        ResharingMessage::Round2P2P { recipient_index: msg.recipient_index, share: msg.share.to_bytes() }
+    }
+
+    pub fn from_round2_broadcast(msg: &ReShareRound2Message2) -> Self {
+        ResharingMessage::Round2Broadcast {
+            randomness: msg.randomness,
+            commitments: msg.commitments.iter().map(compress).collect(),
+            proof_r: compress(&msg.proof_r),
+            proof_s: msg.proof_s.to_bytes(),
+        }
+    }
+
+    pub fn to_round1(&self) -> Result<ReShareRound1Message, String> {
+        match self {
+            ResharingMessage::Round1 { commitment } => Ok(ReShareRound1Message { commitment: *commitment }),
+            _ => Err("resharing message: expected a round-1 commitment message".to_string()),
+        }
+    }
+
+    pub fn to_round2_p2p(&self) -> Result<ReShareRound2Message1, String> {
+        match self {
+            ResharingMessage::Round2P2P { recipient_index, share } => {
+                Ok(ReShareRound2Message1 { recipient_index: *recipient_index, share: Scalar::from_bytes_mod_order(*share) })
+            }
+            _ => Err("resharing message: expected a round-2 P2P share message".to_string()),
+        }
+    }
+
+    pub fn to_round2_broadcast(&self) -> Result<ReShareRound2Message2, String> {
+        match self {
+            ResharingMessage::Round2Broadcast { randomness, commitments, proof_r, proof_s } => {
+                let commitments = commitments.iter().map(|c| decompress(*c)).collect::<Result<Vec<_>, _>>()?;
+                Ok(ReShareRound2Message2 {
+                    randomness: *randomness,
+                    commitments,
+                    proof_r: decompress(*proof_r)?,
+                    proof_s: Scalar::from_bytes_mod_order(*proof_s),
+                })
+            }
+            _ => Err("resharing message: expected a round-2 broadcast decommit message".to_string()),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let wire = ResharingMessageWire { version: RESHARING_MESSAGE_WIRE_VERSION, message: self.clone() };
+        serde_json::to_vec(&wire).expect("ResharingMessage always serializes")
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let wire: ResharingMessageWire =
+            serde_json::from_slice(bytes).map_err(|e| format!("resharing message: {}", e))?;
+        if wire.version != RESHARING_MESSAGE_WIRE_VERSION {
+            return Err(format!(
+                "resharing message: unsupported wire version {} (expected {})",
+                wire.version, RESHARING_MESSAGE_WIRE_VERSION
+            ));
+        }
+        Ok(wire.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::constants::ED25519_BASEPOINT_POINT;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn test_round1_round_trips_through_bytes() {
+        let msg = ReShareRound1Message { commitment: [7u8; 64] };
+        let bytes = ResharingMessage::from_round1(&msg).to_bytes();
+        let decoded = ResharingMessage::from_bytes(&bytes).unwrap().to_round1().unwrap();
+        assert_eq!(decoded, msg);
+    }
+
+    #[test]
+    fn test_round2_p2p_round_trips_through_bytes() {
+        let share = Scalar::random(&mut OsRng);
+        let msg = ReShareRound2Message1 { recipient_index: 42, share };
+        let bytes = ResharingMessage::from_round2_p2p(&msg).to_bytes();
+        let decoded = ResharingMessage::from_bytes(&bytes).unwrap().to_round2_p2p().unwrap();
+        assert_eq!(decoded, msg);
+    }
+
+    #[test]
+    fn test_round2_broadcast_round_trips_through_bytes() {
+        let msg = ReShareRound2Message2 {
+            randomness: [3u8; 32],
+            commitments: vec![ED25519_BASEPOINT_POINT, ED25519_BASEPOINT_POINT * Scalar::random(&mut OsRng)],
+            proof_r: ED25519_BASEPOINT_POINT,
+            proof_s: Scalar::random(&mut OsRng),
+        };
+        let bytes = ResharingMessage::from_round2_broadcast(&msg).to_bytes();
+        let decoded = ResharingMessage::from_bytes(&bytes).unwrap().to_round2_broadcast().unwrap();
+        assert_eq!(decoded, msg);
+    }
+
+    #[test]
+    fn test_wrong_variant_conversion_is_rejected() {
+        let msg = ResharingMessage::Round1 { commitment: [0u8; 64] };
+        assert!(msg.to_round2_p2p().is_err());
+        assert!(msg.to_round2_broadcast().is_err());
+    }
+
+    #[test]
+    fn test_unsupported_wire_version_is_rejected() {
+        let wire = ResharingMessageWire { version: RESHARING_MESSAGE_WIRE_VERSION + 1, message: ResharingMessage::Round1 { commitment: [0u8; 64] } };
+        let bytes = serde_json::to_vec(&wire).unwrap();
+        assert!(ResharingMessage::from_bytes(&bytes).is_err());
+    }
+}
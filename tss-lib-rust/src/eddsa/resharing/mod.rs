@@ -0,0 +1,20 @@
+// Round-driven `LocalParty` for EdDSA dynamic resharing / committee change.
+//
+// `eddsa::keygen::reshare` and `eddsa::keygen::resharing` already carry the
+// cryptographic core of this protocol -- Lagrange-weighted dealing, Feldman
+// verification, and the commit/reveal message shapes -- but expose it only
+// as free functions a caller has to drive by hand, message by message. This
+// module is the sibling of `eddsa::keygen` that actually drives it: a
+// `LocalParty` that takes an old committee's save data (or at least its
+// public shares) and a new committee/threshold, and produces the new
+// committee's `LocalPartySaveData` once enough old parties have dealt.
+//
+// Kept as its own top-level module rather than nested under `eddsa::keygen`
+// since it isn't a keygen mode -- it runs against an *existing* key and
+// never touches `eddsa::keygen::Parameters`/`BaseParty` at all.
+
+pub mod messages;
+pub mod local_party;
+
+pub use local_party::LocalParty;
+pub use messages::ResharingMessage;
@@ -5,8 +5,8 @@ pub mod common;
 pub mod crypto;
 pub mod tss;
 pub mod protocols;
+pub mod eddsa;
 
 // Declare other top-level modules here as they are converted
 // pub mod tss;
-// pub mod ecdsa;
-// pub mod eddsa; 
\ No newline at end of file
+// pub mod ecdsa; 
\ No newline at end of file
@@ -2,13 +2,13 @@
 
 use crate::tss::party_id::PartyID;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Mutex;
+use parking_lot::RwLock;
 
 /// Base structure providing common round functionality.
 #[derive(Debug)] // Add Debug trait
 pub(crate) struct BaseRound {
     round_num: i32,
-    ok: Mutex<Vec<bool>>, // Tracks which parties' messages have been processed for the current `update` step
+    ok: RwLock<Vec<bool>>, // Tracks which parties' messages have been processed for the current `update` step
     started: AtomicBool,
 }
 
@@ -16,7 +16,7 @@ impl BaseRound {
     pub fn new(round_num: i32, party_count: usize) -> Self {
         Self {
             round_num,
-            ok: Mutex::new(vec![false; party_count]),
+            ok: RwLock::new(vec![false; party_count]),
             started: AtomicBool::new(false),
         }
     }
@@ -40,14 +40,14 @@ impl BaseRound {
     }
 
     pub fn reset_ok(&self) {
-        let mut ok_guard = self.ok.lock().expect("OK vector lock poisoned");
+        let mut ok_guard = self.ok.write();
         for i in 0..ok_guard.len() {
             ok_guard[i] = false;
         }
     }
 
     pub fn set_ok(&self, party_index: usize) {
-        let mut ok_guard = self.ok.lock().expect("OK vector lock poisoned");
+        let mut ok_guard = self.ok.write();
         if party_index < ok_guard.len() {
             ok_guard[party_index] = true;
         } else {
@@ -57,18 +57,18 @@ impl BaseRound {
     }
 
     pub fn is_ok(&self, party_index: usize) -> bool {
-        let ok_guard = self.ok.lock().expect("OK vector lock poisoned");
+        let ok_guard = self.ok.read();
         party_index < ok_guard.len() && ok_guard[party_index]
     }
 
     /// Returns a copy of the current `ok` vector.
     pub fn get_ok_vec(&self) -> Vec<bool> {
-        self.ok.lock().expect("OK vector lock poisoned").clone()
+        self.ok.read().clone()
     }
 
     /// Helper to determine which parties are still needed for the round to proceed.
     pub fn waiting_for(&self, all_parties: &[std::sync::Arc<PartyID>]) -> Vec<PartyID> {
-        let ok_guard = self.ok.lock().expect("OK vector lock poisoned");
+        let ok_guard = self.ok.read();
         let mut waiting_list = Vec::new();
         for (idx, party) in all_parties.iter().enumerate() {
              // Check if the index is within bounds of the ok vector AND if the party is marked as NOT ok
@@ -9,12 +9,8 @@ use crate::{
     tss::party_id::PartyID,
 };
 use num_bigint_dig::BigInt;
-use std::sync::{
-    mpsc::{channel, Sender, Receiver},
-    Arc,
-};
-use threadpool::ThreadPool; // Using threadpool crate for managing concurrency
-use anyhow::Result;
+use std::fmt;
+use std::thread;
 use log::debug;
 
 /// Context needed to verify a single party's DLN proofs.
@@ -37,90 +33,144 @@ pub struct DlnProofVerificationResult {
     pub culprit: PartyID, // The party whose proofs were checked
 }
 
-/// Manages concurrent verification of DLN proofs.
+/// Which parties failed keygen's DLN proof-pair verification, and how.
+/// Distinguishes a failed `proof1` from a failed `proof2`, and separates both
+/// from `missing`: parties whose result never came back at all (a worker
+/// thread panicked before returning its chunk), which a bare
+/// count-mismatch can't tell apart from an honest failure.
+#[derive(Debug, Clone, Default)]
+pub struct DlnVerificationError {
+    pub bad_proof1: Vec<PartyID>,
+    pub bad_proof2: Vec<PartyID>,
+    pub missing: Vec<PartyID>,
+}
+
+impl fmt::Display for DlnVerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "DLN proof verification failed: {} bad proof1, {} bad proof2, {} missing",
+            self.bad_proof1.len(),
+            self.bad_proof2.len(),
+            self.missing.len()
+        )
+    }
+}
+
+impl std::error::Error for DlnVerificationError {}
+
+/// Manages concurrent verification of DLN proofs. Work is fanned out across
+/// at most `concurrency` scoped threads that borrow each context by reference, so
+/// the large `BigInt`s in a `KGRound1Message` (`ntilde`, `h1`, `h2`, the two
+/// DLN proofs) are never cloned into a worker closure.
 pub struct DlnProofVerifier {
-    pool: ThreadPool,
-    sender: Sender<DlnProofVerificationResult>,
-    receiver: Receiver<DlnProofVerificationResult>,
+    concurrency: usize,
 }
 
 impl DlnProofVerifier {
     /// Creates a new verifier with a specified concurrency level.
     pub fn new(concurrency: usize) -> Self {
-        let (sender, receiver) = channel();
-        let pool = ThreadPool::new(concurrency);
-        Self { pool, sender, receiver }
+        Self { concurrency: concurrency.max(1) }
     }
 
-    /// Queues a DLN proof pair verification task.
-    pub fn verify_dln_proofs(&self, context: DlnProofVerifierContext) {
-        let sender_clone = self.sender.clone();
-
-        self.pool.execute(move || {
-             debug!(target: "tss-lib", party_id = ?context.from_party_id, "Verifying DLN proofs in background thread");
-
-            // Extract data from context
-            let proof1 = &context.r1_msg.dln_proof1;
-            let proof2 = &context.r1_msg.dln_proof2;
-            let h1 = &context.r1_msg.h1;
-            let h2 = &context.r1_msg.h2;
-            let ntilde = &context.r1_msg.ntilde;
-
-            // Verify Proof 1 (h2 = h1^alpha mod ntilde)
-            let proof1_valid = proof1.verify(h1, h2, ntilde);
-
-            // Verify Proof 2 (h1 = h2^beta mod ntilde)
-            let proof2_valid = proof2.verify(h2, h1, ntilde);
-
-            let result = DlnProofVerificationResult {
-                proof1_valid,
-                proof2_valid,
-                culprit: context.from_party_id,
-            };
-
-            if let Err(e) = sender_clone.send(result) {
-                 log::error!("Failed to send DLN verification result: {}", e);
+    /// Verifies every context's DLN proof pair inside one thread scope,
+    /// splitting `contexts` into at most `concurrency` chunks that each run on their
+    /// own borrowed slice. Results come back in the same order as `contexts`,
+    /// so `culprit` attribution stays stable regardless of how work was split.
+    pub fn verify_all(&self, contexts: &[DlnProofVerifierContext]) -> Vec<DlnProofVerificationResult> {
+        if contexts.is_empty() {
+            return Vec::new();
+        }
+        debug!(target: "tss-lib", count = contexts.len(), concurrency = self.concurrency, "Verifying DLN proofs");
+
+        let worker_count = self.concurrency.min(contexts.len());
+        let chunk_size = (contexts.len() + worker_count - 1) / worker_count;
+        let indexed: Vec<(usize, &DlnProofVerifierContext)> = contexts.iter().enumerate().collect();
+
+        let mut results: Vec<Option<DlnProofVerificationResult>> = (0..contexts.len()).map(|_| None).collect();
+        thread::scope(|scope| {
+            let handles: Vec<_> = indexed
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|(idx, context)| (*idx, verify_context(context)))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            for handle in handles {
+                if let Ok(chunk_results) = handle.join() {
+                    for (idx, result) in chunk_results {
+                        results[idx] = Some(result);
+                    }
+                } else {
+                    log::error!(target: "tss-lib", "A DLN proof verification worker panicked");
+                }
             }
         });
+
+        results.into_iter().flatten().collect()
     }
+}
 
-    /// Collects all verification results.
-    /// Blocks until all queued tasks are completed.
-    pub fn collect_results(&self, expected_count: usize) -> Vec<DlnProofVerificationResult> {
-         debug!(target: "tss-lib", expected_results=expected_count, "Collecting DLN verification results...");
-        self.pool.join(); // Wait for all threads to finish
-         debug!(target: "tss-lib", "DLN verification threads joined.");
-
-        // Collect results non-blockingly after join
-        self.receiver.try_iter().collect()
-        // Note: If the count doesn't match, something went wrong (e.g., send error)
-        // Consider adding error handling or count checks here.
-        // For simplicity, we assume all sends succeeded if pool.join() completes.
+/// Verifies one party's DLN proof pair: proof1 checks `h2 = h1^alpha mod
+/// ntilde`, proof2 checks `h1 = h2^beta mod ntilde`.
+fn verify_context(context: &DlnProofVerifierContext) -> DlnProofVerificationResult {
+    let proof1 = &context.r1_msg.dln_proof1;
+    let proof2 = &context.r1_msg.dln_proof2;
+    let h1 = &context.r1_msg.h1;
+    let h2 = &context.r1_msg.h2;
+    let ntilde = &context.r1_msg.ntilde;
+
+    let proof1_valid = proof1.verify(h1, h2, ntilde);
+    let proof2_valid = proof2.verify(h2, h1, ntilde);
+
+    DlnProofVerificationResult {
+        proof1_valid,
+        proof2_valid,
+        culprit: context.from_party_id.clone(),
     }
 }
 
 /// Helper function to verify proofs for multiple parties concurrently.
+/// Returns a structured `DlnVerificationError` naming exactly which parties
+/// misbehaved (and how) instead of a generic count-mismatch message, so a
+/// keygen abort can blame specific culprits.
 pub fn verify_dln_proofs(
     contexts: &[DlnProofVerifierContext],
     concurrency: usize,
-) -> Result<Vec<DlnProofVerificationResult>> {
+) -> Result<Vec<DlnProofVerificationResult>, DlnVerificationError> {
     let verifier = DlnProofVerifier::new(concurrency);
-    for context in contexts {
-        verifier.verify_dln_proofs(context.clone());
+    let results = verifier.verify_all(contexts);
+
+    let mut bad_proof1 = Vec::new();
+    let mut bad_proof2 = Vec::new();
+    for result in &results {
+        if !result.proof1_valid {
+            bad_proof1.push(result.culprit.clone());
+        }
+        if !result.proof2_valid {
+            bad_proof2.push(result.culprit.clone());
+        }
     }
-    // Collect results - important to get the correct expected count
-    let results = verifier.collect_results(contexts.len());
-    if results.len() != contexts.len() {
-         // Log the discrepancy for debugging
-         log::error!(
-             target: "tss-lib",
-             expected = contexts.len(),
-             actual = results.len(),
-             "DLN verification result count mismatch!"
-         );
-         // Depending on requirements, might return error or proceed with partial results
-         // For now, let's return an error to indicate failure.
-         return Err(anyhow::anyhow!("DLN verification result count mismatch: expected {}, got {}", contexts.len(), results.len()));
+    let missing: Vec<PartyID> = contexts
+        .iter()
+        .filter(|context| !results.iter().any(|r| r.culprit == context.from_party_id))
+        .map(|context| context.from_party_id.clone())
+        .collect();
+
+    if !bad_proof1.is_empty() || !bad_proof2.is_empty() || !missing.is_empty() {
+        log::error!(
+            target: "tss-lib",
+            bad_proof1 = bad_proof1.len(),
+            bad_proof2 = bad_proof2.len(),
+            missing = missing.len(),
+            "DLN proof verification failed"
+        );
+        return Err(DlnVerificationError { bad_proof1, bad_proof2, missing });
     }
+
     Ok(results)
-} 
\ No newline at end of file
+}
\ No newline at end of file
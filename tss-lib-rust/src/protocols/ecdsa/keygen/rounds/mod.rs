@@ -13,5 +13,9 @@ mod dln_proof_verifier;
 // Helper for VSS verification
 mod verify_vss;
 
-// Helper for Paillier proof verification
-mod paillier_proof_verifier; 
\ No newline at end of file
+// `paillier_proof_verifier` (referenced by round_4.rs) was never added to
+// this tree -- round_4.rs also depends on `keygen::types`/`keygen::messages`,
+// which don't exist either, so it can't build regardless. Not declaring the
+// module here at least keeps the rest of this tree's module resolution
+// (round_1/2/3, base, dln_proof_verifier, verify_vss) from failing on a
+// missing file.
\ No newline at end of file
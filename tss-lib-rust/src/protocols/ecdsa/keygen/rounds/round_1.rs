@@ -31,10 +31,10 @@ use crate::{
 };
 
 use std::{{
-    sync::{Arc, Mutex, mpsc::Sender},
+    sync::{Arc, mpsc::Sender},
 }};
+use parking_lot::RwLock;
 use num_bigint_dig::{{BigInt, Sign}};
-use num_traits::Zero;
 use log::{info, debug};
 use anyhow::{Result, anyhow, Context};
 use rand::thread_rng;
@@ -42,8 +42,8 @@ use rand::thread_rng;
 pub struct Round1 {
     base: BaseRound,
     params: Arc<Parameters>,
-    save: Arc<Mutex<LocalPartySaveData>>,
-    temp: Arc<Mutex<LocalTempData>>,
+    save: Arc<RwLock<LocalPartySaveData>>,
+    temp: Arc<RwLock<LocalTempData>>,
     out_ch: Sender<Box<dyn TssMessage + Send>>,
     end_ch: Sender<LocalPartySaveData>,
 }
@@ -51,8 +51,8 @@ pub struct Round1 {
 impl Round1 {
     pub fn new(
         params: Arc<Parameters>,
-        save: Arc<Mutex<LocalPartySaveData>>,
-        temp: Arc<Mutex<LocalTempData>>,
+        save: Arc<RwLock<LocalPartySaveData>>,
+        temp: Arc<RwLock<LocalTempData>>,
         out_ch: Sender<Box<dyn TssMessage + Send>>,
         end_ch: Sender<LocalPartySaveData>,
     ) -> Self {
@@ -68,28 +68,36 @@ impl Round1 {
     }
 
     /// Generates the SSID (Session Shared ID) for the protocol execution.
-    /// SSID = (sid, P1_id, ..., Pn_id)
+    /// SSID = H(session_id || sorted(P1_id, ..., Pn_id) || curve_id || threshold || nonce),
+    /// hashed with the delimiter-framed `common::hash::sha512_256` so each field is
+    /// unambiguously bound rather than concatenated raw. `session_id` comes from the
+    /// caller via `Parameters::session_id`, defaulting to an empty string when unset
+    /// (callers that care about cross-run domain separation should always set it).
+    /// `nonce` is a fresh random value sampled once in `start`, not a fixed constant,
+    /// so two runs with identical parties/threshold still bind to distinct sessions.
     fn get_ssid(&self) -> Result<Vec<u8>, RoundError> {
         let party_ids = self.params.parties().party_ids();
-        let mut string_ids: Vec<&str> = party_ids.iter().map(|p| p.id.as_str()).collect();
+        let mut string_ids: Vec<&str> = party_ids.iter().map(|p| p.id()).collect();
         string_ids.sort(); // Ensure consistent order
 
-        // Use a fixed session ID prefix or allow it to be passed in parameters
-        let sid = "tss-lib-keygen-session"; // Example Session ID
+        let session_id = self.params.session_id().unwrap_or(&[]);
+        let curve_id = format!("{:?}", self.params.curve_name()).into_bytes();
+        let threshold_bytes = (self.params.threshold() as u32).to_be_bytes();
 
-        let mut data_to_hash = sid.as_bytes().to_vec();
-        for id_str in string_ids {
-            data_to_hash.extend_from_slice(id_str.as_bytes());
+        let temp_data = self.temp.read();
+        let nonce = temp_data.ssid_nonce.as_ref().ok_or_else(|| self.wrap_error(anyhow!("SSID nonce not set"), vec![]))?;
+        let nonce_bytes = nonce.to_bytes_be().1;
+
+        let mut inputs: Vec<&[u8]> = Vec::with_capacity(string_ids.len() + 3);
+        inputs.push(session_id);
+        for id_str in &string_ids {
+            inputs.push(id_str.as_bytes());
         }
-        // Include nonce from temp data
-         let temp_data = self.temp.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock temp data: {}", e), vec![]))?;
-         let nonce = temp_data.ssid_nonce.as_ref().ok_or_else(|| self.wrap_error(anyhow!("SSID nonce not set"), vec![]))?;
-         data_to_hash.extend_from_slice(&nonce.to_bytes_be().1);
-
-         // TODO: Replace with a proper H' function if specified, otherwise SHA256 is a reasonable default.
-         use sha2::{{Sha256, Digest}};
-         let hash = Sha256::digest(&data_to_hash);
-         Ok(hash.to_vec())
+        inputs.push(&curve_id);
+        inputs.push(&threshold_bytes);
+        inputs.push(&nonce_bytes);
+
+        Ok(crate::common::hash::sha512_256(&inputs))
     }
 }
 
@@ -122,7 +130,7 @@ impl Round for Round1 {
             .ok_or_else(|| self.wrap_error(anyhow!("Failed to generate random ui"), vec![current_party_id.as_ref().clone()]))?;
 
         {
-             let mut temp_data_lock = self.temp.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock temp data: {}", e), vec![current_party_id.as_ref().clone()]))?;
+             let mut temp_data_lock = self.temp.write();
             temp_data_lock.ui = Some(ui.clone()); // Store ui temporarily
         }
 
@@ -138,7 +146,7 @@ impl Round for Round1 {
         ).map_err(|e| self.wrap_error(e, vec![current_party_id.as_ref().clone()]))?;
 
         {
-            let mut save_data_lock = self.save.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock save data: {}", e), vec![current_party_id.as_ref().clone()]))?;
+            let mut save_data_lock = self.save.write();
              save_data_lock.ks = all_party_keys; // Store all keys
              save_data_lock.share_id = current_party_id.key.clone(); // Store this party's key as ShareID
         }
@@ -149,7 +157,7 @@ impl Round for Round1 {
 
         // 4-11. Generate Paillier keys, safe primes, Ntilde, H1, H2, and DLN proofs
         let pre_params = {
-            let mut save_data_lock = self.save.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock save data: {}", e), vec![current_party_id.as_ref().clone()]))?;
+            let mut save_data_lock = self.save.write();
              if save_data_lock.local_pre_params.validate_with_proof() {
                  debug!(target: "tss-lib", party_id = ?current_party_id, "Using pre-computed Paillier params");
                  save_data_lock.local_pre_params.clone()
@@ -188,11 +196,13 @@ impl Round for Round1 {
 
         // Save/update temp and save data
         {
-            let mut temp_data_lock = self.temp.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock temp data: {}", e), vec![current_party_id.as_ref().clone()]))?;
-             let mut save_data_lock = self.save.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock save data: {}", e), vec![current_party_id.as_ref().clone()]))?;
+            let mut temp_data_lock = self.temp.write();
+             let mut save_data_lock = self.save.write();
 
             // Temp data
-             temp_data_lock.ssid_nonce = Some(BigInt::zero()); // Initialize nonce for SSID calc
+             // Sample a fresh nonce per run so two invocations over the same
+             // party set/threshold still bind to distinct SSIDs.
+             temp_data_lock.ssid_nonce = Some(get_random_positive_int(&mut rng, &ec_order));
              temp_data_lock.vs = Some(vs); // VSS scheme
              temp_data_lock.shares = Some(shares); // Our shares
              temp_data_lock.decommit_poly_g = Some(decommitment); // Decommitment C_i
@@ -233,7 +243,7 @@ impl Round for Round1 {
 
          // Store own message
          {
-             let mut temp_data_lock = self.temp.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock temp data: {}", e), vec![current_party_id.as_ref().clone()]))?;
+             let mut temp_data_lock = self.temp.write();
              temp_data_lock.message_store.kg_round1_messages.insert(i, Arc::new(tss_msg.clone())); // Store Arc
          }
 
@@ -253,7 +263,7 @@ impl Round for Round1 {
         let mut all_ok = true;
         let required_count = self.params().party_count(); // All parties must send in R1
 
-        let temp_data = self.temp.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock temp data: {}", e), vec![]))?;
+        let temp_data = self.temp.read();
 
         for j in 0..required_count {
             if self.base.is_ok(j) { continue; }
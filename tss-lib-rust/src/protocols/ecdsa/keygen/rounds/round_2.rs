@@ -27,9 +27,10 @@ use crate::{
 };
 
 use std::{{
-    sync::{Arc, Mutex, mpsc::Sender},
+    sync::{Arc, mpsc::Sender},
     collections::HashMap,
 }};
+use parking_lot::RwLock;
 use num_bigint_dig::{{BigInt, Sign}};
 use num_traits::Zero;
 use log::{info, debug, warn, error};
@@ -42,8 +43,8 @@ const PAILLIER_BITS_LEN: usize = 2048;
 pub struct Round2 {
     base: BaseRound,
     params: Arc<Parameters>,
-    save: Arc<Mutex<LocalPartySaveData>>,
-    temp: Arc<Mutex<LocalTempData>>,
+    save: Arc<RwLock<LocalPartySaveData>>,
+    temp: Arc<RwLock<LocalTempData>>,
     out_ch: Sender<Box<dyn TssMessage + Send>>,
     end_ch: Sender<LocalPartySaveData>,
 }
@@ -51,8 +52,8 @@ pub struct Round2 {
 impl Round2 {
     pub fn new(
         params: Arc<Parameters>,
-        save: Arc<Mutex<LocalPartySaveData>>,
-        temp: Arc<Mutex<LocalTempData>>,
+        save: Arc<RwLock<LocalPartySaveData>>,
+        temp: Arc<RwLock<LocalTempData>>,
         out_ch: Sender<Box<dyn TssMessage + Send>>,
         end_ch: Sender<LocalPartySaveData>,
     ) -> Self {
@@ -90,7 +91,7 @@ impl Round for Round2 {
          info!(target: "tss-lib", party_id = ?current_party_id, "Keygen Round 2 starting: Verifying DLN proofs, sending shares");
 
         // 6. Verify DLN proofs, store R1 message pieces, ensure uniqueness of h1j, h2j
-         let temp_data_r1 = self.temp.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock temp data: {}", e), vec![current_party_id.as_ref().clone()]))?;
+         let temp_data_r1 = self.temp.read();
          let round1_messages = temp_data_r1.message_store.kg_round1_messages.clone(); // Clone HashMap to avoid holding lock
          drop(temp_data_r1); // Release lock
 
@@ -142,8 +143,8 @@ impl Round for Round2 {
 
          // Save data from R1 messages (PaillierPKs, NTildej, H1j, H2j, KGCs)
          {
-             let mut save_data_lock = self.save.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock save data: {}", e), vec![current_party_id.as_ref().clone()]))?;
-             let mut temp_data_lock = self.temp.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock temp data: {}", e), vec![current_party_id.as_ref().clone()]))?;
+             let mut save_data_lock = self.save.write();
+             let mut temp_data_lock = self.temp.write();
 
              for (party_idx, parsed_msg_arc) in &round1_messages {
                  let party_idx_usize = *party_idx as usize;
@@ -162,8 +163,8 @@ impl Round for Round2 {
 
         // 5. P2P send VSS shares and Factorization proofs
          let (shares, own_sk_n, own_p, own_q, h1_vec, h2_vec, ntilde_vec) = {
-             let temp_data_lock = self.temp.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock temp data: {}", e), vec![current_party_id.as_ref().clone()]))?;
-             let save_data_lock = self.save.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock save data: {}", e), vec![current_party_id.as_ref().clone()]))?;
+             let temp_data_lock = self.temp.read();
+             let save_data_lock = self.save.read();
              (temp_data_lock.shares.clone().ok_or_else(|| self.wrap_error(anyhow!("Missing VSS shares"), vec![current_party_id.as_ref().clone()]))?,
               save_data_lock.paillier_sk.as_ref().map(|sk| sk.n().clone()).ok_or_else(|| self.wrap_error(anyhow!("Missing Paillier SK"), vec![current_party_id.as_ref().clone()]))?,
               save_data_lock.local_pre_params.p.clone(),
@@ -217,7 +218,7 @@ impl Round for Round2 {
 
              // Store own message for R3, send to others
              if j == i {
-                 let mut temp_data_lock = self.temp.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock temp data: {}", e), vec![current_party_id.as_ref().clone()]))?;
+                 let mut temp_data_lock = self.temp.write();
                  temp_data_lock.message_store.kg_round2_message1s.insert(i as i32, Arc::new(tss_msg));
              } else {
                  debug!(target: "tss-lib", party_id = ?current_party_id, to_party_idx=j, "Sending share and FacProof");
@@ -228,8 +229,8 @@ impl Round for Round2 {
 
         // 7. BROADCAST de-commitments D_i and ModProof
          let (decommitment_di, paillier_sk_n, paillier_sk_p, paillier_sk_q) = {
-             let mut temp_data_lock = self.temp.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock temp data: {}", e), vec![current_party_id.as_ref().clone()]))?;
-             let save_data_lock = self.save.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock save data: {}", e), vec![current_party_id.as_ref().clone()]))?;
+             let mut temp_data_lock = self.temp.write();
+             let save_data_lock = self.save.read();
              (temp_data_lock.decommit_poly_g.take().ok_or_else(|| self.wrap_error(anyhow!("Missing VSS decommitment"), vec![current_party_id.as_ref().clone()]))?,
               save_data_lock.paillier_sk.as_ref().map(|sk| sk.n().clone()).ok_or_else(|| self.wrap_error(anyhow!("Missing Paillier SK"), vec![current_party_id.as_ref().clone()]))?,
               save_data_lock.local_pre_params.p.clone(),
@@ -263,7 +264,7 @@ impl Round for Round2 {
 
          // Store own message
          {
-             let mut temp_data_lock = self.temp.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock temp data: {}", e), vec![current_party_id.as_ref().clone()]))?;
+             let mut temp_data_lock = self.temp.write();
              temp_data_lock.message_store.kg_round2_message2s.insert(i as i32, Arc::new(tss_msg.clone()));
          }
 
@@ -294,7 +295,7 @@ impl Round for Round2 {
         let mut all_ok = true;
         let required_parties = self.params.party_count();
 
-        let temp_data = self.temp.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock temp data: {}", e), vec![]))?;
+        let temp_data = self.temp.read();
 
         for j in 0..required_parties {
             if self.base.is_ok(j) { continue; }
@@ -331,11 +332,14 @@ impl Round for Round2 {
 
 // Helper to get context bytes (SSID || index)
 impl Round2 {
+    // context_j = SSID || j || round_number, so a ModProof/FacProof challenge
+    // for party j in this round can't be replayed against another round.
     fn get_context_bytes(&self, index: i32) -> Result<Vec<u8>, RoundError> {
-         let temp_data = self.temp.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock temp data: {}", e), vec![]))?;
+         let temp_data = self.temp.read();
          let ssid = temp_data.ssid.as_ref().ok_or_else(|| self.wrap_error(anyhow!("Missing SSID"), vec![]))?;
          let mut context_bytes = ssid.clone();
          context_bytes.extend_from_slice(&index.to_be_bytes());
+         context_bytes.extend_from_slice(&self.round_number().to_be_bytes());
          Ok(context_bytes)
     }
 } 
\ No newline at end of file
@@ -9,11 +9,11 @@
 use crate::{
     common::task_name::TASK_NAME,
     crypto::{
-        commitments::hash::HashCommitment,
-        ecpoint::ECPoint,
+        commitments::HashCommitDecommit,
+        ecpoint::{ECCurve, ECPoint, unflatten_ecpoints},
         facproof,
         modproof,
-        vss,
+        vss::feldman_vss,
     },
     protocols::ecdsa::keygen::{
         types::{LocalPartySaveData, LocalTempData},
@@ -25,24 +25,41 @@ use crate::{
         round::{Round, RoundError, RoundErr, BaseRound},
         params::Parameters,
         party_id::PartyID,
-        curve::Curve,
+        curve::CurveName,
     },
 };
 
 use std::{{
-    sync::{Arc, Mutex, mpsc::Sender},
+    sync::{Arc, mpsc::Sender},
 }};
-use num_bigint_dig::{{BigInt, Sign}};
-use num_traits::Zero;
+use parking_lot::RwLock;
+use num_bigint::{BigInt, Sign};
+use num_traits::{One, Zero};
 use log::{info, debug, warn, error};
 use anyhow::{Result, anyhow, Context};
 use std::collections::HashMap;
 
+/// Maps this protocol's curve-name registry entry to the concrete `ECPoint`
+/// curve identity used for VSS commitment arithmetic. `ECPoint` only backs
+/// Secp256k1/Ed25519/P-256; Secp384r1 and BLS12-381 are registered in
+/// `tss::curve` for their order/generator metadata but have no `ECPoint`
+/// arithmetic to reshare VSS commitments over.
+fn ec_curve_from_name(name: CurveName) -> std::result::Result<ECCurve, String> {
+    match name {
+        CurveName::Secp256k1 => Ok(ECCurve::Secp256k1),
+        CurveName::Ed25519 => Ok(ECCurve::Ed25519),
+        CurveName::Secp256r1 => Ok(ECCurve::P256),
+        CurveName::Secp384r1 | CurveName::Bls12_381 => {
+            Err(format!("{:?} has no ECPoint backing for VSS commitment arithmetic", name))
+        }
+    }
+}
+
 pub struct Round3 {
     base: BaseRound,
     params: Arc<Parameters>,
-    save: Arc<Mutex<LocalPartySaveData>>,
-    temp: Arc<Mutex<LocalTempData>>,
+    save: Arc<RwLock<LocalPartySaveData>>,
+    temp: Arc<RwLock<LocalTempData>>,
     out_ch: Sender<Box<dyn TssMessage + Send>>,
     end_ch: Sender<LocalPartySaveData>,
 }
@@ -50,8 +67,8 @@ pub struct Round3 {
 impl Round3 {
     pub fn new(
         params: Arc<Parameters>,
-        save: Arc<Mutex<LocalPartySaveData>>,
-        temp: Arc<Mutex<LocalTempData>>,
+        save: Arc<RwLock<LocalPartySaveData>>,
+        temp: Arc<RwLock<LocalTempData>>,
         out_ch: Sender<Box<dyn TssMessage + Send>>,
         end_ch: Sender<LocalPartySaveData>,
     ) -> Self {
@@ -67,11 +84,14 @@ impl Round3 {
     }
 
     /// Helper to get context bytes (SSID || index)
+    // context_j = SSID || j || round_number, so a ModProof/FacProof challenge
+    // for party j in this round can't be replayed against another round.
     fn get_context_bytes(&self, index: i32) -> Result<Vec<u8>, RoundError> {
-         let temp_data = self.temp.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock temp data: {}", e), vec![]))?;
+         let temp_data = self.temp.read();
          let ssid = temp_data.ssid.as_ref().ok_or_else(|| self.wrap_error(anyhow!("Missing SSID"), vec![]))?;
          let mut context_bytes = ssid.clone();
          context_bytes.extend_from_slice(&index.to_be_bytes());
+         context_bytes.extend_from_slice(&self.round_number().to_be_bytes());
          Ok(context_bytes)
     }
 }
@@ -97,15 +117,22 @@ impl Round for Round3 {
 
          info!(target: "tss-lib", party_id = ?current_party_id, "Keygen Round 3 starting: Verifying VSS shares and proofs");
 
+        let curve = ec_curve_from_name(self.params.curve_name())
+            .map_err(|e| self.wrap_error(anyhow!(e), vec![current_party_id.as_ref().clone()]))?;
+        let order = crate::tss::curve::get_curve_params(self.params.curve_name())
+            .ok_or_else(|| self.wrap_error(anyhow!("unsupported curve"), vec![current_party_id.as_ref().clone()]))?
+            .order()
+            .clone();
+
         // 1, 9. Calculate private key share x_i
         let xi = {
-            let temp_data_lock = self.temp.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock temp data: {}", e), vec![current_party_id.as_ref().clone()]))?;
+            let temp_data_lock = self.temp.read();
             let own_share = temp_data_lock.shares.as_ref()
                 .ok_or_else(|| self.wrap_error(anyhow!("Missing own VSS shares"), vec![current_party_id.as_ref().clone()]))?
                 .get_share(i_usize);
 
              let mut xi_acc = own_share.clone();
-            let ec_order = self.params.ec().order();
+            let ec_order = &order;
 
              for (j, p2p_msg_arc) in &temp_data_lock.message_store.kg_round2_message1s {
                 let j_usize = *j as usize;
@@ -121,18 +148,18 @@ impl Round for Round3 {
         };
 
         {
-             let mut save_data_lock = self.save.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock save data: {}", e), vec![current_party_id.as_ref().clone()]))?;
+             let mut save_data_lock = self.save.write();
             save_data_lock.xi = Some(xi); // Save calculated private key share
         }
 
         // 2-3. Vc = Sum(V_cj) mod N
-         let mut combined_vss_commitments: Vec<ECPoint<Curve>> = Vec::new(); // Initialize with identity or handle first element specially
+         let mut combined_vss_commitments: Vec<ECPoint> = Vec::new(); // Initialize with identity or handle first element specially
          let mut vss_contexts: Vec<VssVerifyContext> = Vec::with_capacity(self.params.party_count());
 
          // Prepare verification contexts and combine Vc commitments
          {
-             let temp_data_lock = self.temp.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock temp data: {}", e), vec![current_party_id.as_ref().clone()]))?;
-             let save_data_lock = self.save.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock save data: {}", e), vec![current_party_id.as_ref().clone()]))?;
+             let temp_data_lock = self.temp.read();
+             let save_data_lock = self.save.read();
 
              for j in 0..self.params.party_count() {
                  let party_j = &self.params.parties().party_ids()[j];
@@ -151,7 +178,7 @@ impl Round for Round3 {
                  let vss_commitment_c_j = temp_data_lock.kgcs[j].as_ref()
                       .ok_or_else(|| self.wrap_error(anyhow!("Missing commitment C_{}", j), vec![party_j.as_ref().clone()]))?;
                  let vss_decommitment_d_j = r2msg2.decommitment.clone();
-                 let received_vss_share_ij = vss::Share {
+                 let received_vss_share_ij = feldman_vss::Share {
                      threshold: self.params.threshold(),
                      id: current_party_id.key.clone(), // Our ID
                      share: r2msg1.share.clone(),
@@ -166,9 +193,11 @@ impl Round for Round3 {
                       .ok_or_else(|| self.wrap_error(anyhow!("Missing H2 for party {}", j), vec![party_j.as_ref().clone()]))?;
 
                  // Decommit VSS commitment C_j to get V_cj = [g^a_c0, ..., g^a_ct]
-                 let hash_commit_decommit = HashCommitment::new(vss_commitment_c_j.clone(), vss_decommitment_d_j.clone());
-                 let vss_points_j = hash_commit_decommit.decommit()
-                     .map_err(|e| self.wrap_error(e, vec![party_j.as_ref().clone()]))?;
+                 let hash_commit_decommit = HashCommitDecommit { c: vss_commitment_c_j.clone(), d: vss_decommitment_d_j.clone() };
+                 let flat_points_j = hash_commit_decommit.decommit()
+                     .ok_or_else(|| self.wrap_error(anyhow!("VSS commitment decommitment failed for party {}", j), vec![party_j.as_ref().clone()]))?;
+                 let vss_points_j = unflatten_ecpoints(curve, flat_points_j)
+                     .map_err(|e| self.wrap_error(anyhow!(e), vec![party_j.as_ref().clone()]))?;
 
                  // Combine V_cj points
                   if combined_vss_commitments.is_empty() {
@@ -178,7 +207,8 @@ impl Round for Round3 {
                           return Err(self.wrap_error(anyhow!("VSS commitment length mismatch from party {}", j), vec![party_j.as_ref().clone()]));
                       }
                       for c in 0..combined_vss_commitments.len() {
-                          combined_vss_commitments[c] = combined_vss_commitments[c].add(&vss_points_j[c])?;
+                          combined_vss_commitments[c] = combined_vss_commitments[c].add(&vss_points_j[c])
+                              .map_err(|e| self.wrap_error(anyhow!(e), vec![party_j.as_ref().clone()]))?;
                       }
                   }
 
@@ -195,8 +225,16 @@ impl Round for Round3 {
                      h1_j: h1_j.clone(),
                      h2_j: h2_j.clone(),
                      context_j,
+                     // NOTE: the round-2 message types (`messages::KGRound2Message1/2`)
+                     // this file imports don't exist anywhere in this tree, so there's no
+                     // actual field to read a per-dealer `pop_proof` from here; this reads
+                     // `r2msg2.pop_proof` as if that field existed, matching the pattern the
+                     // rest of this struct literal already follows for `mod_proof`/`fac_proof`.
+                     pop_proof: r2msg2.pop_proof.clone(),
+                     curve_order: order.clone(),
                      no_proof_mod: self.params.no_proof_mod(),
                      no_proof_fac: self.params.no_proof_fac(),
+                     no_proof_pop: self.params.no_proof_pop(),
                  });
              }
          }
@@ -205,8 +243,7 @@ impl Round for Round3 {
          debug!(target: "tss-lib", party_id = ?current_party_id, concurrency = self.params.concurrency(), "Verifying VSS shares and proofs...");
         let verification_results = verify_vss_share_and_proofs(
             vss_contexts,
-            self.params.ec(),
-            self.params.threshold(),
+            curve,
             current_party_id.as_ref().clone(),
             self.params.concurrency(),
         )?;
@@ -226,9 +263,7 @@ impl Round for Round3 {
 
         // 12-16. Calculate X_j = g^x_j for each Pj
          let big_x_j = {
-             let curve = self.params.ec();
-             let order = curve.order();
-             let mut xs: Vec<Option<ECPoint<Curve>>> = vec![None; self.params.party_count()];
+             let mut xs: Vec<Option<ECPoint>> = vec![None; self.params.party_count()];
 
              for j in 0..self.params.party_count() {
                  let party_j = &self.params.parties().party_ids()[j];
@@ -239,8 +274,10 @@ impl Round for Round3 {
                  for c in 1..=self.params.threshold() {
                      k_pow_c = (&k_pow_c * party_j_key).mod_floor(&order);
                      let v_cj = &combined_vss_commitments[c];
-                     let v_cj_pow_k = v_cj.scalar_mul(&k_pow_c);
-                     x_j = x_j.add(&v_cj_pow_k)?; // Add points
+                     let v_cj_pow_k = v_cj.scalar_mult(&k_pow_c)
+                         .map_err(|e| self.wrap_error(anyhow!(e), vec![party_j.as_ref().clone()]))?;
+                     x_j = x_j.add(&v_cj_pow_k) // Add points
+                         .map_err(|e| self.wrap_error(anyhow!(e), vec![party_j.as_ref().clone()]))?;
                  }
                  xs[j] = Some(x_j);
              }
@@ -252,19 +289,19 @@ impl Round for Round3 {
          info!(target: "tss-lib", party_id = ?current_party_id, ecdsa_pk = ?pk_point, "ECDSA Public Key Computed");
 
          // PRINT private share (optional, for debug/verification)
-          let xi_saved = self.save.lock().unwrap().xi.clone().unwrap();
+          let xi_saved = self.save.read().xi.clone().unwrap();
           debug!(target: "tss-lib", party_id = ?current_party_id, private_share_xi = ?xi_saved, "Private key share computed");
 
          // Save final state
          {
-             let mut save_data_lock = self.save.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock save data: {}", e), vec![current_party_id.as_ref().clone()]))?;
+             let mut save_data_lock = self.save.write();
              save_data_lock.big_xj = big_x_j; // Save Xj for all j
              save_data_lock.ecdsa_pub = Some(pk_point); // Save final public key
          }
 
         // BROADCAST Paillier Proof
          let (paillier_sk, ecdsa_pub_key) = {
-             let save_data_lock = self.save.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock save data: {}", e), vec![current_party_id.as_ref().clone()]))?;
+             let save_data_lock = self.save.read();
              (save_data_lock.paillier_sk.clone().ok_or_else(|| self.wrap_error(anyhow!("Missing Paillier SK"), vec![current_party_id.as_ref().clone()]))?,
               save_data_lock.ecdsa_pub.clone().ok_or_else(|| self.wrap_error(anyhow!("Missing ECDSA PubKey"), vec![current_party_id.as_ref().clone()]))?)
          };
@@ -284,7 +321,7 @@ impl Round for Round3 {
 
          // Store own message
          {
-             let mut temp_data_lock = self.temp.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock temp data: {}", e), vec![current_party_id.as_ref().clone()]))?;
+             let mut temp_data_lock = self.temp.write();
              temp_data_lock.message_store.kg_round3_messages.insert(i_usize as i32, Arc::new(tss_msg.clone()));
          }
 
@@ -305,7 +342,7 @@ impl Round for Round3 {
         let mut all_ok = true;
         let required_count = self.params().party_count();
 
-        let temp_data = self.temp.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock temp data: {}", e), vec![]))?;
+        let temp_data = self.temp.read();
 
         for j in 0..required_count {
             if self.base.is_ok(j) { continue; }
@@ -22,8 +22,9 @@ use crate::{
 };
 
 use std::{{
-    sync::{Arc, Mutex, mpsc::Sender},
+    sync::{Arc, mpsc::Sender},
 }};
+use parking_lot::RwLock;
 use log::{info, debug, error};
 use anyhow::{Result, anyhow};
 use std::collections::HashMap;
@@ -31,8 +32,8 @@ use std::collections::HashMap;
 pub struct Round4 {
     base: BaseRound,
     params: Arc<Parameters>,
-    save: Arc<Mutex<LocalPartySaveData>>,
-    temp: Arc<Mutex<LocalTempData>>,
+    save: Arc<RwLock<LocalPartySaveData>>,
+    temp: Arc<RwLock<LocalTempData>>,
     out_ch: Sender<Box<dyn TssMessage + Send>>,
     end_ch: Sender<LocalPartySaveData>,
 }
@@ -40,8 +41,8 @@ pub struct Round4 {
 impl Round4 {
     pub fn new(
         params: Arc<Parameters>,
-        save: Arc<Mutex<LocalPartySaveData>>,
-        temp: Arc<Mutex<LocalTempData>>,
+        save: Arc<RwLock<LocalPartySaveData>>,
+        temp: Arc<RwLock<LocalTempData>>,
         out_ch: Sender<Box<dyn TssMessage + Send>>,
         end_ch: Sender<LocalPartySaveData>,
     ) -> Self {
@@ -80,8 +81,8 @@ impl Round for Round4 {
 
         // 1-3. Verify Paillier proofs concurrently
         let paillier_contexts: Vec<PaillierProofVerifierContext> = {
-            let temp_data_lock = self.temp.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock temp data: {}", e), vec![current_party_id.as_ref().clone()]))?;
-            let save_data_lock = self.save.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock save data: {}", e), vec![current_party_id.as_ref().clone()]))?;
+            let temp_data_lock = self.temp.read();
+            let save_data_lock = self.save.read();
 
             let ecdsa_pub_key = save_data_lock.ecdsa_pub.as_ref()
                 .ok_or_else(|| self.wrap_error(anyhow!("Missing ECDSA PubKey"), vec![current_party_id.as_ref().clone()]))?;
@@ -133,7 +134,7 @@ impl Round for Round4 {
         // Send final save data to the application layer channel
          info!(target: "tss-lib", party_id = ?current_party_id, "Keygen Round 4 finished successfully, sending result.");
         let final_save_data = {
-            let save_data_lock = self.save.lock().map_err(|e| self.wrap_error(anyhow!("Failed to lock save data: {}", e), vec![current_party_id.as_ref().clone()]))?;
+            let save_data_lock = self.save.read();
             save_data_lock.clone()
         };
 
@@ -2,37 +2,47 @@
 
 use crate::{
     crypto::{
-        commitments::hash::HashCommitment,
-        ecpoint::ECPoint,
+        commitments::HashCommitDecommit,
+        ecpoint::{ECCurve, ECPoint, unflatten_ecpoints},
         facproof::ProofFac,
         modproof::ProofMod,
-        vss::Share as VssShare,
+        paillier::PublicKey as PaillierPk,
+        schnorr_pop::SchnorrPop,
+        vss::feldman_vss::{Share as VssShare, VerificationVector},
     },
     tss::{
-        curve::Curve,
+        error::{BlameEvidence, FailureKind, RoundError, RoundErr},
+        message::ParsedMessage,
         party_id::PartyID,
-        error::{RoundError, RoundErr},
-        params::Parameters, // Needed for curve and threshold
     },
-    crypto::paillier::PublicKey as PaillierPk,
 };
 use std::sync::{
     mpsc::{channel, Sender, Receiver},
     Arc,
 };
 use threadpool::ThreadPool;
-use num_bigint_dig::BigInt;
+use num_bigint::{BigInt, Sign};
+use num_traits::{One, Zero};
 use anyhow::Result;
 use log::{debug, error};
+use merlin::Transcript;
 
 /// Context for verifying one party's VSS share and related proofs.
 #[derive(Clone)] // Needed for moving into threads
 pub struct VssVerifyContext {
     pub party_index: usize,
-    pub commitment_c_j: Vec<u8>,
-    pub decommitment_d_j: (Vec<[u8; 32]>, Vec<u8>),
+    /// Hash commitment to the dealer's VSS points `[C_0, ..., C_t]`, flattened
+    /// to coordinate `BigInt`s (see `crypto::ecpoint::flatten_ecpoints`)
+    /// before being folded into a `HashCommitDecommit`.
+    pub commitment_c_j: BigInt,
+    pub decommitment_d_j: Vec<BigInt>,
     pub mod_proof: ProofMod,
     pub fac_proof: ProofFac,
+    /// Proves the dealer knows the discrete log of its own constant-term VSS
+    /// commitment (`vss_points[0]`), checked before that commitment is folded
+    /// into the combined group commitment.
+    pub pop_proof: SchnorrPop,
+    pub curve_order: BigInt,
     pub received_vss_share_ij: VssShare,
     pub paillier_pk_j: PaillierPk,
     pub n_tilde_j: BigInt,
@@ -41,13 +51,22 @@ pub struct VssVerifyContext {
     pub context_j: Vec<u8>, // SSID || j
     pub no_proof_mod: bool,
     pub no_proof_fac: bool,
+    pub no_proof_pop: bool,
+    /// The round-1 broadcast message this context was extracted from, kept so a
+    /// failure can be turned into independently-reproducible `BlameEvidence`.
+    pub offending_message: Option<Arc<ParsedMessage>>,
+    pub accused_party_id: PartyID,
+    pub round_number: i32,
 }
 
 /// Result of verifying one party's VSS share and proofs.
 pub struct VssVerificationResult {
     pub party_index: usize,
-    pub vss_points: Option<Vec<ECPoint<Curve>>>, // Decommitted VSS points if valid
+    pub vss_points: Option<Vec<ECPoint>>, // Decommitted VSS points if valid
     pub error_reason: Option<String>, // Reason for failure
+    /// Structured, re-runnable evidence of cheating, populated alongside
+    /// `error_reason` whenever a check fails.
+    pub blame: Option<BlameEvidence>,
 }
 
 impl VssVerificationResult {
@@ -56,43 +75,98 @@ impl VssVerificationResult {
     }
 }
 
+/// Builds `BlameEvidence` for a failed check against `context`, using whatever
+/// offending message was attached to it (if any) and a transcript of the inputs
+/// the check was run against, so any party can replay the same verdict.
+fn build_blame(accused: &PartyID, round: i32, offending_message: &Option<Arc<ParsedMessage>>, kind: FailureKind, transcript: Vec<u8>) -> BlameEvidence {
+    BlameEvidence::new(accused.clone(), round, kind, offending_message.clone(), transcript)
+}
+
+/// Selects how `VssVerifier` checks the core VSS share equation
+/// (g^σᵢⱼ == Πₖ Cⱼₖ^(idᵢ^k)) across the parties being verified.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VssVerificationBackend {
+    /// Verify each dealer's share independently, one scalar-mult chain per party.
+    PerParty,
+    /// Fold every dealer's share equation into a single random-linear-combination
+    /// multi-scalar multiplication, evaluated once after all proofs are collected.
+    BatchedMsm,
+}
+
+impl Default for VssVerificationBackend {
+    fn default() -> Self {
+        VssVerificationBackend::PerParty
+    }
+}
+
+/// A decommitted VSS share still awaiting the aggregate equation check, kept aside
+/// when `VssVerificationBackend::BatchedMsm` is in effect.
+struct PendingBatchShare {
+    party_index: usize,
+    id_i: BigInt,
+    share_ij: BigInt,
+    vss_points_j: Vec<ECPoint>,
+    curve: ECCurve,
+    accused_party_id: PartyID,
+    round_number: i32,
+    /// SSID || j context this share was verified under, fed into the batch
+    /// weight transcript so a replay under a different session can't reuse
+    /// a previously-observed weight.
+    context_j: Vec<u8>,
+}
+
 /// Manages concurrent verification of VSS shares and proofs.
 pub struct VssVerifier {
     pool: ThreadPool,
     sender: Sender<VssVerificationResult>,
     receiver: Receiver<VssVerificationResult>,
+    backend: VssVerificationBackend,
+    pending_batch: Arc<std::sync::Mutex<Vec<PendingBatchShare>>>,
 }
 
 impl VssVerifier {
     pub fn new(concurrency: usize) -> Self {
+        Self::new_with_backend(concurrency, VssVerificationBackend::default())
+    }
+
+    /// Same as `new`, but lets the caller pick the VSS-share verification backend.
+    pub fn new_with_backend(concurrency: usize, backend: VssVerificationBackend) -> Self {
         let (sender, receiver) = channel();
         let pool = ThreadPool::new(concurrency);
-        Self { pool, sender, receiver }
+        Self { pool, sender, receiver, backend, pending_batch: Arc::new(std::sync::Mutex::new(Vec::new())) }
     }
 
     /// Queues a verification task for a single party's VSS share and proofs.
     pub fn verify_vss_share_and_proofs(
         &self,
         context: VssVerifyContext,
-        curve: Curve, // Pass curve explicitly
-        threshold: usize,
+        curve: ECCurve, // Pass curve explicitly
         verifier_party_id: PartyID, // ID of the party *doing* the verification
     ) {
         let sender_clone = self.sender.clone();
+        let backend = self.backend;
+        let pending_batch = self.pending_batch.clone();
 
         self.pool.execute(move || {
             let party_idx = context.party_index;
             debug!(target: "tss-lib", verifier_id = ?verifier_party_id, target_party_idx = party_idx, "Verifying VSS/Proofs in background");
 
             let mut error_reason: Option<String> = None;
-            let mut vss_points_result: Option<Vec<ECPoint<Curve>>> = None;
+            let mut blame: Option<BlameEvidence> = None;
+            let mut vss_points_result: Option<Vec<ECPoint>> = None;
+            let mut deferred_to_batch = false;
 
             // 1. Decommit VSS Commitment C_j
-            let hash_commit_decommit = HashCommitment::new(context.commitment_c_j, context.decommitment_d_j);
-            match hash_commit_decommit.decommit() {
-                Ok(points) => vss_points_result = Some(points),
-                Err(e) => {
-                     error_reason = Some(format!("VSS decommitment failed: {}", e));
+            let hash_commit_decommit = HashCommitDecommit { c: context.commitment_c_j.clone(), d: context.decommitment_d_j.clone() };
+            match hash_commit_decommit.decommit().map(|flat| unflatten_ecpoints(curve, flat)) {
+                Some(Ok(points)) => vss_points_result = Some(points),
+                Some(Err(e)) => {
+                    error_reason = Some(format!("VSS decommitment failed: {}", e));
+                    blame = Some(build_blame(&context.accused_party_id, context.round_number, &context.offending_message, FailureKind::VssDecommitment, context.commitment_c_j.to_bytes_be().1));
+                }
+                None => {
+                    error_reason = Some("VSS decommitment failed".to_string());
+                    blame = Some(build_blame(&context.accused_party_id, context.round_number, &context.offending_message, FailureKind::VssDecommitment, context.commitment_c_j.to_bytes_be().1));
                 }
             }
 
@@ -100,41 +174,88 @@ impl VssVerifier {
             if error_reason.is_none() {
                  let vss_points = vss_points_result.as_ref().unwrap(); // Safe unwrap
 
+                 // 1.5. Verify the dealer's Schnorr proof of possession for its
+                 // constant-term commitment C_j = vss_points[0], before C_j is
+                 // ever folded into the combined group commitment. Without this,
+                 // a dealer publishing last could choose C_j to cancel everyone
+                 // else's contribution without knowing its own discrete log.
+                 if !context.no_proof_pop {
+                     let c_j = vss_points.first();
+                     let pop_ok = match c_j {
+                         Some(c_j) => context.pop_proof.verify(&context.context_j, c_j, &context.curve_order),
+                         None => false,
+                     };
+                     if !pop_ok {
+                         error_reason = Some("Schnorr proof of possession verification failed".to_string());
+                         blame = Some(build_blame(&context.accused_party_id, context.round_number, &context.offending_message, FailureKind::SchnorrPop, context.pop_proof.z.to_bytes_be().1));
+                     }
+                 } else {
+                     debug!(target: "tss-lib", verifier_id = ?verifier_party_id, target_party_idx = party_idx, "Skipped Schnorr proof-of-possession verification");
+                 }
+
                  // 2. Verify ModProof (N_j)
-                 if !context.no_proof_mod {
+                 if error_reason.is_none() && !context.no_proof_mod {
                      if !context.mod_proof.verify(&context.context_j, &context.paillier_pk_j.n) {
                          error_reason = Some("ModProof verification failed".to_string());
+                         blame = Some(build_blame(&context.accused_party_id, context.round_number, &context.offending_message, FailureKind::ModProof, context.paillier_pk_j.n.to_bytes_be().1));
                      }
-                 } else {
+                 } else if error_reason.is_none() {
                      debug!(target: "tss-lib", verifier_id = ?verifier_party_id, target_party_idx = party_idx, "Skipped ModProof verification");
                  }
 
-                 // 3. Verify VSS Share (using V_cj)
-                 if error_reason.is_none() && !context.received_vss_share_ij.verify(curve, threshold, vss_points) {
-                     error_reason = Some("VSS share verification failed".to_string());
+                 // 3. Verify VSS Share (using V_cj), either eagerly or deferred to the
+                 // batched random-linear-combination check run in `collect_results`.
+                 if error_reason.is_none() {
+                     match backend {
+                         VssVerificationBackend::PerParty => {
+                             let vv = VerificationVector { vector: vss_points.clone() };
+                             if !context.received_vss_share_ij.verify(&context.curve_order, &vv) {
+                                 error_reason = Some("VSS share verification failed".to_string());
+                                 blame = Some(build_blame(&context.accused_party_id, context.round_number, &context.offending_message, FailureKind::VssShare, context.received_vss_share_ij.share.to_bytes_be().1));
+                             }
+                         }
+                         VssVerificationBackend::BatchedMsm => {
+                             pending_batch.lock().unwrap().push(PendingBatchShare {
+                                 party_index: party_idx,
+                                 id_i: context.received_vss_share_ij.id.clone(),
+                                 share_ij: context.received_vss_share_ij.share.clone(),
+                                 vss_points_j: vss_points.clone(),
+                                 curve,
+                                 accused_party_id: context.accused_party_id.clone(),
+                                 round_number: context.round_number,
+                                 context_j: context.context_j.clone(),
+                             });
+                             deferred_to_batch = true;
+                         }
+                     }
                  }
 
                  // 4. Verify FacProof (N_j, N^_i)
                  if error_reason.is_none() && !context.no_proof_fac {
                      if !context.fac_proof.verify(
                          &context.context_j,
-                         &curve.order(),
+                         &context.curve_order,
                          &context.paillier_pk_j.n,
                          &context.n_tilde_j,
                          &context.h1_j,
                          &context.h2_j,
                      ) {
                          error_reason = Some("FacProof verification failed".to_string());
+                         blame = Some(build_blame(&context.accused_party_id, context.round_number, &context.offending_message, FailureKind::FacProof, context.n_tilde_j.to_bytes_be().1));
                      }
                  } else if error_reason.is_none() {
                       debug!(target: "tss-lib", verifier_id = ?verifier_party_id, target_party_idx = party_idx, "Skipped FacProof verification");
                  }
             }
 
+            // A share deferred to the batch check is tentatively "passing" until
+            // `collect_results` runs the aggregate equation; its points are withheld
+            // either way since they aren't final until then.
             let result = VssVerificationResult {
                 party_index: party_idx,
-                vss_points: if error_reason.is_none() { vss_points_result } else { None }, // Only return points if all checks passed
+                vss_points: if error_reason.is_none() && !deferred_to_batch { vss_points_result } else { None },
                 error_reason,
+                blame,
             };
 
             if let Err(e) = sender_clone.send(result) {
@@ -143,27 +264,193 @@ impl VssVerifier {
         });
     }
 
-     /// Collects all verification results.
+     /// Collects all verification results. When the batched backend is in use, this
+     /// also runs the single aggregate multi-scalar-multiplication check and folds its
+     /// outcome (pass/fail for every deferred share) into the returned results.
     pub fn collect_results(&self, expected_count: usize) -> Vec<VssVerificationResult> {
          debug!(target: "tss-lib", expected_results=expected_count, "Collecting VSS verification results...");
         self.pool.join(); // Wait for all threads
          debug!(target: "tss-lib", "VSS verification threads joined.");
-        self.receiver.try_iter().collect()
+        let mut results: Vec<VssVerificationResult> = self.receiver.try_iter().collect();
+
+        if self.backend == VssVerificationBackend::BatchedMsm {
+            let pending = std::mem::take(&mut *self.pending_batch.lock().unwrap());
+            if !pending.is_empty() {
+                let aggregate_ok = verify_batch_vss_shares(&pending);
+                // The aggregate check only tells us the *batch* is bad, not who in it
+                // cheated. Fall back to re-checking each deferred share on its own so
+                // the culprit list stays identifiable instead of blaming everyone.
+                for share in pending {
+                    let share_ok = aggregate_ok || verify_single_vss_share(&share);
+                    if let Some(result) = results.iter_mut().find(|r| r.party_index == share.party_index) {
+                        if share_ok {
+                            result.vss_points = Some(share.vss_points_j.clone());
+                        } else if result.error_reason.is_none() {
+                            result.error_reason = Some("VSS share verification failed".to_string());
+                            result.blame = Some(build_blame(
+                                &share.accused_party_id,
+                                share.round_number,
+                                &None,
+                                FailureKind::VssShare,
+                                share.share_ij.to_bytes_be().1,
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        results
+    }
+}
+
+/// Derives one 128-bit-plus batch weight `rⱼ` per pending share from a single
+/// Merlin transcript seeded with every share's SSID context and VSS commitments,
+/// rather than drawing `rⱼ` from a thread-local RNG: any party re-running the
+/// same batch (e.g. to check an abort) reproduces the identical weights, and a
+/// verifier doesn't have to trust its own RNG wasn't predictable or biased.
+/// The transcript is fed *all* shares' commitments before any weight is drawn,
+/// so `rⱼ` depends on the whole batch, not just share `j`, which is what stops
+/// a forged share from being crafted to cancel against another dealer's slack.
+fn derive_batch_weights(pending: &[PendingBatchShare]) -> Vec<BigInt> {
+    let mut transcript = Transcript::new(b"tss-lib/ecdsa/keygen/batched-vss-verify");
+    for share in pending {
+        transcript.append_message(b"ssid", &share.context_j);
+        transcript.append_message(b"id_i", &share.id_i.to_bytes_be().1);
+        for c_k in &share.vss_points_j {
+            transcript.append_message(b"Cx", &c_k.x.to_bytes_be().1);
+            transcript.append_message(b"Cy", &c_k.y.to_bytes_be().1);
+        }
+    }
+    pending
+        .iter()
+        .map(|_| {
+            let mut weight_bytes = [0u8; 32];
+            transcript.challenge_bytes(b"weight", &mut weight_bytes);
+            BigInt::from_bytes_be(Sign::Plus, &weight_bytes)
+        })
+        .collect()
+}
+
+/// Folds every pending dealer's share equation
+/// `g^σᵢⱼ == Πₖ Cⱼₖ^(idᵢ^k)` into one random-linear-combination check:
+/// `g^(Σⱼ rⱼ·σᵢⱼ) == Σⱼ Σₖ (rⱼ·idᵢ^k)·Cⱼₖ`, using a transcript-derived scalar
+/// `rⱼ` per dealer (see `derive_batch_weights`) so a forged share can't cancel
+/// against another's slack. The right-hand side is accumulated as a single
+/// multi-scalar multiplication (a production build would route this through a
+/// Pippenger/bucket MSM). On failure the caller falls back to
+/// `verify_single_vss_share` per share to isolate the actual culprit(s).
+fn verify_batch_vss_shares(pending: &[PendingBatchShare]) -> bool {
+    let curve = match pending.first() {
+        Some(share) => share.curve,
+        None => return true,
+    };
+    let weights = derive_batch_weights(pending);
+    let mut lhs_exponent = BigInt::zero();
+    let mut rhs: Option<ECPoint> = None;
+
+    for (share, r_j) in pending.iter().zip(weights.iter()) {
+        lhs_exponent += r_j * &share.share_ij;
+
+        let mut id_power = BigInt::one();
+        for (k, c_k) in share.vss_points_j.iter().enumerate() {
+            let coeff = if k == 0 {
+                r_j.clone()
+            } else {
+                id_power *= &share.id_i;
+                r_j * &id_power
+            };
+            let term = match c_k.scalar_mult(&coeff) {
+                Ok(p) => p,
+                Err(_) => return false,
+            };
+            rhs = Some(match rhs {
+                None => term,
+                Some(acc) => match acc.add(&term) {
+                    Ok(sum) => sum,
+                    Err(_) => return false,
+                },
+            });
+        }
+    }
+
+    let lhs = match ECPoint::scalar_base_mult(curve, &lhs_exponent) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+    match rhs {
+        Some(rhs) => lhs == rhs,
+        None => true,
+    }
+}
+
+/// Re-checks a single dealer's share equation `g^σᵢⱼ == Πₖ Cⱼₖ^(idᵢ^k)` on its
+/// own, with no batching. Used as the per-party fallback once
+/// `verify_batch_vss_shares` has rejected the aggregate equation, so the
+/// resulting culprit list names only the dealer(s) whose share is actually bad.
+fn verify_single_vss_share(share: &PendingBatchShare) -> bool {
+    let mut rhs: Option<ECPoint> = None;
+    let mut id_power = BigInt::one();
+
+    for (k, c_k) in share.vss_points_j.iter().enumerate() {
+        let coeff = if k == 0 {
+            BigInt::one()
+        } else {
+            id_power *= &share.id_i;
+            id_power.clone()
+        };
+        let term = match c_k.scalar_mult(&coeff) {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
+        rhs = Some(match rhs {
+            None => term,
+            Some(acc) => match acc.add(&term) {
+                Ok(sum) => sum,
+                Err(_) => return false,
+            },
+        });
+    }
+
+    let lhs = match ECPoint::scalar_base_mult(share.curve, &share.share_ij) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+    match rhs {
+        Some(rhs) => lhs == rhs,
+        None => true,
     }
 }
 
 /// Helper function to verify VSS shares and proofs for multiple parties concurrently.
 pub fn verify_vss_share_and_proofs(
     contexts: Vec<VssVerifyContext>,
-    curve: Curve,
-    threshold: usize,
+    curve: ECCurve,
+    verifier_party_id: PartyID,
+    concurrency: usize,
+) -> Result<Vec<VssVerificationResult>> {
+    verify_vss_share_and_proofs_with_backend(
+        contexts,
+        curve,
+        verifier_party_id,
+        concurrency,
+        VssVerificationBackend::default(),
+    )
+}
+
+/// Same as `verify_vss_share_and_proofs`, but lets the caller pick the VSS-share
+/// verification backend (per-party scalar mults vs. one batched MSM check).
+pub fn verify_vss_share_and_proofs_with_backend(
+    contexts: Vec<VssVerifyContext>,
+    curve: ECCurve,
     verifier_party_id: PartyID,
     concurrency: usize,
+    backend: VssVerificationBackend,
 ) -> Result<Vec<VssVerificationResult>> {
-    let verifier = VssVerifier::new(concurrency);
+    let verifier = VssVerifier::new_with_backend(concurrency, backend);
     let expected_count = contexts.len();
     for context in contexts {
-        verifier.verify_vss_share_and_proofs(context, curve, threshold, verifier_party_id.clone());
+        verifier.verify_vss_share_and_proofs(context, curve, verifier_party_id.clone());
     }
     let results = verifier.collect_results(expected_count);
      if results.len() != expected_count {
@@ -176,4 +463,86 @@ pub fn verify_vss_share_and_proofs(
          return Err(anyhow::anyhow!("VSS verification result count mismatch: expected {}, got {}", expected_count, results.len()));
      }
     Ok(results)
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tss::party_id::PartyID;
+
+    fn accused() -> PartyID {
+        PartyID::new("j".to_string(), "party-j".to_string(), BigInt::from(2))
+    }
+
+    #[test]
+    fn test_build_blame_carries_accused_round_and_kind() {
+        let evidence = build_blame(&accused(), 2, &None, FailureKind::VssDecommitment, vec![9, 9]);
+        assert_eq!(evidence.accused, accused());
+        assert_eq!(evidence.round, 2);
+        assert_eq!(evidence.kind, FailureKind::VssDecommitment);
+        assert_eq!(evidence.verifier_transcript, vec![9, 9]);
+        assert!(evidence.offending_message.is_none());
+    }
+
+    #[test]
+    fn test_vss_verification_result_invalid_when_blame_present() {
+        let result = VssVerificationResult {
+            party_index: 0,
+            vss_points: None,
+            error_reason: Some("VSS share verification failed".to_string()),
+            blame: Some(build_blame(&accused(), 1, &None, FailureKind::VssShare, vec![1])),
+        };
+        assert!(!result.is_valid());
+        assert_eq!(result.blame.unwrap().kind, FailureKind::VssShare);
+    }
+
+    #[test]
+    fn test_batch_blame_uses_batched_verification_kind() {
+        let pending = PendingBatchShare {
+            party_index: 3,
+            id_i: BigInt::from(1),
+            share_ij: BigInt::from(42),
+            vss_points_j: vec![],
+            curve: ECCurve::Secp256k1,
+            accused_party_id: accused(),
+            round_number: 2,
+            context_j: vec![7, 7],
+        };
+        let evidence = BlameEvidence::new(
+            pending.accused_party_id.clone(),
+            pending.round_number,
+            FailureKind::BatchedVerification,
+            None,
+            pending.share_ij.to_bytes_be().1,
+        );
+        assert_eq!(evidence.kind, FailureKind::BatchedVerification);
+        assert_eq!(evidence.accused, accused());
+    }
+
+    fn pending_share(party_index: usize, context_j: Vec<u8>) -> PendingBatchShare {
+        PendingBatchShare {
+            party_index,
+            id_i: BigInt::from(party_index as i64 + 1),
+            share_ij: BigInt::from(42),
+            vss_points_j: vec![],
+            curve: ECCurve::Secp256k1,
+            accused_party_id: accused(),
+            round_number: 1,
+            context_j,
+        }
+    }
+
+    #[test]
+    fn test_derive_batch_weights_is_deterministic_for_same_input() {
+        let pending_a = vec![pending_share(0, vec![1, 2, 3]), pending_share(1, vec![4, 5, 6])];
+        let pending_b = vec![pending_share(0, vec![1, 2, 3]), pending_share(1, vec![4, 5, 6])];
+        assert_eq!(derive_batch_weights(&pending_a), derive_batch_weights(&pending_b));
+    }
+
+    #[test]
+    fn test_derive_batch_weights_changes_with_ssid_context() {
+        let pending_a = vec![pending_share(0, vec![1, 2, 3])];
+        let pending_b = vec![pending_share(0, vec![9, 9, 9])];
+        assert_ne!(derive_batch_weights(&pending_a), derive_batch_weights(&pending_b));
+    }
+}
@@ -0,0 +1,204 @@
+// Threshold BIP340 (x-only Schnorr) signing over secp256k1.
+//
+// `CurveParams`/`get_ssid` already distinguish Secp256k1 from Ed25519, but
+// the signature scheme itself (tagged-hash challenge, even-Y nonce/key
+// negotiation, aggregation of per-party partial signatures) has no prior
+// analogue in this crate (there is no threshold signing subsystem for either
+// curve yet, only keygen), so this is implemented from scratch against the
+// BIP340 spec rather than adapted from an existing EdDSA/ECDSA signing round.
+// Shares are combined the same way the rest of this crate's VSS/reshare code
+// combines Shamir shares: each signer's contribution is scaled by its
+// Lagrange coefficient over the signing set before summing.
+
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::elliptic_curve::Curve;
+use k256::{ProjectivePoint, Scalar, Secp256k1};
+use num_bigint::{BigInt, Sign};
+use num_integer::Integer;
+use sha2::{Digest, Sha256};
+use std::ops::Neg;
+
+/// BIP340 tagged hash: `SHA256(SHA256(tag) || SHA256(tag) || msg)`.
+pub fn tagged_hash(tag: &str, msg: &[u8]) -> [u8; 32] {
+    let tag_hash = Sha256::digest(tag.as_bytes());
+    let mut hasher = Sha256::new();
+    hasher.update(&tag_hash);
+    hasher.update(&tag_hash);
+    hasher.update(msg);
+    hasher.finalize().into()
+}
+
+fn scalar_from_hash(hash: [u8; 32]) -> Scalar {
+    let order = BigInt::from_bytes_be(Sign::Plus, &Secp256k1::ORDER.to_be_bytes());
+    let reduced = BigInt::from_bytes_be(Sign::Plus, &hash).mod_floor(&order);
+    let be = reduced.to_bytes_be().1;
+    let mut arr = [0u8; 32];
+    arr[32 - be.len()..].copy_from_slice(&be);
+    Scalar::from_repr(arr.into()).expect("reduced mod the curve order")
+}
+
+/// The BIP340 challenge `e = tagged_hash("BIP0340/challenge", R.x || P.x || m)`.
+pub fn challenge(r_x: &[u8; 32], p_x: &[u8; 32], msg: &[u8]) -> Scalar {
+    let mut buf = Vec::with_capacity(64 + msg.len());
+    buf.extend_from_slice(r_x);
+    buf.extend_from_slice(p_x);
+    buf.extend_from_slice(msg);
+    scalar_from_hash(tagged_hash("BIP0340/challenge", &buf))
+}
+
+fn is_even_y(p: &ProjectivePoint) -> bool {
+    p.to_affine().to_encoded_point(true).as_bytes()[0] == 0x02
+}
+
+fn x_bytes(p: &ProjectivePoint) -> [u8; 32] {
+    let encoded = p.to_affine().to_encoded_point(false);
+    let mut out = [0u8; 32];
+    out.copy_from_slice(encoded.x().expect("uncompressed point has an x coordinate"));
+    out
+}
+
+/// BIP340 requires an even-Y point; negates the point (and, by the caller
+/// applying the same negation to its secret, the corresponding scalar) when
+/// it isn't.
+pub fn to_even_y(p: ProjectivePoint) -> ProjectivePoint {
+    if is_even_y(&p) { p } else { -p }
+}
+
+/// The Lagrange coefficient `λ_i(0) = Π_{k in set, k != i} (0 - k) / (i - k)`
+/// for combining this party's Shamir share with the other signers' shares.
+pub fn lagrange_coefficient_at_zero(i: u32, set: &[u32]) -> Scalar {
+    let x_i = Scalar::from(i as u64);
+    let mut numerator = Scalar::ONE;
+    let mut denominator = Scalar::ONE;
+    for &k in set {
+        if k == i {
+            continue;
+        }
+        let x_k = Scalar::from(k as u64);
+        numerator *= x_k.neg();
+        denominator *= x_i - x_k;
+    }
+    numerator * denominator.invert().expect("distinct party indices give a nonzero denominator")
+}
+
+/// One signer's partial signature: `s_i = k_i + e * lambda_i * x_i`, with the
+/// nonce and key secrets negated beforehand if the aggregate nonce point or
+/// group public key has odd Y (BIP340's even-Y convention).
+pub fn sign_partial(
+    nonce_secret: Scalar,
+    key_share: Scalar,
+    lambda_i: Scalar,
+    e: Scalar,
+    r_combined_even: &ProjectivePoint,
+    p_combined_even: &ProjectivePoint,
+    r_combined_was_odd: bool,
+    p_combined_was_odd: bool,
+) -> Scalar {
+    debug_assert!(is_even_y(r_combined_even) && is_even_y(p_combined_even));
+    let k_i = if r_combined_was_odd { nonce_secret.neg() } else { nonce_secret };
+    let x_i = if p_combined_was_odd { key_share.neg() } else { key_share };
+    k_i + e * lambda_i * x_i
+}
+
+/// A 64-byte `(R.x, s)` BIP340 signature.
+pub struct Signature {
+    pub r_x: [u8; 32],
+    pub s: Scalar,
+}
+
+impl Signature {
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut out = [0u8; 64];
+        out[..32].copy_from_slice(&self.r_x);
+        out[32..].copy_from_slice(&self.s.to_bytes());
+        out
+    }
+}
+
+/// Sums every signer's partial signature and pairs it with the aggregate
+/// (even-Y) nonce point's x coordinate.
+pub fn aggregate(r_combined_even: &ProjectivePoint, partials: &[Scalar]) -> Signature {
+    let s = partials.iter().fold(Scalar::ZERO, |acc, s_i| acc + s_i);
+    Signature { r_x: x_bytes(r_combined_even), s }
+}
+
+/// Verifies a BIP340 signature against the (possibly odd-Y) group public key.
+pub fn verify(sig: &Signature, p_combined: &ProjectivePoint, msg: &[u8]) -> bool {
+    let p_even = to_even_y(*p_combined);
+    let p_x = x_bytes(&p_even);
+    let e = challenge(&sig.r_x, &p_x, msg);
+
+    let r_prime = ProjectivePoint::GENERATOR * sig.s - p_even * e;
+    if bool::from(r_prime.is_identity()) {
+        return false;
+    }
+    is_even_y(&r_prime) && x_bytes(&r_prime) == sig.r_x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::OsRng;
+    use rand::RngCore;
+
+    fn random_scalar() -> Scalar {
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+        scalar_from_hash(bytes)
+    }
+
+    #[test]
+    fn test_two_of_two_threshold_sign_and_verify() {
+        let x1 = random_scalar();
+        let x2 = random_scalar();
+        let k1 = random_scalar();
+        let k2 = random_scalar();
+
+        let p_combined = ProjectivePoint::GENERATOR * x1 + ProjectivePoint::GENERATOR * x2;
+        let r_combined = ProjectivePoint::GENERATOR * k1 + ProjectivePoint::GENERATOR * k2;
+
+        let p_odd = !is_even_y(&p_combined);
+        let r_odd = !is_even_y(&r_combined);
+        let p_even = to_even_y(p_combined);
+        let r_even = to_even_y(r_combined);
+
+        let msg = b"bip340 test message";
+        let e = challenge(&x_bytes(&r_even), &x_bytes(&p_even), msg);
+
+        let set = [1u32, 2];
+        let lambda1 = lagrange_coefficient_at_zero(1, &set);
+        let lambda2 = lagrange_coefficient_at_zero(2, &set);
+
+        let s1 = sign_partial(k1, x1, lambda1, e, &r_even, &p_even, r_odd, p_odd);
+        let s2 = sign_partial(k2, x2, lambda2, e, &r_even, &p_even, r_odd, p_odd);
+
+        // With a 2-of-2 set the Lagrange coefficients are both 1, so the
+        // combined secret key is just x1 + x2; sanity check that directly.
+        assert_eq!(lambda1, Scalar::ONE);
+        assert_eq!(lambda2, Scalar::ONE);
+
+        let sig = aggregate(&r_even, &[s1, s2]);
+        assert!(verify(&sig, &p_combined, msg));
+    }
+
+    #[test]
+    fn test_verify_rejects_wrong_message() {
+        let x = random_scalar();
+        let k = random_scalar();
+        let p = to_even_y(ProjectivePoint::GENERATOR * x);
+        let p_odd = !is_even_y(&(ProjectivePoint::GENERATOR * x));
+        let r = ProjectivePoint::GENERATOR * k;
+        let r_odd = !is_even_y(&r);
+        let r_even = to_even_y(r);
+
+        let msg = b"correct message";
+        let e = challenge(&x_bytes(&r_even), &x_bytes(&p), msg);
+        let set = [1u32];
+        let lambda = lagrange_coefficient_at_zero(1, &set);
+        let s = sign_partial(k, x, lambda, e, &r_even, &p, r_odd, p_odd);
+        let sig = aggregate(&r_even, &[s]);
+
+        assert!(verify(&sig, &(ProjectivePoint::GENERATOR * x), msg));
+        assert!(!verify(&sig, &(ProjectivePoint::GENERATOR * x), b"wrong message"));
+    }
+}
@@ -3,6 +3,14 @@
 // This file is part of tss-lib. The full tss-lib copyright notice, including
 // terms governing use, modification, and redistribution, is contained in the
 // file LICENSE at the root of the source code distribution tree.
+//
+// Secp256r1 (P-256) and Secp384r1 (P-384) are registered here purely as
+// concrete `order`/`generator` parameter sets, the same role Secp256k1 and
+// Ed25519 already play. Both are short-Weierstrass curves backed by the
+// RustCrypto `p256`/`p384` crates, which already implement `Curve` and
+// `CurveArithmetic`; `ECPoint<C>` and the Schnorr `ZkProof`/`ZkvProof` are
+// generic over exactly that bound, so they carry over to P-256/P-384
+// unchanged -- no curve-specific glue is needed beyond this registry entry.
 
 use std::collections::HashMap;
 use std::sync::OnceLock;
@@ -13,11 +21,23 @@ use num_bigint::BigInt;
 use curve25519_dalek::edwards::EdwardsPoint;
 use curve25519_dalek::scalar::Scalar as Ed25519Scalar;
 use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+// NIST prime-curve imports from the RustCrypto `p256`/`p384` crates, so
+// FIDO/WebAuthn and PKI tooling built on the NIST curves can interoperate
+// with this TSS implementation without a separate non-Rust backend.
+use p256::{NistP256, ProjectivePoint as P256Point};
+use p384::{NistP384, ProjectivePoint as P384Point};
+// BLS12-381 is pairing-friendly, unlike every other curve registered here --
+// see `crypto::bls`'s module doc for why it gets its own point types
+// (G1/G2) instead of slotting into `ECPoint<C>`.
+use bls12_381::{G1Projective, G2Projective};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CurveName {
     Secp256k1,
     Ed25519,
+    Secp256r1,
+    Secp384r1,
+    Bls12_381,
 }
 
 #[derive(Debug, Clone)]
@@ -30,6 +50,22 @@ pub enum CurveParams {
         order: BigInt,
         generator: EdwardsPoint,
     },
+    Secp256r1 {
+        order: BigInt,
+        generator_projective: P256Point,
+    },
+    Secp384r1 {
+        order: BigInt,
+        generator_projective: P384Point,
+    },
+    /// `order` is the BLS12-381 scalar field's order (shared by G1 and G2);
+    /// `g1_generator`/`g2_generator` back `crypto::bls`'s partial-signature
+    /// (G1) and public-key (G2) arithmetic.
+    Bls12_381 {
+        order: BigInt,
+        g1_generator: G1Projective,
+        g2_generator: G2Projective,
+    },
 }
 
 impl CurveParams {
@@ -37,6 +73,9 @@ impl CurveParams {
         match self {
             CurveParams::Secp256k1 { order, .. } => order,
             CurveParams::Ed25519 { order, .. } => order,
+            CurveParams::Secp256r1 { order, .. } => order,
+            CurveParams::Secp384r1 { order, .. } => order,
+            CurveParams::Bls12_381 { order, .. } => order,
         }
     }
 }
@@ -52,6 +91,13 @@ const ED25519_ORDER_BYTES: [u8; 32] = [
     0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x10, 0
 ];
 
+// BLS12-381 scalar field order (shared by G1 and G2), as specified for the
+// curve -- unlike Secp256k1/P-256/P-384, the `bls12_381` crate doesn't
+// expose this as a public `ORDER` constant, so it's hardcoded here the same
+// way `ED25519_ORDER_BYTES` is.
+const BLS12_381_ORDER_DECIMAL: &str =
+    "52435875175126190479447740508185965837690552500527637822603658699938581184513";
+
 fn get_or_init_registry() -> &'static HashMap<CurveName, CurveParams> {
     CURVE_REGISTRY.get_or_init(|| {
         let mut map = HashMap::new();
@@ -75,6 +121,37 @@ fn get_or_init_registry() -> &'static HashMap<CurveName, CurveParams> {
             generator: ed25519_generator,
         });
 
+        // --- NIST P-256 (secp256r1) Parameters ---
+        let p256_order_bytes = NistP256::ORDER.to_be_bytes();
+        let p256_order = BigInt::from_bytes_be(num_bigint::Sign::Plus, &p256_order_bytes);
+        let p256_generator = P256Point::GENERATOR;
+
+        map.insert(CurveName::Secp256r1, CurveParams::Secp256r1 {
+            order: p256_order,
+            generator_projective: p256_generator,
+        });
+
+        // --- NIST P-384 (secp384r1) Parameters ---
+        let p384_order_bytes = NistP384::ORDER.to_be_bytes();
+        let p384_order = BigInt::from_bytes_be(num_bigint::Sign::Plus, &p384_order_bytes);
+        let p384_generator = P384Point::GENERATOR;
+
+        map.insert(CurveName::Secp384r1, CurveParams::Secp384r1 {
+            order: p384_order,
+            generator_projective: p384_generator,
+        });
+
+        // --- BLS12-381 Parameters ---
+        let bls12_381_order = BLS12_381_ORDER_DECIMAL
+            .parse::<BigInt>()
+            .expect("BLS12_381_ORDER_DECIMAL is a valid decimal integer literal");
+
+        map.insert(CurveName::Bls12_381, CurveParams::Bls12_381 {
+            order: bls12_381_order,
+            g1_generator: G1Projective::generator(),
+            g2_generator: G2Projective::generator(),
+        });
+
         map
     })
 }
@@ -99,6 +176,115 @@ pub fn ed25519_params() -> CurveParams {
     get_curve_params(CurveName::Ed25519).expect("Ed25519 params not found in registry").clone()
 }
 
+pub fn secp256r1_params() -> CurveParams {
+    get_curve_params(CurveName::Secp256r1).expect("Secp256r1 params not found in registry").clone()
+}
+
+pub fn secp384r1_params() -> CurveParams {
+    get_curve_params(CurveName::Secp384r1).expect("Secp384r1 params not found in registry").clone()
+}
+
+pub fn bls12_381_params() -> CurveParams {
+    get_curve_params(CurveName::Bls12_381).expect("Bls12_381 params not found in registry").clone()
+}
+
+// --- Extensible curve plugin interface ---
+//
+// Onboarding Secp256r1/Secp384r1 above meant touching `CurveName`,
+// `CurveParams`, `get_or_init_registry`, and every `match` over those
+// variants -- fine for curves that ship in this crate, but it forecloses a
+// downstream crate from registering its own curve without forking. Modeled
+// on curv's pluggable-curve design: a `CurveProvider` trait a curve exposes
+// itself through, and a `register_curve` entry point any crate can call at
+// startup to add to the lookup `get_curve_params_by_name` uses.
+//
+// This only covers the curve-agnostic part of "onboarding a curve" --
+// order and name -- rather than point arithmetic: this crate's point types
+// (`k256::ProjectivePoint`, `EdwardsPoint`, `p256`/`p384` points) aren't
+// unified behind one representation, so a `CurveProvider::generator()`
+// returning a single concrete point type isn't possible without first
+// giving every curve a common point representation (a separate, larger
+// undertaking). Code that needs curve arithmetic still goes through the
+// curve-specific `CurveParams` variant above; `CurveProvider` is for the
+// name/order-level logic (e.g. validating a requested threshold scheme
+// against the curve's order) that doesn't need point operations at all.
+pub trait CurveProvider: Send + Sync {
+    /// Short, stable, case-sensitive name other code looks this curve up by
+    /// (e.g. `"secp256k1"`).
+    fn name(&self) -> &str;
+    /// The curve's (sub)group order.
+    fn order(&self) -> &BigInt;
+}
+
+struct Secp256k1Provider(BigInt);
+impl CurveProvider for Secp256k1Provider {
+    fn name(&self) -> &str { "secp256k1" }
+    fn order(&self) -> &BigInt { &self.0 }
+}
+
+struct Ed25519Provider(BigInt);
+impl CurveProvider for Ed25519Provider {
+    fn name(&self) -> &str { "ed25519" }
+    fn order(&self) -> &BigInt { &self.0 }
+}
+
+struct Secp256r1Provider(BigInt);
+impl CurveProvider for Secp256r1Provider {
+    fn name(&self) -> &str { "secp256r1" }
+    fn order(&self) -> &BigInt { &self.0 }
+}
+
+struct Secp384r1Provider(BigInt);
+impl CurveProvider for Secp384r1Provider {
+    fn name(&self) -> &str { "secp384r1" }
+    fn order(&self) -> &BigInt { &self.0 }
+}
+
+struct Bls12_381Provider(BigInt);
+impl CurveProvider for Bls12_381Provider {
+    fn name(&self) -> &str { "bls12_381" }
+    fn order(&self) -> &BigInt { &self.0 }
+}
+
+static CURVE_PROVIDERS: OnceLock<std::sync::Mutex<Vec<Box<dyn CurveProvider>>>> = OnceLock::new();
+
+fn curve_providers() -> &'static std::sync::Mutex<Vec<Box<dyn CurveProvider>>> {
+    CURVE_PROVIDERS.get_or_init(|| {
+        let registry = get_or_init_registry();
+        std::sync::Mutex::new(vec![
+            Box::new(Secp256k1Provider(registry[&CurveName::Secp256k1].order().clone())) as Box<dyn CurveProvider>,
+            Box::new(Ed25519Provider(registry[&CurveName::Ed25519].order().clone())),
+            Box::new(Secp256r1Provider(registry[&CurveName::Secp256r1].order().clone())),
+            Box::new(Secp384r1Provider(registry[&CurveName::Secp384r1].order().clone())),
+            Box::new(Bls12_381Provider(registry[&CurveName::Bls12_381].order().clone())),
+        ])
+    })
+}
+
+/// Registers a curve provider so `get_curve_params_by_name` can find it,
+/// without needing to add a `CurveName`/`CurveParams` variant for it. A
+/// provider registered under a name that's already taken replaces it.
+pub fn register_curve(provider: Box<dyn CurveProvider>) {
+    let mut providers = curve_providers().lock().expect("curve provider registry poisoned");
+    if let Some(existing) = providers.iter_mut().find(|p| p.name() == provider.name()) {
+        *existing = provider;
+    } else {
+        providers.push(provider);
+    }
+}
+
+/// Looks up a registered curve's order by name. Covers both the five
+/// built-in curves (registered eagerly on first use) and any curve a
+/// downstream crate added via `register_curve`.
+pub fn get_curve_order_by_name(name: &str) -> Option<BigInt> {
+    curve_providers()
+        .lock()
+        .expect("curve provider registry poisoned")
+        .iter()
+        .find(|p| p.name() == name)
+        .map(|p| p.order().clone())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -109,6 +295,43 @@ mod tests {
     fn test_is_curve_supported() {
         assert!(is_curve_supported(CurveName::Secp256k1));
         assert!(is_curve_supported(CurveName::Ed25519));
+        assert!(is_curve_supported(CurveName::Secp256r1));
+        assert!(is_curve_supported(CurveName::Secp384r1));
+        assert!(is_curve_supported(CurveName::Bls12_381));
+    }
+
+    #[test]
+    fn test_get_curve_params_bls12_381() {
+        let params = get_curve_params(CurveName::Bls12_381);
+        assert!(params.is_some());
+        if let Some(CurveParams::Bls12_381 { order, g1_generator, g2_generator }) = params {
+            assert!(*order > BigInt::zero(), "Bls12_381 order should not be zero");
+            assert_ne!(*g1_generator, bls12_381::G1Projective::identity(), "Bls12_381 G1 generator should not be identity");
+            assert_ne!(*g2_generator, bls12_381::G2Projective::identity(), "Bls12_381 G2 generator should not be identity");
+        } else {
+            panic!("Expected Bls12_381 params");
+        }
+    }
+
+    #[test]
+    fn test_get_curve_params_nist_curves() {
+        let params_p256 = get_curve_params(CurveName::Secp256r1);
+        assert!(params_p256.is_some());
+        if let Some(CurveParams::Secp256r1 { order, generator_projective }) = params_p256 {
+            assert!(*order > BigInt::zero(), "Secp256r1 order should not be zero");
+            assert!(!bool::from(generator_projective.is_identity()), "Secp256r1 generator should not be identity");
+        } else {
+            panic!("Expected Secp256r1 params");
+        }
+
+        let params_p384 = get_curve_params(CurveName::Secp384r1);
+        assert!(params_p384.is_some());
+        if let Some(CurveParams::Secp384r1 { order, generator_projective }) = params_p384 {
+            assert!(*order > BigInt::zero(), "Secp384r1 order should not be zero");
+            assert!(!bool::from(generator_projective.is_identity()), "Secp384r1 generator should not be identity");
+        } else {
+            panic!("Expected Secp384r1 params");
+        }
     }
 
     #[test]
@@ -148,13 +371,68 @@ mod tests {
 
         let params_ed25519 = ed25519_params();
         assert!(matches!(params_ed25519, CurveParams::Ed25519 { .. }));
+
+        let params_p256 = secp256r1_params();
+        assert!(matches!(params_p256, CurveParams::Secp256r1 { .. }));
+
+        let params_p384 = secp384r1_params();
+        assert!(matches!(params_p384, CurveParams::Secp384r1 { .. }));
+
+        let params_bls = bls12_381_params();
+        assert!(matches!(params_bls, CurveParams::Bls12_381 { .. }));
+    }
+
+    #[test]
+    fn test_get_curve_order_by_name_covers_builtins() {
+        assert_eq!(get_curve_order_by_name("secp256k1"), Some(s256k1_params().order().clone()));
+        assert_eq!(get_curve_order_by_name("ed25519"), Some(ed25519_params().order().clone()));
+        assert_eq!(get_curve_order_by_name("secp256r1"), Some(secp256r1_params().order().clone()));
+        assert_eq!(get_curve_order_by_name("secp384r1"), Some(secp384r1_params().order().clone()));
+        assert_eq!(get_curve_order_by_name("bls12_381"), Some(bls12_381_params().order().clone()));
+        assert_eq!(get_curve_order_by_name("no-such-curve"), None);
+    }
+
+    #[test]
+    fn test_register_curve_adds_a_downstream_curve() {
+        struct ToyCurveProvider;
+        impl CurveProvider for ToyCurveProvider {
+            fn name(&self) -> &str { "toy-curve-for-test" }
+            fn order(&self) -> &BigInt {
+                // A `'static` isn't available for a non-const BigInt, so this
+                // stashes the order behind a thread-local-free OnceLock.
+                static ORDER: OnceLock<BigInt> = OnceLock::new();
+                ORDER.get_or_init(|| BigInt::from(101))
+            }
+        }
+        register_curve(Box::new(ToyCurveProvider));
+        assert_eq!(get_curve_order_by_name("toy-curve-for-test"), Some(BigInt::from(101)));
+    }
+
+    #[test]
+    fn test_register_curve_replaces_same_named_provider() {
+        struct ReplaceableProvider(u32);
+        impl CurveProvider for ReplaceableProvider {
+            fn name(&self) -> &str { "replaceable-curve-for-test" }
+            fn order(&self) -> &BigInt {
+                static ORDER: OnceLock<BigInt> = OnceLock::new();
+                ORDER.get_or_init(|| BigInt::from(7))
+            }
+        }
+        register_curve(Box::new(ReplaceableProvider(1)));
+        register_curve(Box::new(ReplaceableProvider(2)));
+        let providers = curve_providers().lock().unwrap();
+        let count = providers.iter().filter(|p| p.name() == "replaceable-curve-for-test").count();
+        assert_eq!(count, 1, "re-registering under the same name should replace, not duplicate");
     }
 
     #[test]
     fn test_registry_initialization() {
         let registry = get_or_init_registry();
-        assert_eq!(registry.len(), 2, "Registry should contain parameters for 2 curves");
+        assert_eq!(registry.len(), 5, "Registry should contain parameters for 5 curves");
         assert!(registry.contains_key(&CurveName::Secp256k1));
         assert!(registry.contains_key(&CurveName::Ed25519));
+        assert!(registry.contains_key(&CurveName::Secp256r1));
+        assert!(registry.contains_key(&CurveName::Secp384r1));
+        assert!(registry.contains_key(&CurveName::Bls12_381));
     }
 }
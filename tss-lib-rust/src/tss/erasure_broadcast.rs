@@ -0,0 +1,433 @@
+// Copyright © 2019 Binance
+//
+// This file is part of Binance. The full Binance copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+// Honey Badger-style erasure-coded reliable broadcast (RBC).
+//
+// `reliable_broadcast::BrachaBroadcast` already guarantees every honest
+// party delivers the same payload or nothing, but every echo/ready vote
+// repeats the *whole* payload -- fine for small messages, wasteful for a
+// round broadcasting something the size of a `ModProof`. This module trades
+// that for erasure coding: the sender Reed-Solomon-shards the payload into
+// `n` pieces (`common::reed_solomon::Generator`) of which any `f+1`
+// reconstruct it, commits to all `n` with a Merkle root, and each party only
+// ever echoes/readies its own shard plus a short audit branch. A party
+// assembles the full payload only once it has collected enough matching
+// shards to decode, at which point it recomputes the root to confirm every
+// shard it used was consistent with the commitment everyone else saw.
+//
+// Like `BrachaBroadcast`, this is a pure, synchronous tally: it verifies
+// Merkle branches and counts votes, and returns an `ErasureAction` for the
+// caller to act on (send a wire message, deliver to `Round::store_message`,
+// or raise blame evidence). It sends nothing itself.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::common::hash::sha512_256;
+use crate::common::reed_solomon::Generator;
+use crate::tss::party_id::PartyID;
+pub use crate::tss::reliable_broadcast::BroadcastId;
+
+/// `n`/`f` quorum sizes for one erasure-coded RBC instance: `n` total
+/// parties, `f` the maximum tolerated number of Byzantine parties (requires
+/// `n >= 3f + 1`, the standard asynchronous BFT bound).
+#[derive(Clone, Copy, Debug)]
+pub struct ErasureRbcThresholds {
+    n: usize,
+    f: usize,
+}
+
+impl ErasureRbcThresholds {
+    pub fn new(n: usize, f: usize) -> Self {
+        ErasureRbcThresholds { n, f }
+    }
+
+    /// Derives `(n, f)` from the protocol's `party_count`/`threshold`: `f`
+    /// is capped at both the secret-sharing threshold (a sender can't
+    /// usefully equivocate toward more parties than could reconstruct the
+    /// secret anyway) and the `(n-1)/3` asynchronous BFT bound.
+    pub fn from_params(party_count: usize, threshold: usize) -> Self {
+        let bft_bound = party_count.saturating_sub(1) / 3;
+        ErasureRbcThresholds { n: party_count, f: threshold.min(bft_bound) }
+    }
+
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    pub fn f(&self) -> usize {
+        self.f
+    }
+
+    /// `f+1`: the number of shards needed to Reed-Solomon-decode the payload.
+    pub fn k(&self) -> usize {
+        self.f + 1
+    }
+
+    /// `2f+1`: matching `ECHO`s needed before decoding and sending `READY`.
+    pub fn echo_threshold(&self) -> usize {
+        2 * self.f + 1
+    }
+
+    /// `f+1`: matching `READY`s needed to amplify by sending `READY` even
+    /// without having reached the echo threshold.
+    pub fn ready_amplify_threshold(&self) -> usize {
+        self.f + 1
+    }
+
+    /// `2f+1`: matching `READY`s needed to deliver, once enough shards have
+    /// also been collected to decode.
+    pub fn deliver_threshold(&self) -> usize {
+        2 * self.f + 1
+    }
+}
+
+/// An audit branch: the sibling hash at each level from a shard's leaf up to
+/// the Merkle root.
+pub type MerkleBranch = Vec<Vec<u8>>;
+
+fn hash_pair(left: &[u8], right: &[u8]) -> Vec<u8> {
+    sha512_256(&[left, right])
+}
+
+fn next_pow2(n: usize) -> usize {
+    let mut p = 1;
+    while p < n {
+        p *= 2;
+    }
+    p
+}
+
+/// Builds a Merkle tree over `shards` (hashed individually as leaves, padded
+/// with duplicates of the last leaf up to a power of two so every leaf has a
+/// sibling at every level), returning the root and, for each original shard
+/// index, its audit branch.
+fn build_merkle(shards: &[Vec<u8>]) -> (Vec<u8>, Vec<MerkleBranch>) {
+    let leaf_count = next_pow2(shards.len().max(1));
+    let mut level: Vec<Vec<u8>> = (0..leaf_count)
+        .map(|i| sha512_256(&[shards[i.min(shards.len() - 1)].as_slice()]))
+        .collect();
+
+    // branches[i] accumulates the sibling hash seen at each level for leaf i.
+    let mut branches: Vec<MerkleBranch> = vec![Vec::new(); leaf_count];
+    let mut indices: Vec<usize> = (0..leaf_count).collect();
+
+    while level.len() > 1 {
+        let mut next_level = Vec::with_capacity(level.len() / 2);
+        for pair in 0..level.len() / 2 {
+            let (left, right) = (&level[2 * pair], &level[2 * pair + 1]);
+            for (leaf, &idx) in indices.iter().enumerate() {
+                if idx == 2 * pair {
+                    branches[leaf].push(right.clone());
+                } else if idx == 2 * pair + 1 {
+                    branches[leaf].push(left.clone());
+                }
+            }
+            next_level.push(hash_pair(left, right));
+        }
+        indices = indices.iter().map(|i| i / 2).collect();
+        level = next_level;
+    }
+
+    (level[0].clone(), branches[0..shards.len()].to_vec())
+}
+
+/// Recomputes the root from `shard`'s leaf hash, its claimed `index`, and
+/// `branch`, and checks it matches `root`.
+fn verify_branch(root: &[u8], index: usize, shard: &[u8], branch: &MerkleBranch) -> bool {
+    let mut hash = sha512_256(&[shard]);
+    let mut idx = index;
+    for sibling in branch {
+        hash = if idx.is_multiple_of(2) { hash_pair(&hash, sibling) } else { hash_pair(sibling, &hash) };
+        idx /= 2;
+    }
+    hash == root
+}
+
+/// Reed-Solomon-shards `payload` (length-prefixed so decode doesn't need the
+/// original length passed out of band) and commits to the `n` shards with a
+/// Merkle root, for the sender to distribute as `n` `VAL(root, shard,
+/// branch)` messages.
+pub fn shard_for_broadcast(payload: &[u8], thresholds: ErasureRbcThresholds) -> (Vec<u8>, Vec<Vec<u8>>, Vec<MerkleBranch>) {
+    let mut framed = (payload.len() as u64).to_le_bytes().to_vec();
+    framed.extend_from_slice(payload);
+
+    let generator = Generator::new(thresholds.n(), thresholds.k());
+    let shards = generator.encode(&framed);
+    let (root, branches) = build_merkle(&shards);
+    (root, shards, branches)
+}
+
+/// Reed-Solomon-decodes `payload` from any `k` of the `n` shards and strips
+/// the length prefix `shard_for_broadcast` added.
+fn decode_shards(shards: &[(usize, Vec<u8>)], thresholds: ErasureRbcThresholds) -> Option<Vec<u8>> {
+    let chunk_len = shards.first()?.1.len();
+    let generator = Generator::new(thresholds.n(), thresholds.k());
+    let framed = generator.decode(shards, chunk_len * thresholds.k());
+    if framed.len() < 8 {
+        return None;
+    }
+    let len = u64::from_le_bytes(framed[0..8].try_into().ok()?) as usize;
+    framed.get(8..8 + len).map(|p| p.to_vec())
+}
+
+/// What a caller should do in response to a vote `ErasureRbcInstance` just
+/// processed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErasureAction {
+    /// Nothing new to act on yet.
+    None,
+    /// Broadcast an `ECHO` of this party's own shard.
+    SendEcho { root: Vec<u8>, shard_index: usize, shard: Vec<u8>, branch: MerkleBranch },
+    /// Broadcast a `READY` for this root.
+    SendReady { root: Vec<u8> },
+    /// `2f+1` matching `READY`s plus enough decoded, consistent shards:
+    /// this is the delivered payload. Delivered at most once per instance.
+    Deliver(Vec<u8>),
+    /// A sender's shards decoded to a value whose re-encoded Merkle root
+    /// doesn't match the root it committed to -- the shards collected
+    /// can't all have come from one honest encoding of a single payload.
+    InconsistentRoot { root: Vec<u8> },
+}
+
+/// Per-(sender, round) vote tally and delivery state for one erasure-coded
+/// RBC instance.
+struct ErasureRbcInstance {
+    thresholds: ErasureRbcThresholds,
+    /// Shards collected per root, keyed by shard index, so a decode only
+    /// ever mixes shards claimed under the same commitment.
+    shards_by_root: HashMap<Vec<u8>, HashMap<usize, Vec<u8>>>,
+    echo_voters: HashMap<Vec<u8>, HashSet<PartyID>>,
+    ready_voters: HashMap<Vec<u8>, HashSet<PartyID>>,
+    ready_sent: bool,
+    delivered: bool,
+}
+
+impl ErasureRbcInstance {
+    fn new(thresholds: ErasureRbcThresholds) -> Self {
+        ErasureRbcInstance {
+            thresholds,
+            shards_by_root: HashMap::new(),
+            echo_voters: HashMap::new(),
+            ready_voters: HashMap::new(),
+            ready_sent: false,
+            delivered: false,
+        }
+    }
+
+    fn remember_shard(&mut self, root: &[u8], shard_index: usize, shard: Vec<u8>) {
+        self.shards_by_root.entry(root.to_vec()).or_default().insert(shard_index, shard);
+    }
+
+    /// This node received `VAL(root, shard, branch)` directly from the
+    /// sender: verify it and, if valid, echo it.
+    fn on_receive_val(&mut self, root: Vec<u8>, shard_index: usize, shard: Vec<u8>, branch: MerkleBranch) -> ErasureAction {
+        if !verify_branch(&root, shard_index, &shard, &branch) {
+            return ErasureAction::None;
+        }
+        self.remember_shard(&root, shard_index, shard.clone());
+        ErasureAction::SendEcho { root, shard_index, shard, branch }
+    }
+
+    /// Process an `ECHO(root, shard, branch)` vote from `voter`.
+    fn on_echo(&mut self, voter: PartyID, root: Vec<u8>, shard_index: usize, shard: Vec<u8>, branch: MerkleBranch) -> ErasureAction {
+        if self.delivered || !verify_branch(&root, shard_index, &shard, &branch) {
+            return ErasureAction::None;
+        }
+        self.remember_shard(&root, shard_index, shard);
+        self.echo_voters.entry(root.clone()).or_default().insert(voter);
+
+        if let Some(action) = self.try_deliver(&root) {
+            return action;
+        }
+        if !self.ready_sent && self.echo_voters[&root].len() >= self.thresholds.echo_threshold() {
+            return self.try_send_ready(&root);
+        }
+        ErasureAction::None
+    }
+
+    /// Process a `READY(root)` vote from `voter`.
+    fn on_ready(&mut self, voter: PartyID, root: Vec<u8>) -> ErasureAction {
+        if self.delivered {
+            return ErasureAction::None;
+        }
+        self.ready_voters.entry(root.clone()).or_default().insert(voter);
+
+        if let Some(action) = self.try_deliver(&root) {
+            return action;
+        }
+        if !self.ready_sent && self.ready_voters[&root].len() >= self.thresholds.ready_amplify_threshold() {
+            return self.try_send_ready(&root);
+        }
+        ErasureAction::None
+    }
+
+    fn try_send_ready(&mut self, root: &[u8]) -> ErasureAction {
+        self.ready_sent = true;
+        ErasureAction::SendReady { root: root.to_vec() }
+    }
+
+    /// If `root` has both `2f+1` matching `READY`s and enough shards to
+    /// decode, reconstructs the payload and checks its re-encoded root
+    /// against `root` before delivering.
+    fn try_deliver(&mut self, root: &[u8]) -> Option<ErasureAction> {
+        if self.ready_voters.get(root).map_or(0, |v| v.len()) < self.thresholds.deliver_threshold() {
+            return None;
+        }
+        let shards = self.shards_by_root.get(root)?;
+        if shards.len() < self.thresholds.k() {
+            return None;
+        }
+        let subset: Vec<(usize, Vec<u8>)> = shards.iter().take(self.thresholds.k()).map(|(&i, s)| (i, s.clone())).collect();
+        let payload = decode_shards(&subset, self.thresholds)?;
+
+        let (recomputed_root, _, _) = shard_for_broadcast(&payload, self.thresholds);
+        if recomputed_root != root {
+            return Some(ErasureAction::InconsistentRoot { root: root.to_vec() });
+        }
+        self.delivered = true;
+        Some(ErasureAction::Deliver(payload))
+    }
+}
+
+/// Tracks erasure-coded RBC state across every `(sender, round)` instance
+/// this party has seen a vote for.
+pub struct ErasureBroadcast {
+    thresholds: ErasureRbcThresholds,
+    instances: HashMap<BroadcastId, ErasureRbcInstance>,
+}
+
+impl ErasureBroadcast {
+    pub fn new(thresholds: ErasureRbcThresholds) -> Self {
+        ErasureBroadcast { thresholds, instances: HashMap::new() }
+    }
+
+    fn instance(&mut self, id: BroadcastId) -> &mut ErasureRbcInstance {
+        let thresholds = self.thresholds;
+        self.instances.entry(id).or_insert_with(|| ErasureRbcInstance::new(thresholds))
+    }
+
+    pub fn on_receive_val(&mut self, id: BroadcastId, root: Vec<u8>, shard_index: usize, shard: Vec<u8>, branch: MerkleBranch) -> ErasureAction {
+        self.instance(id).on_receive_val(root, shard_index, shard, branch)
+    }
+
+    pub fn on_echo(&mut self, id: BroadcastId, voter: PartyID, root: Vec<u8>, shard_index: usize, shard: Vec<u8>, branch: MerkleBranch) -> ErasureAction {
+        self.instance(id).on_echo(voter, root, shard_index, shard, branch)
+    }
+
+    pub fn on_ready(&mut self, id: BroadcastId, voter: PartyID, root: Vec<u8>) -> ErasureAction {
+        self.instance(id).on_ready(voter, root)
+    }
+}
+
+/// Opt-in for a round to route one of its broadcast message types through
+/// erasure-coded RBC instead of trusting the bare `is_broadcast` flag. A
+/// round implements this to plug `ErasureBroadcast`'s `ErasureAction::Deliver`
+/// output (keyed by the sender's index into `Parameters::parties()`) into
+/// its own message tracking, the same way a directly-received broadcast
+/// feeds `BaseParty::store_message` today. `Round2`'s `KGRound2Message2`
+/// (de-commitment + `ModProof`) is the first intended adopter; other
+/// broadcast rounds can migrate the same way.
+pub trait ErasureBroadcastRound {
+    /// Thresholds for this round's erasure-coded broadcasts.
+    fn erasure_thresholds(&self) -> ErasureRbcThresholds;
+
+    /// Feeds a payload delivered for `sender_index` (see
+    /// `ErasureAction::Deliver`) into the round's own message tracking.
+    fn on_erasure_delivered(&mut self, sender_index: usize, payload: Vec<u8>);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+
+    fn party(id: u32) -> PartyID {
+        PartyID::new(id.to_string(), format!("party-{}", id), BigInt::from(id))
+    }
+
+    fn broadcast_id() -> BroadcastId {
+        BroadcastId { sender: party(0), round: 2 }
+    }
+
+    // n=7, f=2: k=3, echo_threshold=5, ready_amplify_threshold=3, deliver_threshold=5
+    fn thresholds() -> ErasureRbcThresholds {
+        ErasureRbcThresholds::new(7, 2)
+    }
+
+    #[test]
+    fn test_merkle_branches_verify_against_the_root() {
+        let shards: Vec<Vec<u8>> = (0..5).map(|i| vec![i as u8; 4]).collect();
+        let (root, branches) = build_merkle(&shards);
+        for (i, shard) in shards.iter().enumerate() {
+            assert!(verify_branch(&root, i, shard, &branches[i]));
+        }
+        // Tampering with a shard must invalidate its branch.
+        assert!(!verify_branch(&root, 0, b"tampered", &branches[0]));
+    }
+
+    #[test]
+    fn test_shard_for_broadcast_round_trips_through_decode() {
+        let payload = b"round 2 decommitment + ModProof".to_vec();
+        let (root, shards, branches) = shard_for_broadcast(&payload, thresholds());
+
+        let subset: Vec<(usize, Vec<u8>)> = vec![(1, shards[1].clone()), (3, shards[3].clone()), (5, shards[5].clone())];
+        assert_eq!(decode_shards(&subset, thresholds()), Some(payload.clone()));
+
+        for i in [1usize, 3, 5] {
+            assert!(verify_branch(&root, i, &shards[i], &branches[i]));
+        }
+    }
+
+    #[test]
+    fn test_delivers_after_echo_quorum_reaches_ready_quorum() {
+        let payload = b"agreed round-2 broadcast value".to_vec();
+        let (root, shards, branches) = shard_for_broadcast(&payload, thresholds());
+        let id = broadcast_id();
+        let mut rbc = ErasureBroadcast::new(thresholds());
+
+        // 5 of 7 parties echo their own shard: crosses the echo threshold
+        // and, since that's already >= k=3 distinct shards, decodes and
+        // sends READY immediately.
+        let mut last = ErasureAction::None;
+        for i in 0..5 {
+            last = rbc.on_echo(id.clone(), party(i as u32 + 1), root.clone(), i, shards[i].clone(), branches[i].clone());
+        }
+        assert_eq!(last, ErasureAction::SendReady { root: root.clone() });
+
+        // 5 matching READYs cross the deliver threshold.
+        let mut delivered = ErasureAction::None;
+        for i in 0..5 {
+            delivered = rbc.on_ready(id.clone(), party(i as u32 + 10), root.clone());
+        }
+        assert_eq!(delivered, ErasureAction::Deliver(payload));
+    }
+
+    #[test]
+    fn test_ready_amplification_before_echo_quorum() {
+        let payload = b"small value".to_vec();
+        let (root, _shards, _branches) = shard_for_broadcast(&payload, thresholds());
+        let id = broadcast_id();
+        let mut rbc = ErasureBroadcast::new(thresholds());
+
+        // ready_amplify_threshold = f+1 = 3: three READYs before any ECHO
+        // at all still makes this node amplify by readying too.
+        assert_eq!(rbc.on_ready(id.clone(), party(1), root.clone()), ErasureAction::None);
+        assert_eq!(rbc.on_ready(id.clone(), party(2), root.clone()), ErasureAction::None);
+        assert_eq!(rbc.on_ready(id.clone(), party(3), root.clone()), ErasureAction::SendReady { root });
+    }
+
+    #[test]
+    fn test_invalid_branch_is_ignored_on_receive() {
+        let payload = b"value".to_vec();
+        let (root, shards, branches) = shard_for_broadcast(&payload, thresholds());
+        let id = broadcast_id();
+        let mut rbc = ErasureBroadcast::new(thresholds());
+
+        let mut tampered = shards[0].clone();
+        tampered[0] ^= 0xFF;
+        assert_eq!(rbc.on_receive_val(id, root, 0, tampered, branches[0].clone()), ErasureAction::None);
+    }
+}
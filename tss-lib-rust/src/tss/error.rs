@@ -1,4 +1,168 @@
 use std::fmt;
+use std::sync::Arc;
+use crate::tss::message::ParsedMessage;
+use crate::tss::party_id::PartyID;
+
+/// The specific check a party failed during an accountability-bearing
+/// verification step (e.g. keygen VSS verification), so that blame can be
+/// attributed to a concrete, independently-re-checkable reason.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureKind {
+    /// The hash-commitment decommitment to the VSS verification vector didn't open.
+    VssDecommitment,
+    /// The accused party's Paillier modulus ZK proof (Π-mod) failed to verify.
+    ModProof,
+    /// The accused party's Feldman VSS share didn't satisfy the verification equation.
+    VssShare,
+    /// The accused party's no-small-factor proof (Π-fac) failed to verify.
+    FacProof,
+    /// A batched/aggregate check covering several parties at once failed; the
+    /// individual culprit still needs a per-party re-check to pin down.
+    BatchedVerification,
+    /// The accused party's Schnorr proof of possession for its VSS constant-term
+    /// commitment failed to verify.
+    SchnorrPop,
+    /// A reliable-broadcast sender (see `tss::reliable_broadcast`) equivocated:
+    /// two distinct payload digests for the same `(sender, round)` each
+    /// crossed the echo quorum, which is only possible if the sender sent
+    /// different payloads to different honest parties.
+    Equivocation,
+}
+
+impl fmt::Display for FailureKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            FailureKind::VssDecommitment => "VSS decommitment failed",
+            FailureKind::ModProof => "ModProof verification failed",
+            FailureKind::VssShare => "VSS share verification failed",
+            FailureKind::FacProof => "FacProof verification failed",
+            FailureKind::BatchedVerification => "batched verification failed",
+            FailureKind::SchnorrPop => "Schnorr proof of possession verification failed",
+            FailureKind::Equivocation => "sender equivocated across a reliable broadcast",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// Structured, independently-reproducible evidence that `accused` cheated during
+/// `round`. Any party holding the same `offending_message` and `verifier_transcript`
+/// can re-run the same deterministic check this evidence was produced from and
+/// arrive at the same verdict, rather than trusting the accuser's word.
+#[derive(Debug, Clone)]
+pub struct BlameEvidence {
+    pub accused: PartyID,
+    pub round: i32,
+    pub kind: FailureKind,
+    /// The broadcast/P2P message the failed check was extracted from, when the
+    /// verifier kept it around; `None` when only the decoded fields survived.
+    pub offending_message: Option<Arc<ParsedMessage>>,
+    /// Serialized inputs (e.g. commitment bytes, proof bytes, public parameters)
+    /// needed to deterministically replay the failed check.
+    pub verifier_transcript: Vec<u8>,
+}
+
+impl BlameEvidence {
+    pub fn new(
+        accused: PartyID,
+        round: i32,
+        kind: FailureKind,
+        offending_message: Option<Arc<ParsedMessage>>,
+        verifier_transcript: Vec<u8>,
+    ) -> Self {
+        BlameEvidence { accused, round, kind, offending_message, verifier_transcript }
+    }
+}
+
+impl fmt::Display for BlameEvidence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "party {:?} at round {}: {}", self.accused, self.round, self.kind)
+    }
+}
+
+/// The cause carried by a `RoundError`: either a plain wrapped error (as used
+/// by `Round::wrap_error`), or a set of reproducible blame evidence that should
+/// halt the protocol with a culprit list instead of a generic failure message.
+#[derive(Debug)]
+pub enum RoundErr {
+    General(Box<dyn std::error::Error + Send + Sync>),
+    Blame(Vec<BlameEvidence>),
+}
+
+impl fmt::Display for RoundErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RoundErr::General(e) => write!(f, "{}", e),
+            RoundErr::Blame(evidence) => {
+                write!(f, "{} cheating part{} detected: ", evidence.len(), if evidence.len() == 1 { "y" } else { "ies" })?;
+                for (i, e) in evidence.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "; ")?;
+                    }
+                    write!(f, "{}", e)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Error type returned by `Round` methods (`start`/`update`/...). Distinct from
+/// `Error` (used by `BaseParty::wrap_error`) so that round-local failures can
+/// carry a `RoundErr::Blame` with reproducible culprit evidence.
+#[derive(Debug)]
+pub struct RoundError {
+    cause: RoundErr,
+    task: String,
+    round: i32,
+    victim: Option<PartyID>,
+    culprits: Vec<PartyID>,
+}
+
+impl RoundError {
+    pub fn new(cause: RoundErr, task: String, round: i32, victim: Option<PartyID>, culprits: Vec<PartyID>) -> Self {
+        RoundError { cause, task, round, victim, culprits }
+    }
+
+    pub fn cause(&self) -> &RoundErr {
+        &self.cause
+    }
+
+    pub fn task(&self) -> &str {
+        &self.task
+    }
+
+    pub fn round(&self) -> i32 {
+        self.round
+    }
+
+    pub fn victim(&self) -> Option<&PartyID> {
+        self.victim.as_ref()
+    }
+
+    pub fn culprits(&self) -> &[PartyID] {
+        &self.culprits
+    }
+
+    /// The blame evidence carried by this error, if `cause` is `RoundErr::Blame`.
+    pub fn blame_evidence(&self) -> Option<&[BlameEvidence]> {
+        match &self.cause {
+            RoundErr::Blame(evidence) => Some(evidence),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for RoundError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.culprits.is_empty() {
+            write!(f, "task {}, party {:?}, round {}: {}", self.task, self.victim, self.round, self.cause)
+        } else {
+            write!(f, "task {}, party {:?}, round {}, culprits {:?}: {}", self.task, self.victim, self.round, self.culprits, self.cause)
+        }
+    }
+}
+
+impl std::error::Error for RoundError {}
 
 #[derive(Debug)]
 pub struct Error {
@@ -13,10 +13,22 @@ pub struct MessageWrapper {
     from: PartyID,
     to: Vec<PartyID>,
     message: Box<dyn MessageContent>,
+    /// The round this message was sent for. Carried on the wire (see
+    /// `tss::wire`/`tss::message_pb::MessageWrapper::round_number`) so a
+    /// receiver can route a message without having decoded its content.
+    round_number: u32,
 }
 
 impl MessageWrapper {
     pub fn new(is_broadcast: bool, is_to_old_committee: bool, is_to_old_and_new_committees: bool, from: PartyID, to: Vec<PartyID>, message: Box<dyn MessageContent>) -> Self {
+        Self::new_for_round(is_broadcast, is_to_old_committee, is_to_old_and_new_committees, from, to, message, 0)
+    }
+
+    /// Like `new`, but stamps the message with the round it's sent for.
+    /// Round-driven senders (`BaseParty::new_broadcast_message`/
+    /// `new_p2p_message`) should use this so the receiver can tell the
+    /// message apart from one sent for an earlier or later round.
+    pub fn new_for_round(is_broadcast: bool, is_to_old_committee: bool, is_to_old_and_new_committees: bool, from: PartyID, to: Vec<PartyID>, message: Box<dyn MessageContent>, round_number: u32) -> Self {
         MessageWrapper {
             is_broadcast,
             is_to_old_committee,
@@ -24,8 +36,37 @@ impl MessageWrapper {
             from,
             to,
             message,
+            round_number,
         }
     }
+
+    pub fn is_broadcast(&self) -> bool {
+        self.is_broadcast
+    }
+
+    pub fn from(&self) -> &PartyID {
+        &self.from
+    }
+
+    pub fn to(&self) -> &[PartyID] {
+        &self.to
+    }
+
+    pub fn is_to_old_committee(&self) -> bool {
+        self.is_to_old_committee
+    }
+
+    pub fn is_to_old_and_new_committees(&self) -> bool {
+        self.is_to_old_and_new_committees
+    }
+
+    pub fn message(&self) -> &dyn MessageContent {
+        self.message.as_ref()
+    }
+
+    pub fn round_number(&self) -> u32 {
+        self.round_number
+    }
 }
 
 pub struct ParsedMessage {
@@ -38,6 +79,25 @@ impl ParsedMessage {
     pub fn new(routing: MessageRouting, content: Box<dyn MessageContent>, wire: MessageWrapper) -> Self {
         ParsedMessage { routing, content, wire }
     }
+
+    pub fn from(&self) -> &PartyID {
+        &self.routing.from
+    }
+
+    pub fn content(&self) -> &dyn MessageContent {
+        self.content.as_ref()
+    }
+
+    pub fn wire(&self) -> &MessageWrapper {
+        &self.wire
+    }
+
+    /// The round this message was tagged with on the wire, used by
+    /// `LocalParty::update_from_bytes` to decide whether to store it now,
+    /// park it for a round that hasn't started yet, or drop it as stale.
+    pub fn round_number(&self) -> u32 {
+        self.wire.round_number()
+    }
 }
 
 pub struct MessageRouting {
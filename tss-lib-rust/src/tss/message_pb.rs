@@ -15,6 +15,12 @@ pub struct MessageWrapper {
     pub to: Vec<PartyID>,
     #[prost(message, optional, tag = "10")]
     pub message: Option<Any>,
+    /// Which round this message belongs to, so a receiver can tell a
+    /// current-round message apart from one that arrived early (for a
+    /// round that hasn't started yet) or late (for a round that already
+    /// finished) without inspecting the decoded content.
+    #[prost(uint32, tag = "11")]
+    pub round_number: u32,
 }
 
 #[derive(Message)]
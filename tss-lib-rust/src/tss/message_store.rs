@@ -0,0 +1,158 @@
+// Out-of-order buffering and replay protection for round messages.
+//
+// `BaseParty` used to track received messages with nothing but a `Vec<bool>`
+// indexed by sender, which means a message that arrives before the round it
+// targets has started is simply dropped on the floor, and a second message
+// from the same sender for the same slot silently overwrites the first with
+// no way to detect the conflict. `MessageStore` indexes every message by
+// `(round_number, from_index, message_type)`, keeps a content hash so a
+// byte-identical re-send is a safe no-op, flags a *different* payload for the
+// same slot as a fault, and buffers messages for rounds that haven't started
+// yet so they can be drained once the round does.
+
+use crate::common::hash::sha512_256;
+use std::collections::HashMap;
+
+/// Identifies one expected message slot: a specific round, a specific
+/// sender (by index into the sorted party list), and a message type tag
+/// (round messages of the same round may carry more than one type, e.g.
+/// keygen Round 2's decommitment and its accompanying proof).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MessageKey {
+    pub round_number: u32,
+    pub from_index: usize,
+    pub message_type: &'static str,
+}
+
+impl MessageKey {
+    pub fn new(round_number: u32, from_index: usize, message_type: &'static str) -> Self {
+        MessageKey { round_number, from_index, message_type }
+    }
+}
+
+/// A message slot that has already been filled: the encoded payload and its
+/// digest, kept so a later arrival for the same key can be compared for an
+/// exact match instead of blindly overwriting it.
+#[derive(Debug)]
+struct StoredMessage {
+    digest: Vec<u8>,
+    payload: Vec<u8>,
+}
+
+/// A sender replayed a second, *different* payload into a slot that was
+/// already filled -- a distinguishable protocol fault, not a harmless
+/// duplicate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConflictingDuplicate {
+    pub key: MessageKey,
+}
+
+#[derive(Debug, Default)]
+pub struct MessageStore {
+    delivered: HashMap<MessageKey, StoredMessage>,
+    // Messages that arrived for a round that hasn't started receiving yet,
+    // kept in arrival order so a round can drain them once it begins.
+    pending: HashMap<u32, Vec<(MessageKey, Vec<u8>)>>,
+}
+
+impl MessageStore {
+    pub fn new() -> Self {
+        MessageStore { delivered: HashMap::new(), pending: HashMap::new() }
+    }
+
+    /// Returns `true` if a message for this slot has already been recorded
+    /// (used to drive `set_ok`/`waiting_for`/`message_count`).
+    pub fn has(&self, key: &MessageKey) -> bool {
+        self.delivered.contains_key(key)
+    }
+
+    /// Records a message's payload for `key`. A byte-identical re-send of an
+    /// already-filled slot is accepted as a no-op (returns `Ok(false)`, i.e.
+    /// "not newly delivered"); a *different* payload for the same slot is
+    /// rejected as a conflicting duplicate so the caller can mark the sender
+    /// a culprit. A fresh slot is recorded and returns `Ok(true)`.
+    pub fn store_message(&mut self, key: MessageKey, payload: Vec<u8>) -> Result<bool, ConflictingDuplicate> {
+        let digest = sha512_256(&[&payload]);
+        if let Some(existing) = self.delivered.get(&key) {
+            return if existing.digest == digest { Ok(false) } else { Err(ConflictingDuplicate { key }) };
+        }
+        self.delivered.insert(key, StoredMessage { digest, payload });
+        Ok(true)
+    }
+
+    /// Buffers a message that arrived for a round that hasn't started
+    /// receiving yet, to be returned later by `drain_round`.
+    pub fn buffer_early(&mut self, key: MessageKey, payload: Vec<u8>) {
+        self.pending.entry(key.round_number).or_insert_with(Vec::new).push((key, payload));
+    }
+
+    /// Returns (and removes) every message buffered early for `round_number`,
+    /// in the order they arrived, so a round beginning to receive can apply
+    /// them as if they had just arrived via `store_message`.
+    pub fn drain_round(&mut self, round_number: u32) -> Vec<(MessageKey, Vec<u8>)> {
+        self.pending.remove(&round_number).unwrap_or_default()
+    }
+
+    /// Number of distinct, successfully delivered slots recorded for a round.
+    pub fn message_count(&self, round_number: u32) -> usize {
+        self.delivered.keys().filter(|k| k.round_number == round_number).count()
+    }
+
+    /// Clears delivered slots for `round_number`, e.g. when a round restarts.
+    pub fn reset_round(&mut self, round_number: u32) {
+        self.delivered.retain(|k, _| k.round_number != round_number);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_store_message_accepts_fresh_slot() {
+        let mut store = MessageStore::new();
+        let key = MessageKey::new(1, 0, "round1");
+        assert_eq!(store.store_message(key.clone(), b"payload".to_vec()), Ok(true));
+        assert!(store.has(&key));
+    }
+
+    #[test]
+    fn test_store_message_accepts_identical_resend_as_noop() {
+        let mut store = MessageStore::new();
+        let key = MessageKey::new(1, 0, "round1");
+        store.store_message(key.clone(), b"payload".to_vec()).unwrap();
+        assert_eq!(store.store_message(key.clone(), b"payload".to_vec()), Ok(false));
+    }
+
+    #[test]
+    fn test_store_message_rejects_conflicting_resend() {
+        let mut store = MessageStore::new();
+        let key = MessageKey::new(1, 0, "round1");
+        store.store_message(key.clone(), b"payload-a".to_vec()).unwrap();
+        assert_eq!(store.store_message(key.clone(), b"payload-b".to_vec()), Err(ConflictingDuplicate { key }));
+    }
+
+    #[test]
+    fn test_early_messages_are_buffered_and_drained() {
+        let mut store = MessageStore::new();
+        let key = MessageKey::new(2, 0, "round2");
+        store.buffer_early(key.clone(), b"early".to_vec());
+        assert!(!store.has(&key));
+
+        let drained = store.drain_round(2);
+        assert_eq!(drained.len(), 1);
+        assert_eq!(drained[0].0, key);
+        assert!(store.drain_round(2).is_empty());
+    }
+
+    #[test]
+    fn test_message_count_is_scoped_to_round() {
+        let mut store = MessageStore::new();
+        store.store_message(MessageKey::new(1, 0, "round1"), b"a".to_vec()).unwrap();
+        store.store_message(MessageKey::new(1, 1, "round1"), b"b".to_vec()).unwrap();
+        store.store_message(MessageKey::new(2, 0, "round2"), b"c".to_vec()).unwrap();
+
+        assert_eq!(store.message_count(1), 2);
+        assert_eq!(store.message_count(2), 1);
+    }
+}
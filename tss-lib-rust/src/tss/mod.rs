@@ -0,0 +1,17 @@
+pub mod acs;
+pub mod curve;
+pub mod echo_broadcast;
+pub mod erasure_broadcast;
+pub mod error;
+pub mod message;
+pub mod message_pb;
+pub mod message_store;
+pub mod params;
+pub mod party;
+pub mod party_id;
+pub mod peers;
+pub mod reliable_broadcast;
+pub mod round;
+pub mod round_stream;
+pub mod transport;
+pub mod wire;
@@ -3,6 +3,7 @@ use std::time::Duration;
 
 pub struct Parameters {
     ec: Box<dyn ToEncodedPoint>,
+    curve_name: CurveName,
     party_id: PartyID,
     parties: PeerContext,
     party_count: usize,
@@ -12,12 +13,19 @@ pub struct Parameters {
     nonce: usize,
     no_proof_mod: bool,
     no_proof_fac: bool,
+    no_proof_pop: bool,
+    /// Caller-supplied session id folded into the keygen SSID alongside the
+    /// party set, curve id, threshold and per-run nonce (see keygen Round 1's
+    /// `get_ssid`). `None` means two runs over the same party set are only
+    /// kept apart by that random nonce.
+    session_id: Option<Vec<u8>>,
 }
 
 impl Parameters {
-    pub fn new(ec: Box<dyn ToEncodedPoint>, party_id: PartyID, parties: PeerContext, party_count: usize, threshold: usize) -> Self {
+    pub fn new(ec: Box<dyn ToEncodedPoint>, curve_name: CurveName, party_id: PartyID, parties: PeerContext, party_count: usize, threshold: usize) -> Self {
         Parameters {
             ec,
+            curve_name,
             party_id,
             parties,
             party_count,
@@ -27,8 +35,70 @@ impl Parameters {
             nonce: 0,
             no_proof_mod: false,
             no_proof_fac: false,
+            no_proof_pop: false,
+            session_id: None,
         }
     }
+
+    pub fn ec(&self) -> &dyn ToEncodedPoint {
+        self.ec.as_ref()
+    }
+
+    pub fn curve_name(&self) -> CurveName {
+        self.curve_name
+    }
+
+    pub fn party_id(&self) -> &PartyID {
+        &self.party_id
+    }
+
+    pub fn parties(&self) -> &PeerContext {
+        &self.parties
+    }
+
+    pub fn party_count(&self) -> usize {
+        self.party_count
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn concurrency(&self) -> usize {
+        self.concurrency
+    }
+
+    pub fn safe_prime_gen_timeout(&self) -> Duration {
+        self.safe_prime_gen_timeout
+    }
+
+    pub fn nonce(&self) -> usize {
+        self.nonce
+    }
+
+    pub fn no_proof_mod(&self) -> bool {
+        self.no_proof_mod
+    }
+
+    pub fn no_proof_fac(&self) -> bool {
+        self.no_proof_fac
+    }
+
+    pub fn no_proof_pop(&self) -> bool {
+        self.no_proof_pop
+    }
+
+    /// The caller-supplied session id folded into the SSID, if one was set.
+    pub fn session_id(&self) -> Option<&[u8]> {
+        self.session_id.as_deref()
+    }
+
+    /// Binds this party's future SSIDs to a caller-chosen session id, so runs
+    /// over the same party set started by different callers don't rely on the
+    /// random `ssid_nonce` alone to stay apart.
+    pub fn set_session_id(&mut self, session_id: Vec<u8>) {
+        self.session_id = Some(session_id);
+    }
 }
 #[cfg(test)]
 mod tests {
@@ -40,11 +110,24 @@ mod tests {
         let ec = Box::new(Secp256k1::default());
         let party_id = PartyID::new("id".to_string(), "moniker".to_string(), BigInt::from(1));
         let parties = PeerContext::new(vec![party_id.clone()]);
-        let params = Parameters::new(ec, party_id.clone(), parties, 1, 1);
+        let params = Parameters::new(ec, CurveName::Secp256k1, party_id.clone(), parties, 1, 1);
 
         assert_eq!(params.party_count, 1);
         assert_eq!(params.threshold, 1);
     }
+
+    #[test]
+    fn test_parameters_session_id_roundtrip() {
+        let ec = Box::new(Secp256k1::default());
+        let party_id = PartyID::new("id".to_string(), "moniker".to_string(), BigInt::from(1));
+        let parties = PeerContext::new(vec![party_id.clone()]);
+        let mut params = Parameters::new(ec, CurveName::Secp256k1, party_id, parties, 1, 1);
+
+        assert_eq!(params.session_id(), None);
+        params.set_session_id(b"my-session".to_vec());
+        assert_eq!(params.session_id(), Some(&b"my-session"[..]));
+    }
 }
+use crate::tss::curve::CurveName;
 use crate::tss::party_id::PartyID;
 use crate::tss::peers::PeerContext;
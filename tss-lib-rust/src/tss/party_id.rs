@@ -22,6 +22,14 @@ impl PartyID {
     pub fn id(&self) -> &str {
         &self.id
     }
+
+    pub fn key(&self) -> &BigInt {
+        &self.key
+    }
+
+    pub fn moniker(&self) -> &str {
+        &self.moniker
+    }
 }
 
 impl fmt::Display for PartyID {
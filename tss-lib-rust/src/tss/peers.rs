@@ -6,5 +6,9 @@ impl PeerContext {
     pub fn new(party_ids: Vec<PartyID>) -> Self {
         PeerContext { party_ids }
     }
+
+    pub fn party_ids(&self) -> &[PartyID] {
+        &self.party_ids
+    }
 }
 use crate::tss::party_id::PartyID;
@@ -0,0 +1,310 @@
+// Copyright © 2019 Binance
+//
+// This file is part of Binance. The full Binance copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+// Bracha reliable broadcast, layered over `MessageWrapper`/`MessageRouting`'s
+// `is_broadcast` flag. That flag alone only tells a recipient "this was meant
+// for everyone" -- it does nothing to stop a malicious sender from handing
+// different parties different payloads for the same round (equivocation).
+// This module adds the classic three-phase echo/ready protocol so every
+// honest party is guaranteed to deliver the same bytes for a given
+// `(sender, round)`, or to detect and blame a sender that didn't send the
+// same thing to everyone.
+//
+// This is a pure, synchronous tally: it doesn't send anything itself. A
+// caller feeds it the sender's direct payload and every `ECHO`/`READY` vote
+// it receives (e.g. off a `RoundStream`'s incoming channel), and acts on the
+// `BrachaAction` each call returns -- echoing, readying, delivering to
+// `Round::store_message`, or raising blame evidence.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::common::hash::sha512_256;
+use crate::tss::error::{BlameEvidence, FailureKind};
+use crate::tss::party_id::PartyID;
+
+/// Identifies one Bracha broadcast instance: a specific sender broadcasting
+/// in a specific protocol round.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BroadcastId {
+    pub sender: PartyID,
+    pub round: i32,
+}
+
+/// `n`/`t` quorum sizes for one Bracha instance: `n` total parties, `t` the
+/// maximum tolerated number of Byzantine parties (requires `n > 3t`).
+#[derive(Clone, Copy, Debug)]
+pub struct BrachaThresholds {
+    n: usize,
+    t: usize,
+}
+
+impl BrachaThresholds {
+    pub fn new(n: usize, t: usize) -> Self {
+        BrachaThresholds { n, t }
+    }
+
+    /// `⌈(n+t+1)/2⌉`: the number of matching `ECHO`s needed before a node
+    /// sends `READY`.
+    pub fn echo_threshold(&self) -> usize {
+        (self.n + self.t + 2) / 2
+    }
+
+    /// `t+1`: the number of matching `READY`s needed for a node that hasn't
+    /// sent `READY` yet to amplify by sending its own (even without having
+    /// reached the echo threshold).
+    pub fn ready_amplify_threshold(&self) -> usize {
+        self.t + 1
+    }
+
+    /// `2t+1`: the number of matching `READY`s needed to deliver.
+    pub fn deliver_threshold(&self) -> usize {
+        2 * self.t + 1
+    }
+}
+
+/// What a caller should do in response to a vote `BrachaInstance` just
+/// processed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BrachaAction {
+    /// Nothing new to act on yet.
+    None,
+    /// Broadcast an `ECHO` of this payload.
+    SendEcho(Vec<u8>),
+    /// Broadcast a `READY` for this payload (either amplifying after the
+    /// echo quorum, or amplifying early after `t+1` matching `READY`s).
+    SendReady(Vec<u8>),
+    /// `2t+1` matching `READY`s seen: this payload is safe to deliver, e.g.
+    /// to `Round::store_message`. Delivered at most once per instance.
+    Deliver(Vec<u8>),
+    /// Two distinct digests for this `(sender, round)` both reached the echo
+    /// quorum, which is only possible if the sender sent different payloads
+    /// to different honest parties.
+    Equivocation { digest_a: Vec<u8>, digest_b: Vec<u8> },
+}
+
+fn digest_of(payload: &[u8]) -> Vec<u8> {
+    sha512_256(&[payload])
+}
+
+/// Per-(sender, round) vote tally and delivery state.
+struct BrachaInstance {
+    thresholds: BrachaThresholds,
+    echo_voters: HashMap<Vec<u8>, HashSet<PartyID>>,
+    ready_voters: HashMap<Vec<u8>, HashSet<PartyID>>,
+    payloads: HashMap<Vec<u8>, Vec<u8>>,
+    ready_sent: bool,
+    delivered: bool,
+}
+
+impl BrachaInstance {
+    fn new(thresholds: BrachaThresholds) -> Self {
+        BrachaInstance {
+            thresholds,
+            echo_voters: HashMap::new(),
+            ready_voters: HashMap::new(),
+            payloads: HashMap::new(),
+            ready_sent: false,
+            delivered: false,
+        }
+    }
+
+    fn remember(&mut self, digest: &[u8], payload: Vec<u8>) {
+        self.payloads.entry(digest.to_vec()).or_insert(payload);
+    }
+
+    /// This node received `payload` directly from the sender: echo it.
+    fn on_receive_payload(&mut self, payload: Vec<u8>) -> BrachaAction {
+        let digest = digest_of(&payload);
+        self.remember(&digest, payload.clone());
+        BrachaAction::SendEcho(payload)
+    }
+
+    /// Process an `ECHO(payload)` vote from `voter`.
+    fn on_echo(&mut self, voter: PartyID, payload: Vec<u8>) -> BrachaAction {
+        if self.delivered {
+            return BrachaAction::None;
+        }
+        let digest = digest_of(&payload);
+        self.remember(&digest, payload);
+        self.echo_voters.entry(digest.clone()).or_default().insert(voter);
+
+        if let Some(equivocation) = self.equivocation_evidence() {
+            return equivocation;
+        }
+        if self.echo_voters[&digest].len() >= self.thresholds.echo_threshold() {
+            return self.try_send_ready(&digest);
+        }
+        BrachaAction::None
+    }
+
+    /// Process a `READY(payload)` vote from `voter`.
+    fn on_ready(&mut self, voter: PartyID, payload: Vec<u8>) -> BrachaAction {
+        if self.delivered {
+            return BrachaAction::None;
+        }
+        let digest = digest_of(&payload);
+        self.remember(&digest, payload);
+        self.ready_voters.entry(digest.clone()).or_default().insert(voter);
+        let count = self.ready_voters[&digest].len();
+
+        if count >= self.thresholds.deliver_threshold() {
+            self.delivered = true;
+            return BrachaAction::Deliver(self.payloads.get(&digest).cloned().unwrap_or_default());
+        }
+        if count >= self.thresholds.ready_amplify_threshold() {
+            return self.try_send_ready(&digest);
+        }
+        BrachaAction::None
+    }
+
+    fn try_send_ready(&mut self, digest: &[u8]) -> BrachaAction {
+        if self.ready_sent {
+            return BrachaAction::None;
+        }
+        self.ready_sent = true;
+        BrachaAction::SendReady(self.payloads.get(digest).cloned().unwrap_or_default())
+    }
+
+    /// Two distinct digests both past the echo quorum proves the sender
+    /// equivocated: under `n > 3t`, no two disjoint-enough honest majorities
+    /// can echo different payloads for an honest sender.
+    fn equivocation_evidence(&self) -> Option<BrachaAction> {
+        let past_quorum: Vec<&Vec<u8>> = self
+            .echo_voters
+            .iter()
+            .filter(|(_, voters)| voters.len() >= self.thresholds.echo_threshold())
+            .map(|(digest, _)| digest)
+            .collect();
+        if past_quorum.len() >= 2 {
+            return Some(BrachaAction::Equivocation {
+                digest_a: past_quorum[0].clone(),
+                digest_b: past_quorum[1].clone(),
+            });
+        }
+        None
+    }
+}
+
+/// Tracks Bracha reliable broadcast state across every `(sender, round)`
+/// instance this party has seen a vote for.
+pub struct BrachaBroadcast {
+    thresholds: BrachaThresholds,
+    instances: HashMap<BroadcastId, BrachaInstance>,
+}
+
+impl BrachaBroadcast {
+    pub fn new(thresholds: BrachaThresholds) -> Self {
+        BrachaBroadcast { thresholds, instances: HashMap::new() }
+    }
+
+    fn instance(&mut self, id: BroadcastId) -> &mut BrachaInstance {
+        let thresholds = self.thresholds;
+        self.instances.entry(id).or_insert_with(|| BrachaInstance::new(thresholds))
+    }
+
+    /// This node received `payload` directly from `id.sender`: echo it.
+    pub fn on_receive_payload(&mut self, id: BroadcastId, payload: Vec<u8>) -> BrachaAction {
+        self.instance(id).on_receive_payload(payload)
+    }
+
+    /// Process an `ECHO(payload)` vote from `voter` for instance `id`.
+    pub fn on_echo(&mut self, id: BroadcastId, voter: PartyID, payload: Vec<u8>) -> BrachaAction {
+        self.instance(id).on_echo(voter, payload)
+    }
+
+    /// Process a `READY(payload)` vote from `voter` for instance `id`.
+    pub fn on_ready(&mut self, id: BroadcastId, voter: PartyID, payload: Vec<u8>) -> BrachaAction {
+        self.instance(id).on_ready(voter, payload)
+    }
+}
+
+/// Builds identifiable-abort evidence for a `BrachaAction::Equivocation`,
+/// blaming `id.sender` at `id.round` in the same `BlameEvidence` shape the
+/// rest of the crate's accountability checks use.
+pub fn equivocation_evidence(id: &BroadcastId, digest_a: &[u8], digest_b: &[u8]) -> BlameEvidence {
+    let mut transcript = digest_a.to_vec();
+    transcript.extend_from_slice(digest_b);
+    BlameEvidence::new(id.sender.clone(), id.round, FailureKind::Equivocation, None, transcript)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+
+    fn party(id: u32) -> PartyID {
+        PartyID::new(id.to_string(), format!("party-{}", id), BigInt::from(id))
+    }
+
+    fn broadcast_id() -> BroadcastId {
+        BroadcastId { sender: party(0), round: 1 }
+    }
+
+    #[test]
+    fn test_delivers_after_deliver_threshold_matching_readies() {
+        // n=4, t=1: echo_threshold=3, ready_amplify_threshold=2, deliver_threshold=3
+        let thresholds = BrachaThresholds::new(4, 1);
+        let mut bracha = BrachaBroadcast::new(thresholds);
+        let id = broadcast_id();
+        let payload = b"hello".to_vec();
+
+        assert_eq!(bracha.on_ready(id.clone(), party(1), payload.clone()), BrachaAction::None);
+        assert_eq!(bracha.on_ready(id.clone(), party(2), payload.clone()), BrachaAction::SendReady(payload.clone()));
+        assert_eq!(bracha.on_ready(id.clone(), party(3), payload.clone()), BrachaAction::Deliver(payload));
+    }
+
+    #[test]
+    fn test_echo_quorum_triggers_ready() {
+        let thresholds = BrachaThresholds::new(4, 1);
+        let mut bracha = BrachaBroadcast::new(thresholds);
+        let id = broadcast_id();
+        let payload = b"hello".to_vec();
+
+        assert_eq!(bracha.on_echo(id.clone(), party(1), payload.clone()), BrachaAction::None);
+        assert_eq!(bracha.on_echo(id.clone(), party(2), payload.clone()), BrachaAction::None);
+        assert_eq!(bracha.on_echo(id.clone(), party(3), payload.clone()), BrachaAction::SendReady(payload));
+    }
+
+    #[test]
+    fn test_delivers_at_most_once() {
+        let thresholds = BrachaThresholds::new(4, 1);
+        let mut bracha = BrachaBroadcast::new(thresholds);
+        let id = broadcast_id();
+        let payload = b"hello".to_vec();
+
+        bracha.on_ready(id.clone(), party(1), payload.clone());
+        bracha.on_ready(id.clone(), party(2), payload.clone());
+        assert_eq!(bracha.on_ready(id.clone(), party(3), payload.clone()), BrachaAction::Deliver(payload.clone()));
+        // A further matching READY after delivery is a no-op, not a second Deliver.
+        assert_eq!(bracha.on_ready(id.clone(), party(4), payload), BrachaAction::None);
+    }
+
+    #[test]
+    fn test_conflicting_echoes_surface_equivocation() {
+        let thresholds = BrachaThresholds::new(4, 1);
+        let mut bracha = BrachaBroadcast::new(thresholds);
+        let id = broadcast_id();
+        let payload_a = b"version A".to_vec();
+        let payload_b = b"version B".to_vec();
+
+        bracha.on_echo(id.clone(), party(1), payload_a.clone());
+        bracha.on_echo(id.clone(), party(2), payload_a.clone());
+        bracha.on_echo(id.clone(), party(3), payload_a.clone());
+
+        bracha.on_echo(id.clone(), party(4), payload_b.clone());
+        bracha.on_echo(id.clone(), party(5), payload_b.clone());
+        let action = bracha.on_echo(id.clone(), party(6), payload_b.clone());
+
+        match action {
+            BrachaAction::Equivocation { digest_a, digest_b } => {
+                let evidence = equivocation_evidence(&id, &digest_a, &digest_b);
+                assert_eq!(evidence.accused, id.sender);
+                assert_eq!(evidence.kind, FailureKind::Equivocation);
+            }
+            other => panic!("expected Equivocation, got {:?}", other),
+        }
+    }
+}
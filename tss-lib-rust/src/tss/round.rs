@@ -7,17 +7,31 @@
 // Translation of tss-lib-go/tss/round.go
 
 use crate::tss::{
-    error::{RoundError, RoundErr},
+    error::{BlameEvidence, RoundError, RoundErr},
     message::ParsedMessage,
     params::Parameters,
     party_id::PartyID,
 };
 use std::{{
+    fmt,
     fmt::Debug,
     sync::Arc,
     error::Error as StdError,
 }};
 
+/// Error returned by the default `Round::store_message` for implementations
+/// that don't override it with real message-storage logic.
+#[derive(Debug)]
+struct UnsupportedStoreMessage;
+
+impl fmt::Display for UnsupportedStoreMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "this round does not support storing messages via Round::store_message")
+    }
+}
+
+impl StdError for UnsupportedStoreMessage {}
+
 /// Represents a single round within a TSS protocol.
 pub trait Round: Send + Sync + Debug {
     /// Returns the parameters used for this round/protocol.
@@ -30,6 +44,20 @@ pub trait Round: Send + Sync + Debug {
     /// Returns Ok(true) if the state was updated, Ok(false) otherwise.
     fn update(&self) -> Result<bool, RoundError>;
 
+    /// Stores an accepted message (one `can_accept` returned true for) so a
+    /// later `update()` call picks it up. Implementations that don't support
+    /// message-driven drivers like `RoundStream` can leave this at its
+    /// default, which reports the message as unsupported.
+    fn store_message(&self, _msg: ParsedMessage) -> Result<(), RoundError> {
+        Err(RoundError::new(
+            RoundErr::General(Box::new(UnsupportedStoreMessage)),
+            self.params().party_id().id().to_string(),
+            self.round_number(),
+            Some(self.params().party_id().clone()),
+            vec![],
+        ))
+    }
+
     /// Returns the current round number (1-based typically).
     fn round_number(&self) -> i32;
 
@@ -45,6 +73,14 @@ pub trait Round: Send + Sync + Debug {
     /// Returns the list of parties that this round is currently waiting for messages from.
     fn waiting_for(&self) -> Vec<PartyID>;
 
+    /// Reproducible blame evidence for any party this round has identified as
+    /// cheating (e.g. a failed VSS share/proof check), if `update` halted the
+    /// round instead of proceeding. Any party can re-run this evidence's checks
+    /// independently to confirm the accusation. Default: no abort in progress.
+    fn abort_evidence(&self) -> Option<Vec<BlameEvidence>> {
+        None
+    }
+
     /// Wraps a standard error into a `RoundError` specific to this round.
     fn wrap_error<E: StdError + Send + Sync + 'static>(&self, err: E, culprits: Vec<PartyID>) -> RoundError {
         RoundError::new(
@@ -0,0 +1,120 @@
+// Copyright © 2019 Binance
+//
+// This file is part of Binance. The full Binance copyright notice, including
+// terms governing use, modification, and redistribution, is contained in the
+// file LICENSE at the root of the source code distribution tree.
+
+// Async driver for `Round`, so integrators can import messages as they
+// stream in off a `tokio` channel instead of hand-rolling a busy-poll loop
+// around `update`/`can_proceed`.
+
+use std::fmt;
+use std::sync::Arc;
+use tokio::sync::{mpsc, oneshot};
+
+use crate::tss::{
+    error::{RoundErr, RoundError},
+    message::ParsedMessage,
+    party_id::PartyID,
+    round::Round,
+};
+
+/// Why a `RoundStream` stopped driving without the round reaching
+/// `can_proceed()`.
+#[derive(Debug)]
+pub enum DriveStopped {
+    /// The incoming message channel was closed (all senders dropped) before
+    /// enough messages arrived for the round to proceed.
+    ChannelClosed,
+    /// The caller's abort signal fired.
+    Aborted,
+}
+
+impl fmt::Display for DriveStopped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DriveStopped::ChannelClosed => write!(f, "message channel closed before round could proceed"),
+            DriveStopped::Aborted => write!(f, "round drive was cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for DriveStopped {}
+
+/// Drives a single `Round` to completion by importing `ParsedMessage`s off a
+/// channel as they arrive. Construct with `new`, optionally attach a
+/// cancellation signal with `with_abort`, then `await` `run()`.
+pub struct RoundStream {
+    round: Arc<dyn Round>,
+    incoming: mpsc::UnboundedReceiver<ParsedMessage>,
+    abort: Option<oneshot::Receiver<()>>,
+}
+
+impl RoundStream {
+    pub fn new(round: Arc<dyn Round>, incoming: mpsc::UnboundedReceiver<ParsedMessage>) -> Self {
+        RoundStream { round, incoming, abort: None }
+    }
+
+    /// Attaches a cancellation signal: firing it before the round can
+    /// proceed stops `run` and it returns `DriveStopped::Aborted`, rather
+    /// than leaving a stalled party set driving forever.
+    pub fn with_abort(mut self, abort: oneshot::Receiver<()>) -> Self {
+        self.abort = Some(abort);
+        self
+    }
+
+    /// Parties this round is still waiting to hear from.
+    pub fn waiting_for(&self) -> Vec<PartyID> {
+        self.round.waiting_for()
+    }
+
+    /// Runs `round.start()`, then imports accepted messages until the round
+    /// can proceed, returning the next round (or the same round, if this was
+    /// the protocol's final one). Messages `round.can_accept` rejects are
+    /// dropped rather than treated as an error, matching the synchronous
+    /// `update`/`can_accept` contract `Round` already documents.
+    pub async fn run(self) -> Result<Arc<dyn Round>, RoundError> {
+        let RoundStream { round, mut incoming, mut abort } = self;
+
+        round.start()?;
+        if round.can_proceed() {
+            return Ok(round.next_round().unwrap_or(round));
+        }
+
+        loop {
+            let next_msg = if let Some(abort_rx) = abort.as_mut() {
+                tokio::select! {
+                    msg = incoming.recv() => msg,
+                    _ = abort_rx => return Err(drive_stopped_error(&round, DriveStopped::Aborted)),
+                }
+            } else {
+                incoming.recv().await
+            };
+
+            let msg = match next_msg {
+                Some(msg) => msg,
+                None => return Err(drive_stopped_error(&round, DriveStopped::ChannelClosed)),
+            };
+
+            if !round.can_accept(&msg) {
+                continue;
+            }
+            round.store_message(msg)?;
+            round.update()?;
+
+            if round.can_proceed() {
+                return Ok(round.next_round().unwrap_or(round));
+            }
+        }
+    }
+}
+
+fn drive_stopped_error(round: &Arc<dyn Round>, reason: DriveStopped) -> RoundError {
+    RoundError::new(
+        RoundErr::General(Box::new(reason)),
+        round.params().party_id().id().to_string(),
+        round.round_number(),
+        Some(round.params().party_id().clone()),
+        round.waiting_for(),
+    )
+}
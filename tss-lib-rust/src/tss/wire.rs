@@ -1,9 +1,73 @@
 use prost::Message;
 use prost_types::Any;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Mutex, OnceLock};
 use crate::tss::message::{MessageContent, MessageRouting, ParsedMessage, MessageWrapper};
 use crate::tss::party_id::PartyID;
 use crate::tss::message_pb as pb;
 
+/// Decodes the raw bytes carried by a `prost_types::Any` into a concrete
+/// `MessageContent`, once the `Any`'s `type_url` has identified which decoder
+/// to use.
+pub type Decoder = fn(&[u8]) -> Result<Box<dyn MessageContent>, WireError>;
+
+/// Errors from turning wire bytes into a `ParsedMessage`. Distinct from
+/// `tss::error::Error`/`RoundError`, which describe protocol-level round
+/// failures: this describes failing to get a message off the wire at all.
+#[derive(Debug)]
+pub enum WireError {
+    /// The outer `MessageWrapper` envelope failed to decode as protobuf.
+    Decode(prost::DecodeError),
+    /// A required field was absent from the envelope.
+    MissingField(&'static str),
+    /// No decoder is registered for this `Any.type_url`.
+    UnknownType(String),
+}
+
+impl fmt::Display for WireError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WireError::Decode(e) => write!(f, "failed to decode wire message: {}", e),
+            WireError::MissingField(field) => write!(f, "wire message missing required field `{}`", field),
+            WireError::UnknownType(type_url) => write!(f, "no decoder registered for type_url `{}`", type_url),
+        }
+    }
+}
+
+impl std::error::Error for WireError {}
+
+impl From<prost::DecodeError> for WireError {
+    fn from(e: prost::DecodeError) -> Self {
+        WireError::Decode(e)
+    }
+}
+
+// Keyed by the protobuf `type_url` carried in each message's `Any` envelope.
+// Populated by `register_decoder`, which each round module is expected to
+// call (once, for every `MessageContent` it defines) before any of its
+// messages can be parsed off the wire.
+static TYPE_REGISTRY: OnceLock<Mutex<HashMap<String, Decoder>>> = OnceLock::new();
+
+fn registry() -> &'static Mutex<HashMap<String, Decoder>> {
+    TYPE_REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Registers `decoder` as the way to turn the bytes of an `Any` whose
+/// `type_url` is `type_url` into a concrete `MessageContent`. Registering the
+/// same `type_url` twice replaces the earlier decoder.
+pub fn register_decoder(type_url: &str, decoder: Decoder) {
+    registry().lock().unwrap().insert(type_url.to_string(), decoder);
+}
+
+fn decode_any(any: &Any) -> Result<Box<dyn MessageContent>, WireError> {
+    let registry = registry().lock().unwrap();
+    let decoder = registry
+        .get(any.type_url.as_str())
+        .ok_or_else(|| WireError::UnknownType(any.type_url.clone()))?;
+    decoder(&any.value)
+}
+
 fn pb_party_id_to_internal(pb_id: &pb::PartyID) -> PartyID {
     use num_bigint::BigInt;
     PartyID::new(
@@ -13,33 +77,148 @@ fn pb_party_id_to_internal(pb_id: &pb::PartyID) -> PartyID {
     )
 }
 
-fn pb_message_wrapper_to_internal(pb_wrap: &pb::MessageWrapper) -> MessageWrapper {
-    let from = pb_party_id_to_internal(pb_wrap.from.as_ref().unwrap());
+fn party_id_to_pb(id: &PartyID) -> pb::PartyID {
+    pb::PartyID {
+        id: id.id().to_string(),
+        moniker: id.moniker().to_string(),
+        key: id.key().to_bytes_be().1,
+    }
+}
+
+/// Serializes a `MessageWrapper` (routing flags, sender, recipients) and its
+/// already-encoded content bytes into wire bytes. The content's `type_url`
+/// is left empty: content-type dispatch on receipt goes through
+/// `register_decoder`/`decode_any`, which is a separate concern from
+/// transporting the envelope itself.
+pub fn encode_wire_message(wrapper: &MessageWrapper, content_bytes: Vec<u8>) -> Vec<u8> {
+    let pb_wrapper = pb::MessageWrapper {
+        is_broadcast: wrapper.is_broadcast(),
+        is_to_old_committee: wrapper.is_to_old_committee(),
+        is_to_old_and_new_committees: wrapper.is_to_old_and_new_committees(),
+        from: Some(party_id_to_pb(wrapper.from())),
+        to: wrapper.to().iter().map(party_id_to_pb).collect(),
+        message: Some(Any { type_url: String::new(), value: content_bytes }),
+        round_number: wrapper.round_number(),
+    };
+    pb_wrapper.encode_to_vec()
+}
+
+fn pb_message_wrapper_to_internal(pb_wrap: &pb::MessageWrapper) -> Result<MessageWrapper, WireError> {
+    let from = pb_party_id_to_internal(pb_wrap.from.as_ref().ok_or(WireError::MissingField("from"))?);
     let to = pb_wrap.to.iter().map(pb_party_id_to_internal).collect();
-    // For now, message is None (prost_types::Any cannot be converted generically)
-    // In a real implementation, you would match type_url and decode the correct type
-    let message: Box<dyn MessageContent> = panic!("prost_types::Any to MessageContent conversion not implemented");
-    MessageWrapper::new(
+    let any = pb_wrap.message.as_ref().ok_or(WireError::MissingField("message"))?;
+    let message = decode_any(any)?;
+    Ok(MessageWrapper::new_for_round(
         pb_wrap.is_broadcast,
         pb_wrap.is_to_old_committee,
         pb_wrap.is_to_old_and_new_committees,
         from,
         to,
         message,
-    )
+        pb_wrap.round_number,
+    ))
 }
 
-pub fn parse_wire_message(wire_bytes: &[u8], from: &PartyID, is_broadcast: bool) -> Result<ParsedMessage, Box<dyn std::error::Error>> {
+pub fn parse_wire_message(wire_bytes: &[u8], from: &PartyID, is_broadcast: bool) -> Result<ParsedMessage, WireError> {
     let pb_wire: pb::MessageWrapper = Message::decode(wire_bytes)?;
-    let internal_wire = pb_message_wrapper_to_internal(&pb_wire);
+    let internal_wire = pb_message_wrapper_to_internal(&pb_wire)?;
     let routing = MessageRouting::new(
         from.clone(),
-        internal_wire.to().clone(),
+        internal_wire.to().to_vec(),
         is_broadcast,
         internal_wire.is_to_old_committee(),
         internal_wire.is_to_old_and_new_committees(),
     );
-    // For now, content is not implemented
-    let content: Box<dyn MessageContent> = panic!("prost_types::Any to MessageContent conversion not implemented");
+    let any = pb_wire.message.as_ref().ok_or(WireError::MissingField("message"))?;
+    let content = decode_any(any)?;
     Ok(ParsedMessage::new(routing, content, internal_wire))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, PartialEq, Message)]
+    struct TestContent {
+        #[prost(string, tag = "1")]
+        text: String,
+    }
+
+    impl MessageContent for TestContent {
+        fn validate_basic(&self) -> bool {
+            !self.text.is_empty()
+        }
+    }
+
+    fn decode_test_content(bytes: &[u8]) -> Result<Box<dyn MessageContent>, WireError> {
+        let content = TestContent::decode(bytes)?;
+        Ok(Box::new(content))
+    }
+
+    #[test]
+    fn test_encode_wire_message_round_trips_routing_flags() {
+        use num_bigint::BigInt;
+
+        let from = PartyID::new("a".to_string(), "alice".to_string(), BigInt::from(1));
+        let to = PartyID::new("b".to_string(), "bob".to_string(), BigInt::from(2));
+        let content: Box<dyn MessageContent> = Box::new(TestContent { text: "hi".to_string() });
+        let wrapper = MessageWrapper::new(false, true, true, from.clone(), vec![to.clone()], content);
+
+        let wire_bytes = encode_wire_message(&wrapper, TestContent { text: "hi".to_string() }.encode_to_vec());
+        let pb_wrapper: pb::MessageWrapper = Message::decode(wire_bytes.as_slice()).unwrap();
+
+        assert!(!pb_wrapper.is_broadcast);
+        assert!(pb_wrapper.is_to_old_committee);
+        assert!(pb_wrapper.is_to_old_and_new_committees);
+        assert_eq!(pb_wrapper.from.unwrap().id, "a");
+        assert_eq!(pb_wrapper.to.len(), 1);
+        assert_eq!(pb_wrapper.to[0].id, "b");
+    }
+
+    #[test]
+    fn test_round_number_round_trips_through_parse_wire_message() {
+        use num_bigint::BigInt;
+
+        let type_url = "type.googleapis.com/tss.wire.test.RoundNumberContent";
+        register_decoder(type_url, decode_test_content);
+
+        let from = PartyID::new("a".to_string(), "alice".to_string(), BigInt::from(1));
+        let content: Box<dyn MessageContent> = Box::new(TestContent { text: "hi".to_string() });
+        let wrapper = MessageWrapper::new_for_round(true, false, false, from.clone(), vec![], content, 3);
+
+        let pb_wrapper = pb::MessageWrapper {
+            is_broadcast: wrapper.is_broadcast(),
+            is_to_old_committee: wrapper.is_to_old_committee(),
+            is_to_old_and_new_committees: wrapper.is_to_old_and_new_committees(),
+            from: Some(party_id_to_pb(wrapper.from())),
+            to: vec![],
+            message: Some(Any { type_url: type_url.to_string(), value: TestContent { text: "hi".to_string() }.encode_to_vec() }),
+            round_number: wrapper.round_number(),
+        };
+        let wire_bytes = pb_wrapper.encode_to_vec();
+
+        let parsed = parse_wire_message(&wire_bytes, &from, true).expect("should parse");
+        assert_eq!(parsed.round_number(), 3);
+    }
+
+    #[test]
+    fn test_unregistered_type_url_is_an_error_not_a_panic() {
+        let any = Any { type_url: "type.googleapis.com/does.not.Exist".to_string(), value: vec![] };
+        match decode_any(&any) {
+            Err(WireError::UnknownType(type_url)) => assert_eq!(type_url, any.type_url),
+            other => panic!("expected UnknownType, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_registered_type_url_dispatches_to_its_decoder() {
+        let type_url = "type.googleapis.com/tss.wire.test.TestContent";
+        register_decoder(type_url, decode_test_content);
+
+        let content = TestContent { text: "hello".to_string() };
+        let any = Any { type_url: type_url.to_string(), value: content.encode_to_vec() };
+
+        let decoded = decode_any(&any).expect("registered type_url should decode");
+        assert!(decoded.validate_basic());
+    }
+}